@@ -8,6 +8,11 @@ pub const MAX_SQRT_PRICE: u128 = 79226673521066979257578248091;
 
 pub const LIQUIDITY_SCALE: u8 = 128;
 
+pub const LIQUIDITY_SCALE_FACTOR_RESOLUTION: u8 = 32;
+
+// Max reserve in bps which may be left out of the curve when price range is updated
+pub const MAX_PRICE_RANGE_EXCESS_BPS: u64 = 1;
+
 pub const REWARD_RATE_SCALE: u8 = 64;
 
 pub const TOTAL_REWARD_SCALE: u8 = 192;
@@ -31,12 +36,39 @@ pub const U24_MAX: u32 = 0xffffff;
 pub const NUM_REWARDS: usize = 2;
 pub const REWARD_INDEX_0: usize = 0;
 pub const REWARD_INDEX_1: usize = 1;
+// Number of additional rewards supported by reward extension, reward index of them starts from NUM_REWARDS
+pub const NUM_EXTENSION_REWARDS: usize = 4;
 
 // Minimum reward duration
 pub const MIN_REWARD_DURATION: u64 = 24 * 60 * 60; // 1 day
 
 pub const MAX_REWARD_DURATION: u64 = 31536000; // 1 year = 365 * 24 * 3600
 
+// Bribes are distributed per epoch of 1 week
+pub const BRIBE_EPOCH_DURATION: u64 = 7 * 24 * 60 * 60;
+// Number of recent epochs kept in bribe ledgers, bribes of older epochs can't be claimed anymore
+pub const NUM_BRIBE_EPOCH_SNAPSHOTS: usize = 12;
+
+// Max extra weight of locked liquidity in reward distribution, locked liquidity counts at most 5x
+pub const MAX_REWARD_BOOST_BPS: u16 = 40_000;
+
+// Max number of admins holding roles in admin registry
+pub const MAX_ADMIN_REGISTRY_MEMBERS: usize = 16;
+
+// Max number of destinations protocol fee is routed to
+pub const MAX_PROTOCOL_FEE_DESTINATIONS: usize = 4;
+
+// Max length of the reason attached when admin disables pool actions
+pub const MAX_DISABLE_REASON_LENGTH: usize = 64;
+
+// Decimals of share mint of fungible share mode
+pub const FUNGIBLE_SHARE_DECIMALS: u8 = 9;
+
+// Virtual shares and liquidity added when pricing fungible shares, so the first depositor can't inflate
+// the share price. Same price as the first deposit, 1 share per Q64 liquidity
+pub const FUNGIBLE_SHARE_VIRTUAL_SHARES: u64 = 1_000;
+pub const FUNGIBLE_SHARE_VIRTUAL_LIQUIDITY: u128 = 1_000u128 << 64;
+
 pub const SPLIT_POSITION_DENOMINATOR: u32 = 1_000_000_000; // 1b
 
 pub const MAX_RATE_LIMITER_DURATION_IN_SECONDS: u32 = 60 * 60 * 12; // 12 hours
@@ -158,6 +190,29 @@ pub mod seeds {
     pub const TOKEN_BADGE_PREFIX: &[u8] = b"token_badge";
     pub const REWARD_VAULT_PREFIX: &[u8] = b"reward_vault";
     pub const CLAIM_FEE_OPERATOR_PREFIX: &[u8] = b"cf_operator";
+    pub const REWARD_EXTENSION_PREFIX: &[u8] = b"reward_extension";
+    pub const POSITION_REWARD_EXTENSION_PREFIX: &[u8] = b"position_reward_extension";
+    pub const LOCKED_FEE_EXTENSION_PREFIX: &[u8] = b"locked_fee_extension";
+    pub const FUNGIBLE_SHARE_PREFIX: &[u8] = b"fungible_share";
+    pub const FUNGIBLE_SHARE_MINT_PREFIX: &[u8] = b"fungible_share_mint";
+    pub const BRIBE_LEDGER_PREFIX: &[u8] = b"bribe_ledger";
+    pub const POSITION_BRIBE_LEDGER_PREFIX: &[u8] = b"position_bribe_ledger";
+    pub const BRIBE_CAMPAIGN_PREFIX: &[u8] = b"bribe_campaign";
+    pub const BRIBE_VAULT_PREFIX: &[u8] = b"bribe_vault";
+    pub const BRIBE_CLAIM_RECEIPT_PREFIX: &[u8] = b"bribe_claim_receipt";
+    pub const ADMIN_REGISTRY_PREFIX: &[u8] = b"admin_registry";
+    pub const PROTOCOL_FEE_ROUTING_PREFIX: &[u8] = b"protocol_fee_routing";
+    pub const PROPOSAL_PREFIX: &[u8] = b"proposal";
+    pub const POOL_PERMISSION_PREFIX: &[u8] = b"pool_permission";
+    pub const ALLOWLIST_ENTRY_PREFIX: &[u8] = b"allowlist_entry";
+}
+
+pub mod position_nft {
+    pub const NAME: &str = "Meteora Position NFT";
+    pub const SYMBOL: &str = "MPN";
+    // `{mint}` is replaced by position nft mint
+    pub const URI_TEMPLATE: &str =
+        "https://raw.githubusercontent.com/MeteoraAg/token-metadata/main/position_nft/{mint}.json";
 }
 
 pub mod treasury {
@@ -249,3 +249,66 @@ pub fn get_next_sqrt_price_from_amount_out_b_rounding_down(
     let result = U256::from(sqrt_price).safe_sub(quotient)?;
     Ok(result.try_into().map_err(|_| PoolError::TypeCastFailed)?)
 }
+
+/// Gets the liquidity that can be provided by a given amount of token_a
+///
+/// # Formula
+///
+/// * `L = Δa * √P_lower * √P_upper / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_a(
+    amount_a: u64,
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+) -> Result<u128> {
+    let liquidity =
+        get_liquidity_from_amount_a_unchecked(amount_a, lower_sqrt_price, upper_sqrt_price)?;
+    liquidity
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed.into())
+}
+
+/// * `L = Δa * √P_lower * √P_upper / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_a_unchecked(
+    amount_a: u64,
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+) -> Result<U256> {
+    let numerator_1 = U256::from(amount_a).safe_mul(U256::from(lower_sqrt_price))?;
+    let numerator_2 = U256::from(upper_sqrt_price);
+    let denominator = U256::from(upper_sqrt_price.safe_sub(lower_sqrt_price)?);
+    require!(denominator > U256::ZERO, PoolError::MathOverflow);
+    let result = mul_div_u256(numerator_1, numerator_2, denominator, Rounding::Down)
+        .ok_or(PoolError::MathOverflow)?;
+    Ok(result)
+}
+
+/// Gets the liquidity that can be provided by a given amount of token_b
+///
+/// # Formula
+///
+/// * `L = Δb / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_b(
+    amount_b: u64,
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+) -> Result<u128> {
+    let liquidity =
+        get_liquidity_from_amount_b_unchecked(amount_b, lower_sqrt_price, upper_sqrt_price)?;
+    liquidity
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed.into())
+}
+
+/// * `L = Δb / (√P_upper - √P_lower)`
+pub fn get_liquidity_from_amount_b_unchecked(
+    amount_b: u64,
+    lower_sqrt_price: u128,
+    upper_sqrt_price: u128,
+) -> Result<U256> {
+    let denominator = U256::from(upper_sqrt_price.safe_sub(lower_sqrt_price)?);
+    require!(denominator > U256::ZERO, PoolError::MathOverflow);
+    let result = U256::from(amount_b)
+        .safe_shl((RESOLUTION as usize) * 2)?
+        .safe_div(denominator)?;
+    Ok(result)
+}
@@ -163,4 +163,103 @@ pub enum PoolError {
 
     #[msg("Invalid pool version")]
     InvalidPoolVersion,
+
+    #[msg("Invalid reward extension")]
+    InvalidRewardExtension,
+
+    #[msg("Position reward extension is not empty")]
+    PositionRewardExtensionIsNotEmpty,
+
+    #[msg("Invalid reward schedule")]
+    InvalidRewardSchedule,
+
+    #[msg("Invalid reward boost")]
+    InvalidRewardBoost,
+
+    #[msg("Reward has been distributed")]
+    RewardAlreadyDistributed,
+
+    #[msg("Invalid bribe ledger")]
+    InvalidBribeLedger,
+
+    #[msg("Invalid bribe vault")]
+    InvalidBribeVault,
+
+    #[msg("Invalid bribe epoch")]
+    InvalidBribeEpoch,
+
+    #[msg("Bribe epoch has not ended")]
+    BribeEpochNotEnded,
+
+    #[msg("Bribe epoch snapshot not found")]
+    BribeEpochSnapshotNotFound,
+
+    #[msg("Bribe campaign has not expired")]
+    BribeCampaignNotExpired,
+
+    #[msg("Ineligible bribe has been withdrawn")]
+    IneligibleBribeWithdrawn,
+
+    #[msg("Invalid transfer hook accounts")]
+    InvalidTransferHookAccounts,
+
+    #[msg("Token badge expired")]
+    TokenBadgeExpired,
+
+    #[msg("Token mint violates token badge policy")]
+    TokenBadgePolicyViolation,
+
+    #[msg("Transfer fee exceeded maximum transfer fee bps")]
+    ExceededMaxTransferFeeBps,
+
+    #[msg("Admin registry is full")]
+    AdminRegistryFull,
+
+    #[msg("Action must be proposed through timelock")]
+    TimelockRequired,
+
+    #[msg("Proposal is not executable yet")]
+    ProposalNotExecutable,
+
+    #[msg("Invalid proposal target")]
+    InvalidProposalTarget,
+
+    #[msg("Invalid pool type")]
+    InvalidPoolType,
+
+    #[msg("Invalid allowlist entry")]
+    InvalidAllowlistEntry,
+
+    #[msg("Invalid pool manager")]
+    InvalidPoolManager,
+
+    #[msg("Pool is not empty")]
+    PoolIsNotEmpty,
+
+    #[msg("Pending withdrawal is not claimed")]
+    PendingWithdrawalNotClaimed,
+
+    #[msg("Invalid locked fee extension")]
+    InvalidLockedFeeExtension,
+
+    #[msg("Invalid locked fee share")]
+    InvalidLockedFeeShare,
+
+    #[msg("Invalid protocol fee routing")]
+    InvalidProtocolFeeRouting,
+
+    #[msg("Invalid protocol fee destination")]
+    InvalidProtocolFeeDestination,
+
+    #[msg("Reward is not supported in fungible share mode")]
+    FungibleShareRewardNotSupported,
+
+    #[msg("Invalid pool permission")]
+    InvalidPoolPermission,
+
+    #[msg("Pool has been activated")]
+    PoolAlreadyActivated,
+
+    #[msg("Invalid writable pool")]
+    InvalidWritablePool,
 }
@@ -2,10 +2,15 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
     params::fee_parameters::PoolFeeParameters,
-    state::{SplitAmountInfo, SplitPositionInfo, SwapResult, SwapResult2},
-    AddLiquidityParameters, RemoveLiquidityParameters, SplitPositionParameters2, SwapParameters,
-    SwapParameters2,
+    state::{
+        ProtocolFeeDestinationParameters, SplitAmountInfo, SplitPositionInfo, SwapResult,
+        SwapResult2,
+    },
+    AddLiquidityParameters, ClaimedProtocolFee, DepositFungibleShareParameters,
+    RemoveLiquidityParameters, SplitPositionParameters2, StaticConfigParameters, SwapParameters,
+    SwapParameters2, UpdateTokenBadgeParameters, WithdrawFungibleShareParameters,
 };
 
 /// Close config
@@ -39,18 +44,170 @@ pub struct EvtCreateDynamicConfig {
     pub index: u64,
 }
 
+/// Update static config
+#[event]
+pub struct EvtUpdateConfig {
+    pub config: Pubkey,
+    pub admin: Pubkey,
+    /// Config version after the update
+    pub version: u64,
+    pub before: StaticConfigParameters,
+    pub after: StaticConfigParameters,
+}
+
 /// Create token badge
 #[event]
 pub struct EvtCreateTokenBadge {
     pub token_mint: Pubkey,
 }
 
+#[event]
+pub struct EvtUpdateTokenBadge {
+    pub token_mint: Pubkey,
+    pub params: UpdateTokenBadgeParameters,
+}
+
+#[event]
+pub struct EvtRefreshPoolTokenBadge {
+    pub pool: Pubkey,
+    pub token_mint: Pubkey,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct EvtRefreshPositionMetadata {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub vesting_end_point: u64,
+}
+
+#[event]
+pub struct EvtTransferPosition {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub fee_a_claimed: u64,
+    pub fee_b_claimed: u64,
+    pub rewards_claimed: [u64; NUM_REWARDS + NUM_EXTENSION_REWARDS],
+}
+
+#[event]
+pub struct EvtInitializeFungibleShare {
+    pub pool: Pubkey,
+    pub fungible_share: Pubkey,
+    pub position: Pubkey,
+    pub share_mint: Pubkey,
+    pub auto_compound: bool,
+}
+
+#[event]
+pub struct EvtDepositFungibleShare {
+    pub pool: Pubkey,
+    pub fungible_share: Pubkey,
+    pub owner: Pubkey,
+    pub params: DepositFungibleShareParameters,
+    pub liquidity_delta: u128,
+    pub share_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// pending fee of shared position paid by depositor
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+    pub total_amount_a: u64,
+    pub total_amount_b: u64,
+}
+
+#[event]
+pub struct EvtWithdrawFungibleShare {
+    pub pool: Pubkey,
+    pub fungible_share: Pubkey,
+    pub owner: Pubkey,
+    pub params: WithdrawFungibleShareParameters,
+    pub liquidity_delta: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// pending fee of shared position received by withdrawer
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+}
+
+#[event]
+pub struct EvtCompoundFungibleShare {
+    pub pool: Pubkey,
+    pub fungible_share: Pubkey,
+    pub liquidity_delta: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// unlocked liquidity of shared position after compounding
+    pub shared_liquidity: u128,
+    pub share_supply: u64,
+}
+
 /// Create claim fee operator
 #[event]
 pub struct EvtCreateClaimFeeOperator {
     pub operator: Pubkey,
 }
 
+#[event]
+pub struct EvtInitializeAdminRegistry {
+    pub super_admin: Pubkey,
+}
+
+#[event]
+pub struct EvtAddAdminRole {
+    pub admin: Pubkey,
+    pub role: u8,
+}
+
+#[event]
+pub struct EvtRemoveAdminRole {
+    pub admin: Pubkey,
+    pub role: u8,
+}
+
+#[event]
+pub struct EvtTransferSuperAdmin {
+    pub old_super_admin: Pubkey,
+    pub new_super_admin: Pubkey,
+}
+
+#[event]
+pub struct EvtUpdateTimelock {
+    pub activation_type: u8,
+    pub delay: u64,
+}
+
+#[event]
+pub struct EvtCreateProposal {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub target: Pubkey,
+    pub action: u8,
+    pub action_data: Vec<u8>,
+    pub activation_type: u8,
+    pub executable_point: u64,
+}
+
+#[event]
+pub struct EvtExecuteProposal {
+    pub proposal: Pubkey,
+    pub target: Pubkey,
+    pub action: u8,
+}
+
+#[event]
+pub struct EvtCancelProposal {
+    pub proposal: Pubkey,
+    pub canceller: Pubkey,
+    pub target: Pubkey,
+    pub action: u8,
+}
+
 /// Close claim fee operator
 #[event]
 pub struct EvtCloseClaimFeeOperator {
@@ -105,6 +262,15 @@ pub struct EvtClaimPositionFee {
     pub fee_b_claimed: u64,
 }
 
+#[event]
+pub struct EvtClaimPendingWithdrawal {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
 #[event]
 pub struct EvtCreatePosition {
     pub pool: Pubkey,
@@ -121,6 +287,18 @@ pub struct EvtClosePosition {
     pub position_nft_mint: Pubkey,
 }
 
+#[event]
+pub struct EvtClosePool {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    /// residual token a swept to treasury
+    pub token_a_amount: u64,
+    /// residual token b swept to treasury
+    pub token_b_amount: u64,
+}
+
 #[deprecated = "Please migrate to EvtLiquidityChange instead. This event will be removed in the future."]
 #[event]
 pub struct EvtRemoveLiquidity {
@@ -160,6 +338,8 @@ pub struct EvtSwap2 {
     pub current_timestamp: u64,
     pub reserve_a_amount: u64,
     pub reserve_b_amount: u64,
+    pub transfer_fee_bps_in: u16,
+    pub transfer_fee_bps_out: u16,
 }
 
 #[event]
@@ -174,6 +354,20 @@ pub struct EvtLockPosition {
     pub liquidity_per_period: u128,
     pub number_of_period: u16,
 }
+/// Pool initialized with liquidity migrated through CPI
+#[event]
+pub struct EvtMigratePool {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub migrator: Pubkey,
+    pub liquidity: u128,
+    pub permanent_lock_liquidity: u128,
+    /// Amounts transferred from migrator, including transfer fee
+    pub total_amount_a: u64,
+    pub total_amount_b: u64,
+}
+
 #[event]
 pub struct EvtPermanentLockPosition {
     pub pool: Pubkey,
@@ -189,6 +383,36 @@ pub struct EvtClaimProtocolFee {
     pub token_b_amount: u64,
 }
 
+#[event]
+pub struct EvtRouteProtocolFee {
+    pub pool: Pubkey,
+    /// owner of destination token accounts
+    pub destination: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[event]
+pub struct EvtInitializeProtocolFeeRouting {
+    pub admin: Pubkey,
+    pub destinations: Vec<ProtocolFeeDestinationParameters>,
+}
+
+#[event]
+pub struct EvtUpdateProtocolFeeRouting {
+    pub admin: Pubkey,
+    pub old_destinations: Vec<ProtocolFeeDestinationParameters>,
+    pub new_destinations: Vec<ProtocolFeeDestinationParameters>,
+}
+
+#[event]
+pub struct EvtBatchClaimProtocolFee {
+    pub claimed_pool_count: u32,
+    pub skipped_pool_count: u32,
+    /// total claimed amount of each mint
+    pub claimed_fees: Vec<ClaimedProtocolFee>,
+}
+
 #[event]
 pub struct EvtClaimPartnerFee {
     pub pool: Pubkey,
@@ -202,6 +426,121 @@ pub struct EvtSetPoolStatus {
     pub status: u8,
 }
 
+#[event]
+pub struct EvtSetPoolDisabledActions {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub previous_disabled_actions: u8,
+    pub disabled_actions: u8,
+    pub reason: String,
+}
+
+/// Update price range of a customizable pool
+#[event]
+pub struct EvtUpdatePriceRange {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub old_sqrt_min_price: u128,
+    pub old_sqrt_max_price: u128,
+    pub sqrt_min_price: u128,
+    pub sqrt_max_price: u128,
+    pub liquidity_scale_factor: u64,
+    /// Reserves after the update
+    pub reserve_a_amount: u64,
+    pub reserve_b_amount: u64,
+    /// Rounding dust of reserves which doesn't fit the new range, left in the vaults
+    pub excess_a_amount: u64,
+    pub excess_b_amount: u64,
+}
+
+#[event]
+pub struct EvtInitializeRewardExtension {
+    pub pool: Pubkey,
+    pub reward_extension: Pubkey,
+}
+
+#[event]
+pub struct EvtInitializeLockedFeeExtension {
+    pub pool: Pubkey,
+    pub locked_fee_extension: Pubkey,
+    pub locked_fee_share_bps: u16,
+}
+
+#[event]
+pub struct EvtCreatePositionRewardExtension {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_reward_extension: Pubkey,
+}
+
+#[event]
+pub struct EvtClosePositionRewardExtension {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub position: Pubkey,
+    pub position_reward_extension: Pubkey,
+}
+
+#[event]
+pub struct EvtInitializeBribeLedger {
+    pub pool: Pubkey,
+    pub bribe_ledger: Pubkey,
+}
+
+#[event]
+pub struct EvtCreatePositionBribeLedger {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_bribe_ledger: Pubkey,
+}
+
+#[event]
+pub struct EvtClosePositionBribeLedger {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub position: Pubkey,
+    pub position_bribe_ledger: Pubkey,
+}
+
+#[event]
+pub struct EvtInitializeBribeCampaign {
+    pub pool: Pubkey,
+    pub bribe_campaign: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub transfer_fee_excluded_amount_in: u64,
+}
+
+#[event]
+pub struct EvtClaimBribe {
+    pub pool: Pubkey,
+    pub bribe_campaign: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtWithdrawIneligibleBribe {
+    pub pool: Pubkey,
+    pub bribe_campaign: Pubkey,
+    pub funder: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtCloseBribeCampaign {
+    pub pool: Pubkey,
+    pub bribe_campaign: Pubkey,
+    pub funder: Pubkey,
+    pub epoch: u64,
+    pub withdrawn_amount: u64,
+}
+
 // Initialize reward
 #[event]
 pub struct EvtInitializeReward {
@@ -239,6 +578,24 @@ pub struct EvtFundReward {
     pub pre_reward_rate: u128,
     // reward rate after funding
     pub post_reward_rate: u128,
+    // reward duration start
+    pub reward_duration_start: u64,
+}
+
+#[event]
+pub struct EvtUpdateRewardBoost {
+    // Liquidity pool
+    pub pool: Pubkey,
+    // Index of the farm reward being updated
+    pub reward_index: u8,
+    // Old extra weight of permanent locked liquidity in bps
+    pub old_permanent_lock_boost_bps: u16,
+    // New extra weight of permanent locked liquidity in bps
+    pub new_permanent_lock_boost_bps: u16,
+    // Old extra weight of vesting locked liquidity in bps
+    pub old_vesting_lock_boost_bps: u16,
+    // New extra weight of vesting locked liquidity in bps
+    pub new_vesting_lock_boost_bps: u16,
 }
 
 #[event]
@@ -322,3 +679,30 @@ pub struct EvtLiquidityChange {
     // 0: add, 1: remove
     pub change_type: u8,
 }
+
+#[event]
+pub struct EvtInitializePoolPermission {
+    pub pool: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct EvtAddAllowlistEntry {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct EvtRemoveAllowlistEntry {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+pub struct EvtTransferPoolManager {
+    pub pool: Pubkey,
+    pub manager: Pubkey,
+    pub new_manager: Pubkey,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{AdminRegistry, AdminRole};
+
 #[cfg(not(feature = "devnet"))]
 pub mod admin {
     use anchor_lang::{prelude::Pubkey, solana_program::pubkey};
@@ -32,3 +34,32 @@ pub fn assert_eq_admin(admin: Pubkey) -> bool {
         .iter()
         .any(|predefined_admin| predefined_admin.eq(&admin))
 }
+
+/// Load admin registry, return None if admin registry is not initialized or invalid
+fn load_admin_registry(admin_registry: &AccountInfo) -> Option<AdminRegistry> {
+    if admin_registry.owner != &crate::ID {
+        return None;
+    }
+
+    let data = admin_registry.try_borrow_data().ok()?;
+    data.strip_prefix(AdminRegistry::DISCRIMINATOR)
+        .and_then(|data| data.get(..AdminRegistry::INIT_SPACE))
+        .and_then(|data| bytemuck::try_from_bytes::<AdminRegistry>(data).ok())
+        .copied()
+}
+
+/// Check if admin has the role in admin registry. Hard-coded admins hold all roles until admin registry is initialized
+pub fn assert_admin_role(admin_registry: &AccountInfo, admin: Pubkey, role: AdminRole) -> bool {
+    if admin_registry.owner != &crate::ID {
+        return assert_eq_admin(admin);
+    }
+
+    load_admin_registry(admin_registry)
+        .is_some_and(|admin_registry| admin_registry.has_role(admin, role))
+}
+
+/// Check if timelocked actions must be proposed instead of taking effect immediately
+pub fn is_timelock_enabled(admin_registry: &AccountInfo) -> bool {
+    load_admin_registry(admin_registry)
+        .is_some_and(|admin_registry| admin_registry.is_timelock_enabled())
+}
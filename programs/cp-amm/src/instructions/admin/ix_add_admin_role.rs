@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{AdminRegistry, AdminRole},
+    EvtAddAdminRole, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAdminRoleCtx<'info> {
+    #[account(mut, has_one = super_admin @ PoolError::InvalidAdmin)]
+    pub admin_registry: AccountLoader<'info, AdminRegistry>,
+
+    /// CHECK: admin whose role is updated
+    pub admin: UncheckedAccount<'info>,
+
+    pub super_admin: Signer<'info>,
+}
+
+pub fn handle_add_admin_role(ctx: Context<UpdateAdminRoleCtx>, role: u8) -> Result<()> {
+    let admin_role = AdminRole::try_from(role).map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut admin_registry = ctx.accounts.admin_registry.load_mut()?;
+    admin_registry.add_role(ctx.accounts.admin.key(), admin_role)?;
+
+    emit_cpi!(EvtAddAdminRole {
+        admin: ctx.accounts.admin.key(),
+        role,
+    });
+
+    Ok(())
+}
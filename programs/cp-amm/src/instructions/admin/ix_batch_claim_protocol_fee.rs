@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::collections::BTreeSet;
+
+use crate::{
+    const_pda,
+    constants::{seeds::PROTOCOL_FEE_ROUTING_PREFIX, treasury},
+    protocol_fee_routing::{
+        load_protocol_fee_destination_accounts, load_protocol_fee_routing,
+        transfer_protocol_fee_to_destinations,
+    },
+    safe_math::SafeMath,
+    state::{ClaimFeeOperator, Pool},
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+    EvtBatchClaimProtocolFee, EvtClaimProtocolFee, EvtRouteProtocolFee, PoolError,
+};
+
+/// Accounts for batch withdraw protocol fees, pool accounts are passed in remaining accounts
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchClaimProtocolFeesCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: protocol fee routing, protocol fee is sent to treasury until it is initialized
+    #[account(seeds = [PROTOCOL_FEE_ROUTING_PREFIX], bump)]
+    pub protocol_fee_routing: UncheckedAccount<'info>,
+
+    /// Claim fee operator
+    #[account(has_one = operator)]
+    pub claim_fee_operator: AccountLoader<'info, ClaimFeeOperator>,
+
+    /// Operator
+    pub operator: Signer<'info>,
+}
+
+/// Accounts of a pool to withdraw protocol fees, in the order passed in remaining accounts
+#[derive(Accounts)]
+pub struct ClaimProtocolFeePoolRemainingAccounts<'info> {
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault, has_one = token_a_mint, has_one = token_b_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// The treasury token a account
+    #[account(
+        mut,
+        associated_token::authority = treasury::ID,
+        associated_token::mint = token_a_mint,
+        associated_token::token_program = token_a_program,
+    )]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The treasury token b account
+    #[account(
+        mut,
+        associated_token::authority = treasury::ID,
+        associated_token::mint = token_b_mint,
+        associated_token::token_program = token_b_program,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ClaimedProtocolFee {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+fn add_claimed_protocol_fee(
+    claimed_fees: &mut Vec<ClaimedProtocolFee>,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    match claimed_fees.iter_mut().find(|fee| fee.mint == mint) {
+        Some(fee) => fee.amount = fee.amount.safe_add(amount)?,
+        None => claimed_fees.push(ClaimedProtocolFee { mint, amount }),
+    }
+    Ok(())
+}
+
+/// Withdraw all protocol fees of many pools. Pools without protocol fee are skipped.
+pub fn handle_batch_claim_protocol_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BatchClaimProtocolFeesCtx<'info>>,
+) -> Result<()> {
+    // remaining accounts layout, repeated for each pool:
+    // 1. pool, token a vault, token a mint, token a program, token b vault, token b mint, token b program,
+    // treasury token a account and treasury token b account
+    // 2. token a accounts of each destination followed by token b accounts, if protocol fee routing is initialized
+    // 3. transfer hook accounts of token a followed by token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    require!(!remaining_accounts.is_empty(), PoolError::InvalidInput);
+
+    let protocol_fee_routing = load_protocol_fee_routing(&ctx.accounts.protocol_fee_routing);
+
+    let mut claimed_pool_count: u32 = 0;
+    let mut skipped_pool_count: u32 = 0;
+    let mut claimed_fees: Vec<ClaimedProtocolFee> = vec![];
+
+    while !remaining_accounts.is_empty() {
+        let accounts = ClaimProtocolFeePoolRemainingAccounts::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut ClaimProtocolFeePoolRemainingAccountsBumps {},
+            &mut BTreeSet::new(),
+        )?;
+        let (destination_accounts_a, destination_accounts_b) = match &protocol_fee_routing {
+            Some(protocol_fee_routing) => (
+                load_protocol_fee_destination_accounts(
+                    protocol_fee_routing,
+                    accounts.token_a_mint.key(),
+                    &mut remaining_accounts,
+                )?,
+                load_protocol_fee_destination_accounts(
+                    protocol_fee_routing,
+                    accounts.token_b_mint.key(),
+                    &mut remaining_accounts,
+                )?,
+            ),
+            None => (vec![], vec![]),
+        };
+        let transfer_hook_accounts_a =
+            load_transfer_hook_accounts(&accounts.token_a_mint, &mut remaining_accounts)?;
+        let transfer_hook_accounts_b =
+            load_transfer_hook_accounts(&accounts.token_b_mint, &mut remaining_accounts)?;
+
+        let (token_a_amount, token_b_amount) = accounts
+            .pool
+            .load_mut()?
+            .claim_protocol_fee(u64::MAX, u64::MAX)?;
+
+        if token_a_amount == 0 && token_b_amount == 0 {
+            skipped_pool_count = skipped_pool_count.safe_add(1)?;
+            continue;
+        }
+
+        if let Some(protocol_fee_routing) = &protocol_fee_routing {
+            let amounts_a = protocol_fee_routing.get_destination_amounts(token_a_amount)?;
+            let amounts_b = protocol_fee_routing.get_destination_amounts(token_b_amount)?;
+
+            transfer_protocol_fee_to_destinations(
+                ctx.accounts.pool_authority.to_account_info(),
+                &accounts.token_a_mint,
+                &accounts.token_a_vault,
+                &accounts.token_a_program,
+                &destination_accounts_a,
+                &amounts_a,
+                transfer_hook_accounts_a,
+            )?;
+            transfer_protocol_fee_to_destinations(
+                ctx.accounts.pool_authority.to_account_info(),
+                &accounts.token_b_mint,
+                &accounts.token_b_vault,
+                &accounts.token_b_program,
+                &destination_accounts_b,
+                &amounts_b,
+                transfer_hook_accounts_b,
+            )?;
+
+            for ((destination, token_a_amount), token_b_amount) in protocol_fee_routing
+                .destinations()
+                .iter()
+                .zip(amounts_a)
+                .zip(amounts_b)
+            {
+                emit_cpi!(EvtRouteProtocolFee {
+                    pool: accounts.pool.key(),
+                    destination: destination.owner,
+                    token_a_amount,
+                    token_b_amount,
+                });
+            }
+        } else {
+            if token_a_amount > 0 {
+                transfer_from_pool(
+                    ctx.accounts.pool_authority.to_account_info(),
+                    &accounts.token_a_mint,
+                    &accounts.token_a_vault,
+                    &accounts.token_a_account,
+                    &accounts.token_a_program,
+                    token_a_amount,
+                    transfer_hook_accounts_a,
+                )?;
+            }
+
+            if token_b_amount > 0 {
+                transfer_from_pool(
+                    ctx.accounts.pool_authority.to_account_info(),
+                    &accounts.token_b_mint,
+                    &accounts.token_b_vault,
+                    &accounts.token_b_account,
+                    &accounts.token_b_program,
+                    token_b_amount,
+                    transfer_hook_accounts_b,
+                )?;
+            }
+        }
+
+        add_claimed_protocol_fee(
+            &mut claimed_fees,
+            accounts.token_a_mint.key(),
+            token_a_amount,
+        )?;
+        add_claimed_protocol_fee(
+            &mut claimed_fees,
+            accounts.token_b_mint.key(),
+            token_b_amount,
+        )?;
+        claimed_pool_count = claimed_pool_count.safe_add(1)?;
+
+        emit_cpi!(EvtClaimProtocolFee {
+            pool: accounts.pool.key(),
+            token_a_amount,
+            token_b_amount
+        });
+    }
+
+    emit_cpi!(EvtBatchClaimProtocolFee {
+        claimed_pool_count,
+        skipped_pool_count,
+        claimed_fees,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    state::{AdminRole, Proposal},
+    EvtCancelProposal, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelProposalCtx<'info> {
+    #[account(mut, has_one = proposer, close = proposer)]
+    pub proposal: AccountLoader<'info, Proposal>,
+
+    /// CHECK: proposer receives rent of the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, canceller.key(), AdminRole::ProposalCanceller) @ PoolError::InvalidAdmin,
+    )]
+    pub canceller: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+pub fn handle_cancel_proposal(ctx: Context<CancelProposalCtx>) -> Result<()> {
+    let proposal = ctx.accounts.proposal.load()?;
+
+    emit_cpi!(EvtCancelProposal {
+        proposal: ctx.accounts.proposal.key(),
+        canceller: ctx.accounts.canceller.key(),
+        target: proposal.target,
+        action: proposal.action,
+    });
+
+    Ok(())
+}
@@ -3,10 +3,14 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     const_pda,
-    constants::treasury,
+    constants::{seeds::PROTOCOL_FEE_ROUTING_PREFIX, treasury},
+    protocol_fee_routing::{
+        load_protocol_fee_destination_accounts, load_protocol_fee_routing,
+        transfer_protocol_fee_to_destinations,
+    },
     state::{ClaimFeeOperator, Pool},
-    token::transfer_from_pool,
-    EvtClaimProtocolFee,
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+    EvtClaimProtocolFee, EvtRouteProtocolFee,
 };
 
 /// Accounts for withdraw protocol fees
@@ -52,6 +56,10 @@ pub struct ClaimProtocolFeesCtx<'info> {
     )]
     pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: protocol fee routing, protocol fee is sent to treasury until it is initialized
+    #[account(seeds = [PROTOCOL_FEE_ROUTING_PREFIX], bump)]
+    pub protocol_fee_routing: UncheckedAccount<'info>,
+
     /// Claim fee operator
     #[account(has_one = operator)]
     pub claim_fee_operator: AccountLoader<'info, ClaimFeeOperator>,
@@ -67,35 +75,100 @@ pub struct ClaimProtocolFeesCtx<'info> {
 }
 
 /// Withdraw protocol fees. Permissionless.
-pub fn handle_claim_protocol_fee(
-    ctx: Context<ClaimProtocolFeesCtx>,
+pub fn handle_claim_protocol_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimProtocolFeesCtx<'info>>,
     max_amount_a: u64,
     max_amount_b: u64,
 ) -> Result<()> {
     let mut pool = ctx.accounts.pool.load_mut()?;
 
+    // remaining accounts layout:
+    // 1. token a accounts of each destination followed by token b accounts, if protocol fee routing is initialized
+    // 2. transfer hook accounts of token a followed by token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let protocol_fee_routing = load_protocol_fee_routing(&ctx.accounts.protocol_fee_routing);
+    let (destination_accounts_a, destination_accounts_b) = match &protocol_fee_routing {
+        Some(protocol_fee_routing) => (
+            load_protocol_fee_destination_accounts(
+                protocol_fee_routing,
+                ctx.accounts.token_a_mint.key(),
+                &mut remaining_accounts,
+            )?,
+            load_protocol_fee_destination_accounts(
+                protocol_fee_routing,
+                ctx.accounts.token_b_mint.key(),
+                &mut remaining_accounts,
+            )?,
+        ),
+        None => (vec![], vec![]),
+    };
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
     let (token_a_amount, token_b_amount) = pool.claim_protocol_fee(max_amount_a, max_amount_b)?;
 
-    if token_a_amount > 0 {
-        transfer_from_pool(
+    if let Some(protocol_fee_routing) = protocol_fee_routing {
+        let amounts_a = protocol_fee_routing.get_destination_amounts(token_a_amount)?;
+        let amounts_b = protocol_fee_routing.get_destination_amounts(token_b_amount)?;
+
+        transfer_protocol_fee_to_destinations(
             ctx.accounts.pool_authority.to_account_info(),
             &ctx.accounts.token_a_mint,
             &ctx.accounts.token_a_vault,
-            &ctx.accounts.token_a_account,
             &ctx.accounts.token_a_program,
-            token_a_amount,
+            &destination_accounts_a,
+            &amounts_a,
+            transfer_hook_accounts_a,
         )?;
-    }
-
-    if token_b_amount > 0 {
-        transfer_from_pool(
+        transfer_protocol_fee_to_destinations(
             ctx.accounts.pool_authority.to_account_info(),
             &ctx.accounts.token_b_mint,
             &ctx.accounts.token_b_vault,
-            &ctx.accounts.token_b_account,
             &ctx.accounts.token_b_program,
-            token_b_amount,
+            &destination_accounts_b,
+            &amounts_b,
+            transfer_hook_accounts_b,
         )?;
+
+        for ((destination, token_a_amount), token_b_amount) in protocol_fee_routing
+            .destinations()
+            .iter()
+            .zip(amounts_a)
+            .zip(amounts_b)
+        {
+            emit_cpi!(EvtRouteProtocolFee {
+                pool: ctx.accounts.pool.key(),
+                destination: destination.owner,
+                token_a_amount,
+                token_b_amount,
+            });
+        }
+    } else {
+        if token_a_amount > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &ctx.accounts.token_a_mint,
+                &ctx.accounts.token_a_vault,
+                &ctx.accounts.token_a_account,
+                &ctx.accounts.token_a_program,
+                token_a_amount,
+                transfer_hook_accounts_a,
+            )?;
+        }
+
+        if token_b_amount > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &ctx.accounts.token_b_mint,
+                &ctx.accounts.token_b_vault,
+                &ctx.accounts.token_b_account,
+                &ctx.accounts.token_b_program,
+                token_b_amount,
+                transfer_hook_accounts_b,
+            )?;
+        }
     }
 
     emit_cpi!(EvtClaimProtocolFee {
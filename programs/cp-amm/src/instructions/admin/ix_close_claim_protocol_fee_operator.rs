@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{assert_eq_admin, state::ClaimFeeOperator, EvtCloseClaimFeeOperator, PoolError};
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    state::{AdminRole, ClaimFeeOperator},
+    EvtCloseClaimFeeOperator, PoolError,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -16,9 +21,13 @@ pub struct CloseClaimFeeOperatorCtx<'info> {
     pub rent_receiver: UncheckedAccount<'info>,
 
     #[account(
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::FeeOperatorManager) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
 }
 
 pub fn handle_close_claim_fee_operator(ctx: Context<CloseClaimFeeOperatorCtx>) -> Result<()> {
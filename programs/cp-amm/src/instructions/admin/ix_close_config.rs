@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::{assert_eq_admin, event, state::Config, PoolError};
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    event, is_timelock_enabled,
+    state::{AdminRole, Config},
+    PoolError,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -12,15 +18,27 @@ pub struct CloseConfigCtx<'info> {
     )]
     pub config: AccountLoader<'info, Config>,
 
-    #[account(mut, constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
+    #[account(
+        mut,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ConfigManager) @ PoolError::InvalidAdmin,
+    )]
     pub admin: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     /// CHECK: Account to receive closed account rental SOL
     #[account(mut)]
     pub rent_receiver: UncheckedAccount<'info>,
 }
 
 pub fn handle_close_config(ctx: Context<CloseConfigCtx>) -> Result<()> {
+    require!(
+        !is_timelock_enabled(&ctx.accounts.admin_registry),
+        PoolError::TimelockRequired
+    );
+
     emit_cpi!(event::EvtCloseConfig {
         config: ctx.accounts.config.key(),
         admin: ctx.accounts.admin.key(),
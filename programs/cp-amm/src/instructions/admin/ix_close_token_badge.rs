@@ -1,4 +1,9 @@
-use crate::{assert_eq_admin, state::TokenBadge, PoolError};
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    state::{AdminRole, TokenBadge},
+    PoolError,
+};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
@@ -12,10 +17,14 @@ pub struct CloseTokenBadgeCtx<'info> {
 
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::TokenBadgeManager) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     /// CHECK: Account to receive closed account rental SOL
     #[account(mut)]
     pub rent_receiver: UncheckedAccount<'info>,
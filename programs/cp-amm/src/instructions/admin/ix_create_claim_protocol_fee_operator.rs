@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    assert_eq_admin, constants::seeds::CLAIM_FEE_OPERATOR_PREFIX, state::ClaimFeeOperator,
+    assert_admin_role,
+    constants::seeds::{ADMIN_REGISTRY_PREFIX, CLAIM_FEE_OPERATOR_PREFIX},
+    state::{AdminRole, ClaimFeeOperator},
     EvtCreateClaimFeeOperator, PoolError,
 };
 
@@ -25,10 +27,14 @@ pub struct CreateClaimFeeOperatorCtx<'info> {
 
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::FeeOperatorManager) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
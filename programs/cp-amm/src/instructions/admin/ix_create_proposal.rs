@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{ADMIN_REGISTRY_PREFIX, PROPOSAL_PREFIX},
+    state::{AdminRegistry, Proposal, ProposalAction},
+    EvtCreateProposal, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreateProposalParameters {
+    /// action of the proposal, refer [ProposalAction]
+    pub action: u8,
+    /// borsh serialized parameters of the action
+    pub action_data: Vec<u8>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateProposalParameters)]
+pub struct CreateProposalCtx<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [
+            PROPOSAL_PREFIX,
+            target.key().as_ref(),
+            &[params.action],
+        ],
+        bump,
+        space = 8 + Proposal::INIT_SPACE
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+
+    /// CHECK: account the action is applied to, validated when the proposal is executed
+    #[account(owner = crate::ID @ PoolError::InvalidProposalTarget)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: AccountLoader<'info, AdminRegistry>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_proposal(
+    ctx: Context<CreateProposalCtx>,
+    params: CreateProposalParameters,
+) -> Result<()> {
+    let action =
+        ProposalAction::try_from(params.action).map_err(|_| PoolError::InvalidParameters)?;
+
+    let admin_registry = ctx.accounts.admin_registry.load()?;
+    require!(
+        admin_registry.has_role(ctx.accounts.proposer.key(), action.get_admin_role()),
+        PoolError::InvalidAdmin
+    );
+
+    let mut proposal = ctx.accounts.proposal.load_init()?;
+    proposal.initialize(
+        ctx.accounts.proposer.key(),
+        ctx.accounts.target.key(),
+        action,
+        &params.action_data,
+        admin_registry.timelock_activation_type,
+        admin_registry.timelock_delay,
+    )?;
+
+    emit_cpi!(EvtCreateProposal {
+        proposal: ctx.accounts.proposal.key(),
+        proposer: ctx.accounts.proposer.key(),
+        target: ctx.accounts.target.key(),
+        action: params.action,
+        action_data: params.action_data,
+        activation_type: proposal.activation_type,
+        executable_point: proposal.executable_point,
+    });
+
+    Ok(())
+}
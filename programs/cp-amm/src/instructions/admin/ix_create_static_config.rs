@@ -2,15 +2,18 @@ use anchor_lang::prelude::*;
 
 use crate::{
     activation_handler::{ActivationHandler, ActivationType},
-    assert_eq_admin,
-    constants::{seeds::CONFIG_PREFIX, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    assert_admin_role,
+    constants::{
+        seeds::{ADMIN_REGISTRY_PREFIX, CONFIG_PREFIX},
+        MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    },
     event,
     params::{activation::ActivationParams, fee_parameters::PoolFeeParameters},
-    state::{CollectFeeMode, Config},
+    state::{AdminRole, CollectFeeMode, Config},
     PoolError,
 };
 
-#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
 pub struct StaticConfigParameters {
     pub pool_fees: PoolFeeParameters,
     pub sqrt_min_price: u128,
@@ -21,6 +24,52 @@ pub struct StaticConfigParameters {
     pub collect_fee_mode: u8,
 }
 
+impl StaticConfigParameters {
+    pub fn validate(&self) -> Result<()> {
+        let &StaticConfigParameters {
+            sqrt_min_price,
+            sqrt_max_price,
+            vault_config_key,
+            activation_type,
+            collect_fee_mode,
+            ..
+        } = self;
+
+        require!(
+            sqrt_min_price >= MIN_SQRT_PRICE && sqrt_max_price <= MAX_SQRT_PRICE,
+            PoolError::InvalidPriceRange
+        );
+        // TODO do we need more buffer here?
+        require!(
+            sqrt_min_price < sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+
+        let has_alpha_vault = vault_config_key.ne(&Pubkey::default());
+
+        let activation_point = Some(ActivationHandler::get_max_activation_point(
+            activation_type,
+        )?);
+
+        let activation_params = ActivationParams {
+            activation_point,
+            activation_type,
+            has_alpha_vault,
+        };
+        activation_params.validate()?;
+
+        let pool_activation_type = ActivationType::try_from(activation_type)
+            .map_err(|_| PoolError::InvalidActivationType)?;
+
+        let pool_collect_fee_mode = CollectFeeMode::try_from(collect_fee_mode)
+            .map_err(|_| PoolError::InvalidCollectFeeMode)?;
+        self.pool_fees
+            .validate(pool_collect_fee_mode, pool_activation_type)?;
+
+        Ok(())
+    }
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(index: u64)]
@@ -34,9 +83,16 @@ pub struct CreateConfigCtx<'info> {
     )]
     pub config: AccountLoader<'info, Config>,
 
-    #[account(mut, constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
+    #[account(
+        mut,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ConfigManager) @ PoolError::InvalidAdmin,
+    )]
     pub admin: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -45,6 +101,8 @@ pub fn handle_create_static_config(
     index: u64,
     config_parameters: StaticConfigParameters,
 ) -> Result<()> {
+    config_parameters.validate()?;
+
     let StaticConfigParameters {
         pool_fees,
         vault_config_key,
@@ -55,36 +113,6 @@ pub fn handle_create_static_config(
         collect_fee_mode,
     } = config_parameters;
 
-    require!(
-        sqrt_min_price >= MIN_SQRT_PRICE && sqrt_max_price <= MAX_SQRT_PRICE,
-        PoolError::InvalidPriceRange
-    );
-    // TODO do we need more buffer here?
-    require!(
-        sqrt_min_price < sqrt_max_price,
-        PoolError::InvalidPriceRange
-    );
-
-    let has_alpha_vault = vault_config_key.ne(&Pubkey::default());
-
-    let activation_point = Some(ActivationHandler::get_max_activation_point(
-        activation_type,
-    )?);
-
-    let activation_params = ActivationParams {
-        activation_point,
-        activation_type,
-        has_alpha_vault,
-    };
-    activation_params.validate()?;
-
-    let pool_activation_type =
-        ActivationType::try_from(activation_type).map_err(|_| PoolError::InvalidActivationType)?;
-
-    let pool_collect_fee_mode =
-        CollectFeeMode::try_from(collect_fee_mode).map_err(|_| PoolError::InvalidCollectFeeMode)?;
-    pool_fees.validate(pool_collect_fee_mode, pool_activation_type)?;
-
     let mut config = ctx.accounts.config.load_init()?;
     config.init_static_config(
         index,
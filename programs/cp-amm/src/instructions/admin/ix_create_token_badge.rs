@@ -2,8 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
 use crate::{
-    assert_eq_admin, constants::seeds::TOKEN_BADGE_PREFIX, state::TokenBadge,
-    token::is_supported_mint, EvtCreateTokenBadge, PoolError,
+    assert_admin_role,
+    constants::seeds::{ADMIN_REGISTRY_PREFIX, TOKEN_BADGE_PREFIX},
+    state::{AdminRole, TokenBadge},
+    token::is_supported_mint,
+    EvtCreateTokenBadge, PoolError,
 };
 
 #[event_cpi]
@@ -25,10 +28,14 @@ pub struct CreateTokenBadgeCtx<'info> {
 
     #[account(
         mut,
-        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::TokenBadgeManager) @ PoolError::InvalidAdmin,
     )]
     pub admin: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_eq_admin, constants::seeds::ADMIN_REGISTRY_PREFIX, state::AdminRegistry,
+    EvtInitializeAdminRegistry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeAdminRegistryCtx<'info> {
+    #[account(
+        init,
+        payer = admin,
+        seeds = [ADMIN_REGISTRY_PREFIX],
+        bump,
+        space = 8 + AdminRegistry::INIT_SPACE
+    )]
+    pub admin_registry: AccountLoader<'info, AdminRegistry>,
+
+    /// CHECK: super admin
+    pub super_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_admin_registry(ctx: Context<InitializeAdminRegistryCtx>) -> Result<()> {
+    let mut admin_registry = ctx.accounts.admin_registry.load_init()?;
+    admin_registry.initialize(ctx.accounts.super_admin.key());
+
+    emit_cpi!(EvtInitializeAdminRegistry {
+        super_admin: ctx.accounts.super_admin.key(),
+    });
+
+    Ok(())
+}
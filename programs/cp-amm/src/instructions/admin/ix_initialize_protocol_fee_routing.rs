@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::seeds::{ADMIN_REGISTRY_PREFIX, PROTOCOL_FEE_ROUTING_PREFIX},
+    state::{AdminRole, ProtocolFeeDestinationParameters, ProtocolFeeRouting},
+    EvtInitializeProtocolFeeRouting, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeRoutingCtx<'info> {
+    #[account(
+        init,
+        payer = admin,
+        seeds = [PROTOCOL_FEE_ROUTING_PREFIX],
+        bump,
+        space = 8 + ProtocolFeeRouting::INIT_SPACE
+    )]
+    pub protocol_fee_routing: AccountLoader<'info, ProtocolFeeRouting>,
+
+    #[account(
+        mut,
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ProtocolFeeRoutingManager) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_protocol_fee_routing(
+    ctx: Context<InitializeProtocolFeeRoutingCtx>,
+    destinations: Vec<ProtocolFeeDestinationParameters>,
+) -> Result<()> {
+    let mut protocol_fee_routing = ctx.accounts.protocol_fee_routing.load_init()?;
+    protocol_fee_routing.update(&destinations)?;
+
+    emit_cpi!(EvtInitializeProtocolFeeRouting {
+        admin: ctx.accounts.admin.key(),
+        destinations,
+    });
+
+    Ok(())
+}
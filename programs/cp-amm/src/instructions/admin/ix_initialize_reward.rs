@@ -2,14 +2,19 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    const_pda,
+    assert_admin_role, const_pda,
     constants::{
-        seeds::REWARD_VAULT_PREFIX, MAX_REWARD_DURATION, MIN_REWARD_DURATION, NUM_REWARDS,
+        seeds::{ADMIN_REGISTRY_PREFIX, REWARD_VAULT_PREFIX},
+        MAX_REWARD_DURATION, MIN_REWARD_DURATION, NUM_EXTENSION_REWARDS, NUM_REWARDS,
     },
     error::PoolError,
     event::EvtInitializeReward,
-    state::Pool,
-    token::{get_token_program_flags, is_supported_mint, is_token_badge_initialized},
+    reward_extension::load_reward_extension,
+    state::{
+        get_reward_info, get_reward_info_mut, is_extension_reward_index, AdminRole, Pool,
+        RewardExtension,
+    },
+    token::{get_token_program_flags, is_supported_mint, validate_token_badge},
 };
 
 #[event_cpi]
@@ -37,6 +42,10 @@ pub struct InitializeRewardCtx<'info> {
 
     pub signer: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -45,21 +54,40 @@ pub struct InitializeRewardCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> InitializeRewardCtx<'info> {
-    fn validate(&self, reward_index: usize, reward_duration: u64) -> Result<()> {
+impl InitializeRewardCtx<'_> {
+    fn validate(
+        &self,
+        reward_index: usize,
+        reward_duration: u64,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
         let pool = self.pool.load()?;
 
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
 
         require!(
-            reward_duration >= MIN_REWARD_DURATION && reward_duration <= MAX_REWARD_DURATION,
+            (MIN_REWARD_DURATION..=MAX_REWARD_DURATION).contains(&reward_duration),
             PoolError::InvalidRewardDuration
         );
 
-        let reward_info = &pool.reward_infos[reward_index];
+        // shared position of fungible share mode can't claim rewards on behalf of share holders
+        require!(
+            !pool.is_fungible_share_enabled(),
+            PoolError::FungibleShareRewardNotSupported
+        );
+
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
         require!(!reward_info.initialized(), PoolError::RewardInitialized);
 
-        pool.validate_authority_to_edit_reward(reward_index, self.signer.key())?;
+        let is_reward_manager = assert_admin_role(
+            &self.admin_registry,
+            self.signer.key(),
+            AdminRole::RewardManager,
+        );
+        pool.validate_authority_to_edit_reward(reward_index, self.signer.key(), is_reward_manager)?;
 
         Ok(())
     }
@@ -71,26 +99,42 @@ pub fn handle_initialize_reward<'c: 'info, 'info>(
     reward_duration: u64,
     funder: Pubkey,
 ) -> Result<()> {
+    let index: usize = reward_index.into();
+
+    // reward extension is passed before token badge in remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loader = if is_extension_reward_index(index) {
+        Some(load_reward_extension(
+            ctx.accounts.pool.key(),
+            &mut remaining_accounts,
+        )?)
+    } else {
+        None
+    };
+
     if !is_supported_mint(&ctx.accounts.reward_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.reward_mint.key(),
-                ctx.remaining_accounts
-                    .get(0)
-                    .ok_or(PoolError::InvalidTokenBadge)?
-            )?,
-            PoolError::InvalidTokenBadge
-        );
+        validate_token_badge(
+            &ctx.accounts.reward_mint,
+            remaining_accounts
+                .first()
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?;
     }
 
-    let index: usize = reward_index
-        .try_into()
-        .map_err(|_| PoolError::TypeCastFailed)?;
+    let mut reward_extension = reward_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
 
-    ctx.accounts.validate(index, reward_duration)?;
+    ctx.accounts
+        .validate(index, reward_duration, reward_extension.as_deref())?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let reward_info = &mut pool.reward_infos[index];
+    if reward_extension.is_some() {
+        // from now on, reward extension must be updated whenever pool liquidity changes
+        pool.reward_extension_flag = 1;
+    }
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
 
     reward_info.init_reward(
         ctx.accounts.reward_mint.key(),
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::AdminRole, EvtRemoveAdminRole, PoolError};
+
+use super::UpdateAdminRoleCtx;
+
+pub fn handle_remove_admin_role(ctx: Context<UpdateAdminRoleCtx>, role: u8) -> Result<()> {
+    let admin_role = AdminRole::try_from(role).map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut admin_registry = ctx.accounts.admin_registry.load_mut()?;
+    admin_registry.remove_role(ctx.accounts.admin.key(), admin_role)?;
+
+    emit_cpi!(EvtRemoveAdminRole {
+        admin: ctx.accounts.admin.key(),
+        role,
+    });
+
+    Ok(())
+}
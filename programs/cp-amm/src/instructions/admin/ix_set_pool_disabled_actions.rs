@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::{seeds::ADMIN_REGISTRY_PREFIX, MAX_DISABLE_REASON_LENGTH},
+    event, is_timelock_enabled,
+    state::{AdminRole, Pool},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolDisabledActionsCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::PoolStatusOperator) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+pub fn handle_set_pool_disabled_actions(
+    ctx: Context<SetPoolDisabledActionsCtx>,
+    disabled_actions: u8,
+    reason: String,
+) -> Result<()> {
+    require!(
+        reason.len() <= MAX_DISABLE_REASON_LENGTH,
+        PoolError::InvalidParameters
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let previous_disabled_actions = pool.disabled_actions;
+    // disabling more actions takes effect immediately to halt the pool in an incident, re-enabling
+    // actions must be proposed through timelock
+    require!(
+        previous_disabled_actions & !disabled_actions == 0
+            || !is_timelock_enabled(&ctx.accounts.admin_registry),
+        PoolError::TimelockRequired
+    );
+    pool.update_disabled_actions(disabled_actions)?;
+
+    emit_cpi!(event::EvtSetPoolDisabledActions {
+        pool: ctx.accounts.pool.key(),
+        admin: ctx.accounts.admin.key(),
+        previous_disabled_actions,
+        disabled_actions,
+        reason,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    assert_eq_admin, event,
-    state::{Pool, PoolStatus},
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    event, is_timelock_enabled,
+    state::{AdminRole, Pool, PoolStatus},
     PoolError,
 };
 
@@ -13,21 +15,27 @@ pub struct SetPoolStatusCtx<'info> {
     #[account(mut)]
     pub pool: AccountLoader<'info, Pool>,
 
-    #[account(constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin)]
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::PoolStatusOperator) @ PoolError::InvalidAdmin,
+    )]
     pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
 }
 
 pub fn handle_set_pool_status(ctx: Context<SetPoolStatusCtx>, status: u8) -> Result<()> {
-    let mut pool = ctx.accounts.pool.load_mut()?;
-    let new_pool_status = PoolStatus::try_from(status).map_err(|_| PoolError::TypeCastFailed)?;
-    let current_pool_status =
-        PoolStatus::try_from(pool.pool_status).map_err(|_| PoolError::TypeCastFailed)?;
-
+    // disabling the pool takes effect immediately to halt it in an incident, enabling the pool
+    // must be proposed through timelock
     require!(
-        new_pool_status != current_pool_status,
-        PoolError::InvalidPoolStatus
+        status == Into::<u8>::into(PoolStatus::Disable)
+            || !is_timelock_enabled(&ctx.accounts.admin_registry),
+        PoolError::TimelockRequired
     );
-    pool.pool_status = new_pool_status.into();
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.update_pool_status(status)?;
 
     emit_cpi!(event::EvtSetPoolStatus {
         pool: ctx.accounts.pool.key(),
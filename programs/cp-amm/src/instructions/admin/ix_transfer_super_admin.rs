@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::AdminRegistry, EvtTransferSuperAdmin, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferSuperAdminCtx<'info> {
+    #[account(mut, has_one = super_admin @ PoolError::InvalidAdmin)]
+    pub admin_registry: AccountLoader<'info, AdminRegistry>,
+
+    /// CHECK: new super admin
+    pub new_super_admin: UncheckedAccount<'info>,
+
+    pub super_admin: Signer<'info>,
+}
+
+pub fn handle_transfer_super_admin(ctx: Context<TransferSuperAdminCtx>) -> Result<()> {
+    let new_super_admin = ctx.accounts.new_super_admin.key();
+    require!(
+        new_super_admin != Pubkey::default(),
+        PoolError::InvalidParameters
+    );
+
+    let mut admin_registry = ctx.accounts.admin_registry.load_mut()?;
+    admin_registry.super_admin = new_super_admin;
+
+    emit_cpi!(EvtTransferSuperAdmin {
+        old_super_admin: ctx.accounts.super_admin.key(),
+        new_super_admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    event::EvtUpdateConfig,
+    is_timelock_enabled,
+    state::{AdminRole, Config, ConfigType},
+    PoolError, StaticConfigParameters,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfigCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ConfigManager) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+fn get_static_config_parameters(config: &Config) -> StaticConfigParameters {
+    StaticConfigParameters {
+        pool_fees: config.pool_fees.to_pool_fee_parameters(),
+        sqrt_min_price: config.sqrt_min_price,
+        sqrt_max_price: config.sqrt_max_price,
+        vault_config_key: config.vault_config_key,
+        pool_creator_authority: config.pool_creator_authority,
+        activation_type: config.activation_type,
+        collect_fee_mode: config.collect_fee_mode,
+    }
+}
+
+/// Update parameters of the static config, shared by the instruction and the timelocked proposal
+pub fn update_static_config_parameters(
+    config_key: Pubkey,
+    config: &mut Config,
+    admin: Pubkey,
+    config_parameters: StaticConfigParameters,
+) -> Result<EvtUpdateConfig> {
+    config_parameters.validate()?;
+
+    // dynamic config doesn't store any pool parameters
+    require!(
+        config.get_config_type()? == ConfigType::Static,
+        PoolError::InvalidConfigType
+    );
+
+    let before = get_static_config_parameters(config);
+
+    config.update_static_config(&config_parameters)?;
+
+    Ok(EvtUpdateConfig {
+        config: config_key,
+        admin,
+        version: config.version,
+        before,
+        after: get_static_config_parameters(config),
+    })
+}
+
+pub fn handle_update_config(
+    ctx: Context<UpdateConfigCtx>,
+    config_parameters: StaticConfigParameters,
+) -> Result<()> {
+    require!(
+        !is_timelock_enabled(&ctx.accounts.admin_registry),
+        PoolError::TimelockRequired
+    );
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    let event = update_static_config_parameters(
+        ctx.accounts.config.key(),
+        &mut config,
+        ctx.accounts.admin.key(),
+        config_parameters,
+    )?;
+
+    emit_cpi!(event);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    event::EvtUpdatePriceRange,
+    is_timelock_enabled,
+    state::{AdminRole, Pool, PoolType},
+    PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePriceRangeCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ConfigManager) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+/// Update price range of the pool, shared by the instruction and the timelocked proposal
+pub fn update_pool_price_range(
+    pool_key: Pubkey,
+    pool: &mut Pool,
+    admin: Pubkey,
+    sqrt_min_price: u128,
+    sqrt_max_price: u128,
+) -> Result<EvtUpdatePriceRange> {
+    // price range of config pools is bound to their config
+    require!(
+        pool.pool_type == Into::<u8>::into(PoolType::Customizable) || pool.is_permissioned(),
+        PoolError::InvalidPoolType
+    );
+
+    let old_sqrt_min_price = pool.sqrt_min_price;
+    let old_sqrt_max_price = pool.sqrt_max_price;
+    let (excess_a_amount, excess_b_amount) =
+        pool.update_price_range(sqrt_min_price, sqrt_max_price)?;
+    let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount()?;
+
+    Ok(EvtUpdatePriceRange {
+        pool: pool_key,
+        admin,
+        old_sqrt_min_price,
+        old_sqrt_max_price,
+        sqrt_min_price,
+        sqrt_max_price,
+        liquidity_scale_factor: pool.liquidity_scale_factor,
+        reserve_a_amount,
+        reserve_b_amount,
+        excess_a_amount,
+        excess_b_amount,
+    })
+}
+
+pub fn handle_update_price_range(
+    ctx: Context<UpdatePriceRangeCtx>,
+    sqrt_min_price: u128,
+    sqrt_max_price: u128,
+) -> Result<()> {
+    require!(
+        !is_timelock_enabled(&ctx.accounts.admin_registry),
+        PoolError::TimelockRequired
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let event = update_pool_price_range(
+        ctx.accounts.pool.key(),
+        &mut pool,
+        ctx.accounts.admin.key(),
+        sqrt_min_price,
+        sqrt_max_price,
+    )?;
+
+    emit_cpi!(event);
+
+    Ok(())
+}
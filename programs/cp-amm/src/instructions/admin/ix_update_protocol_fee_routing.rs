@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::seeds::ADMIN_REGISTRY_PREFIX,
+    state::{AdminRole, ProtocolFeeDestinationParameters, ProtocolFeeRouting},
+    EvtUpdateProtocolFeeRouting, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateProtocolFeeRoutingCtx<'info> {
+    #[account(mut)]
+    pub protocol_fee_routing: AccountLoader<'info, ProtocolFeeRouting>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::ProtocolFeeRoutingManager) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+pub fn handle_update_protocol_fee_routing(
+    ctx: Context<UpdateProtocolFeeRoutingCtx>,
+    destinations: Vec<ProtocolFeeDestinationParameters>,
+) -> Result<()> {
+    let mut protocol_fee_routing = ctx.accounts.protocol_fee_routing.load_mut()?;
+    let old_destinations = protocol_fee_routing
+        .destinations()
+        .iter()
+        .map(|destination| ProtocolFeeDestinationParameters {
+            owner: destination.owner,
+            bps: destination.bps,
+        })
+        .collect();
+    protocol_fee_routing.update(&destinations)?;
+
+    emit_cpi!(EvtUpdateProtocolFeeRouting {
+        admin: ctx.accounts.admin.key(),
+        old_destinations,
+        new_destinations: destinations,
+    });
+
+    Ok(())
+}
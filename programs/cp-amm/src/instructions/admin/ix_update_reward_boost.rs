@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+
+use crate::{
+    assert_admin_role,
+    constants::{seeds::ADMIN_REGISTRY_PREFIX, MAX_REWARD_BOOST_BPS, NUM_REWARDS},
+    state::{AdminRole, Pool},
+    EvtUpdateRewardBoost, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRewardBoostCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    pub signer: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+impl UpdateRewardBoostCtx<'_> {
+    fn validate(
+        &self,
+        reward_index: usize,
+        permanent_lock_boost_bps: u16,
+        vesting_lock_boost_bps: u16,
+    ) -> Result<()> {
+        // boost is only supported by rewards stored in pool, reward extension is not updated on lock
+        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+
+        require!(
+            permanent_lock_boost_bps <= MAX_REWARD_BOOST_BPS
+                && vesting_lock_boost_bps <= MAX_REWARD_BOOST_BPS,
+            PoolError::InvalidRewardBoost
+        );
+
+        let pool = self.pool.load()?;
+        let reward_info = &pool.reward_infos[reward_index];
+        require!(reward_info.initialized(), PoolError::RewardUninitialized);
+
+        // vesting locked liquidity is only tracked for pools created after it is supported
+        if vesting_lock_boost_bps > 0 {
+            require!(
+                pool.is_vesting_liquidity_tracked(),
+                PoolError::InvalidRewardBoost
+            );
+        }
+
+        let is_reward_manager = assert_admin_role(
+            &self.admin_registry,
+            self.signer.key(),
+            AdminRole::RewardManager,
+        );
+        pool.validate_authority_to_edit_reward(reward_index, self.signer.key(), is_reward_manager)?;
+
+        Ok(())
+    }
+}
+
+pub fn handle_update_reward_boost(
+    ctx: Context<UpdateRewardBoostCtx>,
+    reward_index: u8,
+    permanent_lock_boost_bps: u16,
+    vesting_lock_boost_bps: u16,
+) -> Result<()> {
+    let index: usize = reward_index.into();
+
+    ctx.accounts
+        .validate(index, permanent_lock_boost_bps, vesting_lock_boost_bps)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    pool.update_rewards(current_time as u64)?;
+
+    let reward_info = &mut pool.reward_infos[index];
+    // position rewards are settled lazily with the current boost, so it can't be changed once reward is distributed
+    require!(
+        reward_info.reward_per_token_stored() == U256::ZERO,
+        PoolError::RewardAlreadyDistributed
+    );
+
+    let old_permanent_lock_boost_bps = reward_info.permanent_lock_boost_bps;
+    let old_vesting_lock_boost_bps = reward_info.vesting_lock_boost_bps;
+    reward_info.permanent_lock_boost_bps = permanent_lock_boost_bps;
+    reward_info.vesting_lock_boost_bps = vesting_lock_boost_bps;
+
+    emit_cpi!(EvtUpdateRewardBoost {
+        pool: ctx.accounts.pool.key(),
+        reward_index,
+        old_permanent_lock_boost_bps,
+        new_permanent_lock_boost_bps: permanent_lock_boost_bps,
+        old_vesting_lock_boost_bps,
+        new_vesting_lock_boost_bps: vesting_lock_boost_bps,
+    });
+
+    Ok(())
+}
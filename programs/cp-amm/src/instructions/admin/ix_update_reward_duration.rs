@@ -1,8 +1,16 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::{MAX_REWARD_DURATION, MIN_REWARD_DURATION, NUM_REWARDS},
-    state::Pool,
+    assert_admin_role,
+    constants::{
+        seeds::ADMIN_REGISTRY_PREFIX, MAX_REWARD_DURATION, MIN_REWARD_DURATION,
+        NUM_EXTENSION_REWARDS, NUM_REWARDS,
+    },
+    reward_extension::load_reward_extension,
+    state::{
+        get_reward_info, get_reward_info_mut, is_extension_reward_index, AdminRole, Pool,
+        RewardExtension,
+    },
     EvtUpdateRewardDuration, PoolError,
 };
 
@@ -13,11 +21,23 @@ pub struct UpdateRewardDurationCtx<'info> {
     pub pool: AccountLoader<'info, Pool>,
 
     pub signer: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
 }
 
 impl<'info> UpdateRewardDurationCtx<'info> {
-    fn validate(&self, reward_index: usize, new_reward_duration: u64) -> Result<()> {
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+    fn validate(
+        &self,
+        reward_index: usize,
+        new_reward_duration: u64,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
 
         require!(
             new_reward_duration >= MIN_REWARD_DURATION
@@ -26,7 +46,7 @@ impl<'info> UpdateRewardDurationCtx<'info> {
         );
 
         let pool = self.pool.load()?;
-        let reward_info = &pool.reward_infos[reward_index];
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
         require!(reward_info.initialized(), PoolError::RewardInitialized);
 
         require!(
@@ -41,14 +61,19 @@ impl<'info> UpdateRewardDurationCtx<'info> {
             PoolError::RewardCampaignInProgress
         );
 
-        pool.validate_authority_to_edit_reward(reward_index, self.signer.key())?;
+        let is_reward_manager = assert_admin_role(
+            &self.admin_registry,
+            self.signer.key(),
+            AdminRole::RewardManager,
+        );
+        pool.validate_authority_to_edit_reward(reward_index, self.signer.key(), is_reward_manager)?;
 
         Ok(())
     }
 }
 
-pub fn handle_update_reward_duration(
-    ctx: Context<UpdateRewardDurationCtx>,
+pub fn handle_update_reward_duration<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardDurationCtx<'info>>,
     reward_index: u8,
     new_reward_duration: u64,
 ) -> Result<()> {
@@ -56,10 +81,25 @@ pub fn handle_update_reward_duration(
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
 
-    ctx.accounts.validate(index, new_reward_duration)?;
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loader = if is_extension_reward_index(index) {
+        Some(load_reward_extension(
+            ctx.accounts.pool.key(),
+            &mut remaining_accounts,
+        )?)
+    } else {
+        None
+    };
+    let mut reward_extension = reward_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    ctx.accounts
+        .validate(index, new_reward_duration, reward_extension.as_deref())?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let reward_info = &mut pool.reward_infos[index];
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
 
     let old_reward_duration = reward_info.reward_duration;
     reward_info.reward_duration = new_reward_duration;
@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::NUM_REWARDS, state::Pool, EvtUpdateRewardFunder, PoolError};
+use crate::{
+    assert_admin_role,
+    constants::{seeds::ADMIN_REGISTRY_PREFIX, NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    reward_extension::load_reward_extension,
+    state::{
+        get_reward_info, get_reward_info_mut, is_extension_reward_index, AdminRole, Pool,
+        RewardExtension,
+    },
+    EvtUpdateRewardFunder, PoolError,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -9,37 +18,69 @@ pub struct UpdateRewardFunderCtx<'info> {
     pub pool: AccountLoader<'info, Pool>,
 
     pub signer: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
 }
 
 impl<'info> UpdateRewardFunderCtx<'info> {
-    fn validate(&self, reward_index: usize, new_funder: Pubkey) -> Result<()> {
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+    fn validate(
+        &self,
+        reward_index: usize,
+        new_funder: Pubkey,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
 
         let pool = self.pool.load()?;
-        let reward_info = &pool.reward_infos[reward_index];
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
 
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
 
         require!(reward_info.funder != new_funder, PoolError::IdenticalFunder);
 
-        pool.validate_authority_to_edit_reward(reward_index, self.signer.key())?;
+        let is_reward_manager = assert_admin_role(
+            &self.admin_registry,
+            self.signer.key(),
+            AdminRole::RewardManager,
+        );
+        pool.validate_authority_to_edit_reward(reward_index, self.signer.key(), is_reward_manager)?;
 
         Ok(())
     }
 }
 
-pub fn handle_update_reward_funder(
-    ctx: Context<UpdateRewardFunderCtx>,
+pub fn handle_update_reward_funder<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateRewardFunderCtx<'info>>,
     reward_index: u8,
     new_funder: Pubkey,
 ) -> Result<()> {
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index, new_funder)?;
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loader = if is_extension_reward_index(index) {
+        Some(load_reward_extension(
+            ctx.accounts.pool.key(),
+            &mut remaining_accounts,
+        )?)
+    } else {
+        None
+    };
+    let mut reward_extension = reward_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    ctx.accounts
+        .validate(index, new_funder, reward_extension.as_deref())?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
-    let reward_info = &mut pool.reward_infos[index];
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
 
     let old_funder = reward_info.funder;
     reward_info.funder = new_funder;
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::AdminRegistry, EvtUpdateTimelock, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTimelockCtx<'info> {
+    #[account(mut, has_one = super_admin @ PoolError::InvalidAdmin)]
+    pub admin_registry: AccountLoader<'info, AdminRegistry>,
+
+    pub super_admin: Signer<'info>,
+}
+
+pub fn handle_update_timelock(
+    ctx: Context<UpdateTimelockCtx>,
+    activation_type: u8,
+    delay: u64,
+) -> Result<()> {
+    let mut admin_registry = ctx.accounts.admin_registry.load_mut()?;
+    admin_registry.update_timelock(activation_type, delay)?;
+
+    emit_cpi!(EvtUpdateTimelock {
+        activation_type,
+        delay,
+    });
+
+    Ok(())
+}
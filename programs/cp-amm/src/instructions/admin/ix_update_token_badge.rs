@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_admin_role,
+    constants::{seeds::ADMIN_REGISTRY_PREFIX, BASIS_POINT_MAX},
+    state::{AdminRole, TokenBadge},
+    EvtUpdateTokenBadge, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UpdateTokenBadgeParameters {
+    /// bitmap of token2022 extensions the mint is allowed to have, bit i is extension type i. 0 allows all extensions
+    pub allowed_extensions: u64,
+    /// timestamp when the badge expires, 0 if the badge never expires. Badge is revoked by setting a past timestamp
+    pub expiry_timestamp: u64,
+    /// transfer hook program the mint is allowed to use, default pubkey allows any program
+    pub transfer_hook_program_id: Pubkey,
+    /// max transfer fee bps of the mint, None if there is no limit
+    pub max_transfer_fee_bps: Option<u16>,
+}
+
+impl UpdateTokenBadgeParameters {
+    fn validate(&self) -> Result<()> {
+        if let Some(max_transfer_fee_bps) = self.max_transfer_fee_bps {
+            require!(
+                u64::from(max_transfer_fee_bps) <= BASIS_POINT_MAX,
+                PoolError::InvalidParameters
+            );
+        }
+        Ok(())
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTokenBadgeCtx<'info> {
+    #[account(mut)]
+    pub token_badge: AccountLoader<'info, TokenBadge>,
+
+    #[account(
+        constraint = assert_admin_role(&admin_registry, admin.key(), AdminRole::TokenBadgeManager) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+}
+
+pub fn handle_update_token_badge(
+    ctx: Context<UpdateTokenBadgeCtx>,
+    params: UpdateTokenBadgeParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut token_badge = ctx.accounts.token_badge.load_mut()?;
+    token_badge.update_policy(
+        params.allowed_extensions,
+        params.expiry_timestamp,
+        params.transfer_hook_program_id,
+        params.max_transfer_fee_bps,
+    );
+
+    emit_cpi!(EvtUpdateTokenBadge {
+        token_mint: token_badge.token_mint,
+        params,
+    });
+
+    Ok(())
+}
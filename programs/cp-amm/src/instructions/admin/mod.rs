@@ -4,14 +4,26 @@ pub mod ix_create_dynamic_config;
 pub use ix_create_dynamic_config::*;
 pub mod ix_close_config;
 pub use ix_close_config::*;
+pub mod ix_update_config;
+pub use ix_update_config::*;
+pub mod ix_update_price_range;
+pub use ix_update_price_range::*;
 pub mod auth;
 pub use auth::*;
 pub mod ix_create_token_badge;
 pub use ix_create_token_badge::*;
 pub mod ix_claim_protocol_fee;
 pub use ix_claim_protocol_fee::*;
+pub mod ix_batch_claim_protocol_fee;
+pub use ix_batch_claim_protocol_fee::*;
+pub mod ix_initialize_protocol_fee_routing;
+pub use ix_initialize_protocol_fee_routing::*;
+pub mod ix_update_protocol_fee_routing;
+pub use ix_update_protocol_fee_routing::*;
 pub mod ix_set_pool_status;
 pub use ix_set_pool_status::*;
+pub mod ix_set_pool_disabled_actions;
+pub use ix_set_pool_disabled_actions::*;
 pub mod ix_create_claim_protocol_fee_operator;
 pub use ix_create_claim_protocol_fee_operator::*;
 pub mod ix_close_claim_protocol_fee_operator;
@@ -22,5 +34,23 @@ pub mod ix_update_reward_funder;
 pub use ix_update_reward_funder::*;
 pub mod ix_update_reward_duration;
 pub use ix_update_reward_duration::*;
+pub mod ix_update_reward_boost;
+pub use ix_update_reward_boost::*;
 pub mod ix_close_token_badge;
 pub use ix_close_token_badge::*;
+pub mod ix_update_token_badge;
+pub use ix_update_token_badge::*;
+pub mod ix_initialize_admin_registry;
+pub use ix_initialize_admin_registry::*;
+pub mod ix_add_admin_role;
+pub use ix_add_admin_role::*;
+pub mod ix_remove_admin_role;
+pub use ix_remove_admin_role::*;
+pub mod ix_transfer_super_admin;
+pub use ix_transfer_super_admin::*;
+pub mod ix_update_timelock;
+pub use ix_update_timelock::*;
+pub mod ix_create_proposal;
+pub use ix_create_proposal::*;
+pub mod ix_cancel_proposal;
+pub use ix_cancel_proposal::*;
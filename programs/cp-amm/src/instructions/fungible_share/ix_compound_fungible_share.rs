@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    bribe::update_bribe_ledgers,
+    get_pool_access_validator,
+    locked_fee_extension::update_locked_fee,
+    reward_extension::update_reward_extensions,
+    shared_position::compound_shared_position_fee,
+    state::{FungibleShare, Pool, Position},
+    EvtCompoundFungibleShare, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompoundFungibleShareCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool, has_one = position, has_one = share_mint)]
+    pub fungible_share: AccountLoader<'info, FungibleShare>,
+
+    /// shared position
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// share mint
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Compound pending fee of the shared position, permissionless
+pub fn handle_compound_fungible_share<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CompoundFungibleShareCtx<'info>>,
+) -> Result<()> {
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let access_validator = get_pool_access_validator(&pool)?;
+        require!(
+            access_validator.can_add_liquidity(),
+            PoolError::PoolDisabled
+        );
+    }
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    // update current pool reward & postion reward before any logic
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+
+    // remaining accounts layout:
+    // 1. pool reward extension, if pool has extension reward
+    // 2. pool bribe ledger followed by position bribe ledger, if shared position has one
+    // 3. locked fee extension, if pool has one
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut remaining_accounts,
+    )?;
+
+    let compound = compound_shared_position_fee(&mut pool, &mut position)?;
+    require!(compound.liquidity_delta > 0, PoolError::AmountIsZero);
+
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    emit_cpi!(EvtCompoundFungibleShare {
+        pool: ctx.accounts.pool.key(),
+        fungible_share: ctx.accounts.fungible_share.key(),
+        liquidity_delta: compound.liquidity_delta,
+        token_a_amount: compound.token_a_amount,
+        token_b_amount: compound.token_b_amount,
+        shared_liquidity: position.unlocked_liquidity,
+        share_supply: ctx.accounts.share_mint.supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    allowlist::load_allowlisted_wallet,
+    bribe::update_bribe_ledgers,
+    const_pda, get_pool_access_validator_with_allowlist,
+    locked_fee_extension::update_locked_fee,
+    reward_extension::update_reward_extensions,
+    safe_math::SafeMath,
+    shared_position::compound_shared_position_fee,
+    state::{
+        get_pending_fee_from_shares, get_shares_from_liquidity, FungibleShare,
+        ModifyLiquidityResult, Pool, Position,
+    },
+    token::{
+        calculate_transfer_fee_included_amount, load_transfer_hook_accounts, transfer_from_user,
+        validate_transfer_fee_bps,
+    },
+    u128x128_math::Rounding,
+    EvtCompoundFungibleShare, EvtDepositFungibleShare, EvtLiquidityChange, PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct DepositFungibleShareParameters {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// maximum token a amount
+    pub token_a_amount_threshold: u64,
+    /// maximum token b amount
+    pub token_b_amount_threshold: u64,
+    /// minimum shares to be minted
+    pub minimum_share_amount: u64,
+    /// maximum transfer fee bps of token a and token b, none if there is no bound
+    pub max_transfer_fee_bps: Option<u16>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositFungibleShareCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = token_a_vault, has_one = token_b_vault, has_one = token_a_mint, has_one = token_b_mint)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool, has_one = position, has_one = share_mint)]
+    pub fungible_share: AccountLoader<'info, FungibleShare>,
+
+    /// shared position
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// share mint
+    #[account(mut)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account receiving shares
+    #[account(mut, token::mint = share_mint, token::token_program = share_token_program)]
+    pub share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// depositor
+    pub owner: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// Program to mint shares
+    pub share_token_program: Program<'info, Token>,
+}
+
+/// Add liquidity to the shared position and mint shares proportional to its unlocked liquidity
+pub fn handle_deposit_fungible_share<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, DepositFungibleShareCtx<'info>>,
+    params: DepositFungibleShareParameters,
+) -> Result<()> {
+    let DepositFungibleShareParameters {
+        liquidity_delta,
+        token_a_amount_threshold,
+        token_b_amount_threshold,
+        minimum_share_amount,
+        max_transfer_fee_bps,
+    } = params;
+    require!(liquidity_delta > 0, PoolError::InvalidParameters);
+
+    validate_transfer_fee_bps(&ctx.accounts.token_a_mint, max_transfer_fee_bps)?;
+    validate_transfer_fee_bps(&ctx.accounts.token_b_mint, max_transfer_fee_bps)?;
+
+    // remaining accounts layout:
+    // 1. allowlist entry of depositor, if pool is permissioned
+    // 2. pool reward extension, if pool has extension reward
+    // 3. pool bribe ledger followed by position bribe ledger, if shared position has one
+    // 4. locked fee extension, if pool has one
+    // 5. transfer hook accounts of token a followed by token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let allowlisted_wallet = load_allowlisted_wallet(
+            ctx.accounts.pool.key(),
+            &pool,
+            ctx.accounts.owner.key(),
+            &mut remaining_accounts,
+        )?;
+        let access_validator = get_pool_access_validator_with_allowlist(&pool, allowlisted_wallet)?;
+        require!(
+            access_validator.can_add_liquidity(),
+            PoolError::PoolDisabled
+        );
+    }
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    let fungible_share = ctx.accounts.fungible_share.load()?;
+
+    // update current pool reward & postion reward before any logic
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut remaining_accounts,
+    )?;
+
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    // pending fee is compounded first, so the depositor doesn't get a share of fee earned before
+    if fungible_share.is_auto_compound() {
+        let compound = compound_shared_position_fee(&mut pool, &mut position)?;
+        if compound.liquidity_delta > 0 {
+            emit_cpi!(EvtCompoundFungibleShare {
+                pool: ctx.accounts.pool.key(),
+                fungible_share: ctx.accounts.fungible_share.key(),
+                liquidity_delta: compound.liquidity_delta,
+                token_a_amount: compound.token_a_amount,
+                token_b_amount: compound.token_b_amount,
+                shared_liquidity: position.unlocked_liquidity,
+                share_supply: ctx.accounts.share_mint.supply,
+            });
+        }
+    }
+
+    let share_amount = get_shares_from_liquidity(
+        liquidity_delta,
+        ctx.accounts.share_mint.supply,
+        position.unlocked_liquidity,
+    )?;
+    require!(share_amount > 0, PoolError::AmountIsZero);
+    require!(
+        share_amount >= minimum_share_amount,
+        PoolError::ExceededSlippage
+    );
+
+    let ModifyLiquidityResult {
+        token_a_amount,
+        token_b_amount,
+    } = pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)?;
+
+    require!(
+        token_a_amount > 0 || token_b_amount > 0,
+        PoolError::AmountIsZero
+    );
+
+    // depositor buys into the pending fee which isn't compounded, pro rata to its shares
+    let share_supply = ctx.accounts.share_mint.supply;
+    let fee_a_amount = get_pending_fee_from_shares(
+        position.fee_a_pending,
+        share_amount,
+        share_supply,
+        Rounding::Up,
+    )?;
+    let fee_b_amount = get_pending_fee_from_shares(
+        position.fee_b_pending,
+        share_amount,
+        share_supply,
+        Rounding::Up,
+    )?;
+
+    pool.apply_add_liquidity(&mut position, liquidity_delta)?;
+    position.add_fee_pending(fee_a_amount, fee_b_amount)?;
+
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
+    let total_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_a_mint,
+        token_a_amount.safe_add(fee_a_amount)?,
+    )?
+    .amount;
+    let total_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_b_mint,
+        token_b_amount.safe_add(fee_b_amount)?,
+    )?
+    .amount;
+
+    require!(
+        total_amount_a <= token_a_amount_threshold,
+        PoolError::ExceededSlippage
+    );
+    require!(
+        total_amount_b <= token_b_amount_threshold,
+        PoolError::ExceededSlippage
+    );
+
+    transfer_from_user(
+        &ctx.accounts.owner,
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.token_a_account,
+        &ctx.accounts.token_a_vault,
+        &ctx.accounts.token_a_program,
+        total_amount_a,
+        transfer_hook_accounts_a,
+    )?;
+
+    transfer_from_user(
+        &ctx.accounts.owner,
+        &ctx.accounts.token_b_mint,
+        &ctx.accounts.token_b_account,
+        &ctx.accounts.token_b_vault,
+        &ctx.accounts.token_b_program,
+        total_amount_b,
+        transfer_hook_accounts_b,
+    )?;
+
+    let seeds = pool_authority_seeds!();
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.share_token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.share_token_account.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        share_amount,
+    )?;
+
+    emit_cpi!(EvtDepositFungibleShare {
+        pool: ctx.accounts.pool.key(),
+        fungible_share: ctx.accounts.fungible_share.key(),
+        owner: ctx.accounts.owner.key(),
+        params,
+        liquidity_delta,
+        share_amount,
+        token_a_amount,
+        token_b_amount,
+        fee_a_amount,
+        fee_b_amount,
+        total_amount_a,
+        total_amount_b,
+    });
+
+    let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount()?;
+
+    emit_cpi!(EvtLiquidityChange {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        liquidity_delta,
+        token_a_amount_threshold,
+        token_b_amount_threshold,
+        token_a_amount,
+        token_b_amount,
+        transfer_fee_included_token_a_amount: total_amount_a,
+        transfer_fee_included_token_b_amount: total_amount_b,
+        reserve_b_amount,
+        reserve_a_amount,
+        change_type: 0
+    });
+
+    Ok(())
+}
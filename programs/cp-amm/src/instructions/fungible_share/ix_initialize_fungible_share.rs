@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    const_pda,
+    constants::{
+        seeds::{
+            FUNGIBLE_SHARE_MINT_PREFIX, FUNGIBLE_SHARE_PREFIX, POSITION_NFT_ACCOUNT_PREFIX,
+            POSITION_PREFIX,
+        },
+        FUNGIBLE_SHARE_DECIMALS,
+    },
+    create_position_nft, get_pool_access_validator,
+    state::{FungibleShare, Pool, Position},
+    EvtCreatePosition, EvtInitializeFungibleShare, PoolError, PositionNftAccounts,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeFungibleShareCtx<'info> {
+    #[account(mut, has_one = creator)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            FUNGIBLE_SHARE_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + FungibleShare::INIT_SPACE
+    )]
+    pub fungible_share: AccountLoader<'info, FungibleShare>,
+
+    /// share mint
+    #[account(
+        init,
+        seeds = [
+            FUNGIBLE_SHARE_MINT_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        mint::token_program = share_token_program,
+        mint::decimals = FUNGIBLE_SHARE_DECIMALS,
+        mint::authority = pool_authority,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// nft mint of shared position
+    #[account(
+        init,
+        signer,
+        payer = payer,
+        mint::token_program = token_program,
+        mint::decimals = 0,
+        mint::authority = pool_authority,
+        mint::freeze_authority = pool,
+        extensions::metadata_pointer::authority = pool_authority,
+        extensions::metadata_pointer::metadata_address = position_nft_mint,
+        extensions::close_authority::authority = pool_authority,
+    )]
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// nft account of shared position, owned by pool authority
+    #[account(
+        init,
+        seeds = [POSITION_NFT_ACCOUNT_PREFIX, position_nft_mint.key().as_ref()],
+        token::mint = position_nft_mint,
+        token::authority = pool_authority,
+        token::token_program = token_program,
+        payer = payer,
+        bump,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// shared position
+    #[account(
+        init,
+        seeds = [
+            POSITION_PREFIX,
+            position_nft_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Position::INIT_SPACE
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// pool creator
+    pub creator: Signer<'info>,
+
+    /// Address paying to create the accounts. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Program to create NFT mint/token account of shared position
+    pub token_program: Program<'info, Token2022>,
+
+    /// Program to create share mint
+    pub share_token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Enable fungible share mode of a pool. A shared position owned by pool authority is created with a
+/// share mint, so liquidity can be deposited and withdrawn with fungible shares
+pub fn handle_initialize_fungible_share(
+    ctx: Context<InitializeFungibleShareCtx>,
+    auto_compound: bool,
+) -> Result<()> {
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let access_validator = get_pool_access_validator(&pool)?;
+        require!(
+            access_validator.can_create_position(),
+            PoolError::PoolDisabled
+        );
+        // shared position can't claim rewards on behalf of share holders
+        require!(
+            !pool.has_initialized_reward(),
+            PoolError::FungibleShareRewardNotSupported
+        );
+    }
+
+    let mut position = ctx.accounts.position.load_init()?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.fungible_share_flag = 1;
+
+    position.initialize(
+        &mut pool,
+        ctx.accounts.pool.key(),
+        ctx.accounts.position_nft_mint.key(),
+        0,
+    );
+
+    drop(position);
+    create_position_nft(
+        PositionNftAccounts {
+            payer: ctx.accounts.payer.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        },
+        ctx.accounts.pool.key(),
+        &pool,
+    )?;
+
+    let mut fungible_share = ctx.accounts.fungible_share.load_init()?;
+    fungible_share.initialize(
+        ctx.accounts.pool.key(),
+        ctx.accounts.position.key(),
+        ctx.accounts.share_mint.key(),
+        auto_compound,
+    );
+
+    emit_cpi!(EvtCreatePosition {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.pool_authority.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+    });
+
+    emit_cpi!(EvtInitializeFungibleShare {
+        pool: ctx.accounts.pool.key(),
+        fungible_share: ctx.accounts.fungible_share.key(),
+        position: ctx.accounts.position.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        auto_compound,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+pub mod ix_initialize_fungible_share;
+pub use ix_initialize_fungible_share::*;
+pub mod ix_deposit_fungible_share;
+pub use ix_deposit_fungible_share::*;
+pub mod ix_withdraw_fungible_share;
+pub use ix_withdraw_fungible_share::*;
+pub mod ix_compound_fungible_share;
+pub use ix_compound_fungible_share::*;
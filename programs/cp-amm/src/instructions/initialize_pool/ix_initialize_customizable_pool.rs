@@ -21,9 +21,9 @@ use crate::{
     state::{CollectFeeMode, Pool, PoolType, Position},
     token::{
         calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        load_transfer_hook_accounts, skip_token_badges, transfer_from_user, validate_token_badge,
     },
-    EvtCreatePosition, EvtInitializePool, PoolError,
+    EvtCreatePosition, EvtInitializePool, PoolError, PositionNftAccounts,
 };
 
 use super::{max_key, min_key};
@@ -225,29 +225,38 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
     params: InitializeCustomizablePoolParameters,
 ) -> Result<()> {
     params.validate()?;
-    if !is_supported_mint(&ctx.accounts.token_a_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
-                ctx.remaining_accounts
-                    .get(0)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
-
-    if !is_supported_mint(&ctx.accounts.token_b_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
-                ctx.remaining_accounts
-                    .get(1)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
+    let token_a_badge_expiry_timestamp = if is_supported_mint(&ctx.accounts.token_a_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            &ctx.accounts.token_a_mint,
+            ctx.remaining_accounts
+                .first()
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    let token_b_badge_expiry_timestamp = if is_supported_mint(&ctx.accounts.token_b_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            &ctx.accounts.token_b_mint,
+            ctx.remaining_accounts
+                .get(1)
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    // transfer hook accounts of token a and token b follow token badges
+    let mut remaining_accounts = skip_token_badges(
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.token_b_mint,
+        ctx.remaining_accounts,
+    )?;
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
 
     let InitializeCustomizablePoolParameters {
         pool_fees,
@@ -308,6 +317,10 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         collect_fee_mode,
         pool_type,
     );
+    pool.set_token_badge_expiry_timestamps(
+        token_a_badge_expiry_timestamp,
+        token_b_badge_expiry_timestamp,
+    );
 
     let mut position = ctx.accounts.position.load_init()?;
     position.initialize(
@@ -320,12 +333,16 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
     // create position nft
     drop(position);
     create_position_nft(
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.position_nft_mint.to_account_info(),
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.token_2022_program.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
+        PositionNftAccounts {
+            payer: ctx.accounts.payer.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        },
+        ctx.accounts.pool.key(),
+        &pool,
     )?;
 
     emit_cpi!(EvtCreatePosition {
@@ -352,6 +369,7 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        transfer_hook_accounts_a,
     )?;
     transfer_from_user(
         &ctx.accounts.payer,
@@ -360,6 +378,7 @@ pub fn handle_initialize_customizable_pool<'c: 'info, 'info>(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        transfer_hook_accounts_b,
     )?;
 
     emit_cpi!(EvtInitializePool {
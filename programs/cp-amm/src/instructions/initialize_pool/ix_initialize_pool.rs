@@ -20,9 +20,9 @@ use crate::{
     state::{Config, ConfigType, Pool, PoolType, Position},
     token::{
         calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        load_transfer_hook_accounts, skip_token_badges, transfer_from_user, validate_token_badge,
     },
-    EvtCreatePosition, EvtInitializePool, PoolError,
+    EvtCreatePosition, EvtInitializePool, PoolError, PositionNftAccounts,
 };
 
 // To fix IDL generation: https://github.com/coral-xyz/anchor/issues/3209
@@ -186,29 +186,38 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializePoolCtx<'info>>,
     params: InitializePoolParameters,
 ) -> Result<()> {
-    if !is_supported_mint(&ctx.accounts.token_a_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
-                ctx.remaining_accounts
-                    .get(0)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
-
-    if !is_supported_mint(&ctx.accounts.token_b_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
-                ctx.remaining_accounts
-                    .get(1)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
+    let token_a_badge_expiry_timestamp = if is_supported_mint(&ctx.accounts.token_a_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            &ctx.accounts.token_a_mint,
+            ctx.remaining_accounts
+                .first()
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    let token_b_badge_expiry_timestamp = if is_supported_mint(&ctx.accounts.token_b_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            &ctx.accounts.token_b_mint,
+            ctx.remaining_accounts
+                .get(1)
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    // transfer hook accounts of token a and token b follow token badges
+    let mut remaining_accounts = skip_token_badges(
+        &ctx.accounts.token_a_mint,
+        &ctx.accounts.token_b_mint,
+        ctx.remaining_accounts,
+    )?;
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
 
     let InitializePoolParameters {
         liquidity,
@@ -286,6 +295,10 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         config.collect_fee_mode,
         pool_type,
     );
+    pool.set_token_badge_expiry_timestamps(
+        token_a_badge_expiry_timestamp,
+        token_b_badge_expiry_timestamp,
+    );
 
     // init position
     let mut position = ctx.accounts.position.load_init()?;
@@ -300,12 +313,16 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
     // create position nft
     drop(position);
     create_position_nft(
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.position_nft_mint.to_account_info(),
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.token_2022_program.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
+        PositionNftAccounts {
+            payer: ctx.accounts.payer.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        },
+        ctx.accounts.pool.key(),
+        &pool,
     )?;
 
     emit_cpi!(EvtCreatePosition {
@@ -328,6 +345,7 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        transfer_hook_accounts_a,
     )?;
     transfer_from_user(
         &ctx.accounts.payer,
@@ -336,6 +354,7 @@ pub fn handle_initialize_pool<'c: 'info, 'info>(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        transfer_hook_accounts_b,
     )?;
 
     emit_cpi!(EvtInitializePool {
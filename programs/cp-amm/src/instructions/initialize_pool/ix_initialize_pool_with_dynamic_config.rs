@@ -16,9 +16,9 @@ use crate::{
     state::{Config, ConfigType, Pool, PoolType, Position},
     token::{
         calculate_transfer_fee_included_amount, get_token_program_flags, is_supported_mint,
-        is_token_badge_initialized, transfer_from_user,
+        load_transfer_hook_accounts, skip_token_badges, transfer_from_user, validate_token_badge,
     },
-    validate_quote_token, EvtCreatePosition, EvtInitializePool, PoolError,
+    validate_quote_token, EvtCreatePosition, EvtInitializePool, PoolError, PositionNftAccounts,
 };
 
 use super::{max_key, min_key, InitializeCustomizablePoolParameters};
@@ -164,34 +164,115 @@ pub struct InitializePoolWithDynamicConfigCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts used to initialize a pool with dynamic config and its first position
+pub struct DynamicConfigPoolAccounts<'a, 'info> {
+    pub creator: Pubkey,
+    pub payer: &'a Signer<'info>,
+    pub config: &'a AccountLoader<'info, Config>,
+    pub pool_authority: &'a UncheckedAccount<'info>,
+    pub pool: &'a AccountLoader<'info, Pool>,
+    pub position: &'a AccountLoader<'info, Position>,
+    pub position_nft_mint: &'a InterfaceAccount<'info, Mint>,
+    pub position_nft_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_a_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_b_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_a_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_b_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    /// owner of the deposited tokens
+    pub funder: &'a Signer<'info>,
+    pub funder_token_a: &'a InterfaceAccount<'info, TokenAccount>,
+    pub funder_token_b: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_a_program: &'a Interface<'info, TokenInterface>,
+    pub token_b_program: &'a Interface<'info, TokenInterface>,
+    pub token_2022_program: &'a Program<'info, Token2022>,
+    pub system_program: &'a Program<'info, System>,
+}
+
 pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializePoolWithDynamicConfigCtx<'info>>,
     params: InitializeCustomizablePoolParameters,
 ) -> Result<()> {
-    params.validate()?;
-    if !is_supported_mint(&ctx.accounts.token_a_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_a_mint.key(),
-                ctx.remaining_accounts
-                    .get(0)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
+    let evt_initialize_pool = initialize_dynamic_config_pool(
+        DynamicConfigPoolAccounts {
+            creator: ctx.accounts.creator.key(),
+            payer: &ctx.accounts.payer,
+            config: &ctx.accounts.config,
+            pool_authority: &ctx.accounts.pool_authority,
+            pool: &ctx.accounts.pool,
+            position: &ctx.accounts.position,
+            position_nft_mint: &ctx.accounts.position_nft_mint,
+            position_nft_account: &ctx.accounts.position_nft_account,
+            token_a_mint: &ctx.accounts.token_a_mint,
+            token_b_mint: &ctx.accounts.token_b_mint,
+            token_a_vault: &ctx.accounts.token_a_vault,
+            token_b_vault: &ctx.accounts.token_b_vault,
+            funder: &ctx.accounts.payer,
+            funder_token_a: &ctx.accounts.payer_token_a,
+            funder_token_b: &ctx.accounts.payer_token_b,
+            token_a_program: &ctx.accounts.token_a_program,
+            token_b_program: &ctx.accounts.token_b_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        params,
+        0,
+        ctx.remaining_accounts,
+    )?;
 
-    if !is_supported_mint(&ctx.accounts.token_b_mint)? {
-        require!(
-            is_token_badge_initialized(
-                ctx.accounts.token_b_mint.key(),
-                ctx.remaining_accounts
-                    .get(1)
-                    .ok_or(PoolError::InvalidTokenBadge)?,
-            )?,
-            PoolError::InvalidTokenBadge
-        )
-    }
+    emit_cpi!(EvtCreatePosition {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.creator.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+    });
+
+    emit_cpi!(evt_initialize_pool);
+
+    Ok(())
+}
+
+/// Initialize a customizable pool with dynamic config, along with its first position holding the
+/// whole liquidity, of which `permanent_lock_liquidity` is permanently locked. Remaining accounts
+/// are token badges followed by transfer hook accounts of token a and token b
+pub fn initialize_dynamic_config_pool<'c: 'info, 'info>(
+    accounts: DynamicConfigPoolAccounts<'_, 'info>,
+    params: InitializeCustomizablePoolParameters,
+    permanent_lock_liquidity: u128,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<EvtInitializePool> {
+    params.validate()?;
+    let token_a_badge_expiry_timestamp = if is_supported_mint(accounts.token_a_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            accounts.token_a_mint,
+            remaining_accounts
+                .first()
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    let token_b_badge_expiry_timestamp = if is_supported_mint(accounts.token_b_mint)? {
+        0
+    } else {
+        validate_token_badge(
+            accounts.token_b_mint,
+            remaining_accounts
+                .get(1)
+                .ok_or(PoolError::InvalidTokenBadge)?,
+        )?
+    };
+
+    // transfer hook accounts of token a and token b follow token badges
+    let mut remaining_accounts = skip_token_badges(
+        accounts.token_a_mint,
+        accounts.token_b_mint,
+        remaining_accounts,
+    )?;
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(accounts.token_b_mint, &mut remaining_accounts)?;
 
     let InitializeCustomizablePoolParameters {
         pool_fees,
@@ -206,7 +287,7 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
     } = params;
 
     // init pool
-    let config = ctx.accounts.config.load()?;
+    let config = accounts.config.load()?;
 
     require!(
         config.get_config_type()? == ConfigType::Dynamic,
@@ -216,8 +297,8 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
     // validate quote token
     #[cfg(not(feature = "devnet"))]
     validate_quote_token(
-        &ctx.accounts.token_a_mint.key(),
-        &ctx.accounts.token_b_mint.key(),
+        &accounts.token_a_mint.key(),
+        &accounts.token_b_mint.key(),
         has_alpha_vault,
     )?;
 
@@ -228,25 +309,22 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         PoolError::AmountIsZero
     );
 
-    let mut pool = ctx.accounts.pool.load_init()?;
+    let mut pool = accounts.pool.load_init()?;
 
-    let token_a_flag: u8 = get_token_program_flags(&ctx.accounts.token_a_mint).into();
-    let token_b_flag: u8 = get_token_program_flags(&ctx.accounts.token_b_mint).into();
+    let token_a_flag: u8 = get_token_program_flags(accounts.token_a_mint).into();
+    let token_b_flag: u8 = get_token_program_flags(accounts.token_b_mint).into();
     let activation_point =
         activation_point.unwrap_or(ActivationHandler::get_current_point(activation_type)?);
-    let alpha_vault = get_whitelisted_alpha_vault(
-        ctx.accounts.payer.key(),
-        ctx.accounts.pool.key(),
-        has_alpha_vault,
-    );
+    let alpha_vault =
+        get_whitelisted_alpha_vault(accounts.payer.key(), accounts.pool.key(), has_alpha_vault);
     let pool_type: u8 = PoolType::Customizable.into();
     pool.initialize(
-        ctx.accounts.creator.key(),
+        accounts.creator,
         pool_fees.to_pool_fees_struct(),
-        ctx.accounts.token_a_mint.key(),
-        ctx.accounts.token_b_mint.key(),
-        ctx.accounts.token_a_vault.key(),
-        ctx.accounts.token_b_vault.key(),
+        accounts.token_a_mint.key(),
+        accounts.token_b_mint.key(),
+        accounts.token_a_vault.key(),
+        accounts.token_b_vault.key(),
         alpha_vault,
         config.pool_creator_authority,
         sqrt_min_price,
@@ -260,63 +338,71 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         collect_fee_mode,
         pool_type,
     );
+    pool.set_token_badge_expiry_timestamps(
+        token_a_badge_expiry_timestamp,
+        token_b_badge_expiry_timestamp,
+    );
 
-    let mut position = ctx.accounts.position.load_init()?;
+    let mut position = accounts.position.load_init()?;
     position.initialize(
         &mut pool,
-        ctx.accounts.pool.key(),
-        ctx.accounts.position_nft_mint.key(),
+        accounts.pool.key(),
+        accounts.position_nft_mint.key(),
         liquidity,
     );
 
+    if permanent_lock_liquidity > 0 {
+        position.permanent_lock_liquidity(permanent_lock_liquidity)?;
+        pool.accumulate_permanent_locked_liquidity(permanent_lock_liquidity)?;
+    }
+
     // create position nft
     drop(position);
     create_position_nft(
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.position_nft_mint.to_account_info(),
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.token_2022_program.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
+        PositionNftAccounts {
+            payer: accounts.payer.to_account_info(),
+            position_nft_mint: accounts.position_nft_mint.to_account_info(),
+            pool_authority: accounts.pool_authority.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_2022_program.to_account_info(),
+            position_nft_account: accounts.position_nft_account.to_account_info(),
+        },
+        accounts.pool.key(),
+        &pool,
     )?;
 
-    emit_cpi!(EvtCreatePosition {
-        pool: ctx.accounts.pool.key(),
-        owner: ctx.accounts.creator.key(),
-        position: ctx.accounts.position.key(),
-        position_nft_mint: ctx.accounts.position_nft_mint.key(),
-    });
-
     // transfer token
     let total_amount_a =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
+        calculate_transfer_fee_included_amount(accounts.token_a_mint, token_a_amount)?.amount;
     let total_amount_b =
-        calculate_transfer_fee_included_amount(&ctx.accounts.token_b_mint, token_b_amount)?.amount;
+        calculate_transfer_fee_included_amount(accounts.token_b_mint, token_b_amount)?.amount;
 
     transfer_from_user(
-        &ctx.accounts.payer,
-        &ctx.accounts.token_a_mint,
-        &ctx.accounts.payer_token_a,
-        &ctx.accounts.token_a_vault,
-        &ctx.accounts.token_a_program,
+        accounts.funder,
+        accounts.token_a_mint,
+        accounts.funder_token_a,
+        accounts.token_a_vault,
+        accounts.token_a_program,
         total_amount_a,
+        transfer_hook_accounts_a,
     )?;
     transfer_from_user(
-        &ctx.accounts.payer,
-        &ctx.accounts.token_b_mint,
-        &ctx.accounts.payer_token_b,
-        &ctx.accounts.token_b_vault,
-        &ctx.accounts.token_b_program,
+        accounts.funder,
+        accounts.token_b_mint,
+        accounts.funder_token_b,
+        accounts.token_b_vault,
+        accounts.token_b_program,
         total_amount_b,
+        transfer_hook_accounts_b,
     )?;
 
-    emit_cpi!(EvtInitializePool {
-        pool: ctx.accounts.pool.key(),
-        token_a_mint: ctx.accounts.token_a_mint.key(),
-        token_b_mint: ctx.accounts.token_b_mint.key(),
+    Ok(EvtInitializePool {
+        pool: accounts.pool.key(),
+        token_a_mint: accounts.token_a_mint.key(),
+        token_b_mint: accounts.token_b_mint.key(),
         pool_fees,
-        creator: ctx.accounts.creator.key(),
-        payer: ctx.accounts.payer.key(),
+        creator: accounts.creator,
+        payer: accounts.payer.key(),
         activation_point,
         activation_type,
         token_a_flag,
@@ -332,7 +418,5 @@ pub fn handle_initialize_pool_with_dynamic_config<'c: 'info, 'info>(
         total_amount_a,
         total_amount_b,
         pool_type,
-    });
-
-    Ok(())
+    })
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    curve::{get_initialize_amounts, get_liquidity_from_amount_a},
+    params::fee_parameters::PoolFeeParameters,
+    PoolError,
+};
+
+use super::{
+    handle_initialize_customizable_pool, InitializeCustomizablePoolCtx,
+    InitializeCustomizablePoolParameters,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeSingleSidedPoolParameters {
+    /// pool fees
+    pub pool_fees: PoolFeeParameters,
+    /// sqrt min price, the pool price starts here
+    pub sqrt_min_price: u128,
+    /// sqrt max price
+    pub sqrt_max_price: u128,
+    /// has alpha vault
+    pub has_alpha_vault: bool,
+    /// token a amount deposited to the pool, excluding transfer fee
+    pub token_a_amount: u64,
+    /// activation type
+    pub activation_type: u8,
+    /// collect fee mode
+    pub collect_fee_mode: u8,
+    /// activation point
+    pub activation_point: Option<u64>,
+}
+
+impl InitializeSingleSidedPoolParameters {
+    /// Liquidity provided by token a amount over the whole price range
+    pub fn get_liquidity(&self) -> Result<u128> {
+        require!(
+            self.sqrt_min_price >= MIN_SQRT_PRICE && self.sqrt_max_price <= MAX_SQRT_PRICE,
+            PoolError::InvalidPriceRange
+        );
+        require!(
+            self.sqrt_min_price < self.sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+        require!(self.token_a_amount > 0, PoolError::AmountIsZero);
+
+        let liquidity = get_liquidity_from_amount_a(
+            self.token_a_amount,
+            self.sqrt_min_price,
+            self.sqrt_max_price,
+        )?;
+        require!(liquidity > 0, PoolError::InvalidMinimumLiquidity);
+
+        Ok(liquidity)
+    }
+
+    fn into_customizable_pool_parameters(self) -> Result<InitializeCustomizablePoolParameters> {
+        let liquidity = self.get_liquidity()?;
+
+        // price is placed at the lower bound, so the pool starts without any token b. A price
+        // slightly above the bound leaves a dust token b reserve, that anyone can swap out to
+        // move the launch price
+        let (token_a_amount, token_b_amount) = get_initialize_amounts(
+            self.sqrt_min_price,
+            self.sqrt_max_price,
+            self.sqrt_min_price,
+            liquidity,
+        )?;
+        require!(token_b_amount == 0, PoolError::InvalidParameters);
+        require!(
+            token_a_amount > 0 && token_a_amount <= self.token_a_amount,
+            PoolError::InvalidMinimumLiquidity
+        );
+
+        Ok(InitializeCustomizablePoolParameters {
+            pool_fees: self.pool_fees,
+            sqrt_min_price: self.sqrt_min_price,
+            sqrt_max_price: self.sqrt_max_price,
+            has_alpha_vault: self.has_alpha_vault,
+            liquidity,
+            sqrt_price: self.sqrt_min_price,
+            activation_type: self.activation_type,
+            collect_fee_mode: self.collect_fee_mode,
+            activation_point: self.activation_point,
+        })
+    }
+}
+
+pub fn handle_initialize_single_sided_pool<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeCustomizablePoolCtx<'info>>,
+    params: InitializeSingleSidedPoolParameters,
+) -> Result<()> {
+    let params = params.into_customizable_pool_parameters()?;
+    handle_initialize_customizable_pool(ctx, params)
+}
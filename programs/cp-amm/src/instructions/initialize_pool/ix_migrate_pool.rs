@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    const_pda,
+    constants::{
+        seeds::{POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX},
+        BASIS_POINT_MAX,
+    },
+    state::{Config, Pool, Position},
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u128,
+    EvtCreatePosition, EvtMigratePool, EvtPermanentLockPosition, PoolError,
+};
+
+use super::{
+    initialize_dynamic_config_pool, max_key, min_key, DynamicConfigPoolAccounts,
+    InitializeCustomizablePoolParameters,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MigratePoolParameters {
+    /// pool parameters, validated as in `initialize_pool_with_dynamic_config`
+    pub pool_parameters: InitializeCustomizablePoolParameters,
+    /// share of the position liquidity to permanently lock, in basis points
+    pub permanent_lock_liquidity_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigratePoolCtx<'info> {
+    /// CHECK: Pool creator
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: owner of the migrated position
+    pub owner: UncheckedAccount<'info>,
+
+    /// position_nft_mint
+    #[account(
+        init,
+        signer,
+        payer = payer,
+        mint::token_program = token_2022_program,
+        mint::decimals = 0,
+        mint::authority = pool_authority,
+        mint::freeze_authority = pool, // use pool, so we can filter all position_nft_mint given pool address
+        extensions::metadata_pointer::authority = pool_authority,
+        extensions::metadata_pointer::metadata_address = position_nft_mint,
+        extensions::close_authority::authority = pool_authority,
+    )]
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// position nft account
+    #[account(
+        init,
+        seeds = [POSITION_NFT_ACCOUNT_PREFIX, position_nft_mint.key().as_ref()],
+        token::mint = position_nft_mint,
+        token::authority = owner,
+        token::token_program = token_2022_program,
+        payer = payer,
+        bump,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Address paying to create the pool. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of the migrated tokens, usually a PDA of the program migrating liquidity through CPI
+    pub migrator: Signer<'info>,
+
+    pub pool_creator_authority: Signer<'info>,
+
+    /// Which config the pool belongs to.
+    #[account(has_one = pool_creator_authority)]
+    pub config: AccountLoader<'info, Config>,
+
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Initialize an account to store the pool state
+    #[account(
+        init,
+        seeds = [
+            POOL_PREFIX,
+            config.key().as_ref(),
+            &max_key(&token_a_mint.key(), &token_b_mint.key()),
+            &min_key(&token_a_mint.key(), &token_b_mint.key()),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            POSITION_PREFIX,
+            position_nft_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + Position::INIT_SPACE
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Token a mint
+    #[account(
+        constraint = token_a_mint.key() != token_b_mint.key(),
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token b mint
+    #[account(
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token a vault for the pool
+    #[account(
+        init,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            token_a_mint.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        token::token_program = token_a_program,
+        payer = payer,
+        bump,
+    )]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token b vault for the pool
+    #[account(
+        init,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            token_b_mint.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        token::token_program = token_b_program,
+        payer = payer,
+        bump,
+    )]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// migrator token a account
+    #[account(mut)]
+    pub migrator_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// migrator token b account
+    #[account(mut)]
+    pub migrator_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program to create mint account and mint tokens
+    pub token_a_program: Interface<'info, TokenInterface>,
+    /// Program to create mint account and mint tokens
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// Program to create NFT mint/token account and transfer for token22 account
+    pub token_2022_program: Program<'info, Token2022>,
+
+    // Sysvar for program account
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_migrate_pool<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigratePoolCtx<'info>>,
+    params: MigratePoolParameters,
+) -> Result<()> {
+    let MigratePoolParameters {
+        pool_parameters: params,
+        permanent_lock_liquidity_bps,
+    } = params;
+    require!(
+        u64::from(permanent_lock_liquidity_bps) <= BASIS_POINT_MAX,
+        PoolError::InvalidParameters
+    );
+    let liquidity = params.liquidity;
+    let permanent_lock_liquidity = safe_mul_div_cast_u128(
+        liquidity,
+        permanent_lock_liquidity_bps.into(),
+        BASIS_POINT_MAX.into(),
+        Rounding::Down,
+    )?;
+
+    let evt_initialize_pool = initialize_dynamic_config_pool(
+        DynamicConfigPoolAccounts {
+            creator: ctx.accounts.creator.key(),
+            payer: &ctx.accounts.payer,
+            config: &ctx.accounts.config,
+            pool_authority: &ctx.accounts.pool_authority,
+            pool: &ctx.accounts.pool,
+            position: &ctx.accounts.position,
+            position_nft_mint: &ctx.accounts.position_nft_mint,
+            position_nft_account: &ctx.accounts.position_nft_account,
+            token_a_mint: &ctx.accounts.token_a_mint,
+            token_b_mint: &ctx.accounts.token_b_mint,
+            token_a_vault: &ctx.accounts.token_a_vault,
+            token_b_vault: &ctx.accounts.token_b_vault,
+            funder: &ctx.accounts.migrator,
+            funder_token_a: &ctx.accounts.migrator_token_a,
+            funder_token_b: &ctx.accounts.migrator_token_b,
+            token_a_program: &ctx.accounts.token_a_program,
+            token_b_program: &ctx.accounts.token_b_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        params,
+        permanent_lock_liquidity,
+        ctx.remaining_accounts,
+    )?;
+    let total_amount_a = evt_initialize_pool.total_amount_a;
+    let total_amount_b = evt_initialize_pool.total_amount_b;
+
+    emit_cpi!(EvtCreatePosition {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+    });
+
+    emit_cpi!(evt_initialize_pool);
+
+    if permanent_lock_liquidity > 0 {
+        emit_cpi!(EvtPermanentLockPosition {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            lock_liquidity_amount: permanent_lock_liquidity,
+            total_permanent_locked_liquidity: permanent_lock_liquidity,
+        });
+    }
+
+    emit_cpi!(EvtMigratePool {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        migrator: ctx.accounts.migrator.key(),
+        liquidity,
+        permanent_lock_liquidity,
+        total_amount_a,
+        total_amount_b,
+    });
+
+    Ok(())
+}
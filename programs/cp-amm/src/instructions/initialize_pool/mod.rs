@@ -2,5 +2,9 @@ pub mod ix_initialize_pool;
 pub use ix_initialize_pool::*;
 pub mod ix_initialize_customizable_pool;
 pub use ix_initialize_customizable_pool::*;
+pub mod ix_initialize_single_sided_pool;
+pub use ix_initialize_single_sided_pool::*;
 pub mod ix_initialize_pool_with_dynamic_config;
 pub use ix_initialize_pool_with_dynamic_config::*;
+pub mod ix_migrate_pool;
+pub use ix_migrate_pool::*;
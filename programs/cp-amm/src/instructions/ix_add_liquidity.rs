@@ -2,9 +2,15 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    get_pool_access_validator,
+    allowlist::load_allowlisted_wallet,
+    bribe::update_bribe_ledgers,
+    get_pool_access_validator_with_allowlist,
+    reward_extension::update_reward_extensions,
     state::{ModifyLiquidityResult, Pool, Position},
-    token::{calculate_transfer_fee_included_amount, transfer_from_user},
+    token::{
+        calculate_transfer_fee_included_amount, load_transfer_hook_accounts, transfer_from_user,
+        validate_transfer_fee_bps,
+    },
     u128x128_math::Rounding,
     EvtAddLiquidity, EvtLiquidityChange, PoolError,
 };
@@ -19,6 +25,18 @@ pub struct AddLiquidityParameters {
     pub token_b_amount_threshold: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct AddLiquidityParameters2 {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// maximum token a amount
+    pub token_a_amount_threshold: u64,
+    /// maximum token b amount
+    pub token_b_amount_threshold: u64,
+    /// maximum transfer fee bps of token a and token b
+    pub max_transfer_fee_bps: u16,
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct AddLiquidityCtx<'info> {
@@ -71,9 +89,10 @@ pub struct AddLiquidityCtx<'info> {
     pub token_b_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handle_add_liquidity(
-    ctx: Context<AddLiquidityCtx>,
+pub fn handle_add_liquidity<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
     params: AddLiquidityParameters,
+    max_transfer_fee_bps: Option<u16>,
 ) -> Result<()> {
     let AddLiquidityParameters {
         liquidity_delta,
@@ -82,9 +101,20 @@ pub fn handle_add_liquidity(
     } = params;
     require!(params.liquidity_delta > 0, PoolError::InvalidParameters);
 
+    validate_transfer_fee_bps(&ctx.accounts.token_a_mint, max_transfer_fee_bps)?;
+    validate_transfer_fee_bps(&ctx.accounts.token_b_mint, max_transfer_fee_bps)?;
+
+    // allowlist entry of permissioned pool is the first in remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
     {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let allowlisted_wallet = load_allowlisted_wallet(
+            ctx.accounts.pool.key(),
+            &pool,
+            ctx.accounts.owner.key(),
+            &mut remaining_accounts,
+        )?;
+        let access_validator = get_pool_access_validator_with_allowlist(&pool, allowlisted_wallet)?;
         require!(
             access_validator.can_add_liquidity(),
             PoolError::PoolDisabled
@@ -99,6 +129,23 @@ pub fn handle_add_liquidity(
     let current_time = Clock::get()?.unix_timestamp as u64;
     position.update_rewards(&mut pool, current_time)?;
 
+    // update pool & position extension rewards before any liquidity change
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // update pool & position bribe ledgers before any liquidity change
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
     let ModifyLiquidityResult {
         token_a_amount,
         token_b_amount,
@@ -111,6 +158,20 @@ pub fn handle_add_liquidity(
 
     pool.apply_add_liquidity(&mut position, liquidity_delta)?;
 
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    // transfer hook accounts of token a followed by token b are the last in remaining accounts
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
     let total_amount_a =
         calculate_transfer_fee_included_amount(&ctx.accounts.token_a_mint, token_a_amount)?.amount;
     let total_amount_b =
@@ -132,6 +193,7 @@ pub fn handle_add_liquidity(
         &ctx.accounts.token_a_vault,
         &ctx.accounts.token_a_program,
         total_amount_a,
+        transfer_hook_accounts_a,
     )?;
 
     transfer_from_user(
@@ -141,6 +203,7 @@ pub fn handle_add_liquidity(
         &ctx.accounts.token_b_vault,
         &ctx.accounts.token_b_program,
         total_amount_b,
+        transfer_hook_accounts_b,
     )?;
 
     emit_cpi!(EvtAddLiquidity {
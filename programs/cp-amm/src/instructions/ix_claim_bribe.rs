@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    constants::seeds::BRIBE_CLAIM_RECEIPT_PREFIX,
+    error::PoolError,
+    event::EvtClaimBribe,
+    state::{
+        get_bribe_epoch, BribeCampaign, BribeClaimReceipt, BribeLedger, Pool, Position,
+        PositionBribeLedger,
+    },
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimBribeCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = mint,
+        constraint = bribe_campaign.load()?.vault == bribe_vault.key() @ PoolError::InvalidBribeVault,
+    )]
+    pub bribe_campaign: AccountLoader<'info, BribeCampaign>,
+
+    #[account(has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(mut, has_one = position)]
+    pub position_bribe_ledger: AccountLoader<'info, PositionBribeLedger>,
+
+    #[account(
+        init,
+        seeds = [
+            BRIBE_CLAIM_RECEIPT_PREFIX,
+            bribe_campaign.key().as_ref(),
+            position.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = 8 + BribeClaimReceipt::INIT_SPACE
+    )]
+    pub bribe_claim_receipt: AccountLoader<'info, BribeClaimReceipt>,
+
+    /// The vault token account for bribe token
+    #[account(mut)]
+    pub bribe_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bribe mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_bribe<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimBribeCtx<'info>>,
+) -> Result<()> {
+    let mut bribe_campaign = ctx.accounts.bribe_campaign.load_mut()?;
+    let epoch = bribe_campaign.epoch;
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        epoch < get_bribe_epoch(current_time),
+        PoolError::BribeEpochNotEnded
+    );
+
+    let epoch_liquidity_seconds = {
+        let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+        let mut position_bribe_ledger = ctx.accounts.position_bribe_ledger.load_mut()?;
+        bribe_ledger.update(current_time)?;
+        position_bribe_ledger.update(&bribe_ledger, current_time)?;
+        position_bribe_ledger.get_epoch_liquidity_seconds(epoch)?
+    };
+
+    // transfer hook accounts of bribe mint
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.mint, &mut remaining_accounts)?;
+
+    let amount = bribe_campaign.get_claimable_amount(epoch_liquidity_seconds)?;
+    bribe_campaign.accumulate_claimed_amount(amount)?;
+
+    let mut bribe_claim_receipt = ctx.accounts.bribe_claim_receipt.load_init()?;
+    bribe_claim_receipt.bribe_campaign = ctx.accounts.bribe_campaign.key();
+    bribe_claim_receipt.position = ctx.accounts.position.key();
+    bribe_claim_receipt.amount = amount;
+
+    if amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.bribe_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            amount,
+            transfer_hook_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimBribe {
+        pool: ctx.accounts.pool.key(),
+        bribe_campaign: ctx.accounts.bribe_campaign.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        epoch,
+        amount,
+    });
+
+    Ok(())
+}
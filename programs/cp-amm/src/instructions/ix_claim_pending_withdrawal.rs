@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    state::{Pool, Position},
+    token::{is_transfer_frozen, load_transfer_hook_accounts, transfer_from_pool},
+    EvtClaimPendingWithdrawal, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPendingWithdrawalCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut, has_one = pool
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The user token a account
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token b account
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_pending_withdrawal<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPendingWithdrawalCtx<'info>>,
+) -> Result<()> {
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    // transfer hook accounts of token a followed by token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
+    // token that is still frozen stays pending
+    let token_a_amount =
+        if is_transfer_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_a_account) {
+            0
+        } else {
+            position.pending_withdrawal_a
+        };
+    let token_b_amount =
+        if is_transfer_frozen(&ctx.accounts.token_b_vault, &ctx.accounts.token_b_account) {
+            0
+        } else {
+            position.pending_withdrawal_b
+        };
+    require!(
+        token_a_amount > 0 || token_b_amount > 0,
+        PoolError::AmountIsZero
+    );
+
+    position.claim_pending_withdrawal(token_a_amount, token_b_amount)?;
+
+    if token_a_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            token_a_amount,
+            transfer_hook_accounts_a,
+        )?;
+    }
+
+    if token_b_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            token_b_amount,
+            transfer_hook_accounts_b,
+        )?;
+    }
+
+    emit_cpi!(EvtClaimPendingWithdrawal {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        token_a_amount,
+        token_b_amount,
+    });
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     const_pda,
+    locked_fee_extension::update_locked_fee,
     state::{Pool, Position},
-    token::transfer_from_pool,
+    token::{is_transfer_frozen, load_transfer_hook_accounts, transfer_from_pool},
     EvtClaimPositionFee,
 };
 
@@ -70,18 +71,43 @@ pub struct ClaimPositionFeeCtx<'info> {
     pub token_b_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handle_claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()> {
+pub fn handle_claim_position_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeCtx<'info>>,
+) -> Result<()> {
     let mut position = ctx.accounts.position.load_mut()?;
 
+    // locked fee extension if the pool has one, followed by transfer hook accounts of token a and token b
+    let mut remaining_accounts = ctx.remaining_accounts;
     let pool = ctx.accounts.pool.load()?;
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut remaining_accounts,
+    )?;
+
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
     position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
     // update metrics
 
-    let fee_a_pending = position.fee_a_pending;
-    let fee_b_pending = position.fee_b_pending;
-    position
-        .metrics
-        .accumulate_claimed_fee(fee_a_pending, fee_b_pending)?;
+    // fee of frozen token is kept pending, and can be claimed once it's unfrozen
+    let fee_a_pending =
+        if is_transfer_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_a_account) {
+            0
+        } else {
+            position.fee_a_pending
+        };
+    let fee_b_pending =
+        if is_transfer_frozen(&ctx.accounts.token_b_vault, &ctx.accounts.token_b_account) {
+            0
+        } else {
+            position.fee_b_pending
+        };
+    position.claim_fee(fee_a_pending, fee_b_pending)?;
 
     if fee_a_pending > 0 {
         // send to user
@@ -92,6 +118,7 @@ pub fn handle_claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()
             &ctx.accounts.token_a_account,
             &ctx.accounts.token_a_program,
             fee_a_pending,
+            transfer_hook_accounts_a,
         )?;
     }
 
@@ -103,11 +130,10 @@ pub fn handle_claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()
             &ctx.accounts.token_b_account,
             &ctx.accounts.token_b_program,
             fee_b_pending,
+            transfer_hook_accounts_b,
         )?;
     }
 
-    position.reset_pending_fee();
-
     emit_cpi!(EvtClaimPositionFee {
         pool: ctx.accounts.pool.key(),
         position: ctx.accounts.position.key(),
@@ -3,11 +3,15 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     const_pda,
-    constants::NUM_REWARDS,
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
     error::PoolError,
     event::EvtClaimReward,
-    state::{pool::Pool, position::Position},
-    token::transfer_from_pool,
+    get_pool_access_validator,
+    reward_extension::{load_position_reward_extension, load_reward_extension},
+    state::{
+        get_reward_info, is_extension_reward_index, pool::Pool, position::Position, RewardExtension,
+    },
+    token::{load_transfer_hook_accounts, transfer_from_pool},
 };
 
 #[event_cpi]
@@ -51,11 +55,21 @@ pub struct ClaimRewardCtx<'info> {
 }
 
 impl<'info> ClaimRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
+    fn validate(
+        &self,
+        reward_index: usize,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
         let pool = self.pool.load()?;
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+        let access_validator = get_pool_access_validator(&pool)?;
+        require!(access_validator.can_claim_reward(), PoolError::PoolDisabled);
 
-        let reward_info = &pool.reward_infos[reward_index];
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
+
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
         require!(
             reward_info.vault.eq(&self.reward_vault.key()),
@@ -66,15 +80,40 @@ impl<'info> ClaimRewardCtx<'info> {
     }
 }
 
-pub fn handle_claim_reward(
-    ctx: Context<ClaimRewardCtx>,
+pub fn handle_claim_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimRewardCtx<'info>>,
     reward_index: u8,
     skip_reward: u8,
 ) -> Result<()> {
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
+
+    // extension reward requires pool reward extension and position reward extension
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loaders = if is_extension_reward_index(index) {
+        Some((
+            load_reward_extension(ctx.accounts.pool.key(), &mut remaining_accounts)?,
+            load_position_reward_extension(ctx.accounts.position.key(), &mut remaining_accounts)?,
+        ))
+    } else {
+        None
+    };
+
+    let mut reward_extension = reward_extension_loaders
+        .as_ref()
+        .map(|(loader, _)| loader.load_mut())
+        .transpose()?;
+    let mut position_reward_extension = reward_extension_loaders
+        .as_ref()
+        .map(|(_, loader)| loader.load_mut())
+        .transpose()?;
+
+    ctx.accounts.validate(index, reward_extension.as_deref())?;
+
+    // transfer hook accounts of reward mint are the last in remaining accounts
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.reward_mint, &mut remaining_accounts)?;
 
     let mut position = ctx.accounts.position.load_mut()?;
 
@@ -85,7 +124,17 @@ pub fn handle_claim_reward(
     position.update_rewards(&mut pool, current_time)?;
 
     // get all pending reward
-    let total_reward = position.claim_reward(index)?;
+    let total_reward = match (
+        reward_extension.as_mut(),
+        position_reward_extension.as_mut(),
+    ) {
+        (Some(reward_extension), Some(position_reward_extension)) => {
+            reward_extension.update_rewards(current_time)?;
+            position_reward_extension.update_position_reward(reward_extension)?;
+            position_reward_extension.claim_reward(index)?
+        }
+        _ => position.claim_reward(index)?,
+    };
 
     // transfer rewards to user
     if total_reward > 0 {
@@ -99,6 +148,7 @@ pub fn handle_claim_reward(
                 &ctx.accounts.user_token_account,
                 &ctx.accounts.token_program,
                 total_reward,
+                transfer_hook_accounts,
             )?;
         }
     }
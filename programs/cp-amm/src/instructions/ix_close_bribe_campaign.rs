@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+use crate::{
+    const_pda,
+    constants::NUM_BRIBE_EPOCH_SNAPSHOTS,
+    error::PoolError,
+    event::EvtCloseBribeCampaign,
+    safe_math::SafeMath,
+    state::{get_bribe_epoch, BribeCampaign, BribeLedger},
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBribeCampaignCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = funder,
+        has_one = mint,
+        constraint = bribe_campaign.load()?.vault == bribe_vault.key() @ PoolError::InvalidBribeVault,
+        close = funder
+    )]
+    pub bribe_campaign: AccountLoader<'info, BribeCampaign>,
+
+    #[account(
+        mut,
+        constraint = bribe_ledger.load()?.pool == bribe_campaign.load()?.pool @ PoolError::InvalidBribeLedger
+    )]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    /// The vault token account for bribe token
+    #[account(mut)]
+    pub bribe_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bribe mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Once the epoch snapshot is evicted from bribe ledgers, positions can't claim anymore,
+/// so funder takes back what is left in the vault
+pub fn handle_close_bribe_campaign<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CloseBribeCampaignCtx<'info>>,
+) -> Result<()> {
+    let (pool, epoch) = {
+        let bribe_campaign = ctx.accounts.bribe_campaign.load()?;
+        (bribe_campaign.pool, bribe_campaign.epoch)
+    };
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let expired_epoch = epoch.safe_add(NUM_BRIBE_EPOCH_SNAPSHOTS as u64)?;
+    require!(
+        get_bribe_epoch(current_time) > expired_epoch,
+        PoolError::BribeCampaignNotExpired
+    );
+
+    {
+        let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+        bribe_ledger.open_campaign_count = bribe_ledger.open_campaign_count.safe_sub(1)?;
+    }
+
+    // transfer hook accounts of bribe mint
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.mint, &mut remaining_accounts)?;
+
+    let withdrawn_amount = ctx.accounts.bribe_vault.amount;
+    if withdrawn_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.bribe_vault,
+            &ctx.accounts.funder_token_account,
+            &ctx.accounts.token_program,
+            withdrawn_amount,
+            transfer_hook_accounts,
+        )?;
+    }
+
+    let signer_seeds = pool_authority_seeds!();
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bribe_vault.to_account_info(),
+            destination: ctx.accounts.funder.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[&signer_seeds[..]],
+    ))?;
+
+    emit_cpi!(EvtCloseBribeCampaign {
+        pool,
+        bribe_campaign: ctx.accounts.bribe_campaign.key(),
+        funder: ctx.accounts.funder.key(),
+        epoch,
+        withdrawn_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use std::collections::BTreeSet;
+
+use crate::{
+    allowlist::load_pool_permission,
+    bribe::load_bribe_ledger,
+    const_pda,
+    constants::treasury,
+    error::PoolError,
+    event::EvtClosePool,
+    locked_fee_extension::load_locked_fee_extension,
+    reward_extension::load_reward_extension,
+    state::Pool,
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+};
+
+/// Accounts for close pool
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePoolCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = creator,
+        close = creator
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// CHECK: pool creator, receives rent of the pool and the vaults
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// The vault token account for token a
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for token b
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The treasury token a account
+    #[account(
+        mut,
+        associated_token::authority = treasury::ID,
+        associated_token::mint = token_a_mint,
+        associated_token::token_program = token_a_program,
+    )]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The treasury token b account
+    #[account(
+        mut,
+        associated_token::authority = treasury::ID,
+        associated_token::mint = token_b_mint,
+        associated_token::token_program = token_b_program,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RewardVaultRemainingAccount<'info> {
+    #[account(mut, token::token_program = token_program)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close a drained pool. Permissionless.
+/// Residual amounts in the vaults are swept to treasury, and rent of the pool, the vaults, the
+/// reward vaults, the reward extension, the locked fee extension, the bribe ledger and the pool
+/// permission is returned to pool creator. Bribe campaigns and allowlist entries must be closed and
+/// reward vaults must be empty first. Withheld transfer fees of token2022 vaults must be harvested to
+/// the mint first. Fungible share pools can't be closed, the shared position is never closed
+pub fn handle_close_pool<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClosePoolCtx<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    // remaining accounts are, in order and only if the pool has them: reward extension, locked fee
+    // extension, bribe ledger and pool permission, followed by reward vault and its token program of
+    // each initialized reward in index order, and transfer hook accounts of token a and token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut reward_vaults = Vec::new();
+    {
+        let mut pool = ctx.accounts.pool.load_mut()?;
+        pool.update_rewards(current_time)?;
+        require!(pool.is_empty(current_time), PoolError::PoolIsNotEmpty);
+        require!(!pool.is_fungible_share_enabled(), PoolError::PoolIsNotEmpty);
+
+        reward_vaults.extend(
+            pool.reward_infos
+                .iter()
+                .filter(|reward_info| reward_info.initialized())
+                .map(|reward_info| reward_info.vault),
+        );
+
+        if pool.has_extension_reward() {
+            let reward_extension_loader =
+                load_reward_extension(ctx.accounts.pool.key(), &mut remaining_accounts)?;
+            {
+                let mut reward_extension = reward_extension_loader.load_mut()?;
+                reward_extension.update_rewards(current_time)?;
+                require!(
+                    reward_extension
+                        .reward_infos
+                        .iter()
+                        .all(|reward_info| reward_info.is_finished(current_time)),
+                    PoolError::PoolIsNotEmpty
+                );
+                reward_vaults.extend(
+                    reward_extension
+                        .reward_infos
+                        .iter()
+                        .filter(|reward_info| reward_info.initialized())
+                        .map(|reward_info| reward_info.vault),
+                );
+            }
+            reward_extension_loader.close(ctx.accounts.creator.to_account_info())?;
+        }
+
+        if pool.has_locked_fee_extension() {
+            let locked_fee_extension_loader =
+                load_locked_fee_extension(ctx.accounts.pool.key(), &mut remaining_accounts)?;
+            locked_fee_extension_loader.close(ctx.accounts.creator.to_account_info())?;
+        }
+
+        if pool.has_bribe_ledger() {
+            let bribe_ledger_loader =
+                load_bribe_ledger(ctx.accounts.pool.key(), &mut remaining_accounts)?;
+            require!(
+                bribe_ledger_loader.load()?.open_campaign_count == 0,
+                PoolError::PoolIsNotEmpty
+            );
+            bribe_ledger_loader.close(ctx.accounts.creator.to_account_info())?;
+        }
+
+        if pool.is_permissioned() {
+            let pool_permission_loader =
+                load_pool_permission(ctx.accounts.pool.key(), &mut remaining_accounts)?;
+            require!(
+                pool_permission_loader.load()?.allowlist_entry_count == 0,
+                PoolError::PoolIsNotEmpty
+            );
+            pool_permission_loader.close(ctx.accounts.creator.to_account_info())?;
+        }
+    }
+
+    let signer_seeds = pool_authority_seeds!();
+    for reward_vault in reward_vaults {
+        let reward_vault_account = RewardVaultRemainingAccount::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut RewardVaultRemainingAccountBumps {},
+            &mut BTreeSet::new(),
+        )
+        .map_err(|_| PoolError::InvalidRewardVault)?;
+        require!(
+            reward_vault_account.reward_vault.key() == reward_vault,
+            PoolError::InvalidRewardVault
+        );
+        require!(
+            reward_vault_account.reward_vault.amount == 0,
+            PoolError::PoolIsNotEmpty
+        );
+
+        close_account(CpiContext::new_with_signer(
+            reward_vault_account.token_program.to_account_info(),
+            CloseAccount {
+                account: reward_vault_account.reward_vault.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            },
+            &[&signer_seeds[..]],
+        ))?;
+    }
+
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
+    // rounding dust left by liquidity providers, fee claims and swaps
+    let token_a_amount = ctx.accounts.token_a_vault.amount;
+    let token_b_amount = ctx.accounts.token_b_vault.amount;
+
+    if token_a_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            token_a_amount,
+            transfer_hook_accounts_a,
+        )?;
+    }
+
+    if token_b_amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            token_b_amount,
+            transfer_hook_accounts_b,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_a_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.token_a_vault.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[&signer_seeds[..]],
+    ))?;
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_b_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.token_b_vault.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[&signer_seeds[..]],
+    ))?;
+
+    emit_cpi!(EvtClosePool {
+        pool: ctx.accounts.pool.key(),
+        creator: ctx.accounts.creator.key(),
+        token_a_mint: ctx.accounts.token_a_mint.key(),
+        token_b_mint: ctx.accounts.token_b_mint.key(),
+        token_a_amount,
+        token_b_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    state::{BribeLedger, Pool, Position, PositionBribeLedger},
+    EvtClosePositionBribeLedger,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePositionBribeLedgerCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(
+        mut,
+        has_one = position,
+        close = rent_receiver
+    )]
+    pub position_bribe_ledger: AccountLoader<'info, PositionBribeLedger>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Unclaimed bribes of the position can't be claimed after the ledger is closed
+pub fn handle_close_position_bribe_ledger(ctx: Context<ClosePositionBribeLedgerCtx>) -> Result<()> {
+    let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    let position_bribe_ledger = ctx.accounts.position_bribe_ledger.load()?;
+
+    // update bribe ledger before enrolled liquidity changes
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    bribe_ledger.update(current_time)?;
+    bribe_ledger.remove_enrolled_liquidity(position_bribe_ledger.liquidity)?;
+
+    position.bribe_ledger_flag = 0;
+
+    emit_cpi!(EvtClosePositionBribeLedger {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        position: ctx.accounts.position.key(),
+        position_bribe_ledger: ctx.accounts.position_bribe_ledger.key(),
+    });
+
+    Ok(())
+}
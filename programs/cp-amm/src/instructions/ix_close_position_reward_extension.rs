@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    state::{Pool, Position, PositionRewardExtension, RewardExtension},
+    EvtClosePositionRewardExtension, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePositionRewardExtensionCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(
+        mut,
+        has_one = position,
+        close = rent_receiver
+    )]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_close_position_reward_extension(
+    ctx: Context<ClosePositionRewardExtensionCtx>,
+) -> Result<()> {
+    let mut reward_extension = ctx.accounts.reward_extension.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    let mut position_reward_extension = ctx.accounts.position_reward_extension.load_mut()?;
+
+    // settle extension rewards before checking and before enrolled liquidity changes
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    reward_extension.update_rewards(current_time)?;
+    position_reward_extension.update_position_reward(&reward_extension)?;
+
+    require!(
+        position_reward_extension.is_empty(),
+        PoolError::PositionRewardExtensionIsNotEmpty
+    );
+
+    reward_extension.remove_enrolled_liquidity(position_reward_extension.liquidity)?;
+    position.reward_extension_flag = 0;
+
+    emit_cpi!(EvtClosePositionRewardExtension {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        position: ctx.accounts.position.key(),
+        position_reward_extension: ctx.accounts.position_reward_extension.key(),
+    });
+
+    Ok(())
+}
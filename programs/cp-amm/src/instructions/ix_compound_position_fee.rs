@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    allowlist::load_allowlisted_wallet,
+    bribe::update_bribe_ledgers,
+    get_pool_access_validator_with_allowlist,
+    locked_fee_extension::update_locked_fee,
+    params::swap::TradeDirection,
+    reward_extension::update_reward_extensions,
+    state::{fee::FeeMode, LockedFeeExtension, ModifyLiquidityResult, Pool, Position},
+    swap::{validate_single_swap_instruction, SwapMode},
+    u128x128_math::Rounding,
+    EvtLiquidityChange, EvtSwap2, PoolError, SwapParameters2,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct CompoundPositionFeeParameters {
+    /// minimum liquidity to be added to the position
+    pub minimum_liquidity_delta: u128,
+    /// swap the excess side of pending fee through the pool before compounding
+    pub swap_excess: bool,
+    /// pool price after swapping the excess can't be lower than the limit when token a is swapped,
+    /// or higher when token b is swapped
+    pub sqrt_price_limit: u128,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompoundPositionFeeCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+      mut,
+      has_one = pool,
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The token account for nft
+    #[account(
+            constraint = position_nft_account.mint == position.load()?.nft_mint,
+            constraint = position_nft_account.amount == 1,
+            token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// owner of position
+    pub owner: Signer<'info>,
+}
+
+pub fn handle_compound_position_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CompoundPositionFeeCtx<'info>>,
+    params: CompoundPositionFeeParameters,
+) -> Result<()> {
+    let CompoundPositionFeeParameters {
+        minimum_liquidity_delta,
+        swap_excess,
+        sqrt_price_limit,
+    } = params;
+
+    // allowlist entry of permissioned pool is the first in remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
+    {
+        let pool = ctx.accounts.pool.load()?;
+        let allowlisted_wallet = load_allowlisted_wallet(
+            ctx.accounts.pool.key(),
+            &pool,
+            ctx.accounts.owner.key(),
+            &mut remaining_accounts,
+        )?;
+        let access_validator = get_pool_access_validator_with_allowlist(&pool, allowlisted_wallet)?;
+        require!(
+            access_validator.can_add_liquidity(),
+            PoolError::PoolDisabled
+        );
+        if swap_excess {
+            require!(
+                access_validator.can_swap(&ctx.accounts.owner.key()),
+                PoolError::PoolDisabled
+            );
+        }
+    }
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    // update current pool reward & postion reward before any logic
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+
+    // update pool & position extension rewards before any liquidity change
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // update pool & position bribe ledgers before any liquidity change
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // locked fee extension follows bribe ledgers if the pool has one
+    let locked_fee_extension_loader = update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut remaining_accounts,
+    )?;
+
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    if swap_excess {
+        let mut locked_fee_extension = locked_fee_extension_loader
+            .as_ref()
+            .map(|loader| loader.load_mut())
+            .transpose()?;
+        if let Some(swap_event) = swap_excess_pending_fee(
+            ctx.accounts.pool.key(),
+            &mut pool,
+            &mut position,
+            locked_fee_extension.as_deref_mut(),
+            Some(remaining_accounts),
+            current_time,
+            sqrt_price_limit,
+        )? {
+            emit_cpi!(swap_event);
+        }
+    }
+
+    let liquidity_delta =
+        pool.get_liquidity_from_amounts(position.fee_a_pending, position.fee_b_pending)?;
+
+    require!(liquidity_delta > 0, PoolError::AmountIsZero);
+    require!(
+        liquidity_delta >= minimum_liquidity_delta,
+        PoolError::ExceededSlippage
+    );
+
+    let ModifyLiquidityResult {
+        token_a_amount,
+        token_b_amount,
+    } = pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)?;
+
+    let token_a_amount_threshold = position.fee_a_pending;
+    let token_b_amount_threshold = position.fee_b_pending;
+
+    require!(
+        token_a_amount <= token_a_amount_threshold && token_b_amount <= token_b_amount_threshold,
+        PoolError::ExceededSlippage
+    );
+
+    pool.apply_add_liquidity(&mut position, liquidity_delta)?;
+
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    // compounded fee is treated as claimed, the remaining dust is kept as pending fee
+    position.remove_fee_pending(token_a_amount, token_b_amount)?;
+    position
+        .metrics
+        .accumulate_claimed_fee(token_a_amount, token_b_amount)?;
+
+    let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount()?;
+
+    emit_cpi!(EvtLiquidityChange {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        liquidity_delta,
+        token_a_amount_threshold,
+        token_b_amount_threshold,
+        token_a_amount,
+        token_b_amount,
+        transfer_fee_included_token_a_amount: token_a_amount,
+        transfer_fee_included_token_b_amount: token_b_amount,
+        reserve_b_amount,
+        reserve_a_amount,
+        change_type: 0
+    });
+
+    Ok(())
+}
+
+/// Swap the excess side of pending fee of a position through the pool, so it can be compounded at the
+/// pool ratio. Input is taken from pending fee and output is credited to pending fee, no token transfer
+/// is needed. Without instructions sysvar accounts, the swap is skipped while rate limiter is applied.
+/// Fails if the pool price after the swap crosses the price limit
+pub fn swap_excess_pending_fee(
+    pool_key: Pubkey,
+    pool: &mut Pool,
+    position: &mut Position,
+    locked_fee_extension: Option<&mut LockedFeeExtension>,
+    instruction_sysvar_accounts: Option<&[AccountInfo]>,
+    current_time: u64,
+    sqrt_price_limit: u128,
+) -> Result<Option<EvtSwap2>> {
+    let Some((trade_direction, amount_in)) =
+        pool.get_compound_swap_amount(position.fee_a_pending, position.fee_b_pending)?
+    else {
+        return Ok(None);
+    };
+
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+
+    // same as swap, only 1 swap instruction is allowed in a tx when rate limiter is applied
+    if let Ok(rate_limiter) = pool.pool_fees.base_fee.get_fee_rate_limiter() {
+        if rate_limiter.is_rate_limiter_applied(
+            current_point,
+            pool.activation_point,
+            trade_direction,
+        )? {
+            match instruction_sysvar_accounts {
+                Some(remaining_accounts) => {
+                    validate_single_swap_instruction(&pool_key, remaining_accounts)?
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    // update for dynamic fee reference
+    pool.update_pre_swap(current_time)?;
+
+    let fee_mode = FeeMode::get_fee_mode(pool.collect_fee_mode, trade_direction, false)?;
+
+    let swap_result = pool.get_swap_result_from_partial_input(
+        amount_in,
+        &fee_mode,
+        trade_direction,
+        current_point,
+    )?;
+
+    // skip the swap if the excess is too small to be swapped, it is just left as pending fee
+    if swap_result.included_fee_input_amount == 0 || swap_result.output_amount == 0 {
+        return Ok(None);
+    }
+
+    let within_price_limit = match trade_direction {
+        TradeDirection::AtoB => swap_result.next_sqrt_price >= sqrt_price_limit,
+        TradeDirection::BtoA => swap_result.next_sqrt_price <= sqrt_price_limit,
+    };
+    require!(within_price_limit, PoolError::ExceededSlippage);
+
+    pool.apply_swap_result(&swap_result, &fee_mode, current_time, locked_fee_extension)?;
+
+    match trade_direction {
+        TradeDirection::AtoB => {
+            position.remove_fee_pending(swap_result.included_fee_input_amount, 0)?;
+            position.add_fee_pending(0, swap_result.output_amount)?;
+        }
+        TradeDirection::BtoA => {
+            position.remove_fee_pending(0, swap_result.included_fee_input_amount)?;
+            position.add_fee_pending(swap_result.output_amount, 0)?;
+        }
+    }
+
+    let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount()?;
+
+    Ok(Some(EvtSwap2 {
+        pool: pool_key,
+        trade_direction: trade_direction.into(),
+        collect_fee_mode: pool.collect_fee_mode,
+        has_referral: false,
+        params: SwapParameters2 {
+            amount_0: amount_in,
+            amount_1: 0,
+            swap_mode: SwapMode::PartialFill.into(),
+        },
+        swap_result,
+        current_timestamp: current_time,
+        included_transfer_fee_amount_in: swap_result.included_fee_input_amount,
+        included_transfer_fee_amount_out: swap_result.output_amount,
+        excluded_transfer_fee_amount_out: swap_result.output_amount,
+        reserve_a_amount,
+        reserve_b_amount,
+        transfer_fee_bps_in: 0,
+        transfer_fee_bps_out: 0,
+    }))
+}
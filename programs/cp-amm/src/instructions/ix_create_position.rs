@@ -6,8 +6,14 @@ use anchor_spl::{
 
 use crate::{
     const_pda,
-    constants::seeds::{POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX},
+    constants::{
+        position_nft,
+        seeds::{POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX},
+    },
     get_pool_access_validator,
+    position_nft_metadata::{
+        get_pool_metadata_fields, get_position_nft_uri, update_position_nft_metadata,
+    },
     state::{Pool, Position},
     token::update_account_lamports_to_minimum_balance,
     EvtCreatePosition, PoolError,
@@ -100,12 +106,16 @@ pub fn handle_create_position(ctx: Context<CreatePositionCtx>) -> Result<()> {
 
     drop(position);
     create_position_nft(
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.position_nft_mint.to_account_info(),
-        ctx.accounts.pool_authority.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.position_nft_account.to_account_info(),
+        PositionNftAccounts {
+            payer: ctx.accounts.payer.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        },
+        ctx.accounts.pool.key(),
+        &pool,
     )?;
 
     emit_cpi!(EvtCreatePosition {
@@ -118,14 +128,30 @@ pub fn handle_create_position(ctx: Context<CreatePositionCtx>) -> Result<()> {
     Ok(())
 }
 
-pub fn create_position_nft<'info>(
-    payer: AccountInfo<'info>,
-    position_nft_mint: AccountInfo<'info>,
-    pool_authority: AccountInfo<'info>,
-    system_program: AccountInfo<'info>,
-    token_program: AccountInfo<'info>,
-    position_nft_account: AccountInfo<'info>,
+/// Accounts to create and mint position nft
+pub struct PositionNftAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub position_nft_mint: AccountInfo<'info>,
+    pub pool_authority: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub position_nft_account: AccountInfo<'info>,
+}
+
+pub fn create_position_nft(
+    accounts: PositionNftAccounts<'_>,
+    pool_key: Pubkey,
+    pool: &Pool,
 ) -> Result<()> {
+    let PositionNftAccounts {
+        payer,
+        position_nft_mint,
+        pool_authority,
+        system_program,
+        token_program,
+        position_nft_account,
+    } = accounts;
+
     // init token metadata
     let seeds = pool_authority_seeds!();
     let signer_seeds = &[&seeds[..]];
@@ -139,9 +165,15 @@ pub fn create_position_nft<'info>(
     let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
     token_metadata_initialize(
         cpi_ctx,
-        String::from("Meteora Position NFT"), // TODO do we need to allow user to input custom name?
-        String::from("MPN"),
-        String::from("https://raw.githubusercontent.com/MeteoraAg/token-metadata/main/meteora_position_nft.png"), // TODO update image
+        String::from(position_nft::NAME), // TODO do we need to allow user to input custom name?
+        String::from(position_nft::SYMBOL),
+        get_position_nft_uri(position_nft_mint.key()),
+    )?;
+    update_position_nft_metadata(
+        token_program.clone(),
+        position_nft_mint.clone(),
+        pool_authority.clone(),
+        get_pool_metadata_fields(pool_key, pool)?,
     )?;
 
     // transfer minimum rent to mint account
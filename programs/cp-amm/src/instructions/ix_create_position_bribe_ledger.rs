@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POSITION_BRIBE_LEDGER_PREFIX,
+    state::{BribeLedger, Pool, Position, PositionBribeLedger},
+    EvtCreatePositionBribeLedger,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePositionBribeLedgerCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(
+        init,
+        seeds = [
+            POSITION_BRIBE_LEDGER_PREFIX,
+            position.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + PositionBribeLedger::INIT_SPACE
+    )]
+    pub position_bribe_ledger: AccountLoader<'info, PositionBribeLedger>,
+
+    /// Address paying to create the position bribe ledger. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_position_bribe_ledger(
+    ctx: Context<CreatePositionBribeLedgerCtx>,
+) -> Result<()> {
+    let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    let mut position_bribe_ledger = ctx.accounts.position_bribe_ledger.load_init()?;
+
+    // update bribe ledger before enrolled liquidity changes, so position only earns bribes from now on
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    bribe_ledger.update(current_time)?;
+
+    let liquidity = position.get_total_liquidity()?;
+    position_bribe_ledger.initialize(
+        ctx.accounts.position.key(),
+        liquidity,
+        &bribe_ledger,
+        current_time,
+    );
+    bribe_ledger.add_enrolled_liquidity(liquidity)?;
+    position.bribe_ledger_flag = 1;
+
+    emit_cpi!(EvtCreatePositionBribeLedger {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_bribe_ledger: ctx.accounts.position_bribe_ledger.key(),
+    });
+
+    Ok(())
+}
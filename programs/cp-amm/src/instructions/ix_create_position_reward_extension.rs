@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POSITION_REWARD_EXTENSION_PREFIX,
+    state::{Pool, Position, PositionRewardExtension, RewardExtension},
+    EvtCreatePositionRewardExtension,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePositionRewardExtensionCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    #[account(
+        init,
+        seeds = [
+            POSITION_REWARD_EXTENSION_PREFIX,
+            position.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + PositionRewardExtension::INIT_SPACE
+    )]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+
+    /// Address paying to create the position reward extension. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_position_reward_extension(
+    ctx: Context<CreatePositionRewardExtensionCtx>,
+) -> Result<()> {
+    let mut reward_extension = ctx.accounts.reward_extension.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    let mut position_reward_extension = ctx.accounts.position_reward_extension.load_init()?;
+
+    // update pool extension rewards before enrolled liquidity changes, so position only earns rewards from now on
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    reward_extension.update_rewards(current_time)?;
+
+    let liquidity = position.get_total_liquidity()?;
+    position_reward_extension.initialize(ctx.accounts.position.key(), liquidity, &reward_extension);
+    reward_extension.add_enrolled_liquidity(liquidity)?;
+    position.reward_extension_flag = 1;
+
+    emit_cpi!(EvtCreatePositionRewardExtension {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_reward_extension: ctx.accounts.position_reward_extension.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    event,
+    state::{
+        get_disabled_actions_from_action_data, get_pool_status_from_action_data,
+        get_price_range_from_action_data, get_static_config_parameters_from_action_data, Config,
+        Pool, Proposal, ProposalAction,
+    },
+    update_pool_price_range, update_static_config_parameters, EvtExecuteProposal, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposalCtx<'info> {
+    #[account(mut, has_one = proposer, close = proposer)]
+    pub proposal: AccountLoader<'info, Proposal>,
+
+    /// CHECK: proposer receives rent of the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// target pool of pool actions
+    #[account(mut)]
+    pub pool: Option<AccountLoader<'info, Pool>>,
+
+    /// target config of config actions
+    #[account(mut)]
+    pub config: Option<AccountLoader<'info, Config>>,
+}
+
+/// Execute a proposal once its timelock has passed, permissionless
+pub fn handle_execute_proposal(ctx: Context<ExecuteProposalCtx>) -> Result<()> {
+    let proposal = ctx.accounts.proposal.load()?;
+
+    let current_point = ActivationHandler::get_current_point(proposal.activation_type)?;
+    require!(
+        proposal.is_executable(current_point),
+        PoolError::ProposalNotExecutable
+    );
+
+    let action =
+        ProposalAction::try_from(proposal.action).map_err(|_| PoolError::TypeCastFailed)?;
+    match action {
+        ProposalAction::SetPoolStatus => {
+            let pool_loader = ctx
+                .accounts
+                .pool
+                .as_ref()
+                .filter(|pool| pool.key() == proposal.target)
+                .ok_or(PoolError::InvalidProposalTarget)?;
+            let status = get_pool_status_from_action_data(&proposal.action_data)?;
+
+            let mut pool = pool_loader.load_mut()?;
+            pool.update_pool_status(status)?;
+
+            emit_cpi!(event::EvtSetPoolStatus {
+                pool: pool_loader.key(),
+                status,
+            });
+        }
+        ProposalAction::CloseConfig => {
+            let config = ctx
+                .accounts
+                .config
+                .as_ref()
+                .filter(|config| config.key() == proposal.target)
+                .ok_or(PoolError::InvalidProposalTarget)?;
+            config.close(ctx.accounts.proposer.to_account_info())?;
+
+            emit_cpi!(event::EvtCloseConfig {
+                config: config.key(),
+                admin: proposal.proposer,
+            });
+        }
+        ProposalAction::UpdatePriceRange => {
+            let pool_loader = ctx
+                .accounts
+                .pool
+                .as_ref()
+                .filter(|pool| pool.key() == proposal.target)
+                .ok_or(PoolError::InvalidProposalTarget)?;
+            let (sqrt_min_price, sqrt_max_price) =
+                get_price_range_from_action_data(&proposal.action_data)?;
+
+            let mut pool = pool_loader.load_mut()?;
+            let event = update_pool_price_range(
+                pool_loader.key(),
+                &mut pool,
+                proposal.proposer,
+                sqrt_min_price,
+                sqrt_max_price,
+            )?;
+
+            emit_cpi!(event);
+        }
+        ProposalAction::SetPoolDisabledActions => {
+            let pool_loader = ctx
+                .accounts
+                .pool
+                .as_ref()
+                .filter(|pool| pool.key() == proposal.target)
+                .ok_or(PoolError::InvalidProposalTarget)?;
+            let (disabled_actions, reason) =
+                get_disabled_actions_from_action_data(&proposal.action_data)?;
+
+            let mut pool = pool_loader.load_mut()?;
+            let previous_disabled_actions = pool.disabled_actions;
+            pool.update_disabled_actions(disabled_actions)?;
+
+            emit_cpi!(event::EvtSetPoolDisabledActions {
+                pool: pool_loader.key(),
+                admin: proposal.proposer,
+                previous_disabled_actions,
+                disabled_actions,
+                reason,
+            });
+        }
+        ProposalAction::UpdateConfig => {
+            let config_loader = ctx
+                .accounts
+                .config
+                .as_ref()
+                .filter(|config| config.key() == proposal.target)
+                .ok_or(PoolError::InvalidProposalTarget)?;
+            let config_parameters =
+                get_static_config_parameters_from_action_data(&proposal.action_data)?;
+
+            let mut config = config_loader.load_mut()?;
+            let event = update_static_config_parameters(
+                config_loader.key(),
+                &mut config,
+                proposal.proposer,
+                config_parameters,
+            )?;
+
+            emit_cpi!(event);
+        }
+    }
+
+    emit_cpi!(EvtExecuteProposal {
+        proposal: ctx.accounts.proposal.key(),
+        target: proposal.target,
+        action: proposal.action,
+    });
+
+    Ok(())
+}
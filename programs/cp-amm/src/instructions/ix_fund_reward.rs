@@ -2,15 +2,35 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::{NUM_REWARDS, REWARD_RATE_SCALE},
+    constants::{
+        seeds::ADMIN_REGISTRY_PREFIX, MAX_REWARD_DURATION, MIN_REWARD_DURATION,
+        NUM_EXTENSION_REWARDS, NUM_REWARDS, REWARD_RATE_SCALE,
+    },
     event::EvtFundReward,
     math::safe_math::SafeMath,
-    state::Pool,
-    token::{calculate_transfer_fee_excluded_amount, transfer_from_user},
+    reward_extension::load_reward_extension,
+    state::{
+        get_reward_info, get_reward_info_mut, is_extension_reward_index, Pool, RewardExtension,
+    },
+    token::{
+        calculate_transfer_fee_excluded_amount, load_transfer_hook_accounts, transfer_from_user,
+    },
     utils_math::safe_mul_shr_cast,
     PoolError,
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct FundRewardParameters2 {
+    /// amount of reward to fund
+    pub amount: u64,
+    /// carry forward ineligible reward to the new reward window
+    pub carry_forward: bool,
+    /// timestamp when reward starts being distributed, must not be in the past
+    pub reward_duration_start: u64,
+    /// timestamp when reward stops being distributed
+    pub reward_duration_end: u64,
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct FundRewardCtx<'info> {
@@ -27,23 +47,34 @@ pub struct FundRewardCtx<'info> {
 
     pub funder: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl<'info> FundRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
+impl FundRewardCtx<'_> {
+    fn validate(
+        &self,
+        reward_index: usize,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
         let pool = self.pool.load()?;
 
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
 
-        let reward_info = &pool.reward_infos[reward_index];
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
         require!(
             reward_info.vault.eq(&self.reward_vault.key()),
             PoolError::InvalidRewardVault
         );
         require!(
-            reward_info.is_valid_funder(self.funder.key()),
+            reward_info.is_valid_funder(&self.admin_registry, self.funder.key()),
             PoolError::InvalidAdmin
         );
 
@@ -51,16 +82,70 @@ impl<'info> FundRewardCtx<'info> {
     }
 }
 
-pub fn handle_fund_reward(
-    ctx: Context<FundRewardCtx>,
+pub fn handle_fund_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
+    reward_index: u8,
+    amount: u64,
+    carry_forward: bool,
+) -> Result<()> {
+    process_fund_reward(ctx, reward_index, amount, carry_forward, None)
+}
+
+pub fn handle_fund_reward2<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
+    reward_index: u8,
+    params: FundRewardParameters2,
+) -> Result<()> {
+    let FundRewardParameters2 {
+        amount,
+        carry_forward,
+        reward_duration_start,
+        reward_duration_end,
+    } = params;
+
+    let reward_duration = reward_duration_end.safe_sub(reward_duration_start)?;
+    require!(
+        (MIN_REWARD_DURATION..=MAX_REWARD_DURATION).contains(&reward_duration),
+        PoolError::InvalidRewardDuration
+    );
+
+    process_fund_reward(
+        ctx,
+        reward_index,
+        amount,
+        carry_forward,
+        Some((reward_duration_start, reward_duration_end)),
+    )
+}
+
+fn process_fund_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
     reward_index: u8,
     amount: u64,
     carry_forward: bool,
+    schedule: Option<(u64, u64)>,
 ) -> Result<()> {
-    let index: usize = reward_index
-        .try_into()
-        .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
+    let index: usize = reward_index.into();
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loader = if is_extension_reward_index(index) {
+        Some(load_reward_extension(
+            ctx.accounts.pool.key(),
+            &mut remaining_accounts,
+        )?)
+    } else {
+        None
+    };
+    let mut reward_extension = reward_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    ctx.accounts.validate(index, reward_extension.as_deref())?;
+
+    // transfer hook accounts of reward mint are the last in remaining accounts
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.reward_mint, &mut remaining_accounts)?;
 
     // actual amount need to transfer
     let transfer_fee_excluded_amount_in =
@@ -72,9 +157,12 @@ pub fn handle_fund_reward(
     let current_time = Clock::get()?.unix_timestamp;
     // 1. update pool rewards
     pool.update_rewards(current_time as u64)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(current_time as u64)?;
+    }
 
     // 2. set new farming rate
-    let reward_info = &mut pool.reward_infos[index];
+    let reward_info = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?;
     let pre_reward_rate = reward_info.reward_rate;
 
     let total_amount = if carry_forward {
@@ -104,7 +192,16 @@ pub fn handle_fund_reward(
     };
 
     // Reward rate might include ineligible reward based on whether to brought forward
-    reward_info.update_rate_after_funding(current_time as u64, total_amount)?;
+    if let Some((reward_duration_start, reward_duration_end)) = schedule {
+        reward_info.update_rate_after_scheduled_funding(
+            current_time as u64,
+            reward_duration_start,
+            reward_duration_end,
+            total_amount,
+        )?;
+    } else {
+        reward_info.update_rate_after_funding(current_time as u64, total_amount)?;
+    }
 
     // Transfer without ineligible reward because it's already in the vault
     transfer_from_user(
@@ -114,6 +211,7 @@ pub fn handle_fund_reward(
         &ctx.accounts.reward_vault,
         &ctx.accounts.token_program,
         amount,
+        transfer_hook_accounts,
     )?;
 
     emit_cpi!(EvtFundReward {
@@ -125,7 +223,8 @@ pub fn handle_fund_reward(
         transfer_fee_excluded_amount_in,
         pre_reward_rate,
         post_reward_rate: reward_info.reward_rate,
-        reward_duration_end: reward_info.reward_duration_end
+        reward_duration_end: reward_info.reward_duration_end,
+        reward_duration_start: reward_info.reward_duration_start
     });
 
     Ok(())
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    constants::seeds::{BRIBE_CAMPAIGN_PREFIX, BRIBE_VAULT_PREFIX},
+    error::PoolError,
+    event::EvtInitializeBribeCampaign,
+    safe_math::SafeMath,
+    state::{get_bribe_epoch, BribeCampaign, BribeLedger, Pool},
+    token::{
+        calculate_transfer_fee_excluded_amount, is_supported_mint, load_transfer_hook_accounts,
+        transfer_from_user, validate_token_badge,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct InitializeBribeCampaignCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    #[account(
+        init,
+        seeds = [
+            BRIBE_CAMPAIGN_PREFIX,
+            pool.key().as_ref(),
+            funder.key().as_ref(),
+            mint.key().as_ref(),
+            epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = funder,
+        space = 8 + BribeCampaign::INIT_SPACE
+    )]
+    pub bribe_campaign: AccountLoader<'info, BribeCampaign>,
+
+    #[account(
+        init,
+        seeds = [BRIBE_VAULT_PREFIX, bribe_campaign.key().as_ref()],
+        bump,
+        payer = funder,
+        token::mint = mint,
+        token::authority = pool_authority,
+        token::token_program = token_program
+    )]
+    pub bribe_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_bribe_campaign<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeBribeCampaignCtx<'info>>,
+    epoch: u64,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, PoolError::AmountIsZero);
+
+    // token badge is followed by transfer hook accounts of bribe mint
+    let mut remaining_accounts = ctx.remaining_accounts;
+    if !is_supported_mint(&ctx.accounts.mint)? {
+        let (token_badge, rest) = remaining_accounts
+            .split_first()
+            .ok_or(PoolError::InvalidTokenBadge)?;
+        validate_token_badge(&ctx.accounts.mint, token_badge)?;
+        remaining_accounts = rest;
+    }
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.mint, &mut remaining_accounts)?;
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    {
+        let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+        bribe_ledger.update(current_time)?;
+        bribe_ledger.open_campaign_count = bribe_ledger.open_campaign_count.safe_add(1)?;
+
+        // bribe ledger must cover the whole epoch, so empty seconds of the epoch are fully tracked
+        require!(
+            epoch >= get_bribe_epoch(current_time) && epoch > bribe_ledger.start_epoch,
+            PoolError::InvalidBribeEpoch
+        );
+    }
+
+    let transfer_fee_excluded_amount_in =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.mint, amount)?.amount;
+    require!(transfer_fee_excluded_amount_in > 0, PoolError::AmountIsZero);
+
+    let mut bribe_campaign = ctx.accounts.bribe_campaign.load_init()?;
+    bribe_campaign.initialize(
+        ctx.accounts.pool.key(),
+        ctx.accounts.funder.key(),
+        ctx.accounts.mint.key(),
+        ctx.accounts.bribe_vault.key(),
+        epoch,
+        transfer_fee_excluded_amount_in,
+    );
+
+    transfer_from_user(
+        &ctx.accounts.funder,
+        &ctx.accounts.mint,
+        &ctx.accounts.funder_token_account,
+        &ctx.accounts.bribe_vault,
+        &ctx.accounts.token_program,
+        amount,
+        transfer_hook_accounts,
+    )?;
+
+    emit_cpi!(EvtInitializeBribeCampaign {
+        pool: ctx.accounts.pool.key(),
+        bribe_campaign: ctx.accounts.bribe_campaign.key(),
+        funder: ctx.accounts.funder.key(),
+        mint: ctx.accounts.mint.key(),
+        epoch,
+        amount,
+        transfer_fee_excluded_amount_in,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::BRIBE_LEDGER_PREFIX,
+    state::{BribeLedger, Pool},
+    EvtInitializeBribeLedger,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeBribeLedgerCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            BRIBE_LEDGER_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + BribeLedger::INIT_SPACE
+    )]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    /// Address paying to create the bribe ledger. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_bribe_ledger(ctx: Context<InitializeBribeLedgerCtx>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let mut bribe_ledger = ctx.accounts.bribe_ledger.load_init()?;
+    bribe_ledger.initialize(ctx.accounts.pool.key(), current_time);
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.bribe_ledger_flag = 1;
+
+    emit_cpi!(EvtInitializeBribeLedger {
+        pool: ctx.accounts.pool.key(),
+        bribe_ledger: ctx.accounts.bribe_ledger.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{seeds::LOCKED_FEE_EXTENSION_PREFIX, BASIS_POINT_MAX},
+    state::{LockedFeeExtension, Pool},
+    EvtInitializeLockedFeeExtension, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeLockedFeeExtensionCtx<'info> {
+    #[account(mut, has_one = creator)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            LOCKED_FEE_EXTENSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + LockedFeeExtension::INIT_SPACE
+    )]
+    pub locked_fee_extension: AccountLoader<'info, LockedFeeExtension>,
+
+    /// pool creator
+    pub creator: Signer<'info>,
+
+    /// Address paying to create the locked fee extension. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Route a share of LP fee to permanent locked and vesting locked liquidity of the pool, only for
+/// fee collected afterwards. Vesting locked liquidity is only included if the pool tracks it
+pub fn handle_initialize_locked_fee_extension(
+    ctx: Context<InitializeLockedFeeExtensionCtx>,
+    locked_fee_share_bps: u16,
+) -> Result<()> {
+    require!(
+        locked_fee_share_bps > 0 && u64::from(locked_fee_share_bps) <= BASIS_POINT_MAX,
+        PoolError::InvalidLockedFeeShare
+    );
+
+    let mut locked_fee_extension = ctx.accounts.locked_fee_extension.load_init()?;
+    locked_fee_extension.initialize(ctx.accounts.pool.key(), locked_fee_share_bps);
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.locked_fee_extension_flag = 1;
+
+    emit_cpi!(EvtInitializeLockedFeeExtension {
+        pool: ctx.accounts.pool.key(),
+        locked_fee_extension: ctx.accounts.locked_fee_extension.key(),
+        locked_fee_share_bps,
+    });
+
+    Ok(())
+}
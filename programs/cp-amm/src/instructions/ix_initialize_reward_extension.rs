@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::REWARD_EXTENSION_PREFIX,
+    state::{Pool, RewardExtension},
+    EvtInitializeRewardExtension,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeRewardExtensionCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            REWARD_EXTENSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RewardExtension::INIT_SPACE
+    )]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+
+    /// Address paying to create the reward extension. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_reward_extension(
+    ctx: Context<InitializeRewardExtensionCtx>,
+) -> Result<()> {
+    let mut reward_extension = ctx.accounts.reward_extension.load_init()?;
+    reward_extension.initialize(ctx.accounts.pool.key());
+
+    emit_cpi!(EvtInitializeRewardExtension {
+        pool: ctx.accounts.pool.key(),
+        reward_extension: ctx.accounts.reward_extension.key(),
+    });
+
+    Ok(())
+}
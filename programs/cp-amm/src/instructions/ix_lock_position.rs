@@ -4,8 +4,10 @@ use anchor_spl::token_interface::TokenAccount;
 use crate::{
     activation_handler::ActivationHandler,
     error::PoolError,
+    locked_fee_extension::update_locked_fee,
     safe_math::SafeMath,
     state::{Pool, Position, Vesting},
+    writable_pool::load_writable_pool_if_vesting_liquidity_tracked,
     {get_pool_access_validator, EvtLockPosition},
 };
 
@@ -101,19 +103,31 @@ pub struct LockPositionCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_lock_position(
-    ctx: Context<LockPositionCtx>,
+pub fn handle_lock_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, LockPositionCtx<'info>>,
     params: VestingParameters,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.load()?;
-    let access_validator = get_pool_access_validator(&pool)?;
-    require!(
-        access_validator.can_lock_position(),
-        PoolError::PoolDisabled
-    );
+    // writable pool is the first in remaining accounts if the pool tracks vesting locked liquidity
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let (writable_pool_loader, current_point, max_vesting_duration) = {
+        let pool = ctx.accounts.pool.load()?;
+        let access_validator = get_pool_access_validator(&pool)?;
+        require!(
+            access_validator.can_lock_position(),
+            PoolError::PoolDisabled
+        );
 
-    let (current_point, max_vesting_duration) =
-        ActivationHandler::get_current_point_and_max_vesting_duration(pool.activation_type)?;
+        let writable_pool_loader = load_writable_pool_if_vesting_liquidity_tracked(
+            ctx.accounts.pool.key(),
+            &pool,
+            &mut remaining_accounts,
+        )?;
+
+        let (current_point, max_vesting_duration) =
+            ActivationHandler::get_current_point_and_max_vesting_duration(pool.activation_type)?;
+
+        (writable_pool_loader, current_point, max_vesting_duration)
+    };
 
     params.validate(current_point, max_vesting_duration)?;
 
@@ -139,7 +153,32 @@ pub fn handle_lock_position(
     );
 
     let mut position = ctx.accounts.position.load_mut()?;
-    position.lock(total_lock_liquidity)?;
+
+    // locked fee extension is required in remaining accounts if the pool has one, fee weight depends on lock status
+    {
+        let pool = ctx.accounts.pool.load()?;
+        update_locked_fee(
+            ctx.accounts.pool.key(),
+            &pool,
+            &mut [&mut position],
+            &mut remaining_accounts,
+        )?;
+    }
+
+    match writable_pool_loader {
+        Some(pool_loader) => {
+            let mut pool = pool_loader.load_mut()?;
+
+            // update current pool reward & postion reward before locking, reward weight depends on lock status
+            let current_time = Clock::get()?.unix_timestamp as u64;
+            position.update_rewards(&mut pool, current_time)?;
+
+            position.lock(total_lock_liquidity)?;
+            pool.accumulate_vesting_locked_liquidity(total_lock_liquidity)?;
+        }
+        // vesting locked liquidity has no reward boost if it isn't tracked, so reward weight doesn't change
+        None => position.lock(total_lock_liquidity)?,
+    }
 
     emit_cpi!(EvtLockPosition {
         position: ctx.accounts.position.key(),
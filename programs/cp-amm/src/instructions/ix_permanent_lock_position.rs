@@ -3,6 +3,7 @@ use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
     get_pool_access_validator,
+    locked_fee_extension::update_locked_fee,
     state::{Pool, Position},
     EvtPermanentLockPosition, PoolError,
 };
@@ -28,8 +29,8 @@ pub struct PermanentLockPositionCtx<'info> {
     pub owner: Signer<'info>,
 }
 
-pub fn handle_permanent_lock_position(
-    ctx: Context<PermanentLockPositionCtx>,
+pub fn handle_permanent_lock_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PermanentLockPositionCtx<'info>>,
     permanent_lock_liquidity: u128,
 ) -> Result<()> {
     {
@@ -44,6 +45,18 @@ pub fn handle_permanent_lock_position(
     let mut pool = ctx.accounts.pool.load_mut()?;
     let mut position = ctx.accounts.position.load_mut()?;
 
+    // update current pool reward & postion reward before locking, reward weight depends on lock status
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    position.update_rewards(&mut pool, current_time)?;
+
+    // locked fee extension is required in remaining accounts if the pool has one, fee weight depends on lock status
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut &ctx.remaining_accounts[..],
+    )?;
+
     position.permanent_lock_liquidity(permanent_lock_liquidity)?;
     pool.accumulate_permanent_locked_liquidity(permanent_lock_liquidity)?;
 
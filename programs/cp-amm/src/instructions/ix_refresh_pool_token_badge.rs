@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Pool, TokenBadge},
+    EvtRefreshPoolTokenBadge, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefreshPoolTokenBadgeCtx<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    pub token_badge: AccountLoader<'info, TokenBadge>,
+}
+
+/// Sync expiry of an updated token badge to the pool, permissionless
+pub fn handle_refresh_pool_token_badge(ctx: Context<RefreshPoolTokenBadgeCtx>) -> Result<()> {
+    let token_badge = ctx.accounts.token_badge.load()?;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+
+    let expiry_timestamp = token_badge.expiry_timestamp;
+    if token_badge.token_mint == pool.token_a_mint {
+        pool.token_a_badge_expiry_timestamp = expiry_timestamp;
+    } else if token_badge.token_mint == pool.token_b_mint {
+        pool.token_b_badge_expiry_timestamp = expiry_timestamp;
+    } else {
+        return Err(PoolError::InvalidTokenBadge.into());
+    }
+
+    emit_cpi!(EvtRefreshPoolTokenBadge {
+        pool: ctx.accounts.pool.key(),
+        token_mint: token_badge.token_mint,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use spl_token_metadata_interface::state::Field;
+use std::collections::BTreeSet;
+
+use crate::{
+    const_pda,
+    position_nft_metadata::{
+        get_pool_metadata_fields, get_position_metadata_fields, get_position_nft_uri,
+        update_position_nft_metadata,
+    },
+    safe_math::SafeMath,
+    state::{Pool, Position},
+    token::update_account_lamports_to_minimum_balance,
+    EvtRefreshPositionMetadata, PoolError, VestingRemainingAccount, VestingRemainingAccountBumps,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefreshPositionMetadataCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// CHECK: position nft mint, holding the token metadata
+    #[account(mut, address = position.load()?.nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Address paying rent of the larger metadata. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Program to update token metadata of NFT
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sync liquidity and lock status of the position to the token metadata of its NFT, permissionless.
+/// All vestings of the position must be passed in remaining accounts
+pub fn handle_refresh_position_metadata<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RefreshPositionMetadataCtx<'info>>,
+) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+    let position = ctx.accounts.position.load()?;
+
+    // vesting liquidity of the position must be fully covered, so the vesting end can't be faked
+    let mut vesting_keys = BTreeSet::new();
+    let mut vesting_locked_liquidity: u128 = 0;
+    let mut vesting_end_point: u64 = 0;
+    let mut remaining_accounts = ctx.remaining_accounts;
+    while !remaining_accounts.is_empty() {
+        let vesting_account = VestingRemainingAccount::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut VestingRemainingAccountBumps {},
+            &mut BTreeSet::new(),
+        )?;
+        require!(
+            vesting_keys.insert(vesting_account.vesting.key()),
+            PoolError::InvalidVestingAccount
+        );
+
+        let vesting = vesting_account.load_and_validate(ctx.accounts.position.key())?;
+        let locked_liquidity = vesting.get_locked_liquidity()?;
+        if locked_liquidity > 0 {
+            vesting_locked_liquidity = vesting_locked_liquidity.safe_add(locked_liquidity)?;
+            vesting_end_point = vesting_end_point.max(vesting.get_end_point()?);
+        }
+    }
+    require!(
+        vesting_locked_liquidity == position.vested_liquidity,
+        PoolError::InvalidVestingAccount
+    );
+
+    // pool fields are refreshed as well, for positions created before metadata fields were added
+    let mut fields = vec![(
+        Field::Uri,
+        get_position_nft_uri(ctx.accounts.position_nft_mint.key()),
+    )];
+    fields.extend(get_pool_metadata_fields(ctx.accounts.pool.key(), &pool)?);
+    fields.extend(get_position_metadata_fields(&position, vesting_end_point)?);
+
+    update_position_nft_metadata(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.position_nft_mint.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        fields,
+    )?;
+
+    update_account_lamports_to_minimum_balance(
+        ctx.accounts.position_nft_mint.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit_cpi!(EvtRefreshPositionMetadata {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity: position.get_total_liquidity()?,
+        vesting_end_point,
+    });
+
+    Ok(())
+}
@@ -5,7 +5,9 @@ use std::collections::BTreeSet;
 
 use crate::{
     activation_handler::ActivationHandler,
+    locked_fee_extension::update_locked_fee,
     state::{Pool, Position, Vesting},
+    writable_pool::load_writable_pool_if_vesting_liquidity_tracked,
     PoolError,
 };
 
@@ -37,7 +39,7 @@ pub struct VestingRemainingAccount<'info> {
     pub vesting: AccountLoader<'info, Vesting>,
 }
 
-impl<'info> VestingRemainingAccount<'info> {
+impl VestingRemainingAccount<'_> {
     pub fn load_and_validate(&self, position: Pubkey) -> Result<RefMut<'_, Vesting>> {
         let vesting = self.vesting.load_mut()?;
         require!(
@@ -51,13 +53,43 @@ impl<'info> VestingRemainingAccount<'info> {
 pub fn handle_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, RefreshVesting<'info>>,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.load()?;
+    let mut position: RefMut<'_, Position> = ctx.accounts.position.load_mut()?;
+    let mut remaining_accounts = ctx.remaining_accounts;
 
-    let (current_point, _) =
-        ActivationHandler::get_current_point_and_buffer_duration(pool.activation_type)?;
+    // writable pool is the first in remaining accounts if the pool tracks vesting locked liquidity,
+    // followed by locked fee extension if the pool has one, and vestings
+    let (writable_pool_loader, current_point) = {
+        let pool = ctx.accounts.pool.load()?;
 
-    let mut position: RefMut<'_, Position> = ctx.accounts.position.load_mut()?;
-    let mut remaining_accounts = &ctx.remaining_accounts[..];
+        let writable_pool_loader = load_writable_pool_if_vesting_liquidity_tracked(
+            ctx.accounts.pool.key(),
+            &pool,
+            &mut remaining_accounts,
+        )?;
+
+        update_locked_fee(
+            ctx.accounts.pool.key(),
+            &pool,
+            &mut [&mut position],
+            &mut remaining_accounts,
+        )?;
+
+        let (current_point, _) =
+            ActivationHandler::get_current_point_and_buffer_duration(pool.activation_type)?;
+
+        (writable_pool_loader, current_point)
+    };
+
+    // vesting locked liquidity has no reward boost if it isn't tracked, so reward weight doesn't change
+    let mut writable_pool = writable_pool_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+    if let Some(pool) = writable_pool.as_mut() {
+        // update current pool reward & postion reward before releasing, reward weight depends on lock status
+        let current_time = Clock::get()?.unix_timestamp as u64;
+        position.update_rewards(pool, current_time)?;
+    }
 
     loop {
         if remaining_accounts.is_empty() {
@@ -73,7 +105,12 @@ pub fn handle_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
         )?;
 
         let mut vesting = vesting_account.load_and_validate(ctx.accounts.position.key())?;
-        release_vesting_liquidity_to_position(&mut vesting, &mut position, current_point)?;
+        release_vesting_liquidity_to_position(
+            &mut vesting,
+            &mut position,
+            writable_pool.as_deref_mut(),
+            current_point,
+        )?;
 
         if vesting.done()? {
             drop(vesting);
@@ -89,11 +126,15 @@ pub fn handle_refresh_vesting<'a, 'b, 'c: 'info, 'info>(
 fn release_vesting_liquidity_to_position(
     vesting: &mut RefMut<'_, Vesting>,
     position: &mut RefMut<'_, Position>,
+    pool: Option<&mut Pool>,
     current_point: u64,
 ) -> Result<()> {
     let released_liquidity = vesting.get_new_release_liquidity(current_point)?;
     if released_liquidity > 0 {
         position.release_vested_liquidity(released_liquidity)?;
+        if let Some(pool) = pool {
+            pool.release_vesting_locked_liquidity(released_liquidity)?;
+        }
         vesting.accumulate_released_liquidity(released_liquidity)?;
     }
 
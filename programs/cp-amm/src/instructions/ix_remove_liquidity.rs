@@ -4,9 +4,14 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
+    bribe::update_bribe_ledgers,
     const_pda, get_pool_access_validator,
+    reward_extension::update_reward_extensions,
     state::{ModifyLiquidityResult, Pool, Position},
-    token::{calculate_transfer_fee_excluded_amount, transfer_from_pool},
+    token::{
+        calculate_transfer_fee_excluded_amount, is_transfer_frozen, load_transfer_hook_accounts,
+        transfer_from_pool, validate_transfer_fee_bps,
+    },
     u128x128_math::Rounding,
     EvtLiquidityChange, EvtRemoveLiquidity, PoolError,
 };
@@ -21,6 +26,18 @@ pub struct RemoveLiquidityParameters {
     pub token_b_amount_threshold: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct RemoveLiquidityParameters2 {
+    /// delta liquidity
+    pub liquidity_delta: u128,
+    /// minimum token a amount
+    pub token_a_amount_threshold: u64,
+    /// minimum token b amount
+    pub token_b_amount_threshold: u64,
+    /// maximum transfer fee bps of token a and token b
+    pub max_transfer_fee_bps: u16,
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveLiquidityCtx<'info> {
@@ -77,12 +94,16 @@ pub struct RemoveLiquidityCtx<'info> {
     pub token_b_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handle_remove_liquidity(
-    ctx: Context<RemoveLiquidityCtx>,
+pub fn handle_remove_liquidity<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
     liquidity_delta: Option<u128>,
     token_a_amount_threshold: u64,
     token_b_amount_threshold: u64,
+    max_transfer_fee_bps: Option<u16>,
 ) -> Result<()> {
+    validate_transfer_fee_bps(&ctx.accounts.token_a_mint, max_transfer_fee_bps)?;
+    validate_transfer_fee_bps(&ctx.accounts.token_b_mint, max_transfer_fee_bps)?;
+
     {
         let pool = ctx.accounts.pool.load()?;
         let access_validator = get_pool_access_validator(&pool)?;
@@ -105,6 +126,24 @@ pub fn handle_remove_liquidity(
     let current_time = Clock::get()?.unix_timestamp as u64;
     position.update_rewards(&mut pool, current_time)?;
 
+    // update pool & position extension rewards before any liquidity change
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // update pool & position bribe ledgers before any liquidity change
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[(ctx.accounts.position.key(), &position)],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
     let ModifyLiquidityResult {
         token_a_amount,
         token_b_amount,
@@ -131,24 +170,54 @@ pub fn handle_remove_liquidity(
 
     pool.apply_remove_liquidity(&mut position, liquidity_delta)?;
 
-    // send to user
-    transfer_from_pool(
-        ctx.accounts.pool_authority.to_account_info(),
-        &ctx.accounts.token_a_mint,
-        &ctx.accounts.token_a_vault,
-        &ctx.accounts.token_a_account,
-        &ctx.accounts.token_a_program,
-        token_a_amount,
-    )?;
-    transfer_from_pool(
-        ctx.accounts.pool_authority.to_account_info(),
-        &ctx.accounts.token_b_mint,
-        &ctx.accounts.token_b_vault,
-        &ctx.accounts.token_b_account,
-        &ctx.accounts.token_b_program,
-        token_b_amount,
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[(ctx.accounts.position.key(), &position)])?;
+    }
+
+    // transfer hook accounts of token a followed by token b are the last in remaining accounts
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
+    // frozen token is kept as pending withdrawal of the position, and can be claimed once it's unfrozen
+    let token_a_frozen =
+        is_transfer_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_a_account);
+    let token_b_frozen =
+        is_transfer_frozen(&ctx.accounts.token_b_vault, &ctx.accounts.token_b_account);
+    position.add_pending_withdrawal(
+        if token_a_frozen { token_a_amount } else { 0 },
+        if token_b_frozen { token_b_amount } else { 0 },
     )?;
 
+    // send to user
+    if !token_a_frozen {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            token_a_amount,
+            transfer_hook_accounts_a,
+        )?;
+    }
+    if !token_b_frozen {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            token_b_amount,
+            transfer_hook_accounts_b,
+        )?;
+    }
+
     emit_cpi!(EvtRemoveLiquidity {
         pool: ctx.accounts.pool.key(),
         owner: ctx.accounts.owner.key(),
@@ -156,7 +225,7 @@ pub fn handle_remove_liquidity(
         params: RemoveLiquidityParameters {
             liquidity_delta,
             token_a_amount_threshold,
-            token_b_amount_threshold
+            token_b_amount_threshold,
         },
         token_a_amount,
         token_b_amount,
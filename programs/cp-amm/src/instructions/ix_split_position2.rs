@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    bribe::update_bribe_ledgers,
     constants::{REWARD_INDEX_0, REWARD_INDEX_1, SPLIT_POSITION_DENOMINATOR},
     get_pool_access_validator,
+    locked_fee_extension::update_locked_fee,
+    reward_extension::update_reward_extensions,
     state::{SplitAmountInfo, SplitPositionInfo},
     EvtSplitPosition2, PoolError, SplitPositionCtx,
 };
@@ -58,8 +61,8 @@ impl SplitPositionParameters2 {
     }
 }
 
-pub fn handle_split_position2(
-    ctx: Context<SplitPositionCtx>,
+pub fn handle_split_position2<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SplitPositionCtx<'info>>,
     params: SplitPositionParameters2,
 ) -> Result<()> {
     {
@@ -102,6 +105,38 @@ pub fn handle_split_position2(
     first_position.update_position_reward(&pool)?;
     second_position.update_position_reward(&pool)?;
 
+    // update pool & positions extension rewards before any liquidity change
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_accounts = update_reward_extensions(
+        ctx.accounts.pool.key(),
+        &pool,
+        &[
+            (ctx.accounts.first_position.key(), &first_position),
+            (ctx.accounts.second_position.key(), &second_position),
+        ],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // update pool & position bribe ledgers before any liquidity change
+    let bribe_ledger_accounts = update_bribe_ledgers(
+        ctx.accounts.pool.key(),
+        &[
+            (ctx.accounts.first_position.key(), &first_position),
+            (ctx.accounts.second_position.key(), &second_position),
+        ],
+        &mut remaining_accounts,
+        current_time,
+    )?;
+
+    // locked fee extension follows bribe ledgers if the pool has one
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut first_position, &mut second_position],
+        &mut remaining_accounts,
+    )?;
+
     let split_amount_info: SplitAmountInfo = pool.apply_split_position(
         &mut first_position,
         &mut second_position,
@@ -113,6 +148,20 @@ pub fn handle_split_position2(
         reward_1_numerator,
     )?;
 
+    if let Some(reward_extension_accounts) = reward_extension_accounts {
+        reward_extension_accounts.sync_liquidity(&[
+            (ctx.accounts.first_position.key(), &first_position),
+            (ctx.accounts.second_position.key(), &second_position),
+        ])?;
+    }
+
+    if let Some(bribe_ledger_accounts) = bribe_ledger_accounts {
+        bribe_ledger_accounts.sync_liquidity(&[
+            (ctx.accounts.first_position.key(), &first_position),
+            (ctx.accounts.second_position.key(), &second_position),
+        ])?;
+    }
+
     emit_cpi!(EvtSplitPosition2 {
         pool: ctx.accounts.pool.key(),
         first_owner: ctx.accounts.first_owner.key(),
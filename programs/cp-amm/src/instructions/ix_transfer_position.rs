@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use std::collections::BTreeSet;
+
+use crate::{
+    const_pda,
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    error::PoolError,
+    event::EvtTransferPosition,
+    get_pool_access_validator,
+    locked_fee_extension::update_locked_fee,
+    reward_extension::{load_position_reward_extension, load_reward_extension},
+    state::{get_reward_info, Pool, Position},
+    token::{is_transfer_frozen, load_transfer_hook_accounts, transfer_from_pool},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferPositionCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_a_mint,
+        has_one = token_b_mint,
+        has_one = token_a_vault,
+        has_one = token_b_vault,
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// position_nft_mint
+    #[account(address = position.load()?.nft_mint)]
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account for nft of the current owner
+    #[account(
+        mut,
+        constraint = position_nft_account.mint == position.load()?.nft_mint,
+        constraint = position_nft_account.amount == 1,
+        token::authority = owner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account for nft of the new owner
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = new_owner,
+        token::token_program = token_program,
+    )]
+    pub new_position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: new owner of position
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// The user token a account of the current owner
+    #[account(mut)]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token b account of the current owner
+    #[account(mut)]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for input token
+    #[account(mut, token::token_program = token_a_program, token::mint = token_a_mint)]
+    pub token_a_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut, token::token_program = token_b_program, token::mint = token_b_mint)]
+    pub token_b_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token a
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token b
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// current owner of position
+    pub owner: Signer<'info>,
+
+    /// Token a program
+    pub token_a_program: Interface<'info, TokenInterface>,
+
+    /// Token b program
+    pub token_b_program: Interface<'info, TokenInterface>,
+
+    /// Program to transfer the position nft
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Accounts to pay out a pending reward, one group for every initialized reward
+#[derive(Accounts)]
+pub struct RewardTransferRemainingAccount<'info> {
+    /// The vault token account for reward token
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The reward token account of the current owner
+    #[account(mut)]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Settle pending fees and rewards to the current owner, then move the position nft to the new owner.
+/// Fees and rewards of frozen tokens, and rewards of pool with reward claiming disabled, are kept
+/// pending and move with the position
+pub fn handle_transfer_position<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, TransferPositionCtx<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+
+    // withdrawal pending on frozen token must be claimed by the current owner first
+    require!(
+        position.pending_withdrawal_a == 0 && position.pending_withdrawal_b == 0,
+        PoolError::PendingWithdrawalNotClaimed
+    );
+
+    // remaining accounts layout:
+    // 1. locked fee extension, if pool has one
+    // 2. pool reward extension and position reward extension, if position has reward extension
+    // 3. transfer hook accounts of token a followed by token b
+    // 4. for every initialized reward: reward vault, reward mint, user token account,
+    //    token program, followed by transfer hook accounts of reward mint
+    let mut remaining_accounts = ctx.remaining_accounts;
+    update_locked_fee(
+        ctx.accounts.pool.key(),
+        &pool,
+        &mut [&mut position],
+        &mut remaining_accounts,
+    )?;
+
+    let reward_extension_loaders = if position.has_reward_extension() {
+        Some((
+            load_reward_extension(ctx.accounts.pool.key(), &mut remaining_accounts)?,
+            load_position_reward_extension(ctx.accounts.position.key(), &mut remaining_accounts)?,
+        ))
+    } else {
+        None
+    };
+    let mut reward_extension = reward_extension_loaders
+        .as_ref()
+        .map(|(loader, _)| loader.load_mut())
+        .transpose()?;
+    let mut position_reward_extension = reward_extension_loaders
+        .as_ref()
+        .map(|(_, loader)| loader.load_mut())
+        .transpose()?;
+
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
+    // fee
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    let fee_a_claimed =
+        if is_transfer_frozen(&ctx.accounts.token_a_vault, &ctx.accounts.token_a_account) {
+            0
+        } else {
+            position.fee_a_pending
+        };
+    let fee_b_claimed =
+        if is_transfer_frozen(&ctx.accounts.token_b_vault, &ctx.accounts.token_b_account) {
+            0
+        } else {
+            position.fee_b_pending
+        };
+    position.claim_fee(fee_a_claimed, fee_b_claimed)?;
+
+    if fee_a_claimed > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_a_mint,
+            &ctx.accounts.token_a_vault,
+            &ctx.accounts.token_a_account,
+            &ctx.accounts.token_a_program,
+            fee_a_claimed,
+            transfer_hook_accounts_a,
+        )?;
+    }
+
+    if fee_b_claimed > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.token_b_mint,
+            &ctx.accounts.token_b_vault,
+            &ctx.accounts.token_b_account,
+            &ctx.accounts.token_b_program,
+            fee_b_claimed,
+            transfer_hook_accounts_b,
+        )?;
+    }
+
+    // rewards
+    position.update_rewards(&mut pool, current_time)?;
+    if let (Some(reward_extension), Some(position_reward_extension)) = (
+        reward_extension.as_mut(),
+        position_reward_extension.as_mut(),
+    ) {
+        reward_extension.update_rewards(current_time)?;
+        position_reward_extension.update_position_reward(reward_extension)?;
+    }
+
+    let reward_count = if reward_extension.is_some() {
+        NUM_REWARDS + NUM_EXTENSION_REWARDS
+    } else {
+        NUM_REWARDS
+    };
+    let can_claim_reward = get_pool_access_validator(&pool)?.can_claim_reward();
+    let mut rewards_claimed = [0u64; NUM_REWARDS + NUM_EXTENSION_REWARDS];
+    for (reward_index, reward_claimed) in rewards_claimed.iter_mut().enumerate().take(reward_count)
+    {
+        let reward_info = get_reward_info(&pool, reward_extension.as_deref(), reward_index)?;
+        if !reward_info.initialized() {
+            continue;
+        }
+        let reward_vault_key = reward_info.vault;
+
+        let reward_accounts = RewardTransferRemainingAccount::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &[],
+            &mut RewardTransferRemainingAccountBumps {},
+            &mut BTreeSet::new(),
+        )?;
+        require!(
+            reward_accounts.reward_vault.key() == reward_vault_key,
+            PoolError::InvalidRewardVault
+        );
+        let transfer_hook_accounts =
+            load_transfer_hook_accounts(&reward_accounts.reward_mint, &mut remaining_accounts)?;
+
+        if !can_claim_reward || reward_accounts.reward_vault.is_frozen() {
+            continue;
+        }
+
+        let total_reward = match position_reward_extension.as_mut() {
+            Some(position_reward_extension) if reward_index >= NUM_REWARDS => {
+                position_reward_extension.claim_reward(reward_index)?
+            }
+            _ => position.claim_reward(reward_index)?,
+        };
+
+        if total_reward > 0 {
+            transfer_from_pool(
+                ctx.accounts.pool_authority.to_account_info(),
+                &reward_accounts.reward_mint,
+                &reward_accounts.reward_vault,
+                &reward_accounts.user_token_account,
+                &reward_accounts.token_program,
+                total_reward,
+                transfer_hook_accounts,
+            )?;
+        }
+        *reward_claimed = total_reward;
+    }
+
+    // move position nft to the new owner
+    token_2022::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::TransferChecked {
+                from: ctx.accounts.position_nft_account.to_account_info(),
+                mint: ctx.accounts.position_nft_mint.to_account_info(),
+                to: ctx.accounts.new_position_nft_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+        ctx.accounts.position_nft_mint.decimals,
+    )?;
+
+    emit_cpi!(EvtTransferPosition {
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        previous_owner: ctx.accounts.owner.key(),
+        new_owner: ctx.accounts.new_owner.key(),
+        fee_a_claimed,
+        fee_b_claimed,
+        rewards_claimed,
+    });
+
+    Ok(())
+}
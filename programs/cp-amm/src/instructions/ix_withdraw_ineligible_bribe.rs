@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    error::PoolError,
+    event::EvtWithdrawIneligibleBribe,
+    state::{get_bribe_epoch, BribeCampaign, BribeLedger, Pool},
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawIneligibleBribeCtx<'info> {
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(mut, has_one = pool)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = funder,
+        has_one = mint,
+        constraint = bribe_campaign.load()?.vault == bribe_vault.key() @ PoolError::InvalidBribeVault,
+    )]
+    pub bribe_campaign: AccountLoader<'info, BribeCampaign>,
+
+    /// The vault token account for bribe token
+    #[account(mut)]
+    pub bribe_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bribe mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw bribe distributed to seconds of the epoch that had no enrolled liquidity
+pub fn handle_withdraw_ineligible_bribe<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleBribeCtx<'info>>,
+) -> Result<()> {
+    let mut bribe_campaign = ctx.accounts.bribe_campaign.load_mut()?;
+    let epoch = bribe_campaign.epoch;
+
+    require!(
+        bribe_campaign.ineligible_withdrawn == 0,
+        PoolError::IneligibleBribeWithdrawn
+    );
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        epoch < get_bribe_epoch(current_time),
+        PoolError::BribeEpochNotEnded
+    );
+
+    let empty_seconds = {
+        let mut bribe_ledger = ctx.accounts.bribe_ledger.load_mut()?;
+        bribe_ledger.update(current_time)?;
+        bribe_ledger.get_snapshot(epoch)?.empty_seconds
+    };
+
+    // transfer hook accounts of bribe mint
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.mint, &mut remaining_accounts)?;
+
+    let amount = bribe_campaign.get_ineligible_amount(empty_seconds)?;
+    bribe_campaign.ineligible_amount = amount;
+    bribe_campaign.ineligible_withdrawn = 1;
+
+    if amount > 0 {
+        transfer_from_pool(
+            ctx.accounts.pool_authority.to_account_info(),
+            &ctx.accounts.mint,
+            &ctx.accounts.bribe_vault,
+            &ctx.accounts.funder_token_account,
+            &ctx.accounts.token_program,
+            amount,
+            transfer_hook_accounts,
+        )?;
+    }
+
+    emit_cpi!(EvtWithdrawIneligibleBribe {
+        pool: ctx.accounts.pool.key(),
+        bribe_campaign: ctx.accounts.bribe_campaign.key(),
+        funder: ctx.accounts.funder.key(),
+        epoch,
+        amount,
+    });
+
+    Ok(())
+}
@@ -2,8 +2,16 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    const_pda, constants::NUM_REWARDS, error::PoolError, event::EvtWithdrawIneligibleReward,
-    state::pool::Pool, token::transfer_from_pool,
+    const_pda,
+    constants::{seeds::ADMIN_REGISTRY_PREFIX, NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    error::PoolError,
+    event::EvtWithdrawIneligibleReward,
+    reward_extension::load_reward_extension,
+    state::{
+        get_reward_info, get_reward_info_mut, is_extension_reward_index, pool::Pool,
+        RewardExtension,
+    },
+    token::{load_transfer_hook_accounts, transfer_from_pool},
 };
 
 #[event_cpi]
@@ -26,15 +34,26 @@ pub struct WithdrawIneligibleRewardCtx<'info> {
 
     pub funder: Signer<'info>,
 
+    /// CHECK: admin registry, hard-coded admins hold all roles until it is initialized
+    #[account(seeds = [ADMIN_REGISTRY_PREFIX], bump)]
+    pub admin_registry: UncheckedAccount<'info>,
+
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> WithdrawIneligibleRewardCtx<'info> {
-    fn validate(&self, reward_index: usize) -> Result<()> {
+    fn validate(
+        &self,
+        reward_index: usize,
+        reward_extension: Option<&RewardExtension>,
+    ) -> Result<()> {
         let pool = self.pool.load()?;
-        require!(reward_index < NUM_REWARDS, PoolError::InvalidRewardIndex);
+        require!(
+            reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+            PoolError::InvalidRewardIndex
+        );
 
-        let reward_info = &pool.reward_infos[reward_index];
+        let reward_info = get_reward_info(&pool, reward_extension, reward_index)?;
 
         require!(reward_info.initialized(), PoolError::RewardUninitialized);
 
@@ -44,7 +63,7 @@ impl<'info> WithdrawIneligibleRewardCtx<'info> {
         );
 
         require!(
-            reward_info.is_valid_funder(self.funder.key()),
+            reward_info.is_valid_funder(&self.admin_registry, self.funder.key()),
             PoolError::InvalidFunder
         );
 
@@ -58,14 +77,33 @@ impl<'info> WithdrawIneligibleRewardCtx<'info> {
     }
 }
 
-pub fn handle_withdraw_ineligible_reward(
-    ctx: Context<WithdrawIneligibleRewardCtx>,
+pub fn handle_withdraw_ineligible_reward<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleRewardCtx<'info>>,
     reward_index: u8,
 ) -> Result<()> {
     let index: usize = reward_index
         .try_into()
         .map_err(|_| PoolError::TypeCastFailed)?;
-    ctx.accounts.validate(index)?;
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let reward_extension_loader = if is_extension_reward_index(index) {
+        Some(load_reward_extension(
+            ctx.accounts.pool.key(),
+            &mut remaining_accounts,
+        )?)
+    } else {
+        None
+    };
+    let mut reward_extension = reward_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+
+    ctx.accounts.validate(index, reward_extension.as_deref())?;
+
+    // transfer hook accounts of reward mint are the last in remaining accounts
+    let transfer_hook_accounts =
+        load_transfer_hook_accounts(&ctx.accounts.reward_mint, &mut remaining_accounts)?;
 
     let mut pool = ctx.accounts.pool.load_mut()?;
 
@@ -73,8 +111,12 @@ pub fn handle_withdraw_ineligible_reward(
 
     // update pool reward
     pool.update_rewards(current_time)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_rewards(current_time)?;
+    }
 
-    let ineligible_reward = pool.claim_ineligible_reward(index)?;
+    let ineligible_reward = get_reward_info_mut(&mut pool, reward_extension.as_deref_mut(), index)?
+        .claim_ineligible_reward()?;
 
     // transfer rewards to funder
     if ineligible_reward > 0 {
@@ -85,6 +127,7 @@ pub fn handle_withdraw_ineligible_reward(
             &ctx.accounts.funder_token_account,
             &ctx.accounts.token_program,
             ineligible_reward,
+            transfer_hook_accounts,
         )?;
     }
 
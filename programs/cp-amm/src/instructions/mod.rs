@@ -10,6 +10,10 @@ pub mod ix_remove_liquidity;
 pub use ix_remove_liquidity::*;
 pub mod ix_claim_position_fee;
 pub use ix_claim_position_fee::*;
+pub mod ix_claim_pending_withdrawal;
+pub use ix_claim_pending_withdrawal::*;
+pub mod ix_compound_position_fee;
+pub use ix_compound_position_fee::*;
 pub mod initialize_pool;
 pub use initialize_pool::*;
 pub mod ix_lock_position;
@@ -22,12 +26,48 @@ pub mod ix_claim_reward;
 pub use ix_claim_reward::*;
 pub mod partner;
 pub use partner::*;
+pub mod permissioned_pool;
+pub use permissioned_pool::*;
+pub mod fungible_share;
+pub use fungible_share::*;
 pub mod ix_fund_reward;
 pub use ix_fund_reward::*;
 pub mod ix_withdraw_ineligible_reward;
 pub use ix_withdraw_ineligible_reward::*;
+pub mod ix_initialize_reward_extension;
+pub use ix_initialize_reward_extension::*;
+pub mod ix_create_position_reward_extension;
+pub use ix_create_position_reward_extension::*;
+pub mod ix_close_position_reward_extension;
+pub use ix_close_position_reward_extension::*;
+pub mod ix_initialize_locked_fee_extension;
+pub use ix_initialize_locked_fee_extension::*;
+pub mod ix_initialize_bribe_ledger;
+pub use ix_initialize_bribe_ledger::*;
+pub mod ix_create_position_bribe_ledger;
+pub use ix_create_position_bribe_ledger::*;
+pub mod ix_close_position_bribe_ledger;
+pub use ix_close_position_bribe_ledger::*;
+pub mod ix_initialize_bribe_campaign;
+pub use ix_initialize_bribe_campaign::*;
+pub mod ix_claim_bribe;
+pub use ix_claim_bribe::*;
+pub mod ix_withdraw_ineligible_bribe;
+pub use ix_withdraw_ineligible_bribe::*;
+pub mod ix_close_bribe_campaign;
+pub use ix_close_bribe_campaign::*;
 pub mod ix_close_position;
 pub use ix_close_position::*;
+pub mod ix_close_pool;
+pub use ix_close_pool::*;
+pub mod ix_transfer_position;
+pub use ix_transfer_position::*;
+pub mod ix_refresh_pool_token_badge;
+pub use ix_refresh_pool_token_badge::*;
+pub mod ix_refresh_position_metadata;
+pub use ix_refresh_position_metadata::*;
+pub mod ix_execute_proposal;
+pub use ix_execute_proposal::*;
 pub mod ix_split_position;
 pub use ix_split_position::*;
 
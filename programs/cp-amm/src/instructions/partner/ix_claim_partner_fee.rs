@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{const_pda, state::Pool, token::transfer_from_pool, EvtClaimPartnerFee};
+use crate::{
+    const_pda,
+    state::Pool,
+    token::{load_transfer_hook_accounts, transfer_from_pool},
+    EvtClaimPartnerFee,
+};
 
 /// Accounts for partner to claim fees
 #[event_cpi]
@@ -53,14 +58,21 @@ pub struct ClaimPartnerFeesCtx<'info> {
 }
 
 /// Partner claim fees.
-pub fn handle_claim_partner_fee(
-    ctx: Context<ClaimPartnerFeesCtx>,
+pub fn handle_claim_partner_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimPartnerFeesCtx<'info>>,
     max_amount_a: u64,
     max_amount_b: u64,
 ) -> Result<()> {
     let mut pool = ctx.accounts.pool.load_mut()?;
     let (token_a_amount, token_b_amount) = pool.claim_partner_fee(max_amount_a, max_amount_b)?;
 
+    // transfer hook accounts of token a followed by token b
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
     if max_amount_a > 0 {
         transfer_from_pool(
             ctx.accounts.pool_authority.to_account_info(),
@@ -69,6 +81,7 @@ pub fn handle_claim_partner_fee(
             &ctx.accounts.token_a_account,
             &ctx.accounts.token_a_program,
             token_a_amount,
+            transfer_hook_accounts_a,
         )?;
     }
 
@@ -80,6 +93,7 @@ pub fn handle_claim_partner_fee(
             &ctx.accounts.token_b_account,
             &ctx.accounts.token_b_program,
             token_b_amount,
+            transfer_hook_accounts_b,
         )?;
     }
 
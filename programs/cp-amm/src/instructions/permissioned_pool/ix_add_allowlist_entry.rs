@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::{ALLOWLIST_ENTRY_PREFIX, POOL_PERMISSION_PREFIX},
+    safe_math::SafeMath,
+    state::{AllowlistEntry, Pool, PoolPermission},
+    EvtAddAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddAllowlistEntryCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_PERMISSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        has_one = manager @ PoolError::InvalidPoolManager,
+    )]
+    pub pool_permission: AccountLoader<'info, PoolPermission>,
+
+    #[account(
+        init,
+        seeds = [
+            ALLOWLIST_ENTRY_PREFIX,
+            pool.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + AllowlistEntry::INIT_SPACE
+    )]
+    pub allowlist_entry: AccountLoader<'info, AllowlistEntry>,
+
+    /// CHECK: wallet to be allowlisted
+    pub wallet: UncheckedAccount<'info>,
+
+    pub manager: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_add_allowlist_entry(ctx: Context<AddAllowlistEntryCtx>) -> Result<()> {
+    let mut allowlist_entry = ctx.accounts.allowlist_entry.load_init()?;
+    allowlist_entry.initialize(ctx.accounts.pool.key(), ctx.accounts.wallet.key());
+
+    let mut pool_permission = ctx.accounts.pool_permission.load_mut()?;
+    pool_permission.allowlist_entry_count = pool_permission.allowlist_entry_count.safe_add(1)?;
+
+    emit_cpi!(EvtAddAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        wallet: ctx.accounts.wallet.key(),
+        manager: ctx.accounts.manager.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    constants::seeds::POOL_PERMISSION_PREFIX,
+    state::{Pool, PoolPermission, PoolType},
+    EvtInitializePoolPermission, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePoolPermissionCtx<'info> {
+    #[account(mut, has_one = creator)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        init,
+        seeds = [
+            POOL_PERMISSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + PoolPermission::INIT_SPACE
+    )]
+    pub pool_permission: AccountLoader<'info, PoolPermission>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: manager of the allowlist
+    pub manager: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_pool_permission(ctx: Context<InitializePoolPermissionCtx>) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.pool_type == Into::<u8>::into(PoolType::Customizable),
+        PoolError::InvalidPoolType
+    );
+    // nobody has traded the pool yet, liquidity providers joining before activation can still remove
+    // liquidity
+    let current_point = ActivationHandler::get_current_point(pool.activation_type)?;
+    require!(
+        current_point < pool.activation_point,
+        PoolError::PoolAlreadyActivated
+    );
+    pool.pool_type = PoolType::Permissioned.into();
+
+    let mut pool_permission = ctx.accounts.pool_permission.load_init()?;
+    pool_permission.initialize(ctx.accounts.pool.key(), ctx.accounts.manager.key());
+
+    emit_cpi!(EvtInitializePoolPermission {
+        pool: ctx.accounts.pool.key(),
+        manager: ctx.accounts.manager.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POOL_PERMISSION_PREFIX,
+    safe_math::SafeMath,
+    state::{AllowlistEntry, Pool, PoolPermission},
+    EvtRemoveAllowlistEntry, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveAllowlistEntryCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_PERMISSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        has_one = manager @ PoolError::InvalidPoolManager,
+    )]
+    pub pool_permission: AccountLoader<'info, PoolPermission>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        close = rent_receiver
+    )]
+    pub allowlist_entry: AccountLoader<'info, AllowlistEntry>,
+
+    pub manager: Signer<'info>,
+
+    /// CHECK: Account to receive closed account rental SOL
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_remove_allowlist_entry(ctx: Context<RemoveAllowlistEntryCtx>) -> Result<()> {
+    let allowlist_entry = ctx.accounts.allowlist_entry.load()?;
+
+    let mut pool_permission = ctx.accounts.pool_permission.load_mut()?;
+    pool_permission.allowlist_entry_count = pool_permission.allowlist_entry_count.safe_sub(1)?;
+
+    emit_cpi!(EvtRemoveAllowlistEntry {
+        pool: ctx.accounts.pool.key(),
+        wallet: allowlist_entry.wallet,
+        manager: ctx.accounts.manager.key(),
+    });
+
+    Ok(())
+}
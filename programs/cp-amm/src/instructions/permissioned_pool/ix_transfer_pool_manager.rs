@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::seeds::POOL_PERMISSION_PREFIX,
+    state::{Pool, PoolPermission},
+    EvtTransferPoolManager, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferPoolManagerCtx<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_PERMISSION_PREFIX,
+            pool.key().as_ref()
+        ],
+        bump,
+        has_one = manager @ PoolError::InvalidPoolManager,
+    )]
+    pub pool_permission: AccountLoader<'info, PoolPermission>,
+
+    pub manager: Signer<'info>,
+
+    /// CHECK: new manager of the allowlist
+    pub new_manager: UncheckedAccount<'info>,
+}
+
+pub fn handle_transfer_pool_manager(ctx: Context<TransferPoolManagerCtx>) -> Result<()> {
+    let mut pool_permission = ctx.accounts.pool_permission.load_mut()?;
+    pool_permission.manager = ctx.accounts.new_manager.key();
+
+    emit_cpi!(EvtTransferPoolManager {
+        pool: ctx.accounts.pool.key(),
+        manager: ctx.accounts.manager.key(),
+        new_manager: ctx.accounts.new_manager.key(),
+    });
+
+    Ok(())
+}
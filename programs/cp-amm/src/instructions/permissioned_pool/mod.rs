@@ -0,0 +1,8 @@
+pub mod ix_initialize_pool_permission;
+pub use ix_initialize_pool_permission::*;
+pub mod ix_add_allowlist_entry;
+pub use ix_add_allowlist_entry::*;
+pub mod ix_remove_allowlist_entry;
+pub use ix_remove_allowlist_entry::*;
+pub mod ix_transfer_pool_manager;
+pub use ix_transfer_pool_manager::*;
@@ -1,14 +1,21 @@
 use crate::{
     activation_handler::ActivationHandler,
-    const_pda, get_pool_access_validator,
+    allowlist::load_allowlisted_wallet,
+    const_pda, get_pool_access_validator_with_allowlist,
+    instruction::CompoundPositionFee as CompoundPositionFeeInstruction,
     instruction::Swap as SwapInstruction,
     instruction::Swap2 as Swap2Instruction,
+    instruction::Swap3 as Swap3Instruction,
+    locked_fee_extension::load_locked_fee_extension_if_initialized,
     params::swap::TradeDirection,
     process_swap_exact_in, process_swap_exact_out, process_swap_partial_fill,
     safe_math::SafeMath,
     state::{fee::FeeMode, Pool, SwapResult2},
     swap::{ProcessSwapParams, ProcessSwapResult},
-    token::{transfer_from_pool, transfer_from_user},
+    token::{
+        load_transfer_hook_accounts, transfer_from_pool, transfer_from_user,
+        validate_transfer_fee_bps,
+    },
     EvtSwap, EvtSwap2, PoolError,
 };
 use anchor_lang::solana_program::sysvar;
@@ -48,6 +55,28 @@ pub struct SwapParameters2 {
     pub swap_mode: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SwapParameters3 {
+    /// When it's exact in, partial fill, this will be amount_in. When it's exact out, this will be amount_out
+    pub amount_0: u64,
+    /// When it's exact in, partial fill, this will be minimum_amount_out. When it's exact out, this will be maximum_amount_in
+    pub amount_1: u64,
+    /// Swap mode, refer [SwapMode]
+    pub swap_mode: u8,
+    /// Maximum transfer fee bps of input and output token
+    pub max_transfer_fee_bps: u16,
+}
+
+impl SwapParameters3 {
+    pub fn to_swap_parameters2(&self) -> SwapParameters2 {
+        SwapParameters2 {
+            amount_0: self.amount_0,
+            amount_1: self.amount_1,
+            swap_mode: self.swap_mode,
+        }
+    }
+}
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct SwapCtx<'info> {
@@ -107,26 +136,55 @@ impl<'info> SwapCtx<'info> {
     }
 }
 
-pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> Result<()> {
+pub fn handle_swap_wrapper<'c: 'info, 'info>(
+    ctx: &Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+    params: SwapParameters2,
+    max_transfer_fee_bps: Option<u16>,
+) -> Result<()> {
     let SwapParameters2 {
         amount_0,
         amount_1,
         swap_mode,
-        ..
     } = params;
 
+    // allowlist entry of permissioned pool and locked fee extension, followed by transfer hook accounts of token a and token b, after instructions sysvar if it's passed
+    let mut remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts
+        .first()
+        .is_some_and(|account| account.key() == sysvar::instructions::ID)
     {
+        remaining_accounts = &remaining_accounts[1..];
+    }
+
+    let locked_fee_extension_loader = {
         let pool = ctx.accounts.pool.load()?;
-        let access_validator = get_pool_access_validator(&pool)?;
+        let allowlisted_wallet = load_allowlisted_wallet(
+            ctx.accounts.pool.key(),
+            &pool,
+            ctx.accounts.payer.key(),
+            &mut remaining_accounts,
+        )?;
+        let access_validator = get_pool_access_validator_with_allowlist(&pool, allowlisted_wallet)?;
         require!(
             access_validator.can_swap(&ctx.accounts.payer.key()),
             PoolError::PoolDisabled
         );
-    }
+
+        load_locked_fee_extension_if_initialized(
+            ctx.accounts.pool.key(),
+            &pool,
+            &mut remaining_accounts,
+        )?
+    };
 
     let swap_mode = SwapMode::try_from(swap_mode).map_err(|_| PoolError::InvalidInput)?;
     let trade_direction = ctx.accounts.get_trade_direction();
 
+    let transfer_hook_accounts_a =
+        load_transfer_hook_accounts(&ctx.accounts.token_a_mint, &mut remaining_accounts)?;
+    let transfer_hook_accounts_b =
+        load_transfer_hook_accounts(&ctx.accounts.token_b_mint, &mut remaining_accounts)?;
+
     let (
         token_in_mint,
         token_out_mint,
@@ -134,6 +192,8 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
         output_vault_account,
         input_program,
         output_program,
+        input_transfer_hook_accounts,
+        output_transfer_hook_accounts,
     ) = match trade_direction {
         TradeDirection::AtoB => (
             &ctx.accounts.token_a_mint,
@@ -142,6 +202,8 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
             &ctx.accounts.token_b_vault,
             &ctx.accounts.token_a_program,
             &ctx.accounts.token_b_program,
+            transfer_hook_accounts_a,
+            transfer_hook_accounts_b,
         ),
         TradeDirection::BtoA => (
             &ctx.accounts.token_b_mint,
@@ -150,9 +212,14 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
             &ctx.accounts.token_a_vault,
             &ctx.accounts.token_b_program,
             &ctx.accounts.token_a_program,
+            transfer_hook_accounts_b,
+            transfer_hook_accounts_a,
         ),
     };
 
+    let transfer_fee_bps_in = validate_transfer_fee_bps(token_in_mint, max_transfer_fee_bps)?;
+    let transfer_fee_bps_out = validate_transfer_fee_bps(token_out_mint, max_transfer_fee_bps)?;
+
     // redundant validation, but we can just keep it
     require!(amount_0 > 0, PoolError::AmountIsZero);
 
@@ -201,7 +268,16 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
         SwapMode::ExactOut => process_swap_exact_out(process_swap_params),
     }?;
 
-    pool.apply_swap_result(&swap_result, &fee_mode, current_timestamp)?;
+    let mut locked_fee_extension = locked_fee_extension_loader
+        .as_ref()
+        .map(|loader| loader.load_mut())
+        .transpose()?;
+    pool.apply_swap_result(
+        &swap_result,
+        &fee_mode,
+        current_timestamp,
+        locked_fee_extension.as_deref_mut(),
+    )?;
 
     let SwapResult2 {
         included_fee_input_amount,
@@ -217,6 +293,7 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
         input_vault_account,
         input_program,
         included_transfer_fee_amount_in,
+        input_transfer_hook_accounts,
     )?;
 
     // send to user
@@ -227,6 +304,7 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
         &ctx.accounts.output_token_account,
         output_program,
         included_transfer_fee_amount_out,
+        output_transfer_hook_accounts,
     )?;
 
     // send to referral
@@ -239,6 +317,7 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
                 &ctx.accounts.referral_token_account.clone().unwrap(),
                 &ctx.accounts.token_a_program,
                 referral_fee,
+                transfer_hook_accounts_a,
             )?;
         } else {
             transfer_from_pool(
@@ -248,6 +327,7 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
                 &ctx.accounts.referral_token_account.clone().unwrap(),
                 &ctx.accounts.token_b_program,
                 referral_fee,
+                transfer_hook_accounts_b,
             )?;
         }
     }
@@ -276,7 +356,9 @@ pub fn handle_swap_wrapper(ctx: &Context<SwapCtx>, params: SwapParameters2) -> R
         included_transfer_fee_amount_out,
         excluded_transfer_fee_amount_out,
         reserve_a_amount,
-        reserve_b_amount
+        reserve_b_amount,
+        transfer_fee_bps_in,
+        transfer_fee_bps_out
     });
 
     Ok(())
@@ -350,8 +432,13 @@ fn is_instruction_include_pool_swap(instruction: &Instruction, pool: &Pubkey) ->
     let instruction_discriminator = &instruction.data[..8];
     if instruction_discriminator.eq(SwapInstruction::DISCRIMINATOR)
         || instruction_discriminator.eq(Swap2Instruction::DISCRIMINATOR)
+        || instruction_discriminator.eq(Swap3Instruction::DISCRIMINATOR)
     {
         return instruction.accounts[1].pubkey.eq(pool);
     }
+    // compound position fee may swap the excess fee through the pool
+    if instruction_discriminator.eq(CompoundPositionFeeInstruction::DISCRIMINATOR) {
+        return instruction.accounts[0].pubkey.eq(pool);
+    }
     false
 }
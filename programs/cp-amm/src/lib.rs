@@ -69,6 +69,21 @@ pub mod cp_amm {
         instructions::handle_close_config(ctx)
     }
 
+    pub fn update_config(
+        ctx: Context<UpdateConfigCtx>,
+        config_parameters: StaticConfigParameters,
+    ) -> Result<()> {
+        instructions::handle_update_config(ctx, config_parameters)
+    }
+
+    pub fn update_price_range(
+        ctx: Context<UpdatePriceRangeCtx>,
+        sqrt_min_price: u128,
+        sqrt_max_price: u128,
+    ) -> Result<()> {
+        instructions::handle_update_price_range(ctx, sqrt_min_price, sqrt_max_price)
+    }
+
     pub fn initialize_reward<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeRewardCtx<'info>>,
         reward_index: u8,
@@ -78,8 +93,8 @@ pub mod cp_amm {
         instructions::handle_initialize_reward(ctx, reward_index, reward_duration, funder)
     }
 
-    pub fn fund_reward(
-        ctx: Context<FundRewardCtx>,
+    pub fn fund_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
         reward_index: u8,
         amount: u64,
         carry_forward: bool,
@@ -87,43 +102,93 @@ pub mod cp_amm {
         instructions::handle_fund_reward(ctx, reward_index, amount, carry_forward)
     }
 
-    pub fn withdraw_ineligible_reward(
-        ctx: Context<WithdrawIneligibleRewardCtx>,
+    pub fn fund_reward2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundRewardCtx<'info>>,
+        reward_index: u8,
+        params: FundRewardParameters2,
+    ) -> Result<()> {
+        instructions::handle_fund_reward2(ctx, reward_index, params)
+    }
+
+    pub fn withdraw_ineligible_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleRewardCtx<'info>>,
         reward_index: u8,
     ) -> Result<()> {
         instructions::handle_withdraw_ineligible_reward(ctx, reward_index)
     }
 
-    pub fn update_reward_funder(
-        ctx: Context<UpdateRewardFunderCtx>,
+    pub fn update_reward_funder<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardFunderCtx<'info>>,
         reward_index: u8,
         new_funder: Pubkey,
     ) -> Result<()> {
         instructions::handle_update_reward_funder(ctx, reward_index, new_funder)
     }
 
-    pub fn update_reward_duration(
-        ctx: Context<UpdateRewardDurationCtx>,
+    pub fn update_reward_duration<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateRewardDurationCtx<'info>>,
         reward_index: u8,
         new_duration: u64,
     ) -> Result<()> {
         instructions::handle_update_reward_duration(ctx, reward_index, new_duration)
     }
 
+    pub fn update_reward_boost(
+        ctx: Context<UpdateRewardBoostCtx>,
+        reward_index: u8,
+        permanent_lock_boost_bps: u16,
+        vesting_lock_boost_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_update_reward_boost(
+            ctx,
+            reward_index,
+            permanent_lock_boost_bps,
+            vesting_lock_boost_bps,
+        )
+    }
+
     pub fn set_pool_status(ctx: Context<SetPoolStatusCtx>, status: u8) -> Result<()> {
         instructions::handle_set_pool_status(ctx, status)
     }
 
-    pub fn claim_protocol_fee(
-        ctx: Context<ClaimProtocolFeesCtx>,
+    pub fn set_pool_disabled_actions(
+        ctx: Context<SetPoolDisabledActionsCtx>,
+        disabled_actions: u8,
+        reason: String,
+    ) -> Result<()> {
+        instructions::handle_set_pool_disabled_actions(ctx, disabled_actions, reason)
+    }
+
+    pub fn claim_protocol_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimProtocolFeesCtx<'info>>,
         max_amount_a: u64,
         max_amount_b: u64,
     ) -> Result<()> {
         instructions::handle_claim_protocol_fee(ctx, max_amount_a, max_amount_b)
     }
 
-    pub fn claim_partner_fee(
-        ctx: Context<ClaimPartnerFeesCtx>,
+    pub fn batch_claim_protocol_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BatchClaimProtocolFeesCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_batch_claim_protocol_fee(ctx)
+    }
+
+    pub fn initialize_protocol_fee_routing(
+        ctx: Context<InitializeProtocolFeeRoutingCtx>,
+        destinations: Vec<state::ProtocolFeeDestinationParameters>,
+    ) -> Result<()> {
+        instructions::handle_initialize_protocol_fee_routing(ctx, destinations)
+    }
+
+    pub fn update_protocol_fee_routing(
+        ctx: Context<UpdateProtocolFeeRoutingCtx>,
+        destinations: Vec<state::ProtocolFeeDestinationParameters>,
+    ) -> Result<()> {
+        instructions::handle_update_protocol_fee_routing(ctx, destinations)
+    }
+
+    pub fn claim_partner_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPartnerFeesCtx<'info>>,
         max_amount_a: u64,
         max_amount_b: u64,
     ) -> Result<()> {
@@ -134,6 +199,48 @@ pub mod cp_amm {
         Ok(())
     }
 
+    pub fn update_token_badge(
+        ctx: Context<UpdateTokenBadgeCtx>,
+        params: UpdateTokenBadgeParameters,
+    ) -> Result<()> {
+        instructions::handle_update_token_badge(ctx, params)
+    }
+
+    pub fn initialize_admin_registry(ctx: Context<InitializeAdminRegistryCtx>) -> Result<()> {
+        instructions::handle_initialize_admin_registry(ctx)
+    }
+
+    pub fn add_admin_role(ctx: Context<UpdateAdminRoleCtx>, role: u8) -> Result<()> {
+        instructions::handle_add_admin_role(ctx, role)
+    }
+
+    pub fn remove_admin_role(ctx: Context<UpdateAdminRoleCtx>, role: u8) -> Result<()> {
+        instructions::handle_remove_admin_role(ctx, role)
+    }
+
+    pub fn transfer_super_admin(ctx: Context<TransferSuperAdminCtx>) -> Result<()> {
+        instructions::handle_transfer_super_admin(ctx)
+    }
+
+    pub fn update_timelock(
+        ctx: Context<UpdateTimelockCtx>,
+        activation_type: u8,
+        delay: u64,
+    ) -> Result<()> {
+        instructions::handle_update_timelock(ctx, activation_type, delay)
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposalCtx>,
+        params: CreateProposalParameters,
+    ) -> Result<()> {
+        instructions::handle_create_proposal(ctx, params)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposalCtx>) -> Result<()> {
+        instructions::handle_cancel_proposal(ctx)
+    }
+
     /// USER FUNCTIONS ////
 
     pub fn initialize_pool<'c: 'info, 'info>(
@@ -150,6 +257,13 @@ pub mod cp_amm {
         instructions::handle_initialize_pool_with_dynamic_config(ctx, params)
     }
 
+    pub fn migrate_pool<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigratePoolCtx<'info>>,
+        params: MigratePoolParameters,
+    ) -> Result<()> {
+        instructions::handle_migrate_pool(ctx, params)
+    }
+
     pub fn initialize_customizable_pool<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeCustomizablePoolCtx<'info>>,
         params: InitializeCustomizablePoolParameters,
@@ -157,19 +271,41 @@ pub mod cp_amm {
         instructions::handle_initialize_customizable_pool(ctx, params)
     }
 
+    pub fn initialize_single_sided_pool<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeCustomizablePoolCtx<'info>>,
+        params: InitializeSingleSidedPoolParameters,
+    ) -> Result<()> {
+        instructions::handle_initialize_single_sided_pool(ctx, params)
+    }
+
     pub fn create_position(ctx: Context<CreatePositionCtx>) -> Result<()> {
         instructions::handle_create_position(ctx)
     }
 
-    pub fn add_liquidity(
-        ctx: Context<AddLiquidityCtx>,
+    pub fn add_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
         params: AddLiquidityParameters,
     ) -> Result<()> {
-        instructions::handle_add_liquidity(ctx, params)
+        instructions::handle_add_liquidity(ctx, params, None)
+    }
+
+    pub fn add_liquidity2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, AddLiquidityCtx<'info>>,
+        params: AddLiquidityParameters2,
+    ) -> Result<()> {
+        instructions::handle_add_liquidity(
+            ctx,
+            AddLiquidityParameters {
+                liquidity_delta: params.liquidity_delta,
+                token_a_amount_threshold: params.token_a_amount_threshold,
+                token_b_amount_threshold: params.token_b_amount_threshold,
+            },
+            Some(params.max_transfer_fee_bps),
+        )
     }
 
-    pub fn remove_liquidity(
-        ctx: Context<RemoveLiquidityCtx>,
+    pub fn remove_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
         params: RemoveLiquidityParameters,
     ) -> Result<()> {
         instructions::handle_remove_liquidity(
@@ -177,11 +313,25 @@ pub mod cp_amm {
             Some(params.liquidity_delta),
             params.token_a_amount_threshold,
             params.token_b_amount_threshold,
+            None,
         )
     }
 
-    pub fn remove_all_liquidity(
-        ctx: Context<RemoveLiquidityCtx>,
+    pub fn remove_liquidity2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
+        params: RemoveLiquidityParameters2,
+    ) -> Result<()> {
+        instructions::handle_remove_liquidity(
+            ctx,
+            Some(params.liquidity_delta),
+            params.token_a_amount_threshold,
+            params.token_b_amount_threshold,
+            Some(params.max_transfer_fee_bps),
+        )
+    }
+
+    pub fn remove_all_liquidity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RemoveLiquidityCtx<'info>>,
         token_a_amount_threshold: u64,
         token_b_amount_threshold: u64,
     ) -> Result<()> {
@@ -190,6 +340,7 @@ pub mod cp_amm {
             None,
             token_a_amount_threshold,
             token_b_amount_threshold,
+            None,
         )
     }
 
@@ -197,7 +348,52 @@ pub mod cp_amm {
         instructions::handle_close_position(ctx)
     }
 
-    pub fn swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()> {
+    pub fn transfer_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, TransferPositionCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_transfer_position(ctx)
+    }
+
+    pub fn close_pool<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClosePoolCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_close_pool(ctx)
+    }
+
+    pub fn refresh_pool_token_badge(ctx: Context<RefreshPoolTokenBadgeCtx>) -> Result<()> {
+        instructions::handle_refresh_pool_token_badge(ctx)
+    }
+
+    pub fn refresh_position_metadata<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RefreshPositionMetadataCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_refresh_position_metadata(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposalCtx>) -> Result<()> {
+        instructions::handle_execute_proposal(ctx)
+    }
+
+    pub fn initialize_pool_permission(ctx: Context<InitializePoolPermissionCtx>) -> Result<()> {
+        instructions::handle_initialize_pool_permission(ctx)
+    }
+
+    pub fn add_allowlist_entry(ctx: Context<AddAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_add_allowlist_entry(ctx)
+    }
+
+    pub fn remove_allowlist_entry(ctx: Context<RemoveAllowlistEntryCtx>) -> Result<()> {
+        instructions::handle_remove_allowlist_entry(ctx)
+    }
+
+    pub fn transfer_pool_manager(ctx: Context<TransferPoolManagerCtx>) -> Result<()> {
+        instructions::handle_transfer_pool_manager(ctx)
+    }
+
+    pub fn swap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters,
+    ) -> Result<()> {
         instructions::swap::handle_swap_wrapper(
             &ctx,
             SwapParameters2 {
@@ -205,18 +401,51 @@ pub mod cp_amm {
                 amount_1: params.minimum_amount_out,
                 swap_mode: SwapMode::ExactIn.into(),
             },
+            None,
         )
     }
 
-    pub fn swap2(ctx: Context<SwapCtx>, params: SwapParameters2) -> Result<()> {
-        instructions::swap::handle_swap_wrapper(&ctx, params)
+    pub fn swap2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters2,
+    ) -> Result<()> {
+        instructions::swap::handle_swap_wrapper(&ctx, params, None)
+    }
+
+    pub fn swap3<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters3,
+    ) -> Result<()> {
+        instructions::swap::handle_swap_wrapper(
+            &ctx,
+            params.to_swap_parameters2(),
+            Some(params.max_transfer_fee_bps),
+        )
     }
 
-    pub fn claim_position_fee(ctx: Context<ClaimPositionFeeCtx>) -> Result<()> {
+    pub fn claim_position_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPositionFeeCtx<'info>>,
+    ) -> Result<()> {
         instructions::handle_claim_position_fee(ctx)
     }
 
-    pub fn lock_position(ctx: Context<LockPositionCtx>, params: VestingParameters) -> Result<()> {
+    pub fn claim_pending_withdrawal<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimPendingWithdrawalCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_claim_pending_withdrawal(ctx)
+    }
+
+    pub fn compound_position_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CompoundPositionFeeCtx<'info>>,
+        params: CompoundPositionFeeParameters,
+    ) -> Result<()> {
+        instructions::handle_compound_position_fee(ctx, params)
+    }
+
+    pub fn lock_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, LockPositionCtx<'info>>,
+        params: VestingParameters,
+    ) -> Result<()> {
         instructions::handle_lock_position(ctx, params)
     }
 
@@ -226,29 +455,120 @@ pub mod cp_amm {
         instructions::handle_refresh_vesting(ctx)
     }
 
-    pub fn permanent_lock_position(
-        ctx: Context<PermanentLockPositionCtx>,
+    pub fn permanent_lock_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PermanentLockPositionCtx<'info>>,
         permanent_lock_liquidity: u128,
     ) -> Result<()> {
         instructions::handle_permanent_lock_position(ctx, permanent_lock_liquidity)
     }
 
-    pub fn claim_reward(
-        ctx: Context<ClaimRewardCtx>,
+    pub fn claim_reward<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimRewardCtx<'info>>,
         reward_index: u8,
         skip_reward: u8,
     ) -> Result<()> {
         instructions::handle_claim_reward(ctx, reward_index, skip_reward)
     }
 
-    pub fn split_position(
-        ctx: Context<SplitPositionCtx>,
+    pub fn initialize_reward_extension(ctx: Context<InitializeRewardExtensionCtx>) -> Result<()> {
+        instructions::handle_initialize_reward_extension(ctx)
+    }
+
+    pub fn create_position_reward_extension(
+        ctx: Context<CreatePositionRewardExtensionCtx>,
+    ) -> Result<()> {
+        instructions::handle_create_position_reward_extension(ctx)
+    }
+
+    pub fn close_position_reward_extension(
+        ctx: Context<ClosePositionRewardExtensionCtx>,
+    ) -> Result<()> {
+        instructions::handle_close_position_reward_extension(ctx)
+    }
+
+    pub fn initialize_locked_fee_extension(
+        ctx: Context<InitializeLockedFeeExtensionCtx>,
+        locked_fee_share_bps: u16,
+    ) -> Result<()> {
+        instructions::handle_initialize_locked_fee_extension(ctx, locked_fee_share_bps)
+    }
+
+    pub fn initialize_fungible_share(
+        ctx: Context<InitializeFungibleShareCtx>,
+        auto_compound: bool,
+    ) -> Result<()> {
+        instructions::handle_initialize_fungible_share(ctx, auto_compound)
+    }
+
+    pub fn deposit_fungible_share<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DepositFungibleShareCtx<'info>>,
+        params: DepositFungibleShareParameters,
+    ) -> Result<()> {
+        instructions::handle_deposit_fungible_share(ctx, params)
+    }
+
+    pub fn withdraw_fungible_share<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawFungibleShareCtx<'info>>,
+        params: WithdrawFungibleShareParameters,
+    ) -> Result<()> {
+        instructions::handle_withdraw_fungible_share(ctx, params)
+    }
+
+    pub fn compound_fungible_share<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CompoundFungibleShareCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_compound_fungible_share(ctx)
+    }
+
+    pub fn initialize_bribe_ledger(ctx: Context<InitializeBribeLedgerCtx>) -> Result<()> {
+        instructions::handle_initialize_bribe_ledger(ctx)
+    }
+
+    pub fn create_position_bribe_ledger(ctx: Context<CreatePositionBribeLedgerCtx>) -> Result<()> {
+        instructions::handle_create_position_bribe_ledger(ctx)
+    }
+
+    pub fn close_position_bribe_ledger(ctx: Context<ClosePositionBribeLedgerCtx>) -> Result<()> {
+        instructions::handle_close_position_bribe_ledger(ctx)
+    }
+
+    pub fn initialize_bribe_campaign<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeBribeCampaignCtx<'info>>,
+        epoch: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::handle_initialize_bribe_campaign(ctx, epoch, amount)
+    }
+
+    pub fn claim_bribe<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimBribeCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_claim_bribe(ctx)
+    }
+
+    pub fn withdraw_ineligible_bribe<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawIneligibleBribeCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_withdraw_ineligible_bribe(ctx)
+    }
+
+    pub fn close_bribe_campaign<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CloseBribeCampaignCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_close_bribe_campaign(ctx)
+    }
+
+    pub fn split_position<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SplitPositionCtx<'info>>,
         params: SplitPositionParameters,
     ) -> Result<()> {
         instructions::handle_split_position2(ctx, params.get_split_position_parameters2()?)
     }
 
-    pub fn split_position2(ctx: Context<SplitPositionCtx>, numerator: u32) -> Result<()> {
+    pub fn split_position2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SplitPositionCtx<'info>>,
+        numerator: u32,
+    ) -> Result<()> {
         instructions::handle_split_position2(
             ctx,
             SplitPositionParameters2 {
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::pubkey::Pubkey;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::{state::Pool, PermissionlessActionAccess};
+use crate::{state::Pool, PermissionedActionAccess, PermissionlessActionAccess};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
@@ -19,9 +19,25 @@ pub trait PoolActionAccess {
     fn can_create_position(&self) -> bool;
     fn can_lock_position(&self) -> bool;
     fn can_split_position(&self) -> bool;
+    fn can_claim_reward(&self) -> bool;
 }
 
 pub fn get_pool_access_validator<'a>(pool: &'a Pool) -> Result<Box<dyn PoolActionAccess + 'a>> {
+    get_pool_access_validator_with_allowlist(pool, None)
+}
+
+/// Access validator of permissioned pool only grants swap and add liquidity to the allowlisted wallet
+pub fn get_pool_access_validator_with_allowlist<'a>(
+    pool: &'a Pool,
+    allowlisted_wallet: Option<Pubkey>,
+) -> Result<Box<dyn PoolActionAccess + 'a>> {
     let access_validator = PermissionlessActionAccess::new(pool)?;
-    Ok(Box::new(access_validator))
+    if pool.is_permissioned() {
+        Ok(Box::new(PermissionedActionAccess::new(
+            access_validator,
+            allowlisted_wallet,
+        )))
+    } else {
+        Ok(Box::new(access_validator))
+    }
 }
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{PermissionlessActionAccess, PoolActionAccess};
+
+/// Permissionless access restricted to the wallet proven to be allowlisted
+pub struct PermissionedActionAccess {
+    base: PermissionlessActionAccess,
+    allowlisted_wallet: Option<Pubkey>,
+}
+
+impl PermissionedActionAccess {
+    pub fn new(base: PermissionlessActionAccess, allowlisted_wallet: Option<Pubkey>) -> Self {
+        Self {
+            base,
+            allowlisted_wallet,
+        }
+    }
+}
+
+impl PoolActionAccess for PermissionedActionAccess {
+    fn can_add_liquidity(&self) -> bool {
+        self.allowlisted_wallet.is_some() && self.base.can_add_liquidity()
+    }
+
+    // removed wallets can still withdraw
+    fn can_remove_liquidity(&self) -> bool {
+        self.base.can_remove_liquidity()
+    }
+
+    fn can_swap(&self, sender: &Pubkey) -> bool {
+        self.allowlisted_wallet.eq(&Some(*sender)) && self.base.can_swap(sender)
+    }
+
+    fn can_create_position(&self) -> bool {
+        self.base.can_create_position()
+    }
+    fn can_lock_position(&self) -> bool {
+        self.base.can_lock_position()
+    }
+    fn can_split_position(&self) -> bool {
+        self.base.can_split_position()
+    }
+    fn can_claim_reward(&self) -> bool {
+        self.base.can_claim_reward()
+    }
+}
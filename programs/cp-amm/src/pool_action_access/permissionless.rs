@@ -3,16 +3,18 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::activation::{SLOT_BUFFER, TIME_BUFFER},
     safe_math::SafeMath,
-    state::{Pool, PoolStatus},
+    state::{Pool, PoolAction, PoolStatus},
     PoolError, {ActivationType, PoolActionAccess},
 };
 
 pub struct PermissionlessActionAccess {
     is_enabled: bool,
+    disabled_actions: u8,
     activation_point: u64,
     pre_activation_point: u64,
     current_point: u64,
     whitelisted_vault: Pubkey,
+    is_token_badge_expired: bool,
 }
 
 impl PermissionlessActionAccess {
@@ -28,19 +30,26 @@ impl PermissionlessActionAccess {
         } else {
             0
         };
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
         Ok(Self {
             is_enabled: pool.pool_status == Into::<u8>::into(PoolStatus::Enable),
+            disabled_actions: pool.disabled_actions,
+            is_token_badge_expired: pool.is_token_badge_expired(current_timestamp),
             current_point,
             activation_point: pool.activation_point,
             whitelisted_vault: pool.whitelisted_vault,
             pre_activation_point,
         })
     }
+
+    fn is_action_enabled(&self, action: PoolAction) -> bool {
+        self.is_enabled && self.disabled_actions & action.mask() == 0
+    }
 }
 
 impl PoolActionAccess for PermissionlessActionAccess {
     fn can_add_liquidity(&self) -> bool {
-        self.is_enabled
+        self.is_action_enabled(PoolAction::AddLiquidity)
     }
 
     fn can_remove_liquidity(&self) -> bool {
@@ -48,7 +57,8 @@ impl PoolActionAccess for PermissionlessActionAccess {
     }
 
     fn can_swap(&self, sender: &Pubkey) -> bool {
-        if self.is_enabled {
+        // pool with expired token badge can only be withdrawn
+        if self.is_action_enabled(PoolAction::Swap) && !self.is_token_badge_expired {
             if sender.eq(&self.whitelisted_vault) {
                 self.current_point >= self.pre_activation_point
             } else {
@@ -60,12 +70,16 @@ impl PoolActionAccess for PermissionlessActionAccess {
     }
 
     fn can_create_position(&self) -> bool {
-        self.is_enabled
+        self.is_action_enabled(PoolAction::CreatePosition)
     }
     fn can_lock_position(&self) -> bool {
-        self.is_enabled
+        self.is_action_enabled(PoolAction::LockPosition)
     }
     fn can_split_position(&self) -> bool {
-        self.is_enabled
+        self.is_action_enabled(PoolAction::SplitPosition)
+    }
+    // claiming reward doesn't depend on pool status
+    fn can_claim_reward(&self) -> bool {
+        self.disabled_actions & PoolAction::ClaimReward.mask() == 0
     }
 }
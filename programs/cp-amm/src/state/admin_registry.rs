@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use static_assertions::const_assert_eq;
+
+use crate::{
+    activation_handler::ActivationType,
+    constants::{
+        activation::{MAX_ACTIVATION_SLOT_DURATION, MAX_ACTIVATION_TIME_DURATION},
+        MAX_ADMIN_REGISTRY_MEMBERS,
+    },
+    PoolError,
+};
+
+/// admin role
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum AdminRole {
+    /// create and close config
+    ConfigManager,
+    /// enable and disable pool
+    PoolStatusOperator,
+    /// create, update and close token badge
+    TokenBadgeManager,
+    /// initialize and update reward
+    RewardManager,
+    /// create and close claim protocol fee operator
+    FeeOperatorManager,
+    /// cancel timelocked proposal
+    ProposalCanceller,
+    /// initialize and update protocol fee routing
+    ProtocolFeeRoutingManager,
+}
+
+impl AdminRole {
+    fn mask(&self) -> u8 {
+        1 << u8::from(*self)
+    }
+}
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct AdminRegistryMember {
+    /// admin address, default pubkey if the slot is empty
+    pub admin: Pubkey,
+    /// bitmap of roles, bit i is AdminRole i
+    pub roles: u8,
+    /// padding
+    pub _padding: [u8; 7],
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct AdminRegistry {
+    /// super admin, manages roles of other admins and holds all roles
+    pub super_admin: Pubkey,
+    /// admins holding roles
+    pub members: [AdminRegistryMember; MAX_ADMIN_REGISTRY_MEMBERS],
+    /// activation type of timelock delay, refer [ActivationType]
+    pub timelock_activation_type: u8,
+    /// padding
+    pub _padding_0: [u8; 7],
+    /// delay in slots or seconds before a proposal can be executed, timelock is disabled if it's 0
+    pub timelock_delay: u64,
+    /// Reserve
+    pub _padding: [u8; 112],
+}
+
+const_assert_eq!(AdminRegistry::INIT_SPACE, 800);
+
+impl AdminRegistry {
+    pub fn initialize(&mut self, super_admin: Pubkey) {
+        self.super_admin = super_admin;
+    }
+
+    pub fn update_timelock(&mut self, activation_type: u8, delay: u64) -> Result<()> {
+        let max_delay = match ActivationType::try_from(activation_type)
+            .map_err(|_| PoolError::InvalidActivationType)?
+        {
+            ActivationType::Slot => MAX_ACTIVATION_SLOT_DURATION,
+            ActivationType::Timestamp => MAX_ACTIVATION_TIME_DURATION,
+        };
+        require!(delay <= max_delay, PoolError::InvalidParameters);
+
+        self.timelock_activation_type = activation_type;
+        self.timelock_delay = delay;
+
+        Ok(())
+    }
+
+    pub fn is_timelock_enabled(&self) -> bool {
+        self.timelock_delay > 0
+    }
+
+    pub fn has_role(&self, admin: Pubkey, role: AdminRole) -> bool {
+        if admin == self.super_admin {
+            return true;
+        }
+        self.members
+            .iter()
+            .any(|member| member.admin == admin && member.roles & role.mask() != 0)
+    }
+
+    pub fn add_role(&mut self, admin: Pubkey, role: AdminRole) -> Result<()> {
+        require!(
+            admin != Pubkey::default() && !self.has_role(admin, role),
+            PoolError::InvalidParameters
+        );
+
+        let member_index = self
+            .members
+            .iter()
+            .position(|member| member.admin == admin)
+            .or_else(|| {
+                self.members
+                    .iter()
+                    .position(|member| member.admin == Pubkey::default())
+            })
+            .ok_or(PoolError::AdminRegistryFull)?;
+
+        let member = &mut self.members[member_index];
+        member.admin = admin;
+        member.roles |= role.mask();
+
+        Ok(())
+    }
+
+    pub fn remove_role(&mut self, admin: Pubkey, role: AdminRole) -> Result<()> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|member| member.admin == admin && member.roles & role.mask() != 0)
+            .ok_or(PoolError::InvalidParameters)?;
+
+        member.roles &= !role.mask();
+        // free the slot once the admin has no role
+        if member.roles == 0 {
+            *member = AdminRegistryMember::default();
+        }
+
+        Ok(())
+    }
+}
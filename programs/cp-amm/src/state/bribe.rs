@@ -0,0 +1,355 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{BRIBE_EPOCH_DURATION, LIQUIDITY_SCALE, NUM_BRIBE_EPOCH_SNAPSHOTS},
+    safe_math::SafeMath,
+    u128x128_math::shl_div_256,
+    PoolError,
+};
+
+pub fn get_bribe_epoch(timestamp: u64) -> u64 {
+    timestamp / BRIBE_EPOCH_DURATION
+}
+
+fn get_snapshot_index(epoch: u64) -> usize {
+    (epoch % NUM_BRIBE_EPOCH_SNAPSHOTS as u64) as usize
+}
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct BribeEpochSnapshot {
+    /// bribe epoch of the snapshot
+    pub epoch: u64,
+    /// seconds in the epoch when there was no enrolled liquidity
+    pub empty_seconds: u64,
+    /// seconds per enrolled liquidity at the end of the epoch
+    pub seconds_per_liquidity: [u8; 32], // U256
+    /// 1 if the snapshot has been recorded
+    pub initialized: u8,
+    /// padding
+    pub _padding_0: [u8; 7],
+}
+
+const_assert_eq!(BribeEpochSnapshot::INIT_SPACE, 56);
+
+/// Time-weighted liquidity of positions enrolled in bribes of a pool
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct BribeLedger {
+    /// pool
+    pub pool: Pubkey,
+    /// total liquidity of positions having a position bribe ledger
+    pub enrolled_liquidity: u128,
+    /// accumulated seconds per enrolled liquidity
+    pub seconds_per_liquidity: [u8; 32], // U256
+    /// last time the ledger was updated
+    pub last_update_time: u64,
+    /// seconds in the current epoch when there was no enrolled liquidity
+    pub empty_seconds: u64,
+    /// epoch in which the ledger was created
+    pub start_epoch: u64,
+    /// number of bribe campaigns not closed yet
+    pub open_campaign_count: u64,
+    /// snapshots of recent epochs
+    pub snapshots: [BribeEpochSnapshot; NUM_BRIBE_EPOCH_SNAPSHOTS],
+    /// padding for future usage
+    pub padding: [u128; 4],
+}
+
+const_assert_eq!(BribeLedger::INIT_SPACE, 848);
+
+impl BribeLedger {
+    pub fn initialize(&mut self, pool: Pubkey, current_time: u64) {
+        self.pool = pool;
+        self.last_update_time = current_time;
+        self.start_epoch = get_bribe_epoch(current_time);
+    }
+
+    pub fn seconds_per_liquidity(&self) -> U256 {
+        U256::from_le_bytes(self.seconds_per_liquidity)
+    }
+
+    fn accumulate(&mut self, seconds: u64) -> Result<()> {
+        if self.enrolled_liquidity > 0 {
+            let delta = shl_div_256(seconds.into(), self.enrolled_liquidity, LIQUIDITY_SCALE)
+                .ok_or(PoolError::MathOverflow)?;
+            self.seconds_per_liquidity =
+                self.seconds_per_liquidity().safe_add(delta)?.to_le_bytes();
+        } else {
+            self.empty_seconds = self.empty_seconds.safe_add(seconds)?;
+        }
+        Ok(())
+    }
+
+    fn record_snapshot(&mut self, epoch: u64) {
+        self.snapshots[get_snapshot_index(epoch)] = BribeEpochSnapshot {
+            epoch,
+            empty_seconds: self.empty_seconds,
+            seconds_per_liquidity: self.seconds_per_liquidity,
+            initialized: 1,
+            ..Default::default()
+        };
+        self.empty_seconds = 0;
+    }
+
+    /// Update the accumulator, must be called before any enrolled liquidity change
+    pub fn update(&mut self, current_time: u64) -> Result<()> {
+        let current_epoch = get_bribe_epoch(current_time);
+
+        while self.last_update_time < current_time {
+            let epoch = get_bribe_epoch(self.last_update_time);
+            if epoch == current_epoch {
+                self.accumulate(current_time.safe_sub(self.last_update_time)?)?;
+                self.last_update_time = current_time;
+                break;
+            }
+
+            // close the epoch of last update
+            let epoch_end = epoch.safe_add(1)?.safe_mul(BRIBE_EPOCH_DURATION)?;
+            self.accumulate(epoch_end.safe_sub(self.last_update_time)?)?;
+            self.last_update_time = epoch_end;
+            self.record_snapshot(epoch);
+
+            // skip epochs that would be evicted from snapshots anyway
+            let first_kept_epoch = current_epoch.saturating_sub(NUM_BRIBE_EPOCH_SNAPSHOTS as u64);
+            if epoch.safe_add(1)? < first_kept_epoch {
+                let first_kept_epoch_start = first_kept_epoch.safe_mul(BRIBE_EPOCH_DURATION)?;
+                self.accumulate(first_kept_epoch_start.safe_sub(self.last_update_time)?)?;
+                self.last_update_time = first_kept_epoch_start;
+                self.empty_seconds = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_snapshot(&self, epoch: u64) -> Result<&BribeEpochSnapshot> {
+        let snapshot = &self.snapshots[get_snapshot_index(epoch)];
+        require!(
+            snapshot.initialized == 1 && snapshot.epoch == epoch,
+            PoolError::BribeEpochSnapshotNotFound
+        );
+        Ok(snapshot)
+    }
+
+    pub fn add_enrolled_liquidity(&mut self, liquidity: u128) -> Result<()> {
+        self.enrolled_liquidity = self.enrolled_liquidity.safe_add(liquidity)?;
+        Ok(())
+    }
+
+    pub fn remove_enrolled_liquidity(&mut self, liquidity: u128) -> Result<()> {
+        self.enrolled_liquidity = self.enrolled_liquidity.safe_sub(liquidity)?;
+        Ok(())
+    }
+}
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct PositionBribeEpochSnapshot {
+    /// bribe epoch of the snapshot
+    pub epoch: u64,
+    /// 1 if the snapshot has been recorded
+    pub initialized: u8,
+    /// padding
+    pub _padding_0: [u8; 7],
+    /// liquidity seconds of the position at the end of the epoch
+    pub liquidity_seconds: [u8; 32], // U256
+}
+
+const_assert_eq!(PositionBribeEpochSnapshot::INIT_SPACE, 48);
+
+/// Time-weighted liquidity of a position enrolled in bribes
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PositionBribeLedger {
+    /// position
+    pub position: Pubkey,
+    /// position liquidity counted in pool enrolled liquidity
+    pub liquidity: u128,
+    /// accumulated liquidity seconds, scaled by seconds per liquidity
+    pub liquidity_seconds: [u8; 32], // U256
+    /// seconds per liquidity of pool bribe ledger at last update
+    pub seconds_per_liquidity_checkpoint: [u8; 32], // U256
+    /// last time the ledger was updated
+    pub last_update_time: u64,
+    /// epoch in which the ledger was created
+    pub start_epoch: u64,
+    /// snapshots of recent epochs
+    pub snapshots: [PositionBribeEpochSnapshot; NUM_BRIBE_EPOCH_SNAPSHOTS],
+    /// padding for future usage
+    pub padding: [u128; 4],
+}
+
+const_assert_eq!(PositionBribeLedger::INIT_SPACE, 768);
+
+impl PositionBribeLedger {
+    /// Position only earns bribes from the time the ledger is created, bribe ledger must be updated before
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        liquidity: u128,
+        bribe_ledger: &BribeLedger,
+        current_time: u64,
+    ) {
+        self.position = position;
+        self.liquidity = liquidity;
+        self.seconds_per_liquidity_checkpoint = bribe_ledger.seconds_per_liquidity;
+        self.last_update_time = current_time;
+        self.start_epoch = get_bribe_epoch(current_time);
+    }
+
+    pub fn liquidity_seconds(&self) -> U256 {
+        U256::from_le_bytes(self.liquidity_seconds)
+    }
+
+    fn get_liquidity_seconds_at(&self, seconds_per_liquidity: U256) -> Result<U256> {
+        let checkpoint = U256::from_le_bytes(self.seconds_per_liquidity_checkpoint);
+        let delta =
+            U256::from(self.liquidity).safe_mul(seconds_per_liquidity.safe_sub(checkpoint)?)?;
+        Ok(self.liquidity_seconds().safe_add(delta)?)
+    }
+
+    /// Update the accumulator with liquidity before any change, bribe ledger must be updated before
+    pub fn update(&mut self, bribe_ledger: &BribeLedger, current_time: u64) -> Result<()> {
+        let current_epoch = get_bribe_epoch(current_time);
+        let last_epoch = get_bribe_epoch(self.last_update_time);
+        let first_kept_epoch = current_epoch.saturating_sub(NUM_BRIBE_EPOCH_SNAPSHOTS as u64);
+
+        // record snapshots of epochs ended since last update
+        for epoch in last_epoch.max(first_kept_epoch)..current_epoch {
+            let snapshot = bribe_ledger.get_snapshot(epoch)?;
+            let liquidity_seconds =
+                self.get_liquidity_seconds_at(U256::from_le_bytes(snapshot.seconds_per_liquidity))?;
+            self.snapshots[get_snapshot_index(epoch)] = PositionBribeEpochSnapshot {
+                epoch,
+                initialized: 1,
+                liquidity_seconds: liquidity_seconds.to_le_bytes(),
+                ..Default::default()
+            };
+        }
+
+        self.liquidity_seconds = self
+            .get_liquidity_seconds_at(bribe_ledger.seconds_per_liquidity())?
+            .to_le_bytes();
+        self.seconds_per_liquidity_checkpoint = bribe_ledger.seconds_per_liquidity;
+        self.last_update_time = current_time;
+
+        Ok(())
+    }
+
+    /// Liquidity seconds of the position in an ended epoch
+    pub fn get_epoch_liquidity_seconds(&self, epoch: u64) -> Result<U256> {
+        require!(epoch >= self.start_epoch, PoolError::InvalidBribeEpoch);
+
+        let end = self.get_snapshot_liquidity_seconds(epoch)?;
+        let start = if epoch == self.start_epoch {
+            U256::ZERO
+        } else {
+            self.get_snapshot_liquidity_seconds(epoch.safe_sub(1)?)?
+        };
+
+        Ok(end.safe_sub(start)?)
+    }
+
+    fn get_snapshot_liquidity_seconds(&self, epoch: u64) -> Result<U256> {
+        let snapshot = &self.snapshots[get_snapshot_index(epoch)];
+        require!(
+            snapshot.initialized == 1 && snapshot.epoch == epoch,
+            PoolError::BribeEpochSnapshotNotFound
+        );
+        Ok(U256::from_le_bytes(snapshot.liquidity_seconds))
+    }
+}
+
+/// Bribe deposited by a funder, distributed pro rata to enrolled liquidity over an epoch
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct BribeCampaign {
+    /// pool
+    pub pool: Pubkey,
+    /// funder of the campaign
+    pub funder: Pubkey,
+    /// bribe mint
+    pub mint: Pubkey,
+    /// bribe vault
+    pub vault: Pubkey,
+    /// bribe epoch
+    pub epoch: u64,
+    /// total amount distributed in the epoch
+    pub total_amount: u64,
+    /// amount claimed by positions
+    pub claimed_amount: u64,
+    /// amount refunded to funder for seconds without enrolled liquidity
+    pub ineligible_amount: u64,
+    /// 1 if ineligible amount has been withdrawn
+    pub ineligible_withdrawn: u8,
+    /// padding
+    pub _padding_0: [u8; 15],
+    /// padding for future usage
+    pub padding: [u128; 2],
+}
+
+const_assert_eq!(BribeCampaign::INIT_SPACE, 208);
+
+impl BribeCampaign {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        funder: Pubkey,
+        mint: Pubkey,
+        vault: Pubkey,
+        epoch: u64,
+        total_amount: u64,
+    ) {
+        self.pool = pool;
+        self.funder = funder;
+        self.mint = mint;
+        self.vault = vault;
+        self.epoch = epoch;
+        self.total_amount = total_amount;
+    }
+
+    /// Share of the position over the epoch, rounded down
+    pub fn get_claimable_amount(&self, epoch_liquidity_seconds: U256) -> Result<u64> {
+        let denominator = U256::from(BRIBE_EPOCH_DURATION).safe_shl(LIQUIDITY_SCALE.into())?;
+        let amount = U256::from(self.total_amount)
+            .safe_mul(epoch_liquidity_seconds)?
+            .safe_div(denominator)?;
+        amount
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed.into())
+    }
+
+    /// Amount distributed to seconds without enrolled liquidity, rounded down
+    pub fn get_ineligible_amount(&self, empty_seconds: u64) -> Result<u64> {
+        let amount = u128::from(self.total_amount)
+            .safe_mul(empty_seconds.into())?
+            .safe_div(BRIBE_EPOCH_DURATION.into())?;
+        amount
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed.into())
+    }
+
+    pub fn accumulate_claimed_amount(&mut self, amount: u64) -> Result<()> {
+        self.claimed_amount = self.claimed_amount.safe_add(amount)?;
+        Ok(())
+    }
+}
+
+/// Marks a position has claimed a bribe campaign
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct BribeClaimReceipt {
+    /// bribe campaign
+    pub bribe_campaign: Pubkey,
+    /// position
+    pub position: Pubkey,
+    /// claimed amount
+    pub amount: u64,
+    /// padding
+    pub _padding_0: u64,
+}
+
+const_assert_eq!(BribeClaimReceipt::INIT_SPACE, 80);
@@ -12,6 +12,7 @@ use crate::{
     },
     safe_math::SafeMath,
     state::fee::{BaseFeeStruct, DynamicFeeStruct, PoolFeesStruct},
+    StaticConfigParameters,
 };
 
 /// collect fee mode
@@ -208,9 +209,10 @@ pub struct Config {
     pub sqrt_min_price: u128,
     /// sqrt max price
     pub sqrt_max_price: u128,
-    /// Fee curve point
+    /// Config version, increased every time config parameters are updated
+    pub version: u64,
     /// Padding for further use
-    pub _padding_1: [u64; 10],
+    pub _padding_1: [u64; 9],
 }
 
 const_assert_eq!(Config::INIT_SPACE, 320);
@@ -286,6 +288,29 @@ impl Config {
         self.config_type = ConfigType::Static.into();
     }
 
+    pub fn update_static_config(&mut self, params: &StaticConfigParameters) -> Result<()> {
+        let StaticConfigParameters {
+            pool_fees,
+            vault_config_key,
+            pool_creator_authority,
+            activation_type,
+            sqrt_min_price,
+            sqrt_max_price,
+            collect_fee_mode,
+        } = *params;
+
+        self.pool_fees = pool_fees.to_pool_fees_config();
+        self.vault_config_key = vault_config_key;
+        self.pool_creator_authority = pool_creator_authority;
+        self.activation_type = activation_type;
+        self.sqrt_min_price = sqrt_min_price;
+        self.sqrt_max_price = sqrt_max_price;
+        self.collect_fee_mode = collect_fee_mode;
+        self.version = self.version.safe_add(1)?;
+
+        Ok(())
+    }
+
     pub fn get_config_type(&self) -> Result<ConfigType> {
         let config_type =
             ConfigType::try_from(self.config_type).map_err(|_| PoolError::TypeCastFailed)?;
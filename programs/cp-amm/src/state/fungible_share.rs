@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{FUNGIBLE_SHARE_VIRTUAL_LIQUIDITY, FUNGIBLE_SHARE_VIRTUAL_SHARES},
+    safe_math::SafeMath,
+    u128x128_math::Rounding,
+    utils_math::{safe_mul_div_cast_u128, safe_mul_div_cast_u64},
+    PoolError,
+};
+
+/// Fungible share mode of a pool. Liquidity is deposited to a shared position owned by pool authority,
+/// and depositors hold share mint tokens proportional to unlocked liquidity of the shared position
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct FungibleShare {
+    /// pool
+    pub pool: Pubkey,
+    /// shared position, its nft is held by pool authority
+    pub position: Pubkey,
+    /// share mint
+    pub share_mint: Pubkey,
+    /// 1 if pending fee of shared position is compounded on every deposit and withdrawal
+    pub auto_compound: u8,
+    /// padding
+    pub _padding_0: [u8; 15],
+    /// padding for future usage
+    pub padding: [u128; 4],
+}
+
+const_assert_eq!(FungibleShare::INIT_SPACE, 176);
+
+impl FungibleShare {
+    pub fn initialize(
+        &mut self,
+        pool: Pubkey,
+        position: Pubkey,
+        share_mint: Pubkey,
+        auto_compound: bool,
+    ) {
+        self.pool = pool;
+        self.position = position;
+        self.share_mint = share_mint;
+        self.auto_compound = auto_compound.into();
+    }
+
+    pub fn is_auto_compound(&self) -> bool {
+        self.auto_compound == 1
+    }
+}
+
+/// Shares minted for liquidity deposited to the shared position, rounded down.
+/// Virtual shares and liquidity are added to the supply and the shared liquidity
+pub fn get_shares_from_liquidity(
+    liquidity_delta: u128,
+    share_supply: u64,
+    shared_liquidity: u128,
+) -> Result<u64> {
+    let shares = safe_mul_div_cast_u128(
+        liquidity_delta,
+        share_supply.safe_add(FUNGIBLE_SHARE_VIRTUAL_SHARES)?.into(),
+        shared_liquidity.safe_add(FUNGIBLE_SHARE_VIRTUAL_LIQUIDITY)?,
+        Rounding::Down,
+    )?;
+    Ok(u64::try_from(shares).map_err(|_| PoolError::TypeCastFailed)?)
+}
+
+/// Liquidity withdrawn from the shared position for burnt shares, rounded down.
+/// Burning the whole supply withdraws all shared liquidity, including the part backing virtual shares
+pub fn get_liquidity_from_shares(
+    shares: u64,
+    share_supply: u64,
+    shared_liquidity: u128,
+) -> Result<u128> {
+    require!(
+        shares > 0 && shares <= share_supply,
+        PoolError::InsufficientLiquidity
+    );
+    if shares == share_supply {
+        return Ok(shared_liquidity);
+    }
+    let liquidity = safe_mul_div_cast_u128(
+        shared_liquidity.safe_add(FUNGIBLE_SHARE_VIRTUAL_LIQUIDITY)?,
+        shares.into(),
+        share_supply.safe_add(FUNGIBLE_SHARE_VIRTUAL_SHARES)?.into(),
+        Rounding::Down,
+    )?;
+    Ok(liquidity.min(shared_liquidity))
+}
+
+/// Pending fee of the shared position which isn't compounded yet is settled pro rata to shares.
+/// Depositors pay their part rounded up, and withdrawers receive their part rounded down
+pub fn get_pending_fee_from_shares(
+    fee_pending: u64,
+    shares: u64,
+    share_supply: u64,
+    rounding: Rounding,
+) -> Result<u64> {
+    if share_supply == 0 {
+        return Ok(0);
+    }
+    safe_mul_div_cast_u64(fee_pending, shares, share_supply, rounding)
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{BASIS_POINT_MAX, LIQUIDITY_SCALE},
+    safe_math::SafeMath,
+    u128x128_math::{shl_div_256, Rounding},
+    utils_math::safe_mul_div_cast_u64,
+    PoolError,
+};
+
+/// Share of LP fee routed to permanent locked and vesting locked liquidity of a pool
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct LockedFeeExtension {
+    /// pool
+    pub pool: Pubkey,
+    /// share of LP fee routed to locked liquidity in bps, on top of its share as liquidity
+    pub locked_fee_share_bps: u16,
+    /// padding
+    pub _padding_0: [u8; 14],
+    /// cumulative fee a per locked liquidity
+    pub fee_a_per_locked_liquidity: [u8; 32], // U256
+    /// cumulative fee b per locked liquidity
+    pub fee_b_per_locked_liquidity: [u8; 32], // U256
+    /// total fee a routed to locked liquidity
+    pub total_locked_a_fee: u64,
+    /// total fee b routed to locked liquidity
+    pub total_locked_b_fee: u64,
+    /// padding for future usage
+    pub padding: [u128; 4],
+}
+
+const_assert_eq!(LockedFeeExtension::INIT_SPACE, 192);
+
+impl LockedFeeExtension {
+    pub fn initialize(&mut self, pool: Pubkey, locked_fee_share_bps: u16) {
+        self.pool = pool;
+        self.locked_fee_share_bps = locked_fee_share_bps;
+    }
+
+    pub fn fee_a_per_locked_liquidity(&self) -> U256 {
+        U256::from_le_bytes(self.fee_a_per_locked_liquidity)
+    }
+
+    pub fn fee_b_per_locked_liquidity(&self) -> U256 {
+        U256::from_le_bytes(self.fee_b_per_locked_liquidity)
+    }
+
+    /// Share of LP fee routed to locked liquidity, nothing is routed if there is no locked liquidity
+    pub fn get_locked_fee(&self, lp_fee: u64, locked_liquidity: u128) -> Result<u64> {
+        if locked_liquidity == 0 {
+            return Ok(0);
+        }
+        safe_mul_div_cast_u64(
+            lp_fee,
+            self.locked_fee_share_bps.into(),
+            BASIS_POINT_MAX,
+            Rounding::Down,
+        )
+    }
+
+    pub fn accumulate_locked_fee(
+        &mut self,
+        locked_fee: u64,
+        locked_liquidity: u128,
+        is_token_a: bool,
+    ) -> Result<()> {
+        if locked_fee == 0 {
+            return Ok(());
+        }
+
+        let fee_per_locked_liquidity =
+            shl_div_256(locked_fee.into(), locked_liquidity, LIQUIDITY_SCALE)
+                .ok_or(PoolError::MathOverflow)?;
+
+        if is_token_a {
+            self.fee_a_per_locked_liquidity = self
+                .fee_a_per_locked_liquidity()
+                .safe_add(fee_per_locked_liquidity)?
+                .to_le_bytes();
+            self.total_locked_a_fee = self.total_locked_a_fee.safe_add(locked_fee)?;
+        } else {
+            self.fee_b_per_locked_liquidity = self
+                .fee_b_per_locked_liquidity()
+                .safe_add(fee_per_locked_liquidity)?
+                .to_le_bytes();
+            self.total_locked_b_fee = self.total_locked_b_fee.safe_add(locked_fee)?;
+        }
+
+        Ok(())
+    }
+}
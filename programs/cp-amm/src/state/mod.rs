@@ -11,3 +11,19 @@ pub mod vesting;
 pub use vesting::*;
 pub mod claim_fee_operator;
 pub use claim_fee_operator::*;
+pub mod protocol_fee_routing;
+pub use protocol_fee_routing::*;
+pub mod reward_extension;
+pub use reward_extension::*;
+pub mod locked_fee_extension;
+pub use locked_fee_extension::*;
+pub mod fungible_share;
+pub use fungible_share::*;
+pub mod bribe;
+pub use bribe::*;
+pub mod admin_registry;
+pub use admin_registry::*;
+pub mod proposal;
+pub use proposal::*;
+pub mod pool_permission;
+pub use pool_permission::*;
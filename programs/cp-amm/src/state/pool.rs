@@ -1,6 +1,6 @@
 use ruint::aliases::U256;
 use static_assertions::const_assert_eq;
-use std::cmp::min;
+use std::cmp::{max, min};
 
 use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -9,24 +9,32 @@ use crate::constants::fee::{get_max_fee_numerator, CURRENT_POOL_VERSION};
 use crate::curve::get_next_sqrt_price_from_output;
 use crate::state::fee::{FeeOnAmountResult, SplitFees};
 use crate::{
-    assert_eq_admin,
-    constants::{LIQUIDITY_SCALE, NUM_REWARDS, REWARD_INDEX_0, REWARD_INDEX_1, REWARD_RATE_SCALE},
+    assert_admin_role,
+    constants::{
+        BASIS_POINT_MAX, LIQUIDITY_SCALE, LIQUIDITY_SCALE_FACTOR_RESOLUTION,
+        MAX_PRICE_RANGE_EXCESS_BPS, MAX_SQRT_PRICE, MIN_SQRT_PRICE, NUM_REWARDS, REWARD_INDEX_0,
+        REWARD_INDEX_1, REWARD_RATE_SCALE,
+    },
     curve::{
         get_delta_amount_a_unsigned, get_delta_amount_a_unsigned_unchecked,
-        get_delta_amount_b_unsigned, get_next_sqrt_price_from_input,
+        get_delta_amount_b_unsigned, get_liquidity_from_amount_a_unchecked,
+        get_liquidity_from_amount_b_unchecked, get_next_sqrt_price_from_input,
     },
     params::swap::TradeDirection,
     safe_math::SafeMath,
     state::{
         fee::{DynamicFeeStruct, PoolFeesStruct},
-        Position, SplitFeeAmount,
+        AdminRole, LockedFeeExtension, Position, SplitFeeAmount,
+    },
+    u128x128_math::{mul_div_u256, shl_div_256, Rounding},
+    utils_math::{
+        safe_mul_div_cast_u128, safe_mul_div_cast_u64, safe_mul_shr_cast, safe_shl_div_cast,
     },
-    u128x128_math::{shl_div_256, Rounding},
-    utils_math::{safe_mul_shr_cast, safe_shl_div_cast},
     PoolError,
 };
 
 use super::fee::FeeMode;
+use super::token_badge::is_badge_expired;
 
 /// collect fee mode
 #[repr(u8)]
@@ -64,6 +72,26 @@ pub enum PoolStatus {
     Disable,
 }
 
+/// pool actions that admin can disable separately, pool status disable still halts all of them except claiming reward
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum PoolAction {
+    Swap,
+    AddLiquidity,
+    CreatePosition,
+    LockPosition,
+    SplitPosition,
+    ClaimReward,
+}
+
+impl PoolAction {
+    pub fn mask(self) -> u8 {
+        1 << u8::from(self)
+    }
+}
+
+pub const POOL_ACTION_MASK: u8 = 0b11_1111;
+
 #[repr(u8)]
 #[derive(
     Clone,
@@ -78,6 +106,8 @@ pub enum PoolStatus {
 pub enum PoolType {
     Permissionless,
     Customizable,
+    /// Customizable pool that only allowlisted wallets can swap and add liquidity
+    Permissioned,
 }
 
 #[repr(u8)]
@@ -147,8 +177,8 @@ pub struct Pool {
     pub pool_type: u8,
     /// pool version, 0: max_fee is still capped at 50%, 1: max_fee is capped at 99%
     pub version: u8,
-    /// padding
-    pub _padding_0: u8,
+    /// bitflag of disabled actions, bit index is `PoolAction`
+    pub disabled_actions: u8,
     /// cumulative
     pub fee_a_per_liquidity: [u8; 32], // U256
     /// cumulative
@@ -159,8 +189,26 @@ pub struct Pool {
     pub metrics: PoolMetrics,
     /// pool creator
     pub creator: Pubkey,
-    /// Padding for further use
-    pub _padding_1: [u64; 6],
+    /// 1 if any reward in reward extension has been initialized
+    pub reward_extension_flag: u8,
+    /// 1 if vesting_locked_liquidity is tracked since pool creation
+    pub vesting_liquidity_flag: u8,
+    /// 1 if locked fee extension has been initialized, a share of LP fee is routed to locked liquidity
+    pub locked_fee_extension_flag: u8,
+    /// 1 if fungible share mode has been enabled, rewards can't be initialized for the pool
+    pub fungible_share_flag: u8,
+    /// 1 if bribe ledger has been initialized
+    pub bribe_ledger_flag: u8,
+    /// padding
+    pub _padding_2: [u8; 3],
+    /// Q32.32 ratio of curve liquidity to liquidity, set when the price range is updated. 0 means 1
+    pub liquidity_scale_factor: u64,
+    /// total liquidity locked in vestings, only valid if vesting_liquidity_flag is set
+    pub vesting_locked_liquidity: u128,
+    /// expiry timestamp of token a badge when the pool was created or refreshed, 0 if it never expires
+    pub token_a_badge_expiry_timestamp: u64,
+    /// expiry timestamp of token b badge when the pool was created or refreshed, 0 if it never expires
+    pub token_b_badge_expiry_timestamp: u64,
     /// Farming reward information
     pub reward_infos: [RewardInfo; NUM_REWARDS],
 }
@@ -219,10 +267,14 @@ pub struct RewardInfo {
     pub initialized: u8,
     /// reward token flag
     pub reward_token_flag: u8,
+    /// extra weight of permanent locked liquidity in bps, 0 means no boost
+    pub permanent_lock_boost_bps: u16,
+    /// extra weight of vesting locked liquidity in bps, 0 means no boost
+    pub vesting_lock_boost_bps: u16,
     /// padding
-    pub _padding_0: [u8; 6],
-    /// Padding to ensure `reward_rate: u128` is 16-byte aligned
-    pub _padding_1: [u8; 8], // 8 bytes
+    pub _padding_0: [u8; 2],
+    /// reward duration start, rewards are only distributed from this time. 0 for rewards funded without schedule
+    pub reward_duration_start: u64,
     /// Reward token mint.
     pub mint: Pubkey,
     /// Reward vault token account.
@@ -253,8 +305,49 @@ impl RewardInfo {
         self.initialized != 0
     }
 
-    pub fn is_valid_funder(&self, funder: Pubkey) -> bool {
-        assert_eq_admin(funder) || funder.eq(&self.funder)
+    pub fn is_valid_funder(&self, admin_registry: &AccountInfo, funder: Pubkey) -> bool {
+        funder.eq(&self.funder)
+            || assert_admin_role(admin_registry, funder, AdminRole::RewardManager)
+    }
+
+    /// Returns true if the reward isn't initialized, or it has ended and ineligible reward has been withdrawn
+    pub fn is_finished(&self, current_time: u64) -> bool {
+        !self.initialized()
+            || (current_time > self.reward_duration_end
+                && self.cumulative_seconds_with_empty_liquidity_reward == 0)
+    }
+
+    pub fn has_boost(&self) -> bool {
+        self.permanent_lock_boost_bps > 0 || self.vesting_lock_boost_bps > 0
+    }
+
+    /// Liquidity weighted by lock status, liquidity must include vested and permanent locked liquidity
+    pub fn get_boosted_liquidity(
+        &self,
+        liquidity: u128,
+        vesting_locked_liquidity: u128,
+        permanent_locked_liquidity: u128,
+    ) -> Result<u128> {
+        if !self.has_boost() {
+            return Ok(liquidity);
+        }
+
+        let vesting_boost = safe_mul_div_cast_u128(
+            vesting_locked_liquidity,
+            self.vesting_lock_boost_bps.into(),
+            BASIS_POINT_MAX.into(),
+            Rounding::Down,
+        )?;
+        let permanent_lock_boost = safe_mul_div_cast_u128(
+            permanent_locked_liquidity,
+            self.permanent_lock_boost_bps.into(),
+            BASIS_POINT_MAX.into(),
+            Rounding::Down,
+        )?;
+
+        Ok(liquidity
+            .safe_add(vesting_boost)?
+            .safe_add(permanent_lock_boost)?)
     }
 
     pub fn init_reward(
@@ -306,11 +399,21 @@ impl RewardInfo {
 
     pub fn get_seconds_elapsed_since_last_update(&self, current_time: u64) -> Result<u64> {
         let last_time_reward_applicable = min(current_time, self.reward_duration_end);
-        let time_period = last_time_reward_applicable.safe_sub(self.last_update_time.into())?;
+        // no reward is distributed before the scheduled start
+        let first_time_reward_applicable = max(self.last_update_time, self.reward_duration_start);
+        if last_time_reward_applicable <= first_time_reward_applicable {
+            return Ok(0);
+        }
+        let time_period = last_time_reward_applicable.safe_sub(first_time_reward_applicable)?;
 
         Ok(time_period)
     }
 
+    /// Returns true if the reward is funded with a schedule that has not started yet
+    pub fn is_scheduled(&self, current_time: u64) -> bool {
+        current_time < self.reward_duration_start
+    }
+
     // To make it simple we truncate decimals of liquidity_supply for the calculation
     pub fn calculate_reward_per_token_stored_since_last_update(
         &self,
@@ -320,10 +423,10 @@ impl RewardInfo {
         let time_period: u128 = self
             .get_seconds_elapsed_since_last_update(current_time)?
             .into();
-        let total_reward = time_period.safe_mul(self.reward_rate.into())?;
+        let total_reward = time_period.safe_mul(self.reward_rate)?;
 
         let reward_per_token_stored = shl_div_256(total_reward, liquidity_supply, LIQUIDITY_SCALE)
-            .ok_or_else(|| PoolError::MathOverflow)?;
+            .ok_or(PoolError::MathOverflow)?;
         Ok(reward_per_token_stored)
     }
 
@@ -335,6 +438,19 @@ impl RewardInfo {
         Ok(())
     }
 
+    pub fn claim_ineligible_reward(&mut self) -> Result<u64> {
+        // calculate ineligible reward
+        let ineligible_reward: u64 = safe_mul_shr_cast(
+            self.cumulative_seconds_with_empty_liquidity_reward.into(),
+            self.reward_rate,
+            REWARD_RATE_SCALE,
+        )?;
+
+        self.cumulative_seconds_with_empty_liquidity_reward = 0;
+
+        Ok(ineligible_reward)
+    }
+
     pub fn reward_per_token_stored(&self) -> U256 {
         U256::from_le_bytes(self.reward_per_token_stored)
     }
@@ -347,6 +463,17 @@ impl RewardInfo {
     ) -> Result<()> {
         let reward_duration_end = self.reward_duration_end;
 
+        // funding before the scheduled start is added to the scheduled window
+        if self.is_scheduled(current_time) {
+            let reward_duration_start = self.reward_duration_start;
+            return self.update_rate_after_scheduled_funding(
+                current_time,
+                reward_duration_start,
+                reward_duration_end,
+                funding_amount,
+            );
+        }
+
         let total_amount = if current_time >= reward_duration_end {
             funding_amount
         } else {
@@ -367,10 +494,58 @@ impl RewardInfo {
             Rounding::Down,
         )?;
         self.last_update_time = current_time;
+        self.reward_duration_start = current_time;
         self.reward_duration_end = current_time.safe_add(self.reward_duration)?;
 
         Ok(())
     }
+
+    /// Farming rate after funding with a schedule, rewards are only distributed from reward_duration_start to reward_duration_end
+    pub fn update_rate_after_scheduled_funding(
+        &mut self,
+        current_time: u64,
+        reward_duration_start: u64,
+        reward_duration_end: u64,
+        funding_amount: u64,
+    ) -> Result<()> {
+        require!(
+            reward_duration_start >= current_time && reward_duration_end > reward_duration_start,
+            PoolError::InvalidRewardSchedule
+        );
+
+        let total_amount = if self.is_scheduled(current_time) {
+            // only the same schedule can be topped up before it starts
+            require!(
+                self.reward_duration_start == reward_duration_start
+                    && self.reward_duration_end == reward_duration_end,
+                PoolError::InvalidRewardSchedule
+            );
+            let scheduled_amount: u64 = safe_mul_shr_cast(
+                self.reward_rate,
+                reward_duration_end.safe_sub(reward_duration_start)?.into(),
+                REWARD_RATE_SCALE,
+            )?;
+            funding_amount.safe_add(scheduled_amount)?
+        } else {
+            require!(
+                current_time >= self.reward_duration_end,
+                PoolError::RewardCampaignInProgress
+            );
+            funding_amount
+        };
+
+        self.reward_rate = safe_shl_div_cast(
+            total_amount.into(),
+            reward_duration_end.safe_sub(reward_duration_start)?.into(),
+            REWARD_RATE_SCALE,
+            Rounding::Down,
+        )?;
+        self.last_update_time = current_time;
+        self.reward_duration_start = reward_duration_start;
+        self.reward_duration_end = reward_duration_end;
+
+        Ok(())
+    }
 }
 
 impl Pool {
@@ -396,6 +571,7 @@ impl Pool {
         pool_type: u8,
     ) {
         self.creator = creator;
+        self.vesting_liquidity_flag = 1;
         self.pool_fees = pool_fees;
         self.token_a_mint = token_a_mint;
         self.token_b_mint = token_b_mint;
@@ -758,19 +934,16 @@ impl Pool {
         &self,
         amount_out: u64,
     ) -> Result<SwapAmountFromOutput> {
+        let liquidity = self.get_curve_liquidity()?;
         let next_sqrt_price =
-            get_next_sqrt_price_from_output(self.sqrt_price, self.liquidity, amount_out, false)?;
+            get_next_sqrt_price_from_output(self.sqrt_price, liquidity, amount_out, false)?;
 
         if next_sqrt_price > self.sqrt_max_price {
             return Err(PoolError::PriceRangeViolation.into());
         }
 
-        let in_amount = get_delta_amount_b_unsigned(
-            self.sqrt_price,
-            next_sqrt_price,
-            self.liquidity,
-            Rounding::Up,
-        )?;
+        let in_amount =
+            get_delta_amount_b_unsigned(self.sqrt_price, next_sqrt_price, liquidity, Rounding::Up)?;
 
         Ok(SwapAmountFromOutput {
             input_amount: in_amount,
@@ -782,19 +955,16 @@ impl Pool {
         &self,
         amount_out: u64,
     ) -> Result<SwapAmountFromOutput> {
+        let liquidity = self.get_curve_liquidity()?;
         let next_sqrt_price =
-            get_next_sqrt_price_from_output(self.sqrt_price, self.liquidity, amount_out, true)?;
+            get_next_sqrt_price_from_output(self.sqrt_price, liquidity, amount_out, true)?;
 
         if next_sqrt_price < self.sqrt_min_price {
             return Err(PoolError::PriceRangeViolation.into());
         }
 
-        let in_amount = get_delta_amount_a_unsigned(
-            next_sqrt_price,
-            self.sqrt_price,
-            self.liquidity,
-            Rounding::Up,
-        )?;
+        let in_amount =
+            get_delta_amount_a_unsigned(next_sqrt_price, self.sqrt_price, liquidity, Rounding::Up)?;
 
         Ok(SwapAmountFromOutput {
             input_amount: in_amount,
@@ -806,10 +976,11 @@ impl Pool {
         &self,
         amount_in: u64,
     ) -> Result<SwapAmountFromInput> {
+        let liquidity = self.get_curve_liquidity()?;
         let max_amount_in = get_delta_amount_b_unsigned(
             self.sqrt_price,
             self.sqrt_max_price,
-            self.liquidity,
+            liquidity,
             Rounding::Up,
         )?;
 
@@ -817,14 +988,14 @@ impl Pool {
             (max_amount_in, self.sqrt_max_price)
         } else {
             let next_sqrt_price =
-                get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, false)?;
+                get_next_sqrt_price_from_input(self.sqrt_price, liquidity, amount_in, false)?;
             (amount_in, next_sqrt_price)
         };
 
         let output_amount = get_delta_amount_a_unsigned(
             self.sqrt_price,
             next_sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
@@ -841,10 +1012,11 @@ impl Pool {
         &self,
         amount_in: u64,
     ) -> Result<SwapAmountFromInput> {
+        let liquidity = self.get_curve_liquidity()?;
         let max_amount_in = get_delta_amount_a_unsigned(
             self.sqrt_min_price,
             self.sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Up,
         )?;
 
@@ -852,14 +1024,14 @@ impl Pool {
             (max_amount_in, self.sqrt_min_price)
         } else {
             let next_sqrt_price =
-                get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, true)?;
+                get_next_sqrt_price_from_input(self.sqrt_price, liquidity, amount_in, true)?;
             (amount_in, next_sqrt_price)
         };
 
         let output_amount = get_delta_amount_b_unsigned(
             next_sqrt_price,
             self.sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
@@ -873,9 +1045,10 @@ impl Pool {
     }
 
     fn calculate_a_to_b_from_amount_in(&self, amount_in: u64) -> Result<SwapAmountFromInput> {
+        let liquidity = self.get_curve_liquidity()?;
         // finding new target price
         let next_sqrt_price =
-            get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, true)?;
+            get_next_sqrt_price_from_input(self.sqrt_price, liquidity, amount_in, true)?;
 
         if next_sqrt_price < self.sqrt_min_price {
             return Err(PoolError::PriceRangeViolation.into());
@@ -885,7 +1058,7 @@ impl Pool {
         let output_amount = get_delta_amount_b_unsigned(
             next_sqrt_price,
             self.sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
@@ -897,9 +1070,10 @@ impl Pool {
     }
 
     fn calculate_b_to_a_from_amount_in(&self, amount_in: u64) -> Result<SwapAmountFromInput> {
+        let liquidity = self.get_curve_liquidity()?;
         // finding new target price
         let next_sqrt_price =
-            get_next_sqrt_price_from_input(self.sqrt_price, self.liquidity, amount_in, false)?;
+            get_next_sqrt_price_from_input(self.sqrt_price, liquidity, amount_in, false)?;
 
         if next_sqrt_price > self.sqrt_max_price {
             return Err(PoolError::PriceRangeViolation.into());
@@ -908,7 +1082,7 @@ impl Pool {
         let output_amount = get_delta_amount_a_unsigned(
             self.sqrt_price,
             next_sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
@@ -919,11 +1093,13 @@ impl Pool {
         })
     }
 
+    /// Locked fee extension is required if the pool has one
     pub fn apply_swap_result(
         &mut self,
         swap_result: &SwapResult2,
         fee_mode: &FeeMode,
         current_timestamp: u64,
+        locked_fee_extension: Option<&mut LockedFeeExtension>,
     ) -> Result<()> {
         let &SwapResult2 {
             trading_fee: lp_fee,
@@ -936,8 +1112,33 @@ impl Pool {
         let old_sqrt_price = self.sqrt_price;
         self.sqrt_price = next_sqrt_price;
 
-        let fee_per_token_stored = shl_div_256(lp_fee.into(), self.liquidity, LIQUIDITY_SCALE)
-            .ok_or_else(|| PoolError::MathOverflow)?;
+        // a share of LP fee is routed to locked liquidity, the rest is shared by all liquidity
+        let locked_fee = match locked_fee_extension {
+            Some(locked_fee_extension) => {
+                let locked_liquidity = self.get_fee_share_locked_liquidity()?;
+                let locked_fee = locked_fee_extension.get_locked_fee(lp_fee, locked_liquidity)?;
+                locked_fee_extension.accumulate_locked_fee(
+                    locked_fee,
+                    locked_liquidity,
+                    fee_mode.fees_on_token_a,
+                )?;
+                locked_fee
+            }
+            None => {
+                require!(
+                    !self.has_locked_fee_extension(),
+                    PoolError::InvalidLockedFeeExtension
+                );
+                0
+            }
+        };
+
+        let fee_per_token_stored = shl_div_256(
+            lp_fee.safe_sub(locked_fee)?.into(),
+            self.liquidity,
+            LIQUIDITY_SCALE,
+        )
+        .ok_or(PoolError::MathOverflow)?;
 
         if fee_mode.fees_on_token_a {
             self.partner_a_fee = self.partner_a_fee.safe_add(partner_fee)?;
@@ -969,6 +1170,7 @@ impl Pool {
         liquidity_delta: u128,
         round: Rounding,
     ) -> Result<ModifyLiquidityResult> {
+        let liquidity_delta = self.to_curve_liquidity(liquidity_delta, round)?;
         // finding output amount
         let token_a_amount = get_delta_amount_a_unsigned(
             self.sqrt_price,
@@ -990,6 +1192,206 @@ impl Pool {
         })
     }
 
+    /// Liquidity that can be provided by the given amounts at the current price
+    pub fn get_liquidity_from_amounts(&self, amount_a: u64, amount_b: u64) -> Result<u128> {
+        let (liquidity_from_a, liquidity_from_b) =
+            self.get_liquidity_from_amounts_unchecked(amount_a, amount_b)?;
+        let curve_liquidity: u128 = liquidity_from_a
+            .min(liquidity_from_b)
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+        self.get_liquidity_from_curve_liquidity(curve_liquidity)
+    }
+
+    /// Liquidity backing the curve, it differs from `liquidity` once the price range is updated
+    pub fn get_curve_liquidity(&self) -> Result<u128> {
+        self.to_curve_liquidity(self.liquidity, Rounding::Down)
+    }
+
+    pub fn to_curve_liquidity(&self, liquidity: u128, rounding: Rounding) -> Result<u128> {
+        if self.liquidity_scale_factor == 0 {
+            return Ok(liquidity);
+        }
+        safe_mul_div_cast_u128(
+            liquidity,
+            self.liquidity_scale_factor.into(),
+            1u128 << LIQUIDITY_SCALE_FACTOR_RESOLUTION,
+            rounding,
+        )
+    }
+
+    // round down, so the curve liquidity of the result never exceeds the given one
+    fn get_liquidity_from_curve_liquidity(&self, curve_liquidity: u128) -> Result<u128> {
+        if self.liquidity_scale_factor == 0 {
+            return Ok(curve_liquidity);
+        }
+        safe_shl_div_cast(
+            curve_liquidity,
+            self.liquidity_scale_factor.into(),
+            LIQUIDITY_SCALE_FACTOR_RESOLUTION,
+            Rounding::Down,
+        )
+    }
+
+    /// Move the price range around the current price. Positions keep their liquidity, the curve
+    /// liquidity is rebuilt from the current reserves through `liquidity_scale_factor` instead.
+    /// The new range must preserve the reserves, so the principal of every position (including the
+    /// locked one) is kept. Returns the rounding dust which doesn't fit the new range
+    pub fn update_price_range(
+        &mut self,
+        sqrt_min_price: u128,
+        sqrt_max_price: u128,
+    ) -> Result<(u64, u64)> {
+        require!(
+            sqrt_min_price >= MIN_SQRT_PRICE && sqrt_max_price <= MAX_SQRT_PRICE,
+            PoolError::InvalidPriceRange
+        );
+        require!(
+            sqrt_min_price < sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+        require!(
+            self.sqrt_price >= sqrt_min_price && self.sqrt_price <= sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+        require!(
+            sqrt_min_price != self.sqrt_min_price || sqrt_max_price != self.sqrt_max_price,
+            PoolError::InvalidPriceRange
+        );
+
+        let (reserve_a_amount, reserve_b_amount) = self.get_reserves_amount()?;
+
+        self.sqrt_min_price = sqrt_min_price;
+        self.sqrt_max_price = sqrt_max_price;
+
+        if self.liquidity == 0 {
+            self.liquidity_scale_factor = 0;
+            return Ok((0, 0));
+        }
+
+        let (liquidity_from_a, liquidity_from_b) =
+            self.get_liquidity_from_amounts_unchecked(reserve_a_amount, reserve_b_amount)?;
+        let curve_liquidity: u128 = liquidity_from_a
+            .min(liquidity_from_b)
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+        let liquidity_scale_factor: u64 = safe_shl_div_cast(
+            curve_liquidity,
+            self.liquidity,
+            LIQUIDITY_SCALE_FACTOR_RESOLUTION,
+            Rounding::Down,
+        )?;
+        // reserve of a side needed by the new range is empty
+        require!(liquidity_scale_factor > 0, PoolError::InvalidPriceRange);
+        self.liquidity_scale_factor = liquidity_scale_factor;
+
+        let (new_reserve_a_amount, new_reserve_b_amount) = self.get_reserves_amount()?;
+        let excess_a_amount = reserve_a_amount.safe_sub(new_reserve_a_amount)?;
+        let excess_b_amount = reserve_b_amount.safe_sub(new_reserve_b_amount)?;
+
+        // reserves are kept for the positions, only rounding dust may be left out of the curve
+        require!(
+            is_price_range_excess_dust(excess_a_amount, reserve_a_amount)?
+                && is_price_range_excess_dust(excess_b_amount, reserve_b_amount)?,
+            PoolError::InvalidPriceRange
+        );
+
+        Ok((excess_a_amount, excess_b_amount))
+    }
+
+    // a side which doesn't need to be provided at the current price is returned as U256::MAX
+    fn get_liquidity_from_amounts_unchecked(
+        &self,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<(U256, U256)> {
+        let liquidity_from_a = if self.sqrt_price >= self.sqrt_max_price {
+            U256::MAX
+        } else {
+            get_liquidity_from_amount_a_unchecked(amount_a, self.sqrt_price, self.sqrt_max_price)?
+        };
+        let liquidity_from_b = if self.sqrt_price <= self.sqrt_min_price {
+            U256::MAX
+        } else {
+            get_liquidity_from_amount_b_unchecked(amount_b, self.sqrt_min_price, self.sqrt_price)?
+        };
+        Ok((liquidity_from_a, liquidity_from_b))
+    }
+
+    /// Direction and amount to swap so that the excess side of the given amounts matches the pool ratio.
+    /// Price impact and trading fee are ignored, so some dust may still be left after the swap.
+    pub fn get_compound_swap_amount(
+        &self,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<Option<(TradeDirection, u64)>> {
+        let (liquidity_from_a, liquidity_from_b) =
+            self.get_liquidity_from_amounts_unchecked(amount_a, amount_b)?;
+
+        if liquidity_from_a == liquidity_from_b {
+            return Ok(None);
+        }
+
+        let sqrt_price = U256::from(self.sqrt_price);
+        let sqrt_min_price = U256::from(self.sqrt_min_price);
+        let sqrt_max_price = U256::from(self.sqrt_max_price);
+
+        // share_a = √P * (√P_max - √P), share_b = √P_max * (√P - √P_min)
+        // swap amount = excess * share_of_other_side / (share_a + share_b)
+        let share_a = sqrt_price.safe_mul(sqrt_max_price.safe_sub(sqrt_price)?)?;
+        let share_b = sqrt_max_price.safe_mul(sqrt_price.safe_sub(sqrt_min_price)?)?;
+        let total_share = share_a.safe_add(share_b)?;
+
+        let (trade_direction, excess_amount, share) = if liquidity_from_a < liquidity_from_b {
+            let liquidity: u128 = liquidity_from_a
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?;
+            let used_amount_b = get_delta_amount_b_unsigned(
+                self.sqrt_min_price,
+                self.sqrt_price,
+                liquidity,
+                Rounding::Up,
+            )?;
+            (
+                TradeDirection::BtoA,
+                amount_b.safe_sub(used_amount_b)?,
+                share_a,
+            )
+        } else {
+            let liquidity: u128 = liquidity_from_b
+                .try_into()
+                .map_err(|_| PoolError::TypeCastFailed)?;
+            let used_amount_a = get_delta_amount_a_unsigned(
+                self.sqrt_price,
+                self.sqrt_max_price,
+                liquidity,
+                Rounding::Up,
+            )?;
+            (
+                TradeDirection::AtoB,
+                amount_a.safe_sub(used_amount_a)?,
+                share_b,
+            )
+        };
+
+        let swap_amount = mul_div_u256(
+            U256::from(excess_amount),
+            share,
+            total_share,
+            Rounding::Down,
+        )
+        .ok_or(PoolError::MathOverflow)?;
+        let swap_amount: u64 = swap_amount
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+
+        if swap_amount == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some((trade_direction, swap_amount)))
+    }
+
     pub fn apply_add_liquidity(
         &mut self,
         position: &mut Position,
@@ -1123,17 +1525,18 @@ impl Pool {
     }
 
     pub fn get_max_amount_in(&self, trade_direction: TradeDirection) -> Result<u64> {
+        let liquidity = self.get_curve_liquidity()?;
         let amount = match trade_direction {
             TradeDirection::AtoB => get_delta_amount_a_unsigned_unchecked(
                 self.sqrt_min_price,
                 self.sqrt_price,
-                self.liquidity,
+                liquidity,
                 Rounding::Down,
             )?,
             TradeDirection::BtoA => get_delta_amount_a_unsigned_unchecked(
                 self.sqrt_price,
                 self.sqrt_max_price,
-                self.liquidity,
+                liquidity,
                 Rounding::Down,
             )?,
         };
@@ -1210,27 +1613,116 @@ impl Pool {
     /// Update the rewards per token stored.
     pub fn update_rewards(&mut self, current_time: u64) -> Result<()> {
         for reward_idx in 0..NUM_REWARDS {
+            let liquidity_supply = self.get_reward_liquidity_supply(reward_idx)?;
             let reward_info = &mut self.reward_infos[reward_idx];
-            reward_info.update_rewards(self.liquidity, current_time)?;
+            reward_info.update_rewards(liquidity_supply, current_time)?;
+        }
+
+        Ok(())
+    }
+
+    /// Liquidity supply used to distribute the reward, weighted by lock status if the reward has boost
+    pub fn get_reward_liquidity_supply(&self, reward_index: usize) -> Result<u128> {
+        self.reward_infos[reward_index].get_boosted_liquidity(
+            self.liquidity,
+            self.vesting_locked_liquidity,
+            self.permanent_lock_liquidity,
+        )
+    }
+
+    pub fn set_token_badge_expiry_timestamps(
+        &mut self,
+        token_a_badge_expiry_timestamp: u64,
+        token_b_badge_expiry_timestamp: u64,
+    ) {
+        self.token_a_badge_expiry_timestamp = token_a_badge_expiry_timestamp;
+        self.token_b_badge_expiry_timestamp = token_b_badge_expiry_timestamp;
+    }
+
+    /// Swap is disabled once a token badge of the pool expired
+    pub fn is_token_badge_expired(&self, current_timestamp: u64) -> bool {
+        is_badge_expired(self.token_a_badge_expiry_timestamp, current_timestamp)
+            || is_badge_expired(self.token_b_badge_expiry_timestamp, current_timestamp)
+    }
+
+    pub fn is_vesting_liquidity_tracked(&self) -> bool {
+        self.vesting_liquidity_flag == 1
+    }
+
+    pub fn accumulate_vesting_locked_liquidity(
+        &mut self,
+        vesting_locked_liquidity: u128,
+    ) -> Result<()> {
+        if self.is_vesting_liquidity_tracked() {
+            self.vesting_locked_liquidity = self
+                .vesting_locked_liquidity
+                .safe_add(vesting_locked_liquidity)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn release_vesting_locked_liquidity(&mut self, released_liquidity: u128) -> Result<()> {
+        if self.is_vesting_liquidity_tracked() {
+            self.vesting_locked_liquidity =
+                self.vesting_locked_liquidity.safe_sub(released_liquidity)?;
         }
 
         Ok(())
     }
 
     pub fn claim_ineligible_reward(&mut self, reward_index: usize) -> Result<u64> {
-        // calculate ineligible reward
-        let reward_info = &mut self.reward_infos[reward_index];
-        let ineligible_reward: u64 = safe_mul_shr_cast(
-            reward_info
-                .cumulative_seconds_with_empty_liquidity_reward
-                .into(),
-            reward_info.reward_rate,
-            REWARD_RATE_SCALE,
-        )?;
+        self.reward_infos[reward_index].claim_ineligible_reward()
+    }
 
-        reward_info.cumulative_seconds_with_empty_liquidity_reward = 0;
+    /// Returns true if all positions are closed, protocol and partner fees are claimed and all
+    /// rewards are finished. Rewards must be updated to current time first
+    pub fn is_empty(&self, current_time: u64) -> bool {
+        self.liquidity == 0
+            && self.metrics.total_position == 0
+            && self.protocol_a_fee == 0
+            && self.protocol_b_fee == 0
+            && self.partner_a_fee == 0
+            && self.partner_b_fee == 0
+            && self
+                .reward_infos
+                .iter()
+                .all(|reward_info| reward_info.is_finished(current_time))
+    }
 
-        Ok(ineligible_reward)
+    pub fn has_extension_reward(&self) -> bool {
+        self.reward_extension_flag == 1
+    }
+
+    pub fn has_locked_fee_extension(&self) -> bool {
+        self.locked_fee_extension_flag == 1
+    }
+
+    pub fn has_bribe_ledger(&self) -> bool {
+        self.bribe_ledger_flag == 1
+    }
+
+    pub fn is_fungible_share_enabled(&self) -> bool {
+        self.fungible_share_flag == 1
+    }
+
+    pub fn has_initialized_reward(&self) -> bool {
+        self.has_extension_reward()
+            || self
+                .reward_infos
+                .iter()
+                .any(|reward_info| reward_info.initialized())
+    }
+
+    /// Liquidity sharing the locked fee, vesting locked liquidity is only included if it's tracked
+    pub fn get_fee_share_locked_liquidity(&self) -> Result<u128> {
+        if self.is_vesting_liquidity_tracked() {
+            Ok(self
+                .permanent_lock_liquidity
+                .safe_add(self.vesting_locked_liquidity)?)
+        } else {
+            Ok(self.permanent_lock_liquidity)
+        }
     }
 
     pub fn fee_a_per_liquidity(&self) -> U256 {
@@ -1245,32 +1737,71 @@ impl Pool {
         &self,
         reward_index: usize,
         signer: Pubkey,
+        is_reward_manager: bool,
     ) -> Result<()> {
         // pool creator is allowed to initialize reward with only index 0
         if signer == self.creator {
             require!(reward_index == 0, PoolError::InvalidRewardIndex)
         } else {
-            require!(assert_eq_admin(signer), PoolError::InvalidAdmin);
+            require!(is_reward_manager, PoolError::InvalidAdmin);
         }
         Ok(())
     }
 
+    pub fn update_pool_status(&mut self, status: u8) -> Result<()> {
+        let new_pool_status =
+            PoolStatus::try_from(status).map_err(|_| PoolError::TypeCastFailed)?;
+        let current_pool_status =
+            PoolStatus::try_from(self.pool_status).map_err(|_| PoolError::TypeCastFailed)?;
+
+        require!(
+            new_pool_status != current_pool_status,
+            PoolError::InvalidPoolStatus
+        );
+        self.pool_status = new_pool_status.into();
+
+        Ok(())
+    }
+
+    pub fn update_disabled_actions(&mut self, disabled_actions: u8) -> Result<()> {
+        require!(
+            disabled_actions & !POOL_ACTION_MASK == 0,
+            PoolError::InvalidParameters
+        );
+        require!(
+            disabled_actions != self.disabled_actions,
+            PoolError::InvalidPoolStatus
+        );
+        self.disabled_actions = disabled_actions;
+
+        Ok(())
+    }
+
+    pub fn is_permissioned(&self) -> bool {
+        self.pool_type == Into::<u8>::into(PoolType::Permissioned)
+    }
+
+    pub fn is_action_disabled(&self, action: PoolAction) -> bool {
+        self.disabled_actions & action.mask() != 0
+    }
+
     pub fn has_partner(&self) -> bool {
         self.partner != Pubkey::default()
     }
 
     pub fn get_reserves_amount(&self) -> Result<(u64, u64)> {
+        let liquidity = self.get_curve_liquidity()?;
         let reserve_b_amount = get_delta_amount_b_unsigned(
             self.sqrt_min_price,
             self.sqrt_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
         let reserve_a_amount = get_delta_amount_a_unsigned(
             self.sqrt_price,
             self.sqrt_max_price,
-            self.liquidity,
+            liquidity,
             Rounding::Down,
         )?;
 
@@ -1342,3 +1873,17 @@ pub struct SplitAmountInfo {
     pub reward_0: u64,
     pub reward_1: u64,
 }
+
+// excess of a price range update is only rounding dust when it's at most 1 or a tiny share of the reserve
+fn is_price_range_excess_dust(excess_amount: u64, reserve_amount: u64) -> Result<bool> {
+    if excess_amount <= 1 {
+        return Ok(true);
+    }
+    let max_excess_amount: u64 = safe_mul_div_cast_u64(
+        reserve_amount,
+        MAX_PRICE_RANGE_EXCESS_BPS,
+        BASIS_POINT_MAX,
+        Rounding::Down,
+    )?;
+    Ok(excess_amount <= max_excess_amount)
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Permission of a permissioned pool, only allowlisted wallets can swap and add liquidity
+pub struct PoolPermission {
+    /// pool
+    pub pool: Pubkey,
+    /// manager of the allowlist
+    pub manager: Pubkey,
+    /// number of allowlist entries not removed yet
+    pub allowlist_entry_count: u64,
+    /// Reserve
+    pub _padding: [u8; 120],
+}
+
+const_assert_eq!(PoolPermission::INIT_SPACE, 192);
+
+impl PoolPermission {
+    pub fn initialize(&mut self, pool: Pubkey, manager: Pubkey) {
+        self.pool = pool;
+        self.manager = manager;
+    }
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+/// Wallet allowlisted in a permissioned pool
+pub struct AllowlistEntry {
+    /// pool
+    pub pool: Pubkey,
+    /// allowlisted wallet
+    pub wallet: Pubkey,
+    /// Reserve
+    pub _padding: [u8; 32],
+}
+
+const_assert_eq!(AllowlistEntry::INIT_SPACE, 96);
+
+impl AllowlistEntry {
+    pub fn initialize(&mut self, pool: Pubkey, wallet: Pubkey) {
+        self.pool = pool;
+        self.wallet = wallet;
+    }
+}
@@ -73,8 +73,20 @@ pub struct Position {
     pub metrics: PositionMetrics,
     /// Farming reward information
     pub reward_infos: [UserRewardInfo; NUM_REWARDS],
-    /// padding for future usage
-    pub padding: [u128; 6],
+    /// 1 if position reward extension has been created
+    pub reward_extension_flag: u8,
+    /// 1 if position has bribe ledger, which must be updated whenever position liquidity changes
+    pub bribe_ledger_flag: u8,
+    /// padding
+    pub _padding_0: [u8; 14],
+    /// token a removed from pool but not transferred to owner, because the token is frozen
+    pub pending_withdrawal_a: u64,
+    /// token b removed from pool but not transferred to owner, because the token is frozen
+    pub pending_withdrawal_b: u64,
+    /// fee a checkpoint of locked liquidity
+    pub fee_a_per_locked_liquidity_checkpoint: [u8; 32], // U256
+    /// fee b checkpoint of locked liquidity
+    pub fee_b_per_locked_liquidity_checkpoint: [u8; 32], // U256
 }
 
 const_assert_eq!(Position::INIT_SPACE, 400);
@@ -234,6 +246,47 @@ impl Position {
         Ok(())
     }
 
+    /// Liquidity sharing the locked fee, vested liquidity is only included if the pool tracks it
+    pub fn get_fee_share_locked_liquidity(
+        &self,
+        is_vesting_liquidity_tracked: bool,
+    ) -> Result<u128> {
+        if is_vesting_liquidity_tracked {
+            Ok(self
+                .permanent_locked_liquidity
+                .safe_add(self.vested_liquidity)?)
+        } else {
+            Ok(self.permanent_locked_liquidity)
+        }
+    }
+
+    /// Add the locked fee share to pending fee, must be called before locked liquidity changes
+    pub fn update_locked_fee(
+        &mut self,
+        fee_a_per_locked_liquidity: U256,
+        fee_b_per_locked_liquidity: U256,
+        locked_liquidity: u128,
+    ) -> Result<()> {
+        if locked_liquidity > 0 {
+            let new_fee_a: u64 = safe_mul_shr_256_cast(
+                U256::from(locked_liquidity),
+                fee_a_per_locked_liquidity
+                    .safe_sub(self.fee_a_per_locked_liquidity_checkpoint())?,
+                LIQUIDITY_SCALE,
+            )?;
+            let new_fee_b: u64 = safe_mul_shr_256_cast(
+                U256::from(locked_liquidity),
+                fee_b_per_locked_liquidity
+                    .safe_sub(self.fee_b_per_locked_liquidity_checkpoint())?,
+                LIQUIDITY_SCALE,
+            )?;
+            self.add_fee_pending(new_fee_a, new_fee_b)?;
+        }
+        self.fee_a_per_locked_liquidity_checkpoint = fee_a_per_locked_liquidity.to_le_bytes();
+        self.fee_b_per_locked_liquidity_checkpoint = fee_b_per_locked_liquidity.to_le_bytes();
+        Ok(())
+    }
+
     pub fn release_vested_liquidity(&mut self, released_liquidity: u128) -> Result<()> {
         self.vested_liquidity = self.vested_liquidity.safe_sub(released_liquidity)?;
         self.add_liquidity(released_liquidity)?;
@@ -250,9 +303,32 @@ impl Position {
         Ok(())
     }
 
-    pub fn reset_pending_fee(&mut self) {
-        self.fee_a_pending = 0;
-        self.fee_b_pending = 0;
+    pub fn claim_fee(&mut self, fee_a_amount: u64, fee_b_amount: u64) -> Result<()> {
+        self.fee_a_pending = self.fee_a_pending.safe_sub(fee_a_amount)?;
+        self.fee_b_pending = self.fee_b_pending.safe_sub(fee_b_amount)?;
+        self.metrics
+            .accumulate_claimed_fee(fee_a_amount, fee_b_amount)?;
+        Ok(())
+    }
+
+    pub fn add_pending_withdrawal(
+        &mut self,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        self.pending_withdrawal_a = self.pending_withdrawal_a.safe_add(token_a_amount)?;
+        self.pending_withdrawal_b = self.pending_withdrawal_b.safe_add(token_b_amount)?;
+        Ok(())
+    }
+
+    pub fn claim_pending_withdrawal(
+        &mut self,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        self.pending_withdrawal_a = self.pending_withdrawal_a.safe_sub(token_a_amount)?;
+        self.pending_withdrawal_b = self.pending_withdrawal_b.safe_sub(token_b_amount)?;
+        Ok(())
     }
 
     pub fn update_rewards(&mut self, pool: &mut RefMut<'_, Pool>, current_time: u64) -> Result<()> {
@@ -269,15 +345,19 @@ impl Position {
 
     pub fn update_position_reward(&mut self, pool: &Pool) -> Result<()> {
         let position_liquidity = self.get_total_liquidity()?;
-        let position_reward_infos = &mut self.reward_infos;
         for reward_idx in 0..NUM_REWARDS {
             let pool_reward_info = pool.reward_infos[reward_idx];
 
             if pool_reward_info.initialized() {
                 let reward_per_token_stored =
                     U256::from_le_bytes(pool_reward_info.reward_per_token_stored);
-                position_reward_infos[reward_idx]
-                    .update_rewards(position_liquidity, reward_per_token_stored)?;
+                let boosted_liquidity = pool_reward_info.get_boosted_liquidity(
+                    position_liquidity,
+                    self.vested_liquidity,
+                    self.permanent_locked_liquidity,
+                )?;
+                self.reward_infos[reward_idx]
+                    .update_rewards(boosted_liquidity, reward_per_token_stored)?;
             }
         }
 
@@ -315,6 +395,14 @@ impl Position {
         U256::from_le_bytes(self.fee_b_per_token_checkpoint)
     }
 
+    pub fn fee_a_per_locked_liquidity_checkpoint(&self) -> U256 {
+        U256::from_le_bytes(self.fee_a_per_locked_liquidity_checkpoint)
+    }
+
+    pub fn fee_b_per_locked_liquidity_checkpoint(&self) -> U256 {
+        U256::from_le_bytes(self.fee_b_per_locked_liquidity_checkpoint)
+    }
+
     pub fn is_empty(&self) -> Result<bool> {
         // check reward
         for i in 0..NUM_REWARDS {
@@ -322,8 +410,24 @@ impl Position {
                 return Ok(false);
             }
         }
-        // check liquidity and fee
-        Ok(self.get_total_liquidity()? == 0 && self.fee_a_pending == 0 && self.fee_b_pending == 0)
+        // position reward extension and bribe ledger must be closed first
+        if self.has_reward_extension() || self.has_bribe_ledger() {
+            return Ok(false);
+        }
+        // check liquidity, fee and pending withdrawal
+        Ok(self.get_total_liquidity()? == 0
+            && self.fee_a_pending == 0
+            && self.fee_b_pending == 0
+            && self.pending_withdrawal_a == 0
+            && self.pending_withdrawal_b == 0)
+    }
+
+    pub fn has_reward_extension(&self) -> bool {
+        self.reward_extension_flag == 1
+    }
+
+    pub fn has_bribe_ledger(&self) -> bool {
+        self.bribe_ledger_flag == 1
     }
 
     pub fn get_unlocked_liquidity_by_numerator(&self, numerator: u32) -> Result<u128> {
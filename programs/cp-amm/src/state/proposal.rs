@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use static_assertions::const_assert_eq;
+
+use crate::{
+    activation_handler::ActivationHandler,
+    constants::MAX_DISABLE_REASON_LENGTH,
+    safe_math::SafeMath,
+    state::{AdminRole, PoolStatus, POOL_ACTION_MASK},
+    PoolError, StaticConfigParameters,
+};
+
+pub const PROPOSAL_ACTION_DATA_SIZE: usize = 192;
+
+/// action of timelocked proposal
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum ProposalAction {
+    /// set status of the target pool, action data is the new status
+    SetPoolStatus,
+    /// close the target config, no action data
+    CloseConfig,
+    /// update price range of the target pool, action data is the new sqrt min price and sqrt max price
+    UpdatePriceRange,
+    /// set disabled actions of the target pool, action data is the new disabled actions and reason
+    SetPoolDisabledActions,
+    /// update the target static config, action data is the new static config parameters
+    UpdateConfig,
+}
+
+impl ProposalAction {
+    pub fn get_admin_role(&self) -> AdminRole {
+        match self {
+            ProposalAction::SetPoolStatus | ProposalAction::SetPoolDisabledActions => {
+                AdminRole::PoolStatusOperator
+            }
+            ProposalAction::CloseConfig
+            | ProposalAction::UpdatePriceRange
+            | ProposalAction::UpdateConfig => AdminRole::ConfigManager,
+        }
+    }
+
+    pub fn validate_action_data(&self, action_data: &[u8]) -> Result<()> {
+        match self {
+            ProposalAction::SetPoolStatus => {
+                let status = get_pool_status_from_action_data(action_data)?;
+                PoolStatus::try_from(status).map_err(|_| PoolError::InvalidParameters)?;
+            }
+            ProposalAction::CloseConfig => {
+                require!(action_data.is_empty(), PoolError::InvalidParameters);
+            }
+            ProposalAction::UpdatePriceRange => {
+                let (sqrt_min_price, sqrt_max_price) =
+                    get_price_range_from_action_data(action_data)?;
+                require!(
+                    sqrt_min_price < sqrt_max_price,
+                    PoolError::InvalidPriceRange
+                );
+            }
+            ProposalAction::SetPoolDisabledActions => {
+                let (disabled_actions, reason) =
+                    get_disabled_actions_from_action_data(action_data)?;
+                require!(
+                    disabled_actions & !POOL_ACTION_MASK == 0
+                        && reason.len() <= MAX_DISABLE_REASON_LENGTH,
+                    PoolError::InvalidParameters
+                );
+            }
+            ProposalAction::UpdateConfig => {
+                get_static_config_parameters_from_action_data(action_data)?.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn get_pool_status_from_action_data(action_data: &[u8]) -> Result<u8> {
+    let status =
+        u8::deserialize(&mut &action_data[..]).map_err(|_| PoolError::InvalidParameters)?;
+    Ok(status)
+}
+
+pub fn get_price_range_from_action_data(action_data: &[u8]) -> Result<(u128, u128)> {
+    let price_range = <(u128, u128)>::deserialize(&mut &action_data[..])
+        .map_err(|_| PoolError::InvalidParameters)?;
+    Ok(price_range)
+}
+
+pub fn get_disabled_actions_from_action_data(action_data: &[u8]) -> Result<(u8, String)> {
+    let disabled_actions = <(u8, String)>::deserialize(&mut &action_data[..])
+        .map_err(|_| PoolError::InvalidParameters)?;
+    Ok(disabled_actions)
+}
+
+pub fn get_static_config_parameters_from_action_data(
+    action_data: &[u8],
+) -> Result<StaticConfigParameters> {
+    let config_parameters = StaticConfigParameters::deserialize(&mut &action_data[..])
+        .map_err(|_| PoolError::InvalidParameters)?;
+    Ok(config_parameters)
+}
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct Proposal {
+    /// admin proposed the action, receives rent once the proposal is executed or cancelled
+    pub proposer: Pubkey,
+    /// account the action is applied to
+    pub target: Pubkey,
+    /// action, refer [ProposalAction]
+    pub action: u8,
+    /// activation type of executable point
+    pub activation_type: u8,
+    /// padding
+    pub _padding_0: [u8; 6],
+    /// slot or timestamp when the proposal can be executed
+    pub executable_point: u64,
+    /// borsh serialized parameters of the action, zero padded
+    pub action_data: [u8; PROPOSAL_ACTION_DATA_SIZE],
+}
+
+const_assert_eq!(Proposal::INIT_SPACE, 272);
+
+impl Proposal {
+    pub fn initialize(
+        &mut self,
+        proposer: Pubkey,
+        target: Pubkey,
+        action: ProposalAction,
+        action_data: &[u8],
+        activation_type: u8,
+        delay: u64,
+    ) -> Result<()> {
+        require!(
+            action_data.len() <= PROPOSAL_ACTION_DATA_SIZE,
+            PoolError::InvalidParameters
+        );
+        action.validate_action_data(action_data)?;
+
+        let current_point = ActivationHandler::get_current_point(activation_type)?;
+
+        self.proposer = proposer;
+        self.target = target;
+        self.action = action.into();
+        self.activation_type = activation_type;
+        self.executable_point = current_point.safe_add(delay)?;
+        self.action_data[..action_data.len()].copy_from_slice(action_data);
+
+        Ok(())
+    }
+
+    pub fn is_executable(&self, current_point: u64) -> bool {
+        current_point >= self.executable_point
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{BASIS_POINT_MAX, MAX_PROTOCOL_FEE_DESTINATIONS},
+    safe_math::SafeMath,
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+    PoolError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ProtocolFeeDestinationParameters {
+    /// owner of destination token accounts
+    pub owner: Pubkey,
+    /// share of protocol fee in bps
+    pub bps: u16,
+}
+
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct ProtocolFeeDestination {
+    /// owner of destination token accounts, default pubkey if the slot is empty
+    pub owner: Pubkey,
+    /// share of protocol fee in bps
+    pub bps: u16,
+    /// padding
+    pub _padding: [u8; 6],
+}
+
+/// Destinations and splits of protocol fee, protocol fee is sent to treasury until it is initialized
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct ProtocolFeeRouting {
+    /// destinations, the first `destination_count` slots are used
+    pub destinations: [ProtocolFeeDestination; MAX_PROTOCOL_FEE_DESTINATIONS],
+    /// number of destinations
+    pub destination_count: u8,
+    /// padding
+    pub _padding_0: [u8; 15],
+    /// padding for future usage
+    pub padding: [u128; 4],
+}
+
+const_assert_eq!(ProtocolFeeRouting::INIT_SPACE, 240);
+
+impl ProtocolFeeRouting {
+    pub fn update(&mut self, destinations: &[ProtocolFeeDestinationParameters]) -> Result<()> {
+        validate_protocol_fee_destinations(destinations)?;
+
+        self.destinations = Default::default();
+        for (slot, destination) in self.destinations.iter_mut().zip(destinations) {
+            slot.owner = destination.owner;
+            slot.bps = destination.bps;
+        }
+        self.destination_count = destinations
+            .len()
+            .try_into()
+            .map_err(|_| PoolError::TypeCastFailed)?;
+
+        Ok(())
+    }
+
+    pub fn destinations(&self) -> &[ProtocolFeeDestination] {
+        &self.destinations[..usize::from(self.destination_count)]
+    }
+
+    /// Split claimed amount by bps of destinations, the last destination takes the rounding remainder
+    pub fn get_destination_amounts(&self, amount: u64) -> Result<Vec<u64>> {
+        let destinations = self.destinations();
+        let mut amounts = Vec::with_capacity(destinations.len());
+        let mut remaining_amount = amount;
+        for (i, destination) in destinations.iter().enumerate() {
+            let destination_amount = if i + 1 == destinations.len() {
+                remaining_amount
+            } else {
+                safe_mul_div_cast_u64(
+                    amount,
+                    destination.bps.into(),
+                    BASIS_POINT_MAX,
+                    Rounding::Down,
+                )?
+            };
+            remaining_amount = remaining_amount.safe_sub(destination_amount)?;
+            amounts.push(destination_amount);
+        }
+        Ok(amounts)
+    }
+}
+
+pub fn validate_protocol_fee_destinations(
+    destinations: &[ProtocolFeeDestinationParameters],
+) -> Result<()> {
+    require!(
+        !destinations.is_empty() && destinations.len() <= MAX_PROTOCOL_FEE_DESTINATIONS,
+        PoolError::InvalidProtocolFeeRouting
+    );
+
+    let mut total_bps: u64 = 0;
+    for (i, destination) in destinations.iter().enumerate() {
+        require!(
+            destination.owner != Pubkey::default() && destination.bps > 0,
+            PoolError::InvalidProtocolFeeRouting
+        );
+        require!(
+            destinations[..i]
+                .iter()
+                .all(|other| other.owner != destination.owner),
+            PoolError::InvalidProtocolFeeRouting
+        );
+        total_bps = total_bps.safe_add(destination.bps.into())?;
+    }
+    require!(
+        total_bps == BASIS_POINT_MAX,
+        PoolError::InvalidProtocolFeeRouting
+    );
+
+    Ok(())
+}
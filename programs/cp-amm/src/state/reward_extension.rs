@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    safe_math::SafeMath,
+    state::{Pool, RewardInfo, UserRewardInfo},
+    PoolError,
+};
+
+/// Additional farming rewards of a pool, beside the ones stored in pool account
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct RewardExtension {
+    /// pool
+    pub pool: Pubkey,
+    /// total liquidity of positions having a position reward extension
+    pub enrolled_liquidity: u128,
+    /// Farming reward information, reward index starts from NUM_REWARDS
+    pub reward_infos: [RewardInfo; NUM_EXTENSION_REWARDS],
+    /// padding for future usage
+    pub padding: [u128; 3],
+}
+
+const_assert_eq!(RewardExtension::INIT_SPACE, 864);
+
+/// Additional farming rewards of a position, matching with pool reward extension
+#[account(zero_copy)]
+#[derive(InitSpace, Debug, Default)]
+pub struct PositionRewardExtension {
+    /// position
+    pub position: Pubkey,
+    /// position liquidity counted in pool enrolled liquidity
+    pub liquidity: u128,
+    /// Farming reward information, reward index starts from NUM_REWARDS
+    pub reward_infos: [UserRewardInfo; NUM_EXTENSION_REWARDS],
+    /// padding for future usage
+    pub padding: [u128; 3],
+}
+
+const_assert_eq!(PositionRewardExtension::INIT_SPACE, 288);
+
+pub fn is_extension_reward_index(reward_index: usize) -> bool {
+    reward_index >= NUM_REWARDS
+}
+
+/// Convert reward index to index in reward extension
+pub fn get_extension_reward_index(reward_index: usize) -> Result<usize> {
+    require!(
+        is_extension_reward_index(reward_index)
+            && reward_index < NUM_REWARDS + NUM_EXTENSION_REWARDS,
+        PoolError::InvalidRewardIndex
+    );
+    Ok(reward_index - NUM_REWARDS)
+}
+
+/// Get reward info by reward index, reward extension is required for extension reward index
+pub fn get_reward_info<'a>(
+    pool: &'a Pool,
+    reward_extension: Option<&'a RewardExtension>,
+    reward_index: usize,
+) -> Result<&'a RewardInfo> {
+    if is_extension_reward_index(reward_index) {
+        let reward_extension = reward_extension.ok_or(PoolError::InvalidRewardExtension)?;
+        Ok(&reward_extension.reward_infos[get_extension_reward_index(reward_index)?])
+    } else {
+        Ok(&pool.reward_infos[reward_index])
+    }
+}
+
+pub fn get_reward_info_mut<'a>(
+    pool: &'a mut Pool,
+    reward_extension: Option<&'a mut RewardExtension>,
+    reward_index: usize,
+) -> Result<&'a mut RewardInfo> {
+    if is_extension_reward_index(reward_index) {
+        let reward_extension = reward_extension.ok_or(PoolError::InvalidRewardExtension)?;
+        Ok(&mut reward_extension.reward_infos[get_extension_reward_index(reward_index)?])
+    } else {
+        Ok(&mut pool.reward_infos[reward_index])
+    }
+}
+
+impl RewardExtension {
+    pub fn initialize(&mut self, pool: Pubkey) {
+        self.pool = pool;
+    }
+
+    pub fn reward_initialized(&self) -> bool {
+        self.reward_infos
+            .iter()
+            .any(|reward_info| reward_info.initialized())
+    }
+
+    /// Update the rewards per token stored, must be called before any enrolled liquidity change.
+    /// Rewards of the time without enrolled liquidity are ineligible and can be withdrawn by funder
+    pub fn update_rewards(&mut self, current_time: u64) -> Result<()> {
+        for reward_info in self.reward_infos.iter_mut() {
+            reward_info.update_rewards(self.enrolled_liquidity, current_time)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn add_enrolled_liquidity(&mut self, liquidity: u128) -> Result<()> {
+        self.enrolled_liquidity = self.enrolled_liquidity.safe_add(liquidity)?;
+        Ok(())
+    }
+
+    pub fn remove_enrolled_liquidity(&mut self, liquidity: u128) -> Result<()> {
+        self.enrolled_liquidity = self.enrolled_liquidity.safe_sub(liquidity)?;
+        Ok(())
+    }
+}
+
+impl PositionRewardExtension {
+    /// Position only earns extension rewards from the time the extension is created, and its liquidity
+    /// must be added to the enrolled liquidity of pool reward extension
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        liquidity: u128,
+        reward_extension: &RewardExtension,
+    ) {
+        self.position = position;
+        self.liquidity = liquidity;
+        for (user_reward_info, reward_info) in self
+            .reward_infos
+            .iter_mut()
+            .zip(reward_extension.reward_infos.iter())
+        {
+            user_reward_info.reward_per_token_checkpoint = reward_info.reward_per_token_stored;
+        }
+    }
+
+    /// Pool reward extension must be updated before
+    pub fn update_position_reward(&mut self, reward_extension: &RewardExtension) -> Result<()> {
+        for (user_reward_info, reward_info) in self
+            .reward_infos
+            .iter_mut()
+            .zip(reward_extension.reward_infos.iter())
+        {
+            if reward_info.initialized() {
+                user_reward_info.update_rewards(
+                    self.liquidity,
+                    U256::from_le_bytes(reward_info.reward_per_token_stored),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn claim_reward(&mut self, reward_index: usize) -> Result<u64> {
+        let user_reward_info = &mut self.reward_infos[get_extension_reward_index(reward_index)?];
+        let total_reward = user_reward_info.reward_pendings;
+
+        user_reward_info.total_claimed_rewards = user_reward_info
+            .total_claimed_rewards
+            .wrapping_add(total_reward);
+        user_reward_info.reward_pendings = 0;
+
+        Ok(total_reward)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reward_infos
+            .iter()
+            .all(|user_reward_info| user_reward_info.reward_pendings == 0)
+    }
+}
@@ -7,15 +7,70 @@ use static_assertions::const_assert_eq;
 pub struct TokenBadge {
     /// token mint
     pub token_mint: Pubkey,
+    /// bitmap of token2022 extensions the mint is allowed to have, bit i is extension type i. 0 allows all extensions
+    pub allowed_extensions: u64,
+    /// timestamp when the badge expires, 0 if the badge never expires
+    pub expiry_timestamp: u64,
+    /// transfer hook program the mint is allowed to use, default pubkey allows any program
+    pub transfer_hook_program_id: Pubkey,
+    /// max transfer fee bps of the mint, only valid if has_max_transfer_fee is set
+    pub max_transfer_fee_bps: u16,
+    /// 1 if transfer fee of the mint is limited by max_transfer_fee_bps
+    pub has_max_transfer_fee: u8,
+    /// padding
+    pub _padding_0: [u8; 5],
     /// Reserve
-    pub _padding: [u8; 128],
+    pub _padding: [u8; 72],
 }
 
 const_assert_eq!(TokenBadge::INIT_SPACE, 160);
 
+/// 0 means never expire
+pub fn is_badge_expired(expiry_timestamp: u64, current_timestamp: u64) -> bool {
+    expiry_timestamp != 0 && current_timestamp >= expiry_timestamp
+}
+
 impl TokenBadge {
     pub fn initialize(&mut self, token_mint: Pubkey) -> Result<()> {
         self.token_mint = token_mint;
         Ok(())
     }
+
+    pub fn update_policy(
+        &mut self,
+        allowed_extensions: u64,
+        expiry_timestamp: u64,
+        transfer_hook_program_id: Pubkey,
+        max_transfer_fee_bps: Option<u16>,
+    ) {
+        self.allowed_extensions = allowed_extensions;
+        self.expiry_timestamp = expiry_timestamp;
+        self.transfer_hook_program_id = transfer_hook_program_id;
+        self.has_max_transfer_fee = max_transfer_fee_bps.is_some().into();
+        self.max_transfer_fee_bps = max_transfer_fee_bps.unwrap_or(0);
+    }
+
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        is_badge_expired(self.expiry_timestamp, current_timestamp)
+    }
+
+    pub fn is_extension_allowed(&self, extension_type: u16) -> bool {
+        if self.allowed_extensions == 0 {
+            return true;
+        }
+        extension_type < u64::BITS as u16 && self.allowed_extensions & (1 << extension_type) != 0
+    }
+
+    pub fn is_transfer_hook_program_allowed(&self, transfer_hook_program_id: Pubkey) -> bool {
+        self.transfer_hook_program_id == Pubkey::default()
+            || self.transfer_hook_program_id == transfer_hook_program_id
+    }
+
+    pub fn has_max_transfer_fee(&self) -> bool {
+        self.has_max_transfer_fee == 1
+    }
+
+    pub fn is_transfer_fee_allowed(&self, transfer_fee_bps: u16) -> bool {
+        !self.has_max_transfer_fee() || transfer_fee_bps <= self.max_transfer_fee_bps
+    }
 }
@@ -46,6 +46,25 @@ impl Vesting {
         Ok(total_amount)
     }
 
+    /// Point when the last period is unlocked
+    pub fn get_end_point(&self) -> Result<u64> {
+        let end_point = self.cliff_point.safe_add(
+            self.period_frequency
+                .safe_mul(self.number_of_period.into())?,
+        )?;
+
+        Ok(end_point)
+    }
+
+    /// Liquidity still locked in the vesting
+    pub fn get_locked_liquidity(&self) -> Result<u128> {
+        let locked_liquidity = self
+            .get_total_lock_amount()?
+            .safe_sub(self.total_released_liquidity)?;
+
+        Ok(locked_liquidity)
+    }
+
     pub fn get_max_unlocked_liquidity(&self, current_point: u64) -> Result<u128> {
         if current_point < self.cliff_point {
             return Ok(0);
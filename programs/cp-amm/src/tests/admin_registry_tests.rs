@@ -0,0 +1,165 @@
+use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+
+use crate::{
+    constants::{
+        activation::MAX_ACTIVATION_SLOT_DURATION, MAX_ADMIN_REGISTRY_MEMBERS,
+        MAX_DISABLE_REASON_LENGTH,
+    },
+    params::fee_parameters::{DynamicFeeParameters, PoolFeeParameters},
+    state::{AdminRegistry, AdminRole, ProposalAction, PROPOSAL_ACTION_DATA_SIZE},
+    StaticConfigParameters,
+};
+
+fn get_admin_registry(super_admin: Pubkey) -> AdminRegistry {
+    let mut admin_registry: AdminRegistry = bytemuck::Zeroable::zeroed();
+    admin_registry.initialize(super_admin);
+    admin_registry
+}
+
+#[test]
+fn test_admin_registry_roles() {
+    let super_admin = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let mut admin_registry = get_admin_registry(super_admin);
+
+    // super admin holds all roles
+    assert!(admin_registry.has_role(super_admin, AdminRole::ConfigManager));
+    assert!(admin_registry.has_role(super_admin, AdminRole::FeeOperatorManager));
+    assert!(!admin_registry.has_role(operator, AdminRole::ConfigManager));
+
+    admin_registry
+        .add_role(operator, AdminRole::ConfigManager)
+        .unwrap();
+    admin_registry
+        .add_role(operator, AdminRole::PoolStatusOperator)
+        .unwrap();
+    assert!(admin_registry.has_role(operator, AdminRole::ConfigManager));
+    assert!(admin_registry.has_role(operator, AdminRole::PoolStatusOperator));
+    assert!(!admin_registry.has_role(operator, AdminRole::RewardManager));
+
+    // both roles are kept in the same slot
+    assert_eq!(admin_registry.members[0].admin, operator);
+    assert_eq!(admin_registry.members[1].admin, Pubkey::default());
+
+    // role can't be added twice
+    assert!(admin_registry
+        .add_role(operator, AdminRole::ConfigManager)
+        .is_err());
+
+    admin_registry
+        .remove_role(operator, AdminRole::ConfigManager)
+        .unwrap();
+    assert!(!admin_registry.has_role(operator, AdminRole::ConfigManager));
+    assert!(admin_registry.has_role(operator, AdminRole::PoolStatusOperator));
+
+    // slot is freed once the last role is removed
+    admin_registry
+        .remove_role(operator, AdminRole::PoolStatusOperator)
+        .unwrap();
+    assert_eq!(admin_registry.members[0].admin, Pubkey::default());
+    assert!(admin_registry
+        .remove_role(operator, AdminRole::PoolStatusOperator)
+        .is_err());
+}
+
+#[test]
+fn test_admin_registry_full() {
+    let mut admin_registry = get_admin_registry(Pubkey::new_unique());
+    for _ in 0..MAX_ADMIN_REGISTRY_MEMBERS {
+        admin_registry
+            .add_role(Pubkey::new_unique(), AdminRole::RewardManager)
+            .unwrap();
+    }
+    assert!(admin_registry
+        .add_role(Pubkey::new_unique(), AdminRole::RewardManager)
+        .is_err());
+}
+
+#[test]
+fn test_admin_registry_timelock() {
+    let mut admin_registry = get_admin_registry(Pubkey::new_unique());
+    assert!(!admin_registry.is_timelock_enabled());
+
+    // invalid activation type
+    assert!(admin_registry.update_timelock(2, 10).is_err());
+    assert!(admin_registry
+        .update_timelock(0, MAX_ACTIVATION_SLOT_DURATION + 1)
+        .is_err());
+
+    admin_registry
+        .update_timelock(0, MAX_ACTIVATION_SLOT_DURATION)
+        .unwrap();
+    assert!(admin_registry.is_timelock_enabled());
+
+    admin_registry.update_timelock(1, 0).unwrap();
+    assert!(!admin_registry.is_timelock_enabled());
+}
+
+#[test]
+fn test_proposal_action_data() {
+    let set_pool_status = ProposalAction::SetPoolStatus;
+    assert_eq!(
+        set_pool_status.get_admin_role(),
+        AdminRole::PoolStatusOperator
+    );
+    assert!(set_pool_status.validate_action_data(&[1]).is_ok());
+    assert!(set_pool_status.validate_action_data(&[2]).is_err());
+    assert!(set_pool_status.validate_action_data(&[]).is_err());
+
+    let close_config = ProposalAction::CloseConfig;
+    assert_eq!(close_config.get_admin_role(), AdminRole::ConfigManager);
+    assert!(close_config.validate_action_data(&[]).is_ok());
+    assert!(close_config.validate_action_data(&[0]).is_err());
+
+    let set_pool_disabled_actions = ProposalAction::SetPoolDisabledActions;
+    assert_eq!(
+        set_pool_disabled_actions.get_admin_role(),
+        AdminRole::PoolStatusOperator
+    );
+    let get_action_data = |disabled_actions: u8, reason: String| {
+        let mut action_data = vec![];
+        (disabled_actions, reason)
+            .serialize(&mut action_data)
+            .unwrap();
+        action_data
+    };
+    assert!(set_pool_disabled_actions
+        .validate_action_data(&get_action_data(0b1, "incident".to_string()))
+        .is_ok());
+    assert!(set_pool_disabled_actions
+        .validate_action_data(&get_action_data(0b1000_0000, String::new()))
+        .is_err());
+    assert!(set_pool_disabled_actions
+        .validate_action_data(&get_action_data(
+            0b1,
+            "a".repeat(MAX_DISABLE_REASON_LENGTH + 1)
+        ))
+        .is_err());
+    assert!(set_pool_disabled_actions
+        .validate_action_data(&[0b1])
+        .is_err());
+}
+
+#[test]
+fn test_static_config_parameters_fit_in_action_data() {
+    let config_parameters = StaticConfigParameters {
+        pool_fees: PoolFeeParameters {
+            dynamic_fee: Some(DynamicFeeParameters::default()),
+            ..Default::default()
+        },
+        sqrt_min_price: 0,
+        sqrt_max_price: 0,
+        vault_config_key: Pubkey::default(),
+        pool_creator_authority: Pubkey::default(),
+        activation_type: 0,
+        collect_fee_mode: 0,
+    };
+    let mut action_data = vec![];
+    config_parameters.serialize(&mut action_data).unwrap();
+    assert!(action_data.len() <= PROPOSAL_ACTION_DATA_SIZE);
+
+    assert_eq!(
+        ProposalAction::UpdateConfig.get_admin_role(),
+        AdminRole::ConfigManager
+    );
+}
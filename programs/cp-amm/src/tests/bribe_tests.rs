@@ -0,0 +1,190 @@
+use ruint::aliases::U256;
+
+use crate::{
+    constants::{BRIBE_EPOCH_DURATION, NUM_BRIBE_EPOCH_SNAPSHOTS},
+    state::{get_bribe_epoch, BribeCampaign, BribeLedger, PositionBribeLedger},
+};
+
+const EPOCH: u64 = 100;
+const EPOCH_START: u64 = EPOCH * BRIBE_EPOCH_DURATION;
+
+fn get_bribe_campaign(epoch: u64, total_amount: u64) -> BribeCampaign {
+    BribeCampaign {
+        epoch,
+        total_amount,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bribe_pro_rata_liquidity_seconds() {
+    let mut bribe_ledger = BribeLedger::default();
+    // ledger is created in the previous epoch, so the whole epoch is tracked
+    bribe_ledger.initialize(Default::default(), EPOCH_START - 1);
+
+    let mut ledger_a = PositionBribeLedger::default();
+    let mut ledger_b = PositionBribeLedger::default();
+
+    bribe_ledger.update(EPOCH_START - 1).unwrap();
+    ledger_a.initialize(Default::default(), 100, &bribe_ledger, EPOCH_START - 1);
+    bribe_ledger.add_enrolled_liquidity(100).unwrap();
+
+    // b enrolls with 3x liquidity in the middle of the epoch
+    let half_epoch = EPOCH_START + BRIBE_EPOCH_DURATION / 2;
+    bribe_ledger.update(half_epoch).unwrap();
+    ledger_a.update(&bribe_ledger, half_epoch).unwrap();
+    ledger_b.initialize(Default::default(), 300, &bribe_ledger, half_epoch);
+    bribe_ledger.add_enrolled_liquidity(300).unwrap();
+
+    let epoch_end = EPOCH_START + BRIBE_EPOCH_DURATION;
+    bribe_ledger.update(epoch_end).unwrap();
+    ledger_a.update(&bribe_ledger, epoch_end).unwrap();
+    ledger_b.update(&bribe_ledger, epoch_end).unwrap();
+
+    let total_amount = 1_000_000;
+    let bribe_campaign = get_bribe_campaign(EPOCH, total_amount);
+
+    // a gets all of the first half and 1/4 of the second half
+    let claim_a = bribe_campaign
+        .get_claimable_amount(ledger_a.get_epoch_liquidity_seconds(EPOCH).unwrap())
+        .unwrap();
+    // b gets 3/4 of the second half
+    let claim_b = bribe_campaign
+        .get_claimable_amount(ledger_b.get_epoch_liquidity_seconds(EPOCH).unwrap())
+        .unwrap();
+
+    assert!((625_000 - 1..=625_000).contains(&claim_a));
+    assert!((375_000 - 1..=375_000).contains(&claim_b));
+    assert!(claim_a + claim_b <= total_amount);
+
+    assert_eq!(bribe_ledger.get_snapshot(EPOCH).unwrap().empty_seconds, 0);
+}
+
+#[test]
+fn test_bribe_empty_seconds_refund() {
+    let mut bribe_ledger = BribeLedger::default();
+    bribe_ledger.initialize(Default::default(), EPOCH_START - 1);
+
+    // nothing enrolled for the first quarter of the epoch
+    let quarter_epoch = EPOCH_START + BRIBE_EPOCH_DURATION / 4;
+    bribe_ledger.update(quarter_epoch).unwrap();
+    let mut position_bribe_ledger = PositionBribeLedger::default();
+    position_bribe_ledger.initialize(Default::default(), 1_000, &bribe_ledger, quarter_epoch);
+    bribe_ledger.add_enrolled_liquidity(1_000).unwrap();
+
+    let epoch_end = EPOCH_START + BRIBE_EPOCH_DURATION;
+    bribe_ledger.update(epoch_end).unwrap();
+    position_bribe_ledger
+        .update(&bribe_ledger, epoch_end)
+        .unwrap();
+
+    let total_amount = 4_000_000;
+    let bribe_campaign = get_bribe_campaign(EPOCH, total_amount);
+    let empty_seconds = bribe_ledger.get_snapshot(EPOCH).unwrap().empty_seconds;
+    assert_eq!(empty_seconds, BRIBE_EPOCH_DURATION / 4);
+
+    let ineligible_amount = bribe_campaign.get_ineligible_amount(empty_seconds).unwrap();
+    assert_eq!(ineligible_amount, 1_000_000);
+
+    let claimable_amount = bribe_campaign
+        .get_claimable_amount(
+            position_bribe_ledger
+                .get_epoch_liquidity_seconds(EPOCH)
+                .unwrap(),
+        )
+        .unwrap();
+    assert!((3_000_000 - 1..=3_000_000).contains(&claimable_amount));
+    assert!(claimable_amount + ineligible_amount <= total_amount);
+}
+
+#[test]
+fn test_bribe_snapshots_after_long_inactivity() {
+    let mut bribe_ledger = BribeLedger::default();
+    bribe_ledger.initialize(Default::default(), EPOCH_START);
+    let mut position_bribe_ledger = PositionBribeLedger::default();
+    position_bribe_ledger.initialize(Default::default(), 1_000, &bribe_ledger, EPOCH_START);
+    bribe_ledger.add_enrolled_liquidity(1_000).unwrap();
+
+    // no update for much longer than the snapshot window
+    let current_epoch = EPOCH + 5 * NUM_BRIBE_EPOCH_SNAPSHOTS as u64;
+    let current_time = current_epoch * BRIBE_EPOCH_DURATION + 10;
+    bribe_ledger.update(current_time).unwrap();
+    position_bribe_ledger
+        .update(&bribe_ledger, current_time)
+        .unwrap();
+    assert_eq!(
+        get_bribe_epoch(bribe_ledger.last_update_time),
+        current_epoch
+    );
+
+    // evicted epochs are not available anymore
+    let first_kept_epoch = current_epoch - NUM_BRIBE_EPOCH_SNAPSHOTS as u64;
+    assert!(bribe_ledger.get_snapshot(first_kept_epoch - 1).is_err());
+    assert!(position_bribe_ledger
+        .get_epoch_liquidity_seconds(first_kept_epoch)
+        .is_err());
+
+    // every kept epoch is fully owned by the only enrolled position
+    let bribe_campaign = get_bribe_campaign(first_kept_epoch + 1, 1_000_000);
+    for epoch in first_kept_epoch + 1..current_epoch {
+        assert_eq!(bribe_ledger.get_snapshot(epoch).unwrap().empty_seconds, 0);
+        let claimable_amount = bribe_campaign
+            .get_claimable_amount(
+                position_bribe_ledger
+                    .get_epoch_liquidity_seconds(epoch)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!((1_000_000 - 1..=1_000_000).contains(&claimable_amount));
+    }
+
+    // current epoch has not ended
+    assert!(bribe_ledger.get_snapshot(current_epoch).is_err());
+}
+
+#[test]
+fn test_bribe_position_earns_from_creation() {
+    let mut bribe_ledger = BribeLedger::default();
+    bribe_ledger.initialize(Default::default(), EPOCH_START - 1);
+
+    let mut ledger_a = PositionBribeLedger::default();
+    ledger_a.initialize(Default::default(), 100, &bribe_ledger, EPOCH_START - 1);
+    bribe_ledger.add_enrolled_liquidity(100).unwrap();
+
+    // b enrolls in the next epoch, it can't claim campaigns of earlier epochs
+    let next_epoch_start = EPOCH_START + BRIBE_EPOCH_DURATION;
+    bribe_ledger.update(next_epoch_start).unwrap();
+    let mut ledger_b = PositionBribeLedger::default();
+    ledger_b.initialize(Default::default(), 100, &bribe_ledger, next_epoch_start);
+    bribe_ledger.add_enrolled_liquidity(100).unwrap();
+
+    let current_time = next_epoch_start + BRIBE_EPOCH_DURATION;
+    bribe_ledger.update(current_time).unwrap();
+    ledger_a.update(&bribe_ledger, current_time).unwrap();
+    ledger_b.update(&bribe_ledger, current_time).unwrap();
+
+    assert!(ledger_b.get_epoch_liquidity_seconds(EPOCH).is_err());
+    assert_eq!(
+        ledger_a.get_epoch_liquidity_seconds(EPOCH + 1).unwrap(),
+        ledger_b.get_epoch_liquidity_seconds(EPOCH + 1).unwrap()
+    );
+}
+
+#[test]
+fn test_bribe_claimable_amount_bounded() {
+    let bribe_campaign = get_bribe_campaign(EPOCH, u64::MAX);
+    let full_epoch_liquidity_seconds =
+        U256::from(BRIBE_EPOCH_DURATION) << crate::constants::LIQUIDITY_SCALE;
+    assert_eq!(
+        bribe_campaign
+            .get_claimable_amount(full_epoch_liquidity_seconds)
+            .unwrap(),
+        u64::MAX
+    );
+    assert_eq!(
+        bribe_campaign
+            .get_ineligible_amount(BRIBE_EPOCH_DURATION)
+            .unwrap(),
+        u64::MAX
+    );
+}
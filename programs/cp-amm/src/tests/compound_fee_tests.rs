@@ -0,0 +1,99 @@
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::swap::TradeDirection,
+    state::Pool,
+    u128x128_math::Rounding,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 10000, .. ProptestConfig::default()
+    })]
+    #[test]
+    fn test_compound_liquidity_wont_exceed_fee(
+        sqrt_price in MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+        fee_a in 0..=u64::MAX,
+        fee_b in 0..=u64::MAX,
+    ) {
+        let pool = Pool {
+            sqrt_price,
+            sqrt_min_price: MIN_SQRT_PRICE,
+            sqrt_max_price: MAX_SQRT_PRICE,
+            ..Default::default()
+        };
+
+        if let Ok(liquidity_delta) = pool.get_liquidity_from_amounts(fee_a, fee_b) {
+            if let Ok(result) = pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up) {
+                assert!(result.token_a_amount <= fee_a);
+                assert!(result.token_b_amount <= fee_b);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_compound_liquidity_at_price_bound() {
+    let pool = Pool {
+        sqrt_price: MIN_SQRT_PRICE,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        ..Default::default()
+    };
+    // only token a is needed at min price
+    let liquidity_delta = pool.get_liquidity_from_amounts(1_000_000, 0).unwrap();
+    assert!(liquidity_delta > 0);
+    assert!(pool
+        .get_compound_swap_amount(1_000_000, 0)
+        .unwrap()
+        .is_none());
+
+    let pool = Pool {
+        sqrt_price: MAX_SQRT_PRICE,
+        ..pool
+    };
+    // only token b is needed at max price
+    let liquidity_delta = pool.get_liquidity_from_amounts(0, 1_000_000).unwrap();
+    assert!(liquidity_delta > 0);
+    assert!(pool
+        .get_compound_swap_amount(0, 1_000_000)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_compound_swap_amount() {
+    // price = 1, range [0.25, 4]
+    let pool = Pool {
+        sqrt_price: 1u128 << 64,
+        sqrt_min_price: 1u128 << 63,
+        sqrt_max_price: 1u128 << 65,
+        ..Default::default()
+    };
+
+    // balanced amounts, nothing to swap
+    let liquidity_delta = 1_000_000_000u128 << 64;
+    let result = pool
+        .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)
+        .unwrap();
+    let swap = pool
+        .get_compound_swap_amount(result.token_a_amount, result.token_b_amount)
+        .unwrap();
+    assert!(swap.is_none_or(|(_, amount)| amount <= 1));
+
+    // only token a, half of it should be swapped as the range is symmetric around the price
+    let (trade_direction, amount) = pool
+        .get_compound_swap_amount(1_000_000, 0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(trade_direction, TradeDirection::AtoB);
+    assert_eq!(amount, 500_000);
+
+    // only token b
+    let (trade_direction, amount) = pool
+        .get_compound_swap_amount(0, 1_000_000)
+        .unwrap()
+        .unwrap();
+    assert_eq!(trade_direction, TradeDirection::BtoA);
+    assert_eq!(amount, 500_000);
+}
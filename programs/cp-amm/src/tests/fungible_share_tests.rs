@@ -0,0 +1,124 @@
+use crate::{
+    constants::ONE_Q64,
+    state::{get_liquidity_from_shares, get_pending_fee_from_shares, get_shares_from_liquidity},
+    u128x128_math::Rounding,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 10000, .. ProptestConfig::default()
+    })]
+    #[test]
+    fn test_withdrawn_liquidity_wont_exceed_deposited(
+        share_supply in 1..=u64::MAX / 2,
+        shared_liquidity in 1..=u128::MAX >> 32,
+        liquidity_delta in 1..=u128::MAX >> 32,
+    ) {
+        if let Ok(shares) = get_shares_from_liquidity(liquidity_delta, share_supply, shared_liquidity) {
+            // share mint supply can't exceed u64::MAX
+            if let Some(new_share_supply) = share_supply.checked_add(shares) {
+                if shares > 0 {
+                    let withdrawn_liquidity = get_liquidity_from_shares(
+                        shares,
+                        new_share_supply,
+                        shared_liquidity + liquidity_delta,
+                    )
+                    .unwrap();
+                    assert!(withdrawn_liquidity <= liquidity_delta);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_first_deposit_shares() {
+    assert_eq!(
+        get_shares_from_liquidity(ONE_Q64 * 1_000, 0, 0).unwrap(),
+        1_000
+    );
+    // liquidity left in the shared position doesn't inflate the price of first shares
+    assert_eq!(
+        get_shares_from_liquidity(ONE_Q64 * 1_000, 0, ONE_Q64 * 1_000_000).unwrap(),
+        0
+    );
+    assert_eq!(
+        get_shares_from_liquidity(ONE_Q64 * 1_000, 0, 100).unwrap(),
+        999
+    );
+    // less than 1 share
+    assert_eq!(get_shares_from_liquidity(ONE_Q64 - 1, 0, 0).unwrap(), 0);
+}
+
+#[test]
+fn test_shares_follow_compounded_liquidity() {
+    let share_supply = 1_000;
+    let shared_liquidity = ONE_Q64 * 1_000;
+
+    assert_eq!(
+        get_shares_from_liquidity(ONE_Q64 * 500, share_supply, shared_liquidity).unwrap(),
+        500
+    );
+
+    // compounded fee doubles the liquidity of every share, including the virtual shares
+    let shared_liquidity = shared_liquidity * 3;
+    assert_eq!(
+        get_shares_from_liquidity(ONE_Q64 * 500, share_supply, shared_liquidity).unwrap(),
+        250
+    );
+    assert_eq!(
+        get_liquidity_from_shares(250, share_supply, shared_liquidity).unwrap(),
+        ONE_Q64 * 500
+    );
+}
+
+#[test]
+fn test_withdraw_shares_exceeding_supply() {
+    assert!(get_liquidity_from_shares(0, 1_000, ONE_Q64).is_err());
+    assert!(get_liquidity_from_shares(1_001, 1_000, ONE_Q64).is_err());
+    assert_eq!(
+        get_liquidity_from_shares(1_000, 1_000, ONE_Q64).unwrap(),
+        ONE_Q64
+    );
+}
+
+#[test]
+fn test_last_holder_withdraws_all_shared_liquidity() {
+    let share_supply = 1_000;
+    let shared_liquidity = ONE_Q64 * 3_000;
+    // part of compounded liquidity backs virtual shares
+    assert_eq!(
+        get_liquidity_from_shares(999, share_supply, shared_liquidity).unwrap(),
+        ONE_Q64 * 1_998
+    );
+    assert_eq!(
+        get_liquidity_from_shares(share_supply, share_supply, shared_liquidity).unwrap(),
+        shared_liquidity
+    );
+    assert_eq!(
+        get_liquidity_from_shares(1, 1, ONE_Q64 * 1_000 + 1).unwrap(),
+        ONE_Q64 * 1_000 + 1
+    );
+}
+
+#[test]
+fn test_pending_fee_from_shares() {
+    assert_eq!(
+        get_pending_fee_from_shares(1_000, 100, 0, Rounding::Up).unwrap(),
+        0
+    );
+    // depositor pays rounded up, withdrawer receives rounded down
+    assert_eq!(
+        get_pending_fee_from_shares(1_000, 1, 3, Rounding::Up).unwrap(),
+        334
+    );
+    assert_eq!(
+        get_pending_fee_from_shares(1_000, 1, 3, Rounding::Down).unwrap(),
+        333
+    );
+    assert_eq!(
+        get_pending_fee_from_shares(1_000, 3, 3, Rounding::Down).unwrap(),
+        1_000
+    );
+}
@@ -213,7 +213,8 @@ fn execute_swap_liquidity(
         .get_swap_result_from_exact_input(amount_in, fee_mode, trade_direction, 0)
         .unwrap();
 
-    pool.apply_swap_result(&swap_result, fee_mode, 0).unwrap();
+    pool.apply_swap_result(&swap_result, fee_mode, 0, None)
+        .unwrap();
 
     match trade_direction {
         TradeDirection::AtoB => {
@@ -25,6 +25,9 @@ fn config_account_layout_backward_compatible() {
         period_to_bytes,
         config_state.pool_fees.base_fee.second_factor,
     );
+
+    // version is carved out of the zeroed padding
+    assert_eq!(config_state.version, 0);
 }
 
 #[test]
@@ -49,4 +52,11 @@ fn pool_account_layout_backward_compatible() {
 
     let period_to_bytes = period_frequency.to_le_bytes();
     assert_eq!(period_to_bytes, pool_state.pool_fees.base_fee.second_factor,);
+
+    // disabled actions is carved out of the zeroed padding
+    assert_eq!(pool_state.disabled_actions, 0);
+    assert_eq!(pool_state.liquidity_scale_factor, 0);
+    assert_eq!(pool_state.locked_fee_extension_flag, 0);
+    assert_eq!(pool_state.fungible_share_flag, 0);
+    assert_eq!(pool_state.bribe_ledger_flag, 0);
 }
@@ -0,0 +1,134 @@
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, LockedFeeExtension, Pool, Position, SwapResult2},
+};
+
+fn get_swap_result(pool: &Pool, lp_fee: u64) -> SwapResult2 {
+    SwapResult2 {
+        included_fee_input_amount: 0,
+        excluded_fee_input_amount: 0,
+        amount_left: 0,
+        output_amount: 0,
+        next_sqrt_price: pool.sqrt_price,
+        trading_fee: lp_fee,
+        protocol_fee: 0,
+        partner_fee: 0,
+        referral_fee: 0,
+    }
+}
+
+fn get_pool(liquidity: u128, permanent_lock_liquidity: u128) -> Pool {
+    Pool {
+        liquidity,
+        permanent_lock_liquidity,
+        sqrt_price: 1u128 << 64,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        locked_fee_extension_flag: 1,
+        ..Default::default()
+    }
+}
+
+fn settle_position_fee(
+    pool: &Pool,
+    locked_fee_extension: &LockedFeeExtension,
+    position: &mut Position,
+) -> u64 {
+    let locked_liquidity = position
+        .get_fee_share_locked_liquidity(pool.is_vesting_liquidity_tracked())
+        .unwrap();
+    position
+        .update_locked_fee(
+            locked_fee_extension.fee_a_per_locked_liquidity(),
+            locked_fee_extension.fee_b_per_locked_liquidity(),
+            locked_liquidity,
+        )
+        .unwrap();
+    position
+        .update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())
+        .unwrap();
+    position.fee_a_pending + position.fee_b_pending
+}
+
+#[test]
+fn test_locked_fee_share_distribution() {
+    let liquidity = 1u128 << 64;
+    let lp_fee = 1_000_000;
+    let mut pool = get_pool(liquidity * 2, liquidity);
+    let mut locked_fee_extension = LockedFeeExtension::default();
+    locked_fee_extension.initialize(Default::default(), 2_000);
+
+    let mut locked_position = Position {
+        permanent_locked_liquidity: liquidity,
+        ..Default::default()
+    };
+    let mut unlocked_position = Position {
+        unlocked_liquidity: liquidity,
+        ..Default::default()
+    };
+
+    let fee_mode =
+        FeeMode::get_fee_mode(pool.collect_fee_mode, TradeDirection::AtoB, false).unwrap();
+    let swap_result = get_swap_result(&pool, lp_fee);
+    pool.apply_swap_result(&swap_result, &fee_mode, 0, Some(&mut locked_fee_extension))
+        .unwrap();
+
+    assert_eq!(
+        locked_fee_extension.total_locked_a_fee + locked_fee_extension.total_locked_b_fee,
+        200_000
+    );
+
+    let locked_fee = settle_position_fee(&pool, &locked_fee_extension, &mut locked_position);
+    let unlocked_fee = settle_position_fee(&pool, &locked_fee_extension, &mut unlocked_position);
+
+    // rounding down loss is negligible
+    assert!((599_998..=600_000).contains(&locked_fee));
+    assert!((399_999..=400_000).contains(&unlocked_fee));
+    assert!(locked_fee + unlocked_fee <= lp_fee);
+
+    // settled again without new fee, nothing is added
+    assert_eq!(
+        settle_position_fee(&pool, &locked_fee_extension, &mut locked_position),
+        locked_fee
+    );
+}
+
+#[test]
+fn test_locked_fee_share_without_locked_liquidity() {
+    let liquidity = 1u128 << 64;
+    let lp_fee = 1_000_000;
+    let mut pool = get_pool(liquidity, 0);
+    let mut locked_fee_extension = LockedFeeExtension::default();
+    locked_fee_extension.initialize(Default::default(), 2_000);
+
+    let mut position = Position {
+        unlocked_liquidity: liquidity,
+        ..Default::default()
+    };
+
+    let fee_mode =
+        FeeMode::get_fee_mode(pool.collect_fee_mode, TradeDirection::AtoB, false).unwrap();
+    let swap_result = get_swap_result(&pool, lp_fee);
+    pool.apply_swap_result(&swap_result, &fee_mode, 0, Some(&mut locked_fee_extension))
+        .unwrap();
+
+    // whole LP fee is shared by all liquidity
+    assert_eq!(locked_fee_extension.total_locked_a_fee, 0);
+    assert_eq!(locked_fee_extension.total_locked_b_fee, 0);
+    let fee = settle_position_fee(&pool, &locked_fee_extension, &mut position);
+    assert!(fee <= lp_fee && fee >= lp_fee - 1);
+}
+
+#[test]
+fn test_locked_fee_extension_required() {
+    let liquidity = 1u128 << 64;
+    let mut pool = get_pool(liquidity * 2, liquidity);
+
+    let fee_mode =
+        FeeMode::get_fee_mode(pool.collect_fee_mode, TradeDirection::AtoB, false).unwrap();
+    let swap_result = get_swap_result(&pool, 1_000_000);
+    assert!(pool
+        .apply_swap_result(&swap_result, &fee_mode, 0, None)
+        .is_err());
+}
@@ -7,6 +7,9 @@ mod swap_tests;
 #[cfg(test)]
 mod modify_liquidity_tests;
 
+#[cfg(test)]
+mod compound_fee_tests;
+
 #[cfg(test)]
 mod overflow_tests;
 
@@ -22,6 +25,12 @@ mod price_math;
 #[cfg(test)]
 mod reward_tests;
 
+#[cfg(test)]
+mod reward_extension_tests;
+
+#[cfg(test)]
+mod bribe_tests;
+
 #[cfg(test)]
 mod fee_scheduler_tests;
 
@@ -33,3 +42,24 @@ mod test_rate_limiter;
 
 #[cfg(test)]
 mod layout_test;
+
+#[cfg(test)]
+mod admin_registry_tests;
+
+#[cfg(test)]
+mod price_range_tests;
+
+#[cfg(test)]
+mod position_nft_metadata_tests;
+
+#[cfg(test)]
+mod locked_fee_extension_tests;
+
+#[cfg(test)]
+mod fungible_share_tests;
+
+#[cfg(test)]
+mod protocol_fee_routing_tests;
+
+#[cfg(test)]
+mod token_badge_tests;
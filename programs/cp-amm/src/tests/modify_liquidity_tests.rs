@@ -44,3 +44,40 @@ proptest! {
         assert!(result_0.token_b_amount >= result_1.token_b_amount);
     }
 }
+
+#[test]
+fn test_pending_withdrawal_of_frozen_token() {
+    let mut pool = Pool {
+        sqrt_price: MIN_SQRT_PRICE * 2,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        ..Default::default()
+    };
+    let mut position = Position::default();
+    let liquidity_delta = LIQUIDITY_MAX;
+
+    pool.apply_add_liquidity(&mut position, liquidity_delta)
+        .unwrap();
+    let result = pool
+        .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Down)
+        .unwrap();
+    pool.apply_remove_liquidity(&mut position, liquidity_delta)
+        .unwrap();
+
+    // token a is frozen, only token b is transferred
+    position
+        .add_pending_withdrawal(result.token_a_amount, 0)
+        .unwrap();
+    assert!(!position.is_empty().unwrap());
+
+    // can't claim more than pending
+    assert!(position
+        .claim_pending_withdrawal(result.token_a_amount + 1, 0)
+        .is_err());
+
+    position
+        .claim_pending_withdrawal(result.token_a_amount, 0)
+        .unwrap();
+    assert_eq!(position.pending_withdrawal_a, 0);
+    assert!(position.is_empty().unwrap());
+}
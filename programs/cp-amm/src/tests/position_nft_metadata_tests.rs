@@ -0,0 +1,78 @@
+use anchor_lang::prelude::Pubkey;
+use spl_token_metadata_interface::state::Field;
+
+use crate::{
+    position_nft_metadata::{
+        get_pool_metadata_fields, get_position_metadata_fields, get_position_nft_uri,
+    },
+    state::{Pool, Position, Vesting},
+};
+
+fn get_field(fields: &[(Field, String)], key: &str) -> String {
+    fields
+        .iter()
+        .find(|(field, _)| *field == Field::Key(key.to_string()))
+        .map(|(_, value)| value.clone())
+        .unwrap()
+}
+
+#[test]
+fn test_position_nft_uri() {
+    let mint = Pubkey::new_unique();
+    let uri = get_position_nft_uri(mint);
+    assert!(uri.ends_with(&format!("/{}.json", mint)));
+    assert!(!uri.contains("{mint}"));
+}
+
+#[test]
+fn test_pool_metadata_fields() {
+    let mut pool = Pool {
+        token_a_mint: Pubkey::new_unique(),
+        token_b_mint: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    // 0.25%
+    pool.pool_fees.base_fee.cliff_fee_numerator = 2_500_000;
+
+    let pool_key = Pubkey::new_unique();
+    let fields = get_pool_metadata_fields(pool_key, &pool).unwrap();
+    assert_eq!(get_field(&fields, "pool"), pool_key.to_string());
+    assert_eq!(
+        get_field(&fields, "token_a_mint"),
+        pool.token_a_mint.to_string()
+    );
+    assert_eq!(
+        get_field(&fields, "token_b_mint"),
+        pool.token_b_mint.to_string()
+    );
+    assert_eq!(get_field(&fields, "fee_bps"), "25");
+}
+
+#[test]
+fn test_position_metadata_fields() {
+    let position = Position {
+        unlocked_liquidity: 100,
+        vested_liquidity: 20,
+        permanent_locked_liquidity: 3,
+        ..Default::default()
+    };
+    let vesting = Vesting {
+        cliff_point: 1_000,
+        period_frequency: 10,
+        number_of_period: 2,
+        cliff_unlock_liquidity: 10,
+        liquidity_per_period: 5,
+        total_released_liquidity: 0,
+        ..Default::default()
+    };
+    assert_eq!(vesting.get_locked_liquidity().unwrap(), 20);
+    let vesting_end_point = vesting.get_end_point().unwrap();
+    assert_eq!(vesting_end_point, 1_020);
+
+    let fields = get_position_metadata_fields(&position, vesting_end_point).unwrap();
+    assert_eq!(get_field(&fields, "liquidity"), "123");
+    assert_eq!(get_field(&fields, "unlocked_liquidity"), "100");
+    assert_eq!(get_field(&fields, "vested_liquidity"), "20");
+    assert_eq!(get_field(&fields, "permanent_locked_liquidity"), "3");
+    assert_eq!(get_field(&fields, "vesting_end_point"), "1020");
+}
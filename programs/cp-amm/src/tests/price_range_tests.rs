@@ -0,0 +1,176 @@
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::swap::TradeDirection,
+    state::{fee::FeeMode, Pool, Position},
+    tests::LIQUIDITY_MAX,
+    u128x128_math::Rounding,
+};
+use proptest::prelude::*;
+
+fn new_pool(sqrt_price: u128, liquidity: u128) -> Pool {
+    Pool {
+        sqrt_price,
+        sqrt_min_price: MIN_SQRT_PRICE,
+        sqrt_max_price: MAX_SQRT_PRICE,
+        liquidity,
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 10000, .. ProptestConfig::default()
+    })]
+    #[test]
+    fn test_update_price_range_wont_exceed_reserves(
+        sqrt_price in MIN_SQRT_PRICE * 2..=MAX_SQRT_PRICE / 2,
+        liquidity in 1u128 << 64..=LIQUIDITY_MAX,
+        lower_divisor in 1u128..=1_000,
+        upper_multiplier in 1u128..=1_000,
+    ) {
+        let mut pool = new_pool(sqrt_price, liquidity);
+        let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount().unwrap();
+
+        let sqrt_min_price = (sqrt_price - sqrt_price / (lower_divisor + 1)).max(MIN_SQRT_PRICE);
+        let sqrt_max_price = (sqrt_price + sqrt_price / 1_000 * upper_multiplier).min(MAX_SQRT_PRICE);
+        if let Ok((excess_a_amount, excess_b_amount)) =
+            pool.update_price_range(sqrt_min_price, sqrt_max_price)
+        {
+            let (new_reserve_a_amount, new_reserve_b_amount) = pool.get_reserves_amount().unwrap();
+            assert_eq!(new_reserve_a_amount + excess_a_amount, reserve_a_amount);
+            assert_eq!(new_reserve_b_amount + excess_b_amount, reserve_b_amount);
+            assert_eq!(pool.liquidity, liquidity);
+        }
+    }
+
+    #[test]
+    fn test_modify_liquidity_after_update_price_range_wont_loss(
+        liquidity_delta in 1..=LIQUIDITY_MAX / 4,
+    ) {
+        let mut pool = new_pool(1u128 << 64, 1_000_000_000u128 << 64);
+        pool.update_price_range(1u128 << 63, 1u128 << 65).unwrap();
+        let mut position = Position::default();
+
+        let result_0 = pool
+            .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)
+            .unwrap();
+        pool.apply_add_liquidity(&mut position, liquidity_delta).unwrap();
+
+        let result_1 = pool
+            .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Down)
+            .unwrap();
+        pool.apply_remove_liquidity(&mut position, liquidity_delta).unwrap();
+
+        assert!(result_0.token_a_amount >= result_1.token_a_amount);
+        assert!(result_0.token_b_amount >= result_1.token_b_amount);
+    }
+}
+
+#[test]
+fn test_update_price_range_symmetric() {
+    let liquidity = 1_000_000_000u128 << 64;
+    let mut pool = new_pool(1u128 << 64, liquidity);
+    let (reserve_a_amount, reserve_b_amount) = pool.get_reserves_amount().unwrap();
+
+    // price range is narrowed from [MIN, MAX] to [0.25, 4]
+    let (excess_a_amount, excess_b_amount) =
+        pool.update_price_range(1u128 << 63, 1u128 << 65).unwrap();
+
+    // curve liquidity is doubled, position liquidity is kept
+    assert_eq!(pool.liquidity, liquidity);
+    let curve_liquidity = pool.get_curve_liquidity().unwrap();
+    assert!(curve_liquidity > liquidity * 199 / 100 && curve_liquidity <= liquidity * 2);
+
+    let (new_reserve_a_amount, new_reserve_b_amount) = pool.get_reserves_amount().unwrap();
+    assert!(excess_a_amount <= 1 && excess_b_amount <= 1);
+    assert_eq!(new_reserve_a_amount + excess_a_amount, reserve_a_amount);
+    assert_eq!(new_reserve_b_amount + excess_b_amount, reserve_b_amount);
+}
+
+#[test]
+fn test_update_price_range_rejects_excess_reserve() {
+    let liquidity = 1_000_000_000u128 << 64;
+    let mut pool = new_pool(1u128 << 64, liquidity);
+    let fee_b_per_liquidity = pool.fee_b_per_liquidity();
+
+    // only lower price is raised, token b can't be fully used
+    assert!(pool
+        .update_price_range(1u128 << 63, MAX_SQRT_PRICE)
+        .is_err());
+
+    // principal isn't turned into fee by repeatedly narrowing the range
+    let mut pool = new_pool(1u128 << 64, liquidity);
+    pool.update_price_range(1u128 << 63, 1u128 << 65).unwrap();
+    assert!(pool.update_price_range(3u128 << 62, 1u128 << 65).is_err());
+    assert_eq!(pool.fee_b_per_liquidity(), fee_b_per_liquidity);
+}
+
+#[test]
+fn test_update_price_range_swap_uses_curve_liquidity() {
+    let liquidity = 1_000_000_000u128 << 64;
+    let mut pool = new_pool(1u128 << 64, liquidity);
+    let fee_mode =
+        &FeeMode::get_fee_mode(pool.collect_fee_mode, TradeDirection::BtoA, false).unwrap();
+    let wide_range_result = pool
+        .get_swap_result_from_exact_input(1_000_000, fee_mode, TradeDirection::BtoA, 0)
+        .unwrap();
+
+    pool.update_price_range(1u128 << 63, 1u128 << 65).unwrap();
+    let narrow_range_result = pool
+        .get_swap_result_from_exact_input(1_000_000, fee_mode, TradeDirection::BtoA, 0)
+        .unwrap();
+
+    // deeper liquidity, less price impact
+    assert!(narrow_range_result.output_amount > wide_range_result.output_amount);
+    assert!(narrow_range_result.next_sqrt_price < wide_range_result.next_sqrt_price);
+}
+
+#[test]
+fn test_update_price_range_compound_liquidity_is_rescaled() {
+    let mut pool = new_pool(1u128 << 64, 1_000_000_000u128 << 64);
+    pool.update_price_range(1u128 << 63, 1u128 << 65).unwrap();
+
+    let liquidity_delta = pool
+        .get_liquidity_from_amounts(1_000_000, 1_000_000)
+        .unwrap();
+    let result = pool
+        .get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)
+        .unwrap();
+    assert!(result.token_a_amount <= 1_000_000);
+    assert!(result.token_b_amount <= 1_000_000);
+    // twice the amount is needed for the same liquidity in the wide range
+    assert!(liquidity_delta > (1_000_000u128 << 64) * 99 / 100);
+}
+
+#[test]
+fn test_update_price_range_validation() {
+    let mut pool = new_pool(1u128 << 64, 1_000_000_000u128 << 64);
+
+    // current price is out of the range
+    assert!(pool.update_price_range(1u128 << 65, 1u128 << 66).is_err());
+    // unchanged
+    assert!(pool
+        .update_price_range(MIN_SQRT_PRICE, MAX_SQRT_PRICE)
+        .is_err());
+    // out of bound
+    assert!(pool
+        .update_price_range(MIN_SQRT_PRICE - 1, MAX_SQRT_PRICE)
+        .is_err());
+    assert!(pool.update_price_range(1u128 << 64, 1u128 << 64).is_err());
+
+    // token a reserve is empty, upper price can't be raised
+    let mut pool = Pool {
+        sqrt_max_price: 1u128 << 64,
+        ..new_pool(1u128 << 64, 1_000_000_000u128 << 64)
+    };
+    assert_eq!(pool.get_reserves_amount().unwrap().0, 0);
+    assert!(pool
+        .update_price_range(MIN_SQRT_PRICE, 1u128 << 65)
+        .is_err());
+    // lower price can still be raised
+    let mut pool = Pool {
+        sqrt_max_price: 1u128 << 64,
+        ..new_pool(1u128 << 64, 1_000_000_000u128 << 64)
+    };
+    pool.update_price_range(1u128 << 63, 1u128 << 64).unwrap();
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::state::{ProtocolFeeDestinationParameters, ProtocolFeeRouting};
+
+fn get_destinations(bps: &[u16]) -> Vec<ProtocolFeeDestinationParameters> {
+    bps.iter()
+        .map(|&bps| ProtocolFeeDestinationParameters {
+            owner: Pubkey::new_unique(),
+            bps,
+        })
+        .collect()
+}
+
+#[test]
+fn test_destination_shares_must_sum_to_100_percent() {
+    let mut routing = ProtocolFeeRouting::default();
+
+    assert!(routing.update(&[]).is_err());
+    assert!(routing.update(&get_destinations(&[5_000, 4_999])).is_err());
+    assert!(routing.update(&get_destinations(&[5_000, 5_001])).is_err());
+    assert!(routing.update(&get_destinations(&[10_000, 0])).is_err());
+    assert!(routing
+        .update(&get_destinations(&[2_000, 2_000, 2_000, 2_000, 2_000]))
+        .is_err());
+
+    let mut destinations = get_destinations(&[5_000, 5_000]);
+    destinations[1].owner = destinations[0].owner;
+    assert!(routing.update(&destinations).is_err());
+
+    destinations[1].owner = Pubkey::default();
+    assert!(routing.update(&destinations).is_err());
+
+    let destinations = get_destinations(&[5_000, 3_000, 2_000]);
+    routing.update(&destinations).unwrap();
+    assert_eq!(routing.destination_count, 3);
+    assert_eq!(routing.destinations()[1].owner, destinations[1].owner);
+    assert_eq!(routing.destinations()[1].bps, 3_000);
+
+    // update to fewer destinations clears the unused slots
+    routing.update(&get_destinations(&[10_000])).unwrap();
+    assert_eq!(routing.destinations().len(), 1);
+    assert_eq!(routing.destinations[1].owner, Pubkey::default());
+}
+
+#[test]
+fn test_destination_amounts() {
+    let mut routing = ProtocolFeeRouting::default();
+    routing
+        .update(&get_destinations(&[3_333, 3_333, 3_334]))
+        .unwrap();
+
+    // the last destination takes the rounding remainder
+    assert_eq!(
+        routing.get_destination_amounts(100).unwrap(),
+        vec![33, 33, 34]
+    );
+    assert_eq!(routing.get_destination_amounts(1).unwrap(), vec![0, 0, 1]);
+    assert_eq!(routing.get_destination_amounts(0).unwrap(), vec![0, 0, 0]);
+
+    let amounts = routing.get_destination_amounts(u64::MAX).unwrap();
+    assert_eq!(
+        amounts.iter().map(|&amount| amount as u128).sum::<u128>(),
+        u64::MAX as u128
+    );
+}
@@ -0,0 +1,155 @@
+use crate::{
+    constants::{NUM_EXTENSION_REWARDS, NUM_REWARDS},
+    state::{
+        get_extension_reward_index, get_reward_info, Pool, Position, PositionRewardExtension,
+        RewardExtension,
+    },
+};
+
+const ONE_DAY: u64 = 60 * 60 * 24;
+
+fn get_funded_reward_extension(reward_index: usize, amount: u64) -> RewardExtension {
+    let mut reward_extension = RewardExtension::default();
+    let reward_info =
+        &mut reward_extension.reward_infos[get_extension_reward_index(reward_index).unwrap()];
+    reward_info.initialized = 1;
+    reward_info.reward_duration = ONE_DAY;
+    reward_info.update_rate_after_funding(0, amount).unwrap();
+    reward_extension
+}
+
+#[test]
+fn test_extension_reward_index() {
+    for reward_index in 0..NUM_REWARDS {
+        assert!(get_extension_reward_index(reward_index).is_err());
+    }
+    for reward_index in NUM_REWARDS..NUM_REWARDS + NUM_EXTENSION_REWARDS {
+        assert_eq!(
+            get_extension_reward_index(reward_index).unwrap(),
+            reward_index - NUM_REWARDS
+        );
+    }
+    assert!(get_extension_reward_index(NUM_REWARDS + NUM_EXTENSION_REWARDS).is_err());
+
+    // extension reward index requires reward extension
+    let pool = Pool::default();
+    assert!(get_reward_info(&pool, None, NUM_REWARDS).is_err());
+    assert!(get_reward_info(&pool, None, 0).is_ok());
+}
+
+#[test]
+fn test_extension_reward_distribution() {
+    let reward_index = NUM_REWARDS + 1;
+    let amount = 1_000_000_000;
+    let mut reward_extension = get_funded_reward_extension(reward_index, amount);
+
+    // 2 positions share the enrolled liquidity equally
+    let liquidity = 1u128 << 64;
+    let mut first_position_reward_extension = PositionRewardExtension::default();
+    first_position_reward_extension.initialize(Default::default(), liquidity, &reward_extension);
+    reward_extension.add_enrolled_liquidity(liquidity).unwrap();
+    let mut second_position_reward_extension = PositionRewardExtension::default();
+    second_position_reward_extension.initialize(Default::default(), liquidity, &reward_extension);
+    reward_extension.add_enrolled_liquidity(liquidity).unwrap();
+
+    reward_extension.update_rewards(ONE_DAY).unwrap();
+    first_position_reward_extension
+        .update_position_reward(&reward_extension)
+        .unwrap();
+    second_position_reward_extension
+        .update_position_reward(&reward_extension)
+        .unwrap();
+
+    let first_reward = first_position_reward_extension
+        .claim_reward(reward_index)
+        .unwrap();
+    let second_reward = second_position_reward_extension
+        .claim_reward(reward_index)
+        .unwrap();
+
+    assert_eq!(first_reward, second_reward);
+    assert!(first_reward + second_reward <= amount);
+    // rounding down loss is negligible
+    assert!(amount - (first_reward + second_reward) <= 2);
+    assert!(first_position_reward_extension.is_empty());
+}
+
+#[test]
+fn test_position_reward_extension_created_later() {
+    let reward_index = NUM_REWARDS;
+    let amount = 1_000_000_000;
+    let mut reward_extension = get_funded_reward_extension(reward_index, amount);
+    let liquidity = 1u128 << 64;
+
+    // position reward extension is created at half of reward duration, nothing is enrolled before
+    reward_extension.update_rewards(ONE_DAY / 2).unwrap();
+    let mut position_reward_extension = PositionRewardExtension::default();
+    position_reward_extension.initialize(Default::default(), liquidity, &reward_extension);
+    reward_extension.add_enrolled_liquidity(liquidity).unwrap();
+
+    reward_extension.update_rewards(ONE_DAY).unwrap();
+    position_reward_extension
+        .update_position_reward(&reward_extension)
+        .unwrap();
+
+    // only earn reward from the time extension is created
+    let reward = position_reward_extension
+        .claim_reward(reward_index)
+        .unwrap();
+    assert!(reward <= amount / 2);
+    assert!(amount / 2 - reward <= 1);
+
+    // reward of the time without enrolled liquidity is withdrawable by funder
+    let ineligible_reward = reward_extension.reward_infos[0]
+        .claim_ineligible_reward()
+        .unwrap();
+    assert!(reward + ineligible_reward <= amount);
+    assert!(amount - (reward + ineligible_reward) <= 2);
+}
+
+#[test]
+fn test_extension_reward_only_shared_by_enrolled_liquidity() {
+    let reward_index = NUM_REWARDS;
+    let amount = 1_000_000_000;
+    let mut reward_extension = get_funded_reward_extension(reward_index, amount);
+    let liquidity = 1u128 << 64;
+
+    // the only enrolled position earns all rewards, regardless of liquidity without reward extension
+    let mut position_reward_extension = PositionRewardExtension::default();
+    position_reward_extension.initialize(Default::default(), liquidity, &reward_extension);
+    reward_extension.add_enrolled_liquidity(liquidity).unwrap();
+
+    reward_extension.update_rewards(ONE_DAY / 2).unwrap();
+    position_reward_extension
+        .update_position_reward(&reward_extension)
+        .unwrap();
+
+    // position doubles its liquidity, enrolled liquidity is synced after the change
+    reward_extension
+        .remove_enrolled_liquidity(liquidity)
+        .unwrap();
+    reward_extension
+        .add_enrolled_liquidity(liquidity * 2)
+        .unwrap();
+    position_reward_extension.liquidity = liquidity * 2;
+
+    reward_extension.update_rewards(ONE_DAY).unwrap();
+    position_reward_extension
+        .update_position_reward(&reward_extension)
+        .unwrap();
+
+    let reward = position_reward_extension
+        .claim_reward(reward_index)
+        .unwrap();
+    assert!(reward <= amount);
+    assert!(amount - reward <= 2);
+}
+
+#[test]
+fn test_position_with_reward_extension_is_not_empty() {
+    let mut position = Position::default();
+    assert!(position.is_empty().unwrap());
+
+    position.reward_extension_flag = 1;
+    assert!(!position.is_empty().unwrap());
+}
@@ -2,9 +2,13 @@ use std::u128;
 
 use proptest::proptest;
 
+use ruint::aliases::U256;
+
 use crate::{
-    constants::REWARD_RATE_SCALE, state::Pool, u128x128_math::Rounding,
-    utils_math::safe_shl_div_cast,
+    constants::{REWARD_RATE_SCALE, TOTAL_REWARD_SCALE},
+    state::{Pool, Position, RewardInfo},
+    u128x128_math::Rounding,
+    utils_math::{safe_mul_shr_256_cast, safe_shl_div_cast},
 };
 use proptest::prelude::*;
 const U64_MAX: u64 = u64::MAX;
@@ -25,3 +29,184 @@ proptest! {
         assert!(expect_rate == reward_info.reward_rate)
     }
 }
+
+#[test]
+fn test_scheduled_reward_only_accrues_inside_window() {
+    let liquidity: u128 = 1 << 100;
+    let mut reward_info = RewardInfo {
+        initialized: 1,
+        reward_duration: PER_DAY,
+        ..Default::default()
+    };
+
+    let current_time = 1_000;
+    let start = current_time + PER_DAY;
+    let end = start + PER_DAY;
+    reward_info
+        .update_rate_after_scheduled_funding(current_time, start, end, 1_000_000)
+        .unwrap();
+    assert!(reward_info.is_scheduled(current_time));
+
+    // nothing is distributed before start, even with empty liquidity
+    reward_info.update_rewards(0, start - 1).unwrap();
+    assert_eq!(
+        reward_info.cumulative_seconds_with_empty_liquidity_reward,
+        0
+    );
+    reward_info.update_rewards(liquidity, start).unwrap();
+    assert_eq!(reward_info.reward_per_token_stored(), U256::ZERO);
+
+    // distributed inside the window
+    reward_info.update_rewards(liquidity, start + 100).unwrap();
+    assert!(reward_info.reward_per_token_stored() > U256::ZERO);
+
+    // whole window distributed after end
+    reward_info
+        .update_rewards(liquidity, end + PER_DAY)
+        .unwrap();
+    let total_reward: u64 = safe_mul_shr_256_cast(
+        U256::from(liquidity),
+        reward_info.reward_per_token_stored(),
+        TOTAL_REWARD_SCALE,
+    )
+    .unwrap();
+    assert!(total_reward <= 1_000_000);
+    assert!(1_000_000 - total_reward <= 1);
+}
+
+#[test]
+fn test_fund_scheduled_reward_before_start() {
+    let mut reward_info = RewardInfo {
+        initialized: 1,
+        reward_duration: PER_DAY,
+        ..Default::default()
+    };
+
+    let current_time = 1_000;
+    let start = current_time + PER_DAY;
+    let end = start + PER_DAY;
+    reward_info
+        .update_rate_after_scheduled_funding(current_time, start, end, 1_000_000)
+        .unwrap();
+
+    // normal funding before start tops up the scheduled window
+    reward_info
+        .update_rate_after_funding(current_time + 10, 1_000_000)
+        .unwrap();
+    assert_eq!(reward_info.reward_duration_start, start);
+    assert_eq!(reward_info.reward_duration_end, end);
+    let expect_rate: u128 = safe_shl_div_cast(
+        1_999_999u128,
+        (end - start).into(),
+        REWARD_RATE_SCALE,
+        Rounding::Down,
+    )
+    .unwrap();
+    assert!(reward_info.reward_rate >= expect_rate);
+
+    // a different schedule can't be funded before the current one ends
+    assert!(reward_info
+        .update_rate_after_scheduled_funding(current_time + 10, start + 1, end, 1_000)
+        .is_err());
+
+    // schedule can't start in the past
+    assert!(reward_info
+        .update_rate_after_scheduled_funding(end + 10, end, end + PER_DAY, 1_000)
+        .is_err());
+}
+
+#[test]
+fn test_reward_boost_for_locked_liquidity() {
+    let liquidity: u128 = 1 << 100;
+
+    let mut pool = Pool {
+        liquidity: liquidity * 3,
+        permanent_lock_liquidity: liquidity,
+        vesting_locked_liquidity: liquidity,
+        vesting_liquidity_flag: 1,
+        ..Default::default()
+    };
+    let reward_info = &mut pool.reward_infos[0];
+    reward_info.initialized = 1;
+    reward_info.reward_duration = PER_DAY;
+    // permanent locked liquidity counts 2x, vesting locked liquidity counts 1.5x
+    reward_info.permanent_lock_boost_bps = 10_000;
+    reward_info.vesting_lock_boost_bps = 5_000;
+    reward_info.update_rate_after_funding(0, 4_500_000).unwrap();
+
+    assert_eq!(
+        pool.get_reward_liquidity_supply(0).unwrap(),
+        liquidity * 9 / 2
+    );
+
+    let mut unlocked_position = Position {
+        unlocked_liquidity: liquidity,
+        ..Default::default()
+    };
+    let mut vesting_position = Position {
+        vested_liquidity: liquidity,
+        ..Default::default()
+    };
+    let mut permanent_locked_position = Position {
+        permanent_locked_liquidity: liquidity,
+        ..Default::default()
+    };
+
+    pool.update_rewards(PER_DAY).unwrap();
+    unlocked_position.update_position_reward(&pool).unwrap();
+    vesting_position.update_position_reward(&pool).unwrap();
+    permanent_locked_position
+        .update_position_reward(&pool)
+        .unwrap();
+
+    let unlocked_reward = unlocked_position.reward_infos[0].reward_pendings;
+    let vesting_reward = vesting_position.reward_infos[0].reward_pendings;
+    let permanent_locked_reward = permanent_locked_position.reward_infos[0].reward_pendings;
+
+    assert!(unlocked_reward.abs_diff(1_000_000) <= 1);
+    assert!(vesting_reward.abs_diff(1_500_000) <= 1);
+    assert!(permanent_locked_reward.abs_diff(2_000_000) <= 1);
+    assert!(unlocked_reward + vesting_reward + permanent_locked_reward <= 4_500_000);
+}
+
+#[test]
+fn test_vesting_locked_liquidity_is_only_tracked_with_flag() {
+    let mut pool = Pool::default();
+    pool.accumulate_vesting_locked_liquidity(100).unwrap();
+    assert_eq!(pool.vesting_locked_liquidity, 0);
+    pool.release_vesting_locked_liquidity(100).unwrap();
+
+    pool.vesting_liquidity_flag = 1;
+    pool.accumulate_vesting_locked_liquidity(100).unwrap();
+    pool.release_vesting_locked_liquidity(40).unwrap();
+    assert_eq!(pool.vesting_locked_liquidity, 60);
+}
+
+#[test]
+fn test_pool_is_empty_only_after_rewards_finished() {
+    let mut pool = Pool::default();
+    assert!(pool.is_empty(0));
+
+    pool.protocol_a_fee = 1;
+    assert!(!pool.is_empty(0));
+    pool.protocol_a_fee = 0;
+
+    let current_time = 1_000;
+    let reward_info = &mut pool.reward_infos[0];
+    reward_info.initialized = 1;
+    reward_info.reward_duration = PER_DAY;
+    reward_info
+        .update_rate_after_funding(current_time, 1_000_000)
+        .unwrap();
+    let reward_duration_end = reward_info.reward_duration_end;
+
+    // reward is still running
+    assert!(!pool.is_empty(current_time));
+
+    // reward was distributed to empty liquidity, it must be withdrawn by the funder
+    pool.update_rewards(reward_duration_end + 1).unwrap();
+    assert!(!pool.is_empty(reward_duration_end + 1));
+
+    pool.claim_ineligible_reward(0).unwrap();
+    assert!(pool.is_empty(reward_duration_end + 1));
+}
@@ -37,7 +37,7 @@ proptest! {
             .get_swap_result_from_exact_input(amount_in, fee_mode, trade_direction, 0)
             .unwrap();
 
-            pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+            pool.apply_swap_result(&swap_result_0, fee_mode, 0, None).unwrap();
             // swap back
 
             let swap_result_1 = pool
@@ -73,7 +73,7 @@ proptest! {
             .get_swap_result_from_exact_input(amount_in, fee_mode, trade_direction, 0)
             .unwrap();
 
-            pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+            pool.apply_swap_result(&swap_result_0, fee_mode, 0, None).unwrap();
             // swap back
 
             let swap_result_1 = pool
@@ -138,7 +138,8 @@ fn test_reserve_wont_lost_when_swap_from_b_to_a_single() {
 
     println!("{:?}", swap_result_0);
 
-    pool.apply_swap_result(&swap_result_0, fee_mode, 0).unwrap();
+    pool.apply_swap_result(&swap_result_0, fee_mode, 0, None)
+        .unwrap();
 
     let swap_result_1 = pool
         .get_swap_result_from_exact_input(
@@ -202,10 +203,16 @@ fn test_swap_basic() {
 
     // return;
 
-    pool.apply_swap_result(&swap_result, fee_mode, 0).unwrap();
+    pool.apply_swap_result(&swap_result, fee_mode, 0, None)
+        .unwrap();
 
     let swap_result_referse = pool
-        .get_swap_result_from_exact_input(swap_result.output_amount, fee_mode, TradeDirection::BtoA, 0)
+        .get_swap_result_from_exact_input(
+            swap_result.output_amount,
+            fee_mode,
+            TradeDirection::BtoA,
+            0,
+        )
         .unwrap();
 
     println!("reverse {:?}", swap_result_referse);
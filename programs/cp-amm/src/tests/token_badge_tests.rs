@@ -0,0 +1,26 @@
+use crate::state::TokenBadge;
+use anchor_lang::prelude::Pubkey;
+
+#[test]
+fn test_transfer_fee_without_limit() {
+    // badges created before the policy was introduced have zeroed policy fields
+    let token_badge: TokenBadge = bytemuck::Zeroable::zeroed();
+    assert!(token_badge.is_transfer_fee_allowed(0));
+    assert!(token_badge.is_transfer_fee_allowed(10_000));
+}
+
+#[test]
+fn test_transfer_fee_limit() {
+    let mut token_badge: TokenBadge = bytemuck::Zeroable::zeroed();
+    token_badge.update_policy(0, 0, Pubkey::default(), Some(100));
+    assert!(token_badge.is_transfer_fee_allowed(100));
+    assert!(!token_badge.is_transfer_fee_allowed(101));
+
+    // zero limit requires the mint to have no transfer fee
+    token_badge.update_policy(0, 0, Pubkey::default(), Some(0));
+    assert!(token_badge.is_transfer_fee_allowed(0));
+    assert!(!token_badge.is_transfer_fee_allowed(1));
+
+    token_badge.update_policy(0, 0, Pubkey::default(), None);
+    assert!(token_badge.is_transfer_fee_allowed(10_000));
+}
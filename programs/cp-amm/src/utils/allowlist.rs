@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{
+    state::{AllowlistEntry, Pool, PoolPermission},
+    PoolError,
+};
+
+#[derive(Accounts)]
+pub struct AllowlistEntryRemainingAccount<'info> {
+    pub allowlist_entry: AccountLoader<'info, AllowlistEntry>,
+}
+
+#[derive(Accounts)]
+pub struct PoolPermissionRemainingAccount<'info> {
+    #[account(mut)]
+    pub pool_permission: AccountLoader<'info, PoolPermission>,
+}
+
+/// Load allowlist entry of the wallet from the front of remaining accounts, only permissioned pool requires it.
+/// Returns the allowlisted wallet, none if the pool is not permissioned
+pub fn load_allowlisted_wallet<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    pool: &Pool,
+    wallet: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<Option<Pubkey>> {
+    if !pool.is_permissioned() {
+        return Ok(None);
+    }
+
+    let account = AllowlistEntryRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut AllowlistEntryRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidAllowlistEntry)?;
+
+    let allowlist_entry = account.allowlist_entry.load()?;
+    require!(
+        allowlist_entry.pool == pool_key && allowlist_entry.wallet == wallet,
+        PoolError::InvalidAllowlistEntry
+    );
+
+    Ok(Some(wallet))
+}
+
+/// Load pool permission from the front of remaining accounts
+pub fn load_pool_permission<'c: 'info, 'info>(
+    pool: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, PoolPermission>> {
+    let account = PoolPermissionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut PoolPermissionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidPoolPermission)?;
+
+    require!(
+        account.pool_permission.load()?.pool == pool,
+        PoolError::InvalidPoolPermission
+    );
+
+    Ok(account.pool_permission)
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{
+    state::{BribeLedger, Position, PositionBribeLedger},
+    PoolError,
+};
+
+#[derive(Accounts)]
+pub struct BribeLedgerRemainingAccount<'info> {
+    #[account(mut)]
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+}
+
+#[derive(Accounts)]
+pub struct PositionBribeLedgerRemainingAccount<'info> {
+    #[account(mut)]
+    pub position_bribe_ledger: AccountLoader<'info, PositionBribeLedger>,
+}
+
+/// Load pool bribe ledger from the front of remaining accounts
+pub fn load_bribe_ledger<'c: 'info, 'info>(
+    pool: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, BribeLedger>> {
+    let account = BribeLedgerRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut BribeLedgerRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidBribeLedger)?;
+
+    require!(
+        account.bribe_ledger.load()?.pool == pool,
+        PoolError::InvalidBribeLedger
+    );
+
+    Ok(account.bribe_ledger)
+}
+
+/// Load position bribe ledger from the front of remaining accounts
+pub fn load_position_bribe_ledger<'c: 'info, 'info>(
+    position: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, PositionBribeLedger>> {
+    let account = PositionBribeLedgerRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut PositionBribeLedgerRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidBribeLedger)?;
+
+    require!(
+        account.position_bribe_ledger.load()?.position == position,
+        PoolError::InvalidBribeLedger
+    );
+
+    Ok(account.position_bribe_ledger)
+}
+
+pub struct BribeLedgerAccounts<'info> {
+    pub bribe_ledger: AccountLoader<'info, BribeLedger>,
+    pub position_bribe_ledgers: Vec<(Pubkey, AccountLoader<'info, PositionBribeLedger>)>,
+}
+
+impl BribeLedgerAccounts<'_> {
+    /// Sync enrolled liquidity with position liquidity, must be called after liquidity change
+    pub fn sync_liquidity(&self, positions: &[(Pubkey, &Position)]) -> Result<()> {
+        let mut bribe_ledger = self.bribe_ledger.load_mut()?;
+        for (position_key, position) in positions {
+            if let Some((_, loader)) = self
+                .position_bribe_ledgers
+                .iter()
+                .find(|(key, _)| key == position_key)
+            {
+                let mut position_bribe_ledger = loader.load_mut()?;
+                let liquidity = position.get_total_liquidity()?;
+                bribe_ledger.remove_enrolled_liquidity(position_bribe_ledger.liquidity)?;
+                bribe_ledger.add_enrolled_liquidity(liquidity)?;
+                position_bribe_ledger.liquidity = liquidity;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Update bribe ledgers of the pool and positions, must be called before any liquidity change.
+/// Only required when a position has bribe ledger, in that case the pool bribe ledger followed by
+/// the bribe ledger of each position that has one are taken from the front of remaining accounts.
+pub fn update_bribe_ledgers<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    positions: &[(Pubkey, &Position)],
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    current_time: u64,
+) -> Result<Option<BribeLedgerAccounts<'info>>> {
+    if !positions
+        .iter()
+        .any(|(_, position)| position.has_bribe_ledger())
+    {
+        return Ok(None);
+    }
+
+    let bribe_ledger_loader = load_bribe_ledger(pool_key, remaining_accounts)?;
+    let mut position_bribe_ledgers = Vec::with_capacity(positions.len());
+    {
+        let mut bribe_ledger = bribe_ledger_loader.load_mut()?;
+        bribe_ledger.update(current_time)?;
+
+        for (position_key, position) in positions {
+            if position.has_bribe_ledger() {
+                let position_bribe_ledger_loader =
+                    load_position_bribe_ledger(*position_key, remaining_accounts)?;
+                position_bribe_ledger_loader
+                    .load_mut()?
+                    .update(&bribe_ledger, current_time)?;
+                position_bribe_ledgers.push((*position_key, position_bribe_ledger_loader));
+            }
+        }
+    }
+
+    Ok(Some(BribeLedgerAccounts {
+        bribe_ledger: bribe_ledger_loader,
+        position_bribe_ledgers,
+    }))
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{
+    state::{LockedFeeExtension, Pool, Position},
+    PoolError,
+};
+
+#[derive(Accounts)]
+pub struct LockedFeeExtensionRemainingAccount<'info> {
+    #[account(mut)]
+    pub locked_fee_extension: AccountLoader<'info, LockedFeeExtension>,
+}
+
+/// Load locked fee extension from the front of remaining accounts
+pub fn load_locked_fee_extension<'c: 'info, 'info>(
+    pool: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, LockedFeeExtension>> {
+    let account = LockedFeeExtensionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut LockedFeeExtensionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidLockedFeeExtension)?;
+
+    require!(
+        account.locked_fee_extension.load()?.pool == pool,
+        PoolError::InvalidLockedFeeExtension
+    );
+
+    Ok(account.locked_fee_extension)
+}
+
+/// Load locked fee extension from the front of remaining accounts, only if the pool has one
+pub fn load_locked_fee_extension_if_initialized<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    pool: &Pool,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, LockedFeeExtension>>> {
+    if !pool.has_locked_fee_extension() {
+        return Ok(None);
+    }
+
+    Ok(Some(load_locked_fee_extension(
+        pool_key,
+        remaining_accounts,
+    )?))
+}
+
+/// Add the locked fee share to pending fee of positions, must be called before fee is claimed or
+/// locked liquidity changes. Only required when the pool has locked fee extension, in that case it's
+/// taken from the front of remaining accounts.
+pub fn update_locked_fee<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    pool: &Pool,
+    positions: &mut [&mut Position],
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, LockedFeeExtension>>> {
+    let Some(locked_fee_extension_loader) =
+        load_locked_fee_extension_if_initialized(pool_key, pool, remaining_accounts)?
+    else {
+        return Ok(None);
+    };
+
+    {
+        let locked_fee_extension = locked_fee_extension_loader.load()?;
+        for position in positions.iter_mut() {
+            let locked_liquidity =
+                position.get_fee_share_locked_liquidity(pool.is_vesting_liquidity_tracked())?;
+            position.update_locked_fee(
+                locked_fee_extension.fee_a_per_locked_liquidity(),
+                locked_fee_extension.fee_b_per_locked_liquidity(),
+                locked_liquidity,
+            )?;
+        }
+    }
+
+    Ok(Some(locked_fee_extension_loader))
+}
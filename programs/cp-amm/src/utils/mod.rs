@@ -1,3 +1,11 @@
 pub mod activation_handler;
+pub mod allowlist;
 pub mod alpha_vault;
+pub mod bribe;
+pub mod locked_fee_extension;
+pub mod position_nft_metadata;
+pub mod protocol_fee_routing;
+pub mod reward_extension;
+pub mod shared_position;
 pub mod token;
+pub mod writable_pool;
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{token_metadata_update_field, TokenMetadataUpdateField};
+use spl_token_metadata_interface::state::Field;
+
+use crate::{
+    constants::{fee::FEE_DENOMINATOR, position_nft::URI_TEMPLATE, BASIS_POINT_MAX},
+    safe_math::SafeMath,
+    state::{Pool, Position},
+};
+
+/// Metadata JSON of position nft is rendered off-chain from additional metadata fields
+pub fn get_position_nft_uri(position_nft_mint: Pubkey) -> String {
+    URI_TEMPLATE.replace("{mint}", &position_nft_mint.to_string())
+}
+
+/// Pool pair and fee tier, fixed for the lifetime of the position
+pub fn get_pool_metadata_fields(pool_key: Pubkey, pool: &Pool) -> Result<Vec<(Field, String)>> {
+    let fee_bps = pool
+        .pool_fees
+        .base_fee
+        .cliff_fee_numerator
+        .safe_mul(BASIS_POINT_MAX)?
+        .safe_div(FEE_DENOMINATOR)?;
+
+    Ok(vec![
+        (Field::Key("pool".to_string()), pool_key.to_string()),
+        (
+            Field::Key("token_a_mint".to_string()),
+            pool.token_a_mint.to_string(),
+        ),
+        (
+            Field::Key("token_b_mint".to_string()),
+            pool.token_b_mint.to_string(),
+        ),
+        (Field::Key("fee_bps".to_string()), fee_bps.to_string()),
+    ])
+}
+
+/// Liquidity and lock status of the position, vesting end point is 0 if position has no vesting
+pub fn get_position_metadata_fields(
+    position: &Position,
+    vesting_end_point: u64,
+) -> Result<Vec<(Field, String)>> {
+    Ok(vec![
+        (
+            Field::Key("liquidity".to_string()),
+            position.get_total_liquidity()?.to_string(),
+        ),
+        (
+            Field::Key("unlocked_liquidity".to_string()),
+            position.unlocked_liquidity.to_string(),
+        ),
+        (
+            Field::Key("vested_liquidity".to_string()),
+            position.vested_liquidity.to_string(),
+        ),
+        (
+            Field::Key("permanent_locked_liquidity".to_string()),
+            position.permanent_locked_liquidity.to_string(),
+        ),
+        (
+            Field::Key("vesting_end_point".to_string()),
+            vesting_end_point.to_string(),
+        ),
+    ])
+}
+
+/// Update metadata fields of position nft. Mint account is reallocated by token2022, the caller
+/// must top up the rent afterwards
+pub fn update_position_nft_metadata<'info>(
+    token_program: AccountInfo<'info>,
+    position_nft_mint: AccountInfo<'info>,
+    pool_authority: AccountInfo<'info>,
+    fields: Vec<(Field, String)>,
+) -> Result<()> {
+    let seeds = pool_authority_seeds!();
+    let signer_seeds = &[&seeds[..]];
+
+    for (field, value) in fields {
+        let cpi_accounts = TokenMetadataUpdateField {
+            program_id: token_program.clone(),
+            metadata: position_nft_mint.clone(),
+            update_authority: pool_authority.clone(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token_metadata_update_field(cpi_ctx, field, value)?;
+    }
+
+    Ok(())
+}
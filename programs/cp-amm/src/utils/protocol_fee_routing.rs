@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::collections::BTreeSet;
+
+use crate::{state::ProtocolFeeRouting, token::transfer_from_pool, PoolError};
+
+#[derive(Accounts)]
+pub struct ProtocolFeeDestinationRemainingAccount<'info> {
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Load protocol fee routing, return None if it is not initialized
+pub fn load_protocol_fee_routing(protocol_fee_routing: &AccountInfo) -> Option<ProtocolFeeRouting> {
+    if protocol_fee_routing.owner != &crate::ID {
+        return None;
+    }
+
+    let data = protocol_fee_routing.try_borrow_data().ok()?;
+    data.strip_prefix(ProtocolFeeRouting::DISCRIMINATOR)
+        .and_then(|data| data.get(..ProtocolFeeRouting::INIT_SPACE))
+        .and_then(|data| bytemuck::try_from_bytes::<ProtocolFeeRouting>(data).ok())
+        .copied()
+}
+
+/// Take token account of each destination for a mint from the front of remaining accounts
+pub fn load_protocol_fee_destination_accounts<'c: 'info, 'info>(
+    protocol_fee_routing: &ProtocolFeeRouting,
+    token_mint: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<Vec<Box<InterfaceAccount<'info, TokenAccount>>>> {
+    protocol_fee_routing
+        .destinations()
+        .iter()
+        .map(|destination| {
+            let account = ProtocolFeeDestinationRemainingAccount::try_accounts(
+                &crate::ID,
+                remaining_accounts,
+                &[],
+                &mut ProtocolFeeDestinationRemainingAccountBumps {},
+                &mut BTreeSet::new(),
+            )
+            .map_err(|_| PoolError::InvalidProtocolFeeDestination)?;
+
+            require!(
+                account.token_account.owner == destination.owner
+                    && account.token_account.mint == token_mint,
+                PoolError::InvalidProtocolFeeDestination
+            );
+
+            Ok(account.token_account)
+        })
+        .collect()
+}
+
+/// Transfer split amounts of a token from pool vault to destination token accounts
+pub fn transfer_protocol_fee_to_destinations<'c: 'info, 'info>(
+    pool_authority: AccountInfo<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    destination_token_accounts: &[Box<InterfaceAccount<'info, TokenAccount>>],
+    amounts: &[u64],
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
+) -> Result<()> {
+    for (destination_token_account, &amount) in destination_token_accounts.iter().zip(amounts) {
+        if amount > 0 {
+            transfer_from_pool(
+                pool_authority.clone(),
+                token_mint,
+                token_vault,
+                destination_token_account,
+                token_program,
+                amount,
+                transfer_hook_accounts,
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{
+    state::{Pool, Position, PositionRewardExtension, RewardExtension},
+    PoolError,
+};
+
+#[derive(Accounts)]
+pub struct RewardExtensionRemainingAccount<'info> {
+    #[account(mut)]
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+}
+
+#[derive(Accounts)]
+pub struct PositionRewardExtensionRemainingAccount<'info> {
+    #[account(mut)]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+}
+
+/// Load pool reward extension from the front of remaining accounts
+pub fn load_reward_extension<'c: 'info, 'info>(
+    pool: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, RewardExtension>> {
+    let account = RewardExtensionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut RewardExtensionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidRewardExtension)?;
+
+    require!(
+        account.reward_extension.load()?.pool == pool,
+        PoolError::InvalidRewardExtension
+    );
+
+    Ok(account.reward_extension)
+}
+
+/// Load position reward extension from the front of remaining accounts
+pub fn load_position_reward_extension<'c: 'info, 'info>(
+    position: Pubkey,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<AccountLoader<'info, PositionRewardExtension>> {
+    let account = PositionRewardExtensionRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut PositionRewardExtensionRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidRewardExtension)?;
+
+    require!(
+        account.position_reward_extension.load()?.position == position,
+        PoolError::InvalidRewardExtension
+    );
+
+    Ok(account.position_reward_extension)
+}
+
+pub struct RewardExtensionAccounts<'info> {
+    pub reward_extension: AccountLoader<'info, RewardExtension>,
+    pub position_reward_extensions: Vec<(Pubkey, AccountLoader<'info, PositionRewardExtension>)>,
+}
+
+impl RewardExtensionAccounts<'_> {
+    /// Sync enrolled liquidity with position liquidity, must be called after liquidity change
+    pub fn sync_liquidity(&self, positions: &[(Pubkey, &Position)]) -> Result<()> {
+        let mut reward_extension = self.reward_extension.load_mut()?;
+        for (position_key, position) in positions {
+            if let Some((_, loader)) = self
+                .position_reward_extensions
+                .iter()
+                .find(|(key, _)| key == position_key)
+            {
+                let mut position_reward_extension = loader.load_mut()?;
+                let liquidity = position.get_total_liquidity()?;
+                reward_extension.remove_enrolled_liquidity(position_reward_extension.liquidity)?;
+                reward_extension.add_enrolled_liquidity(liquidity)?;
+                position_reward_extension.liquidity = liquidity;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Update extension rewards of the pool and positions, must be called before any liquidity change.
+/// Only required when the pool has extension reward, in that case the pool reward extension followed by
+/// the reward extension of each position that has one are taken from the front of remaining accounts.
+pub fn update_reward_extensions<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    pool: &Pool,
+    positions: &[(Pubkey, &Position)],
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+    current_time: u64,
+) -> Result<Option<RewardExtensionAccounts<'info>>> {
+    if !pool.has_extension_reward() {
+        return Ok(None);
+    }
+
+    let reward_extension_loader = load_reward_extension(pool_key, remaining_accounts)?;
+    let mut position_reward_extensions = Vec::with_capacity(positions.len());
+    {
+        let mut reward_extension = reward_extension_loader.load_mut()?;
+        reward_extension.update_rewards(current_time)?;
+
+        for (position_key, position) in positions {
+            if position.has_reward_extension() {
+                let position_reward_extension_loader =
+                    load_position_reward_extension(*position_key, remaining_accounts)?;
+                position_reward_extension_loader
+                    .load_mut()?
+                    .update_position_reward(&reward_extension)?;
+                position_reward_extensions.push((*position_key, position_reward_extension_loader));
+            }
+        }
+    }
+
+    Ok(Some(RewardExtensionAccounts {
+        reward_extension: reward_extension_loader,
+        position_reward_extensions,
+    }))
+}
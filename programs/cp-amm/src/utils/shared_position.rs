@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{ModifyLiquidityResult, Pool, Position},
+    u128x128_math::Rounding,
+};
+
+#[derive(Default)]
+pub struct SharedPositionFeeCompound {
+    pub liquidity_delta: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+/// Compound pending fee of the shared position of fungible share mode at the pool ratio. Compounding
+/// is permissionless, so the excess side is never swapped through the pool, it's left as pending fee.
+/// Nothing is compounded if pending fee is too small
+pub fn compound_shared_position_fee(
+    pool: &mut Pool,
+    position: &mut Position,
+) -> Result<SharedPositionFeeCompound> {
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    let liquidity_delta =
+        pool.get_liquidity_from_amounts(position.fee_a_pending, position.fee_b_pending)?;
+    if liquidity_delta == 0 {
+        return Ok(SharedPositionFeeCompound::default());
+    }
+
+    let ModifyLiquidityResult {
+        token_a_amount,
+        token_b_amount,
+    } = pool.get_amounts_for_modify_liquidity(liquidity_delta, Rounding::Up)?;
+    if token_a_amount > position.fee_a_pending || token_b_amount > position.fee_b_pending {
+        return Ok(SharedPositionFeeCompound::default());
+    }
+
+    pool.apply_add_liquidity(position, liquidity_delta)?;
+
+    // compounded fee is treated as claimed, the remaining dust is kept as pending fee
+    position.remove_fee_pending(token_a_amount, token_b_amount)?;
+    position
+        .metrics
+        .accumulate_claimed_fee(token_a_amount, token_b_amount)?;
+
+    Ok(SharedPositionFeeCompound {
+        liquidity_delta,
+        token_a_amount,
+        token_b_amount,
+    })
+}
@@ -2,10 +2,7 @@ use crate::math::safe_math::SafeMath;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_instruction::transfer;
 
-use anchor_lang::{
-    prelude::InterfaceAccount,
-    solana_program::program::{invoke, invoke_signed},
-};
+use anchor_lang::{prelude::InterfaceAccount, solana_program::program::invoke};
 use anchor_spl::{
     token::Token,
     token_2022::spl_token_2022::{
@@ -15,12 +12,18 @@ use anchor_spl::{
             transfer_fee::{TransferFee, MAX_FEE_BASIS_POINTS},
             BaseStateWithExtensions, ExtensionType, StateWithExtensions,
         },
+        onchain::invoke_transfer_checked,
     },
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::ExecuteInstruction,
+};
+use spl_type_length_value::state::TlvStateBorrowed;
 
-use crate::{state::TokenBadge, PoolError};
+use crate::{constants::seeds::TOKEN_BADGE_PREFIX, state::TokenBadge, PoolError};
 
 #[derive(
     AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive,
@@ -31,9 +34,7 @@ pub enum TokenProgramFlags {
     TokenProgram2022,
 }
 
-pub fn get_token_program_flags<'a, 'info>(
-    token_mint: &'a InterfaceAccount<'info, Mint>,
-) -> TokenProgramFlags {
+pub fn get_token_program_flags(token_mint: &InterfaceAccount<'_, Mint>) -> TokenProgramFlags {
     let token_mint_ai = token_mint.to_account_info();
 
     if token_mint_ai.owner.eq(&anchor_spl::token::ID) {
@@ -56,17 +57,17 @@ pub struct TransferFeeExcludedAmount {
     pub transfer_fee: u64,
 }
 
-pub fn calculate_transfer_fee_excluded_amount<'info>(
-    token_mint: &InterfaceAccount<'info, Mint>,
+pub fn calculate_transfer_fee_excluded_amount(
+    token_mint: &InterfaceAccount<'_, Mint>,
     transfer_fee_included_amount: u64,
 ) -> Result<TransferFeeExcludedAmount> {
     if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
         let transfer_fee = epoch_transfer_fee
             .calculate_fee(transfer_fee_included_amount)
-            .ok_or_else(|| PoolError::MathOverflow)?;
+            .ok_or(PoolError::MathOverflow)?;
         let transfer_fee_excluded_amount = transfer_fee_included_amount
             .checked_sub(transfer_fee)
-            .ok_or_else(|| PoolError::MathOverflow)?;
+            .ok_or(PoolError::MathOverflow)?;
         return Ok(TransferFeeExcludedAmount {
             amount: transfer_fee_excluded_amount,
             transfer_fee,
@@ -79,8 +80,8 @@ pub fn calculate_transfer_fee_excluded_amount<'info>(
     })
 }
 
-pub fn calculate_transfer_fee_included_amount<'info>(
-    token_mint: &InterfaceAccount<'info, Mint>,
+pub fn calculate_transfer_fee_included_amount(
+    token_mint: &InterfaceAccount<'_, Mint>,
     transfer_fee_excluded_amount: u64,
 ) -> Result<TransferFeeIncludedAmount> {
     if transfer_fee_excluded_amount == 0 {
@@ -130,8 +131,8 @@ pub fn calculate_transfer_fee_included_amount<'info>(
     })
 }
 
-pub fn get_epoch_transfer_fee<'info>(
-    token_mint: &InterfaceAccount<'info, Mint>,
+pub fn get_epoch_transfer_fee(
+    token_mint: &InterfaceAccount<'_, Mint>,
 ) -> Result<Option<TransferFee>> {
     let token_mint_info = token_mint.to_account_info();
     if *token_mint_info.owner == Token::id() {
@@ -145,12 +146,31 @@ pub fn get_epoch_transfer_fee<'info>(
         token_mint_unpacked.get_extension::<extension::transfer_fee::TransferFeeConfig>()
     {
         let epoch = Clock::get()?.epoch;
-        return Ok(Some(transfer_fee_config.get_epoch_fee(epoch).clone()));
+        return Ok(Some(*transfer_fee_config.get_epoch_fee(epoch)));
     }
 
     Ok(None)
 }
 
+/// Validate epoch transfer fee bps of the mint against the bound given by user, return the epoch transfer fee bps
+pub fn validate_transfer_fee_bps(
+    token_mint: &InterfaceAccount<'_, Mint>,
+    max_transfer_fee_bps: Option<u16>,
+) -> Result<u16> {
+    let transfer_fee_bps = get_epoch_transfer_fee(token_mint)?
+        .map(|epoch_transfer_fee| u16::from(epoch_transfer_fee.transfer_fee_basis_points))
+        .unwrap_or(0);
+
+    if let Some(max_transfer_fee_bps) = max_transfer_fee_bps {
+        require!(
+            transfer_fee_bps <= max_transfer_fee_bps,
+            PoolError::ExceededMaxTransferFeeBps
+        );
+    }
+
+    Ok(transfer_fee_bps)
+}
+
 pub fn transfer_from_user<'a, 'c: 'info, 'info>(
     authority: &'a Signer<'info>,
     token_mint: &'a InterfaceAccount<'info, Mint>,
@@ -158,28 +178,19 @@ pub fn transfer_from_user<'a, 'c: 'info, 'info>(
     destination_token_account: &'a InterfaceAccount<'info, TokenAccount>,
     token_program: &'a Interface<'info, TokenInterface>,
     amount: u64,
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
 ) -> Result<()> {
-    let destination_account = destination_token_account.to_account_info();
-
-    let instruction = spl_token_2022::instruction::transfer_checked(
+    invoke_transfer_checked(
         token_program.key,
-        &token_owner_account.key(),
-        &token_mint.key(),
-        destination_account.key,
-        authority.key,
-        &[],
-        amount,
-        token_mint.decimals,
-    )?;
-
-    let account_infos = vec![
         token_owner_account.to_account_info(),
         token_mint.to_account_info(),
-        destination_account.to_account_info(),
+        destination_token_account.to_account_info(),
         authority.to_account_info(),
-    ];
-
-    invoke_signed(&instruction, &account_infos, &[])?;
+        transfer_hook_accounts,
+        amount,
+        token_mint.decimals,
+        &[],
+    )?;
 
     Ok(())
 }
@@ -191,30 +202,90 @@ pub fn transfer_from_pool<'c: 'info, 'info>(
     token_owner_account: &InterfaceAccount<'info, TokenAccount>,
     token_program: &Interface<'info, TokenInterface>,
     amount: u64,
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
 ) -> Result<()> {
     let signer_seeds = pool_authority_seeds!();
 
-    let instruction = spl_token_2022::instruction::transfer_checked(
+    invoke_transfer_checked(
         token_program.key,
-        &token_vault.key(),
-        &token_mint.key(),
-        &token_owner_account.key(),
-        &pool_authority.key(),
-        &[],
+        token_vault.to_account_info(),
+        token_mint.to_account_info(),
+        token_owner_account.to_account_info(),
+        pool_authority,
+        transfer_hook_accounts,
         amount,
         token_mint.decimals,
+        &[&signer_seeds[..]],
     )?;
 
-    let account_infos = vec![
-        token_vault.to_account_info(),
-        token_mint.to_account_info(),
-        token_owner_account.to_account_info(),
-        pool_authority.to_account_info(),
-    ];
+    Ok(())
+}
 
-    invoke_signed(&instruction, &account_infos, &[&signer_seeds[..]])?;
+pub fn get_transfer_hook_program_id(
+    token_mint: &InterfaceAccount<'_, Mint>,
+) -> Result<Option<Pubkey>> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(None);
+    }
 
-    Ok(())
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_unpacked =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    Ok(extension::transfer_hook::get_program_id(
+        &token_mint_unpacked,
+    ))
+}
+
+/// Take transfer hook accounts of a mint from the front of remaining accounts, in order: transfer hook program,
+/// extra account meta list and the extra accounts declared in the list. Empty if mint doesn't have transfer hook program
+pub fn load_transfer_hook_accounts<'c: 'info, 'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<&'c [AccountInfo<'info>]> {
+    let Some(transfer_hook_program_id) = get_transfer_hook_program_id(token_mint)? else {
+        return Ok(&[]);
+    };
+
+    let transfer_hook_program = remaining_accounts
+        .first()
+        .ok_or(PoolError::InvalidTransferHookAccounts)?;
+    require!(
+        transfer_hook_program.key() == transfer_hook_program_id,
+        PoolError::InvalidTransferHookAccounts
+    );
+
+    // transfer hook program without extra account meta list doesn't require extra accounts
+    let mut accounts_len: usize = 1;
+    let extra_account_meta_list_key =
+        get_extra_account_metas_address(&token_mint.key(), &transfer_hook_program_id);
+    if let Some(extra_account_meta_list) = remaining_accounts
+        .get(1)
+        .filter(|account| account.key() == extra_account_meta_list_key)
+    {
+        let data = extra_account_meta_list.try_borrow_data()?;
+        let tlv_state = TlvStateBorrowed::unpack(&data)?;
+        let extra_account_metas =
+            ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&tlv_state)?;
+        accounts_len = accounts_len
+            .safe_add(1)?
+            .safe_add(extra_account_metas.data().len())?;
+    }
+
+    require!(
+        remaining_accounts.len() >= accounts_len,
+        PoolError::InvalidTransferHookAccounts
+    );
+    let (transfer_hook_accounts, rest) = remaining_accounts.split_at(accounts_len);
+    *remaining_accounts = rest;
+
+    Ok(transfer_hook_accounts)
+}
+
+/// Transfer out of the pool is blocked when either the vault or the destination token account is frozen.
+/// Paused mint isn't checked, pools can't be created with Token-2022 `Pausable` extension
+pub fn is_transfer_frozen(vault: &TokenAccount, destination: &TokenAccount) -> bool {
+    vault.is_frozen() || destination.is_frozen()
 }
 
 pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
@@ -235,7 +306,8 @@ pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool>
         match e {
             ExtensionType::TransferFeeConfig
             | ExtensionType::MetadataPointer
-            | ExtensionType::TokenMetadata => {
+            | ExtensionType::TokenMetadata
+            | ExtensionType::InterestBearingConfig => {
                 // permissionless supported
             }
             ExtensionType::TransferHook => {
@@ -258,15 +330,96 @@ pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool>
     Ok(true)
 }
 
+/// Token badges of token a and token b take the first 2 remaining accounts of pool initialization
+/// if any of the tokens requires token badge
+pub fn skip_token_badges<'c: 'info, 'info>(
+    token_a_mint: &InterfaceAccount<'info, Mint>,
+    token_b_mint: &InterfaceAccount<'info, Mint>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<&'c [AccountInfo<'info>]> {
+    if is_supported_mint(token_a_mint)? && is_supported_mint(token_b_mint)? {
+        return Ok(remaining_accounts);
+    }
+    Ok(remaining_accounts.get(2..).unwrap_or_default())
+}
+
 pub fn is_token_badge_initialized<'c: 'info, 'info>(
     mint: Pubkey,
     token_badge: &'c AccountInfo<'info>,
 ) -> Result<bool> {
+    let (token_badge_key, _) =
+        Pubkey::find_program_address(&[TOKEN_BADGE_PREFIX, mint.as_ref()], &crate::ID);
+    require!(
+        token_badge.key() == token_badge_key,
+        PoolError::InvalidTokenBadge
+    );
+    // token badge slot may be filled with the uninitialized token badge address when transfer hook accounts follow
+    if *token_badge.owner != crate::ID {
+        return Ok(false);
+    }
     let token_badge: AccountLoader<'_, TokenBadge> = AccountLoader::try_from(token_badge)?;
     let token_badge = token_badge.load()?;
     Ok(token_badge.token_mint == mint)
 }
 
+/// Validate token badge of a mint that is not supported permissionlessly, the mint must follow the
+/// extension policy of the badge. Returns the expiry timestamp of the badge
+pub fn validate_token_badge<'c: 'info, 'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_badge: &'c AccountInfo<'info>,
+) -> Result<u64> {
+    require!(
+        is_token_badge_initialized(token_mint.key(), token_badge)?,
+        PoolError::InvalidTokenBadge
+    );
+    let token_badge: AccountLoader<'_, TokenBadge> = AccountLoader::try_from(token_badge)?;
+    let token_badge = token_badge.load()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(
+        !token_badge.is_expired(current_timestamp),
+        PoolError::TokenBadgeExpired
+    );
+
+    let mint_info = token_mint.to_account_info();
+    if *mint_info.owner == Token::id() {
+        return Ok(token_badge.expiry_timestamp);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    for extension_type in mint.get_extension_types()? {
+        require!(
+            token_badge.is_extension_allowed(extension_type.into()),
+            PoolError::TokenBadgePolicyViolation
+        );
+    }
+
+    if let Some(transfer_hook_program_id) = extension::transfer_hook::get_program_id(&mint) {
+        require!(
+            token_badge.is_transfer_hook_program_allowed(transfer_hook_program_id),
+            PoolError::TokenBadgePolicyViolation
+        );
+    }
+
+    if let Ok(transfer_fee_config) =
+        mint.get_extension::<extension::transfer_fee::TransferFeeConfig>()
+    {
+        // both scheduled and current transfer fee must be in the limit
+        for transfer_fee in [
+            transfer_fee_config.older_transfer_fee,
+            transfer_fee_config.newer_transfer_fee,
+        ] {
+            require!(
+                token_badge.is_transfer_fee_allowed(transfer_fee.transfer_fee_basis_points.into()),
+                PoolError::TokenBadgePolicyViolation
+            );
+        }
+    }
+
+    Ok(token_badge.expiry_timestamp)
+}
+
 pub fn update_account_lamports_to_minimum_balance<'info>(
     account: AccountInfo<'info>,
     payer: AccountInfo<'info>,
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::{state::Pool, PoolError};
+
+#[derive(Accounts)]
+pub struct WritablePoolRemainingAccount<'info> {
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+}
+
+/// Load the pool as writable from the front of remaining accounts, only if the pool tracks vesting
+/// locked liquidity. Endpoints changing vesting locked liquidity take the pool read only, so pools which
+/// don't track it aren't write locked.
+pub fn load_writable_pool_if_vesting_liquidity_tracked<'c: 'info, 'info>(
+    pool_key: Pubkey,
+    pool: &Pool,
+    remaining_accounts: &mut &'c [AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, Pool>>> {
+    if !pool.is_vesting_liquidity_tracked() {
+        return Ok(None);
+    }
+
+    let account = WritablePoolRemainingAccount::try_accounts(
+        &crate::ID,
+        remaining_accounts,
+        &[],
+        &mut WritablePoolRemainingAccountBumps {},
+        &mut BTreeSet::new(),
+    )
+    .map_err(|_| PoolError::InvalidWritablePool)?;
+
+    require!(
+        account.pool.key() == pool_key,
+        PoolError::InvalidWritablePool
+    );
+
+    Ok(Some(account.pool))
+}
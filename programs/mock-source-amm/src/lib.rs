@@ -0,0 +1,131 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+//! Mock of a source pool migrating its liquidity to cp-amm through CPI, only used by tests.
+//! Tokens of the source pool are held by the `migrator` PDA, which is also the pool creator
+//! authority of the cp-amm dynamic config.
+use anchor_lang::prelude::*;
+use cp_amm::{cpi::accounts::MigratePoolCtx, program::CpAmm, MigratePoolParameters};
+
+declare_id!("EdwRxTupDH8i25mg54XN7FfUd5sn2PzQXhLZJB7PLmXp");
+
+pub const MIGRATOR_PREFIX: &[u8] = b"migrator";
+
+#[program]
+pub mod mock_source_amm {
+    use super::*;
+
+    pub fn migrate<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateCtx<'info>>,
+        params: MigratePoolParameters,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[MIGRATOR_PREFIX, &[ctx.bumps.migrator]]];
+        let accounts = MigratePoolCtx {
+            creator: ctx.accounts.creator.to_account_info(),
+            owner: ctx.accounts.owner.to_account_info(),
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            migrator: ctx.accounts.migrator.to_account_info(),
+            pool_creator_authority: ctx.accounts.migrator.to_account_info(),
+            config: ctx.accounts.config.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
+            token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            migrator_token_a: ctx.accounts.migrator_token_a.to_account_info(),
+            migrator_token_b: ctx.accounts.migrator_token_b.to_account_info(),
+            token_a_program: ctx.accounts.token_a_program.to_account_info(),
+            token_b_program: ctx.accounts.token_b_program.to_account_info(),
+            token_2022_program: ctx.accounts.token_2022_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.cp_amm_program.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.cp_amm_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+        cp_amm::cpi::migrate_pool(cpi_context, params)
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateCtx<'info> {
+    /// CHECK: owner of the source tokens and pool creator authority of the config
+    #[account(seeds = [MIGRATOR_PREFIX], bump)]
+    pub migrator: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub token_a_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub token_b_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub migrator_token_a: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    #[account(mut)]
+    pub migrator_token_b: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub token_a_program: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub token_b_program: UncheckedAccount<'info>,
+
+    /// CHECK: validated by cp-amm
+    pub token_2022_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: validated by cp-amm
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub cp_amm_program: Program<'info, CpAmm>,
+}
@@ -0,0 +1,33 @@
+use anyhow::{ensure, Ok, Result};
+use cp_amm::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    curve::{get_initialize_amounts, get_liquidity_from_amount_a},
+};
+
+/// Liquidity and token a amount deposited by `initialize_single_sided_pool`.
+/// Pool price starts at min_sqrt_price, so no token b is deposited
+pub fn calculate_single_sided_liquidity(
+    token_a_amount: u64,
+    min_sqrt_price: u128,
+    max_sqrt_price: u128,
+) -> Result<(u128, u64)> {
+    ensure!(
+        min_sqrt_price >= MIN_SQRT_PRICE
+            && max_sqrt_price <= MAX_SQRT_PRICE
+            && min_sqrt_price < max_sqrt_price,
+        "Invalid price range"
+    );
+    ensure!(token_a_amount != 0, "Token amount must be non-zero");
+
+    let liquidity = get_liquidity_from_amount_a(token_a_amount, min_sqrt_price, max_sqrt_price)?;
+    ensure!(liquidity > 0, "Token amount is too small");
+
+    let (deposited_amount_a, deposited_amount_b) =
+        get_initialize_amounts(min_sqrt_price, max_sqrt_price, min_sqrt_price, liquidity)?;
+    ensure!(
+        deposited_amount_a > 0 && deposited_amount_b == 0,
+        "Token amount is too small"
+    );
+
+    Ok((liquidity, deposited_amount_a))
+}
@@ -0,0 +1,79 @@
+use anyhow::{Ok, Result};
+use cp_amm::{
+    state::{LockedFeeExtension, Pool, Position},
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u128,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnclaimedFee {
+    pub fee_a: u64,
+    pub fee_b: u64,
+}
+
+/// Estimate claimable fees of a position, locked fee extension is required if the pool has one
+pub fn get_unclaimed_fee(
+    pool: &Pool,
+    position: &Position,
+    locked_fee_extension: Option<&LockedFeeExtension>,
+) -> Result<UnclaimedFee> {
+    let mut position = *position;
+
+    if let Some(locked_fee_extension) = locked_fee_extension {
+        let locked_liquidity =
+            position.get_fee_share_locked_liquidity(pool.is_vesting_liquidity_tracked())?;
+        position.update_locked_fee(
+            locked_fee_extension.fee_a_per_locked_liquidity(),
+            locked_fee_extension.fee_b_per_locked_liquidity(),
+            locked_liquidity,
+        )?;
+    }
+    position.update_fee(pool.fee_a_per_liquidity(), pool.fee_b_per_liquidity())?;
+
+    Ok(UnclaimedFee {
+        fee_a: position.fee_a_pending,
+        fee_b: position.fee_b_pending,
+    })
+}
+
+/// Estimate the part of a LP fee a position earns at the current liquidity, locked liquidity earns
+/// the locked fee share on top of its share as liquidity
+pub fn get_position_lp_fee(
+    pool: &Pool,
+    position: &Position,
+    locked_fee_extension: Option<&LockedFeeExtension>,
+    lp_fee: u64,
+) -> Result<u64> {
+    if pool.liquidity == 0 {
+        return Ok(0);
+    }
+
+    let locked_liquidity = pool.get_fee_share_locked_liquidity()?;
+    let locked_fee = match locked_fee_extension {
+        Some(locked_fee_extension) => {
+            locked_fee_extension.get_locked_fee(lp_fee, locked_liquidity)?
+        }
+        None => 0,
+    };
+
+    let position_liquidity = position.get_total_liquidity()?;
+    let mut position_fee = safe_mul_div_cast_u128(
+        (lp_fee - locked_fee).into(),
+        position_liquidity,
+        pool.liquidity,
+        Rounding::Down,
+    )?;
+
+    if locked_fee > 0 {
+        let position_locked_liquidity =
+            position.get_fee_share_locked_liquidity(pool.is_vesting_liquidity_tracked())?;
+        position_fee += safe_mul_div_cast_u128(
+            locked_fee.into(),
+            position_locked_liquidity,
+            locked_liquidity,
+            Rounding::Down,
+        )?;
+    }
+
+    Ok(u64::try_from(position_fee)?)
+}
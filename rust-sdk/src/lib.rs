@@ -1,7 +1,11 @@
 pub mod calculate_init_sqrt_price;
+pub mod calculate_single_sided_liquidity;
+pub mod fee;
 pub mod quote_exact_in;
 pub mod quote_exact_out;
 pub mod quote_partial_fill_in;
+pub mod reward;
+pub mod ui_amount;
 mod utils;
 
 #[cfg(test)]
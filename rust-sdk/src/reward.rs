@@ -0,0 +1,130 @@
+use anyhow::{ensure, Ok, Result};
+use cp_amm::{
+    constants::{NUM_REWARDS, REWARD_RATE_SCALE},
+    state::{Pool, Position, RewardInfo},
+    u128x128_math::Rounding,
+    utils_math::{safe_mul_div_cast_u128, safe_mul_shr_cast},
+};
+use std::cmp::max;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewardStatus {
+    /// reward is funded with a schedule that has not started yet
+    Scheduled,
+    /// reward is being distributed
+    Active,
+    /// reward is not distributed anymore
+    Ended,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RewardSchedule {
+    pub status: RewardStatus,
+    /// timestamp when reward starts being distributed
+    pub reward_duration_start: u64,
+    /// timestamp when reward stops being distributed
+    pub reward_duration_end: u64,
+    /// reward distributed per second, rounded down
+    pub reward_per_second: u64,
+    /// reward that is not distributed yet
+    pub remaining_reward: u64,
+}
+
+pub fn get_reward_schedule(
+    reward_info: &RewardInfo,
+    current_timestamp: u64,
+) -> Result<RewardSchedule> {
+    ensure!(reward_info.initialized(), "reward is not initialized");
+
+    let reward_duration_start = reward_info.reward_duration_start;
+    let reward_duration_end = reward_info.reward_duration_end;
+
+    let status = if reward_info.is_scheduled(current_timestamp) {
+        RewardStatus::Scheduled
+    } else if current_timestamp < reward_duration_end {
+        RewardStatus::Active
+    } else {
+        RewardStatus::Ended
+    };
+
+    let remaining_seconds = if status == RewardStatus::Ended {
+        0
+    } else {
+        // reward_duration_end is always after both current_timestamp and reward_duration_start here
+        reward_duration_end - max(current_timestamp, reward_duration_start)
+    };
+
+    let reward_per_second: u64 = safe_mul_shr_cast(reward_info.reward_rate, 1, REWARD_RATE_SCALE)?;
+    let remaining_reward: u64 = safe_mul_shr_cast(
+        reward_info.reward_rate,
+        remaining_seconds.into(),
+        REWARD_RATE_SCALE,
+    )?;
+
+    Ok(RewardSchedule {
+        status,
+        reward_duration_start,
+        reward_duration_end,
+        reward_per_second,
+        remaining_reward,
+    })
+}
+
+/// Estimate claimable rewards of a position at current_timestamp
+pub fn get_unclaimed_reward(
+    pool: &Pool,
+    position: &Position,
+    current_timestamp: u64,
+) -> Result<[u64; NUM_REWARDS]> {
+    let mut pool = *pool;
+    let mut position = *position;
+
+    if pool.pool_reward_initialized() {
+        pool.update_rewards(current_timestamp)?;
+        position.update_position_reward(&pool)?;
+    }
+
+    Ok(position
+        .reward_infos
+        .map(|reward_info| reward_info.reward_pendings))
+}
+
+/// Estimate reward per second a position currently earns, locked liquidity is weighted by reward boost
+pub fn get_position_reward_per_second(
+    pool: &Pool,
+    position: &Position,
+    reward_index: usize,
+    current_timestamp: u64,
+) -> Result<u64> {
+    ensure!(reward_index < NUM_REWARDS, "invalid reward index");
+
+    let reward_info = &pool.reward_infos[reward_index];
+    let schedule = get_reward_schedule(reward_info, current_timestamp)?;
+    if schedule.status != RewardStatus::Active {
+        return Ok(0);
+    }
+
+    let liquidity_supply = pool.get_reward_liquidity_supply(reward_index)?;
+    if liquidity_supply == 0 {
+        return Ok(0);
+    }
+
+    let position_liquidity = reward_info.get_boosted_liquidity(
+        position.get_total_liquidity()?,
+        position.vested_liquidity,
+        position.permanent_locked_liquidity,
+    )?;
+
+    let position_reward_rate = safe_mul_div_cast_u128(
+        reward_info.reward_rate,
+        position_liquidity,
+        liquidity_supply,
+        Rounding::Down,
+    )?;
+
+    Ok(safe_mul_shr_cast(
+        position_reward_rate,
+        1,
+        REWARD_RATE_SCALE,
+    )?)
+}
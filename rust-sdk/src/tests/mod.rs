@@ -1,7 +1,11 @@
 pub mod test_calculate_init_sqrt_price;
+pub mod test_calculate_single_sided_liquidity;
+pub mod test_fee;
 pub mod test_quote_exact_in;
 pub mod test_quote_exact_out;
 pub mod test_quote_partial_fill_in;
+pub mod test_reward;
+pub mod test_ui_amount;
 
 use cp_amm::state::Pool;
 use std::fs;
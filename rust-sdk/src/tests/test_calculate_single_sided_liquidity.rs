@@ -0,0 +1,42 @@
+use cp_amm::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    state::{ModifyLiquidityResult, Pool},
+    u128x128_math::Rounding,
+};
+
+use crate::calculate_single_sided_liquidity::calculate_single_sided_liquidity;
+
+#[test]
+fn test_single_sided_liquidity() {
+    let token_a_amount = 1_000_000 * 1_000_000_000u64;
+    let min_sqrt_price = 1u128 << 64;
+    let max_sqrt_price = MAX_SQRT_PRICE;
+
+    let (liquidity, deposited_amount_a) =
+        calculate_single_sided_liquidity(token_a_amount, min_sqrt_price, max_sqrt_price).unwrap();
+    assert!(deposited_amount_a <= token_a_amount);
+    assert!(token_a_amount - deposited_amount_a <= 1);
+
+    // adding the same liquidity to the initialized pool requires no token b
+    let pool = Pool {
+        sqrt_min_price: min_sqrt_price,
+        sqrt_max_price: max_sqrt_price,
+        sqrt_price: min_sqrt_price,
+        ..Default::default()
+    };
+    let ModifyLiquidityResult {
+        token_a_amount: amount_a,
+        token_b_amount: amount_b,
+    } = pool
+        .get_amounts_for_modify_liquidity(liquidity, Rounding::Up)
+        .unwrap();
+    assert_eq!(amount_a, deposited_amount_a);
+    assert_eq!(amount_b, 0);
+}
+
+#[test]
+fn test_single_sided_liquidity_rejects_invalid_input() {
+    assert!(calculate_single_sided_liquidity(0, MIN_SQRT_PRICE, MAX_SQRT_PRICE).is_err());
+    assert!(calculate_single_sided_liquidity(1_000, 1u128 << 64, 1u128 << 64).is_err());
+    assert!(calculate_single_sided_liquidity(1_000, MIN_SQRT_PRICE - 1, MAX_SQRT_PRICE).is_err());
+}
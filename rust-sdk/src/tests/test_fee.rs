@@ -0,0 +1,73 @@
+use cp_amm::state::{LockedFeeExtension, Position};
+
+use crate::{
+    fee::{get_position_lp_fee, get_unclaimed_fee},
+    tests::{get_pool_account, MACK_USDC_ADDRESS},
+};
+
+#[test]
+fn test_position_lp_fee_with_locked_fee_share() {
+    let mut pool = get_pool_account(MACK_USDC_ADDRESS);
+    let liquidity = pool.liquidity / 4;
+    pool.permanent_lock_liquidity = liquidity;
+    pool.vesting_liquidity_flag = 0;
+
+    let locked_position = Position {
+        permanent_locked_liquidity: liquidity,
+        ..Default::default()
+    };
+    let unlocked_position = Position {
+        unlocked_liquidity: liquidity,
+        ..Default::default()
+    };
+
+    let lp_fee = 1_000_000;
+    let unlocked_fee = get_position_lp_fee(&pool, &unlocked_position, None, lp_fee).unwrap();
+    let locked_fee = get_position_lp_fee(&pool, &locked_position, None, lp_fee).unwrap();
+    assert_eq!(unlocked_fee, locked_fee);
+
+    let mut locked_fee_extension = LockedFeeExtension::default();
+    locked_fee_extension.initialize(Default::default(), 1_000);
+
+    let unlocked_fee_with_share = get_position_lp_fee(
+        &pool,
+        &unlocked_position,
+        Some(&locked_fee_extension),
+        lp_fee,
+    )
+    .unwrap();
+    let locked_fee_with_share =
+        get_position_lp_fee(&pool, &locked_position, Some(&locked_fee_extension), lp_fee).unwrap();
+
+    assert!(unlocked_fee_with_share < unlocked_fee);
+    assert!(locked_fee_with_share > locked_fee);
+    // the only locked position takes the whole locked fee share
+    assert!(locked_fee_with_share - unlocked_fee_with_share >= 99_999);
+}
+
+#[test]
+fn test_unclaimed_fee_includes_locked_fee() {
+    let pool = get_pool_account(MACK_USDC_ADDRESS);
+
+    let locked_liquidity = 1_000_000;
+    let position = Position {
+        permanent_locked_liquidity: locked_liquidity,
+        fee_a_per_token_checkpoint: pool.fee_a_per_liquidity,
+        fee_b_per_token_checkpoint: pool.fee_b_per_liquidity,
+        ..Default::default()
+    };
+
+    // 1 token b per locked liquidity unit, scaled
+    let mut locked_fee_extension = LockedFeeExtension::default();
+    let mut fee_b_per_locked_liquidity = [0u8; 32];
+    fee_b_per_locked_liquidity[16] = 1;
+    locked_fee_extension.fee_b_per_locked_liquidity = fee_b_per_locked_liquidity;
+
+    let unclaimed_fee = get_unclaimed_fee(&pool, &position, None).unwrap();
+    assert_eq!(unclaimed_fee.fee_a, 0);
+    assert_eq!(unclaimed_fee.fee_b, 0);
+
+    let unclaimed_fee = get_unclaimed_fee(&pool, &position, Some(&locked_fee_extension)).unwrap();
+    assert_eq!(unclaimed_fee.fee_a, 0);
+    assert_eq!(u128::from(unclaimed_fee.fee_b), locked_liquidity);
+}
@@ -0,0 +1,97 @@
+use cp_amm::state::Position;
+
+use crate::{
+    reward::{
+        get_position_reward_per_second, get_reward_schedule, get_unclaimed_reward, RewardStatus,
+    },
+    tests::{get_pool_account, MACK_USDC_ADDRESS},
+};
+
+#[test]
+fn test_scheduled_reward() {
+    let mut pool = get_pool_account(MACK_USDC_ADDRESS);
+
+    let current_timestamp: u64 = 1_753_751_761;
+    let reward_duration_start = current_timestamp + 86_400;
+    let reward_duration_end = reward_duration_start + 86_400;
+    let funding_amount = 86_400_000;
+
+    let reward_info = &mut pool.reward_infos[0];
+    reward_info.initialized = 1;
+    reward_info
+        .update_rate_after_scheduled_funding(
+            current_timestamp,
+            reward_duration_start,
+            reward_duration_end,
+            funding_amount,
+        )
+        .unwrap();
+
+    let schedule = get_reward_schedule(&pool.reward_infos[0], current_timestamp).unwrap();
+    assert_eq!(schedule.status, RewardStatus::Scheduled);
+    assert_eq!(schedule.reward_duration_start, reward_duration_start);
+    assert_eq!(schedule.reward_duration_end, reward_duration_end);
+    assert_eq!(schedule.reward_per_second, 1_000);
+    assert_eq!(schedule.remaining_reward, funding_amount);
+
+    let schedule = get_reward_schedule(&pool.reward_infos[0], reward_duration_start + 400).unwrap();
+    assert_eq!(schedule.status, RewardStatus::Active);
+    assert_eq!(schedule.remaining_reward, funding_amount - 400_000);
+
+    let schedule = get_reward_schedule(&pool.reward_infos[0], reward_duration_end).unwrap();
+    assert_eq!(schedule.status, RewardStatus::Ended);
+    assert_eq!(schedule.remaining_reward, 0);
+
+    // position owns the whole pool liquidity
+    let position = Position {
+        unlocked_liquidity: pool.liquidity,
+        ..Default::default()
+    };
+
+    let rewards = get_unclaimed_reward(&pool, &position, reward_duration_start).unwrap();
+    assert_eq!(rewards[0], 0);
+
+    let rewards = get_unclaimed_reward(&pool, &position, reward_duration_start + 400).unwrap();
+    assert!(rewards[0] <= 400_000 && rewards[0] >= 399_999);
+
+    let rewards = get_unclaimed_reward(&pool, &position, reward_duration_end + 400).unwrap();
+    assert!(rewards[0] <= funding_amount && rewards[0] >= funding_amount - 1);
+}
+
+#[test]
+fn test_position_reward_per_second_with_boost() {
+    let mut pool = get_pool_account(MACK_USDC_ADDRESS);
+
+    let current_timestamp: u64 = 1_753_751_761;
+    let liquidity = pool.liquidity / 4;
+    pool.liquidity = liquidity * 4;
+    pool.permanent_lock_liquidity = liquidity;
+
+    let reward_info = &mut pool.reward_infos[0];
+    reward_info.initialized = 1;
+    reward_info.reward_duration = 86_400;
+    // permanent locked liquidity counts 2x
+    reward_info.permanent_lock_boost_bps = 10_000;
+    reward_info
+        .update_rate_after_funding(current_timestamp, 86_400_000)
+        .unwrap();
+
+    let unlocked_position = Position {
+        unlocked_liquidity: liquidity,
+        ..Default::default()
+    };
+    let permanent_locked_position = Position {
+        permanent_locked_liquidity: liquidity,
+        ..Default::default()
+    };
+
+    let unlocked_reward_per_second =
+        get_position_reward_per_second(&pool, &unlocked_position, 0, current_timestamp).unwrap();
+    let permanent_locked_reward_per_second =
+        get_position_reward_per_second(&pool, &permanent_locked_position, 0, current_timestamp)
+            .unwrap();
+
+    // supply is 5 * liquidity
+    assert!(unlocked_reward_per_second.abs_diff(200) <= 1);
+    assert!(permanent_locked_reward_per_second.abs_diff(400) <= 1);
+}
@@ -0,0 +1,104 @@
+use crate::ui_amount::{
+    amount_to_ui_amount, get_price_from_sqrt_price, get_ui_amount_multiplier,
+    get_ui_price_from_sqrt_price, ui_amount_to_amount,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensionsMut, ExtensionType,
+        StateWithExtensionsMut,
+    },
+    solana_program::program_pack::Pack,
+    state::Mint,
+};
+
+const SECONDS_PER_YEAR: i64 = 31_556_736;
+
+fn get_mint_data(decimals: u8) -> Vec<u8> {
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(
+        Mint {
+            decimals,
+            is_initialized: true,
+            ..Default::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+    data
+}
+
+fn get_interest_bearing_mint_data(decimals: u8, rate: i16) -> Vec<u8> {
+    let len =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::InterestBearingConfig])
+            .unwrap();
+    let mut data = vec![0; len];
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    let config = mint.init_extension::<InterestBearingConfig>(true).unwrap();
+    config.pre_update_average_rate = rate.into();
+    config.current_rate = rate.into();
+    mint.base = Mint {
+        decimals,
+        is_initialized: true,
+        ..Default::default()
+    };
+    mint.pack_base();
+    mint.init_account_type().unwrap();
+    data
+}
+
+#[test]
+fn test_ui_amount_without_interest() {
+    let mint_data = get_mint_data(6);
+
+    let multiplier = get_ui_amount_multiplier(&mint_data, SECONDS_PER_YEAR).unwrap();
+    assert_eq!(multiplier, 0.000_001);
+
+    assert_eq!(amount_to_ui_amount(1_500_000, &mint_data, 0).unwrap(), 1.5);
+    assert_eq!(ui_amount_to_amount(1.5, &mint_data, 0).unwrap(), 1_500_000);
+}
+
+#[test]
+fn test_ui_amount_with_interest() {
+    // 5% per year
+    let mint_data = get_interest_bearing_mint_data(6, 500);
+
+    // no interest accrued at initialization
+    assert_eq!(amount_to_ui_amount(1_000_000, &mint_data, 0).unwrap(), 1.0);
+
+    let ui_amount = amount_to_ui_amount(1_000_000, &mint_data, SECONDS_PER_YEAR).unwrap();
+    assert!((ui_amount - 0.05_f64.exp()).abs() < 1e-9);
+
+    // raw amount is rounded down
+    let amount = ui_amount_to_amount(ui_amount, &mint_data, SECONDS_PER_YEAR).unwrap();
+    assert!(amount == 1_000_000 || amount == 999_999);
+
+    assert!(ui_amount_to_amount(-1.0, &mint_data, SECONDS_PER_YEAR).is_err());
+}
+
+#[test]
+fn test_ui_price_reflects_accrued_interest() {
+    // 1 raw token a = 1 raw token b
+    let sqrt_price: u128 = 1 << 64;
+    assert_eq!(get_price_from_sqrt_price(sqrt_price), 1.0);
+
+    let token_a_mint_data = get_interest_bearing_mint_data(6, 500);
+    let token_b_mint_data = get_mint_data(6);
+
+    let ui_price =
+        get_ui_price_from_sqrt_price(sqrt_price, &token_a_mint_data, &token_b_mint_data, 0)
+            .unwrap();
+    assert_eq!(ui_price, 1.0);
+
+    // UI amount of token a grows with interest, so the same raw price is a lower UI price
+    let ui_price = get_ui_price_from_sqrt_price(
+        sqrt_price,
+        &token_a_mint_data,
+        &token_b_mint_data,
+        SECONDS_PER_YEAR,
+    )
+    .unwrap();
+    assert!((ui_price - (-0.05_f64).exp()).abs() < 1e-9);
+
+    // invalid mint data
+    assert!(get_ui_amount_multiplier(&[0; 16], 0).is_err());
+}
@@ -0,0 +1,66 @@
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use anyhow::{ensure, Context, Result};
+
+/// Multiplier converting raw amount of a mint to UI amount at current_timestamp.
+/// It includes decimals and the interest accrued by interest bearing mint, mint_data is the data
+/// of either SPL token or token2022 mint account
+pub fn get_ui_amount_multiplier(mint_data: &[u8], current_timestamp: i64) -> Result<f64> {
+    let mint =
+        StateWithExtensions::<Mint>::unpack(mint_data).context("failed to unpack mint data")?;
+    let decimals_multiplier = 10_f64.powi(-i32::from(mint.base.decimals));
+
+    let interest_multiplier = if let Ok(config) = mint.get_extension::<InterestBearingConfig>() {
+        config
+            .amount_to_ui_amount(1, 0, current_timestamp)
+            .and_then(|ui_amount| ui_amount.parse::<f64>().ok())
+            .context("failed to calculate accrued interest")?
+    } else {
+        1.0
+    };
+
+    Ok(decimals_multiplier * interest_multiplier)
+}
+
+/// Convert raw amount to UI amount
+pub fn amount_to_ui_amount(amount: u64, mint_data: &[u8], current_timestamp: i64) -> Result<f64> {
+    let multiplier = get_ui_amount_multiplier(mint_data, current_timestamp)?;
+    Ok(amount as f64 * multiplier)
+}
+
+/// Convert UI amount to raw amount, rounded down so the result can be used as quote input
+pub fn ui_amount_to_amount(
+    ui_amount: f64,
+    mint_data: &[u8],
+    current_timestamp: i64,
+) -> Result<u64> {
+    let multiplier = get_ui_amount_multiplier(mint_data, current_timestamp)?;
+    let amount = (ui_amount / multiplier).floor();
+    ensure!(
+        amount.is_finite() && amount >= 0.0 && amount <= u64::MAX as f64,
+        "invalid ui amount"
+    );
+    Ok(amount as u64)
+}
+
+/// Price of token a in token b with raw amounts
+pub fn get_price_from_sqrt_price(sqrt_price: u128) -> f64 {
+    let sqrt_price = sqrt_price as f64 / 2_f64.powi(64);
+    sqrt_price * sqrt_price
+}
+
+/// Price of token a in token b with UI amounts, reflects the interest accrued by interest bearing mints
+pub fn get_ui_price_from_sqrt_price(
+    sqrt_price: u128,
+    token_a_mint_data: &[u8],
+    token_b_mint_data: &[u8],
+    current_timestamp: i64,
+) -> Result<f64> {
+    let token_a_multiplier = get_ui_amount_multiplier(token_a_mint_data, current_timestamp)?;
+    let token_b_multiplier = get_ui_amount_multiplier(token_b_mint_data, current_timestamp)?;
+    Ok(get_price_from_sqrt_price(sqrt_price) * token_b_multiplier / token_a_multiplier)
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use cp_amm::{
-    state::{Pool, PoolStatus},
+    state::{Pool, PoolAction, PoolStatus},
     ActivationType,
 };
 
@@ -22,5 +22,7 @@ pub fn get_current_point(
 
 pub fn is_swap_enable(pool: &Pool, current_point: u64) -> Result<bool> {
     let pool_status = PoolStatus::try_from(pool.pool_status).context("invalid pool status")?;
-    Ok(pool_status == PoolStatus::Enable && current_point >= pool.activation_point)
+    Ok(pool_status == PoolStatus::Enable
+        && !pool.is_action_disabled(PoolAction::Swap)
+        && current_point >= pool.activation_point)
 }
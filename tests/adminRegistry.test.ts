@@ -0,0 +1,137 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addAdminRole,
+  AdminRole,
+  closeConfigIx,
+  createConfigIx,
+  CreateConfigParams,
+  getAdminRegistry,
+  getCpAmmProgramErrorCodeHexString,
+  initializeAdminRegistry,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  removeAdminRole,
+  transferSuperAdmin,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Admin registry", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let superAdmin: Keypair;
+  let operator: Keypair;
+  let createConfigParams: CreateConfigParams;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    superAdmin = await generateKpAndFund(context.banksClient, context.payer);
+    operator = await generateKpAndFund(context.banksClient, context.payer);
+
+    createConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    await initializeAdminRegistry(context.banksClient, {
+      admin,
+      superAdmin: superAdmin.publicKey,
+    });
+  });
+
+  it("Admin instruction requires its role", async () => {
+    const errorCode = getCpAmmProgramErrorCodeHexString("InvalidAdmin");
+
+    // hard-coded admin can't act once admin registry is initialized
+    await expectThrowsAsync(async () => {
+      await createConfigIx(
+        context.banksClient,
+        operator,
+        new BN(randomID()),
+        createConfigParams
+      );
+    }, errorCode);
+
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+    const config = await createConfigIx(
+      context.banksClient,
+      operator,
+      new BN(randomID()),
+      createConfigParams
+    );
+
+    await removeAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+    await expectThrowsAsync(async () => {
+      await closeConfigIx(context.banksClient, operator, config);
+    }, errorCode);
+
+    // super admin holds all roles
+    await closeConfigIx(context.banksClient, superAdmin, config);
+  });
+
+  it("Only super admin can manage roles", async () => {
+    const errorCode = getCpAmmProgramErrorCodeHexString("InvalidAdmin");
+    await expectThrowsAsync(async () => {
+      await addAdminRole(context.banksClient, {
+        superAdmin: operator,
+        admin: operator.publicKey,
+        role: AdminRole.ConfigManager,
+      });
+    }, errorCode);
+
+    await transferSuperAdmin(context.banksClient, {
+      superAdmin,
+      newSuperAdmin: operator.publicKey,
+    });
+    const adminRegistry = await getAdminRegistry(context.banksClient);
+    expect(adminRegistry.superAdmin.toString()).eq(
+      operator.publicKey.toString()
+    );
+
+    await addAdminRole(context.banksClient, {
+      superAdmin: operator,
+      admin: admin.publicKey,
+      role: AdminRole.PoolStatusOperator,
+    });
+    await expectThrowsAsync(async () => {
+      await removeAdminRole(context.banksClient, {
+        superAdmin,
+        admin: admin.publicKey,
+        role: AdminRole.PoolStatusOperator,
+      });
+    }, errorCode);
+  });
+});
@@ -88,6 +88,47 @@ export function deriveClaimFeeOperatorAddress(operator: PublicKey): PublicKey {
   )[0];
 }
 
+export function deriveAdminRegistryAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_registry")],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveProtocolFeeRoutingAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_fee_routing")],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveProposalAddress(
+  target: PublicKey,
+  action: number
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("proposal"), target.toBuffer(), Buffer.from([action])],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePoolPermissionAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_permission"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveAllowlistEntryAddress(
+  pool: PublicKey,
+  wallet: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("allowlist_entry"), pool.toBuffer(), wallet.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
 export function derivePositionNftAccount(
   positionNftMint: PublicKey
 ): PublicKey {
@@ -96,3 +137,95 @@ export function derivePositionNftAccount(
     CP_AMM_PROGRAM_ID
   )[0];
 }
+
+export function deriveRewardExtensionAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_extension"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePositionRewardExtensionAddress(
+  position: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position_reward_extension"), position.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveLockedFeeExtensionAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("locked_fee_extension"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFungibleShareAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fungible_share"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFungibleShareMintAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fungible_share_mint"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveBribeLedgerAddress(pool: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bribe_ledger"), pool.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function derivePositionBribeLedgerAddress(
+  position: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position_bribe_ledger"), position.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveBribeCampaignAddress(
+  pool: PublicKey,
+  funder: PublicKey,
+  mint: PublicKey,
+  epoch: BN
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("bribe_campaign"),
+      pool.toBuffer(),
+      funder.toBuffer(),
+      mint.toBuffer(),
+      epoch.toArrayLike(Buffer, "le", 8),
+    ],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveBribeVaultAddress(bribeCampaign: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bribe_vault"), bribeCampaign.toBuffer()],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
+
+export function deriveBribeClaimReceiptAddress(
+  bribeCampaign: PublicKey,
+  position: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("bribe_claim_receipt"),
+      bribeCampaign.toBuffer(),
+      position.toBuffer(),
+    ],
+    CP_AMM_PROGRAM_ID
+  )[0];
+}
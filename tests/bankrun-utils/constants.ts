@@ -9,6 +9,10 @@ export const ALPHA_VAULT_PROGRAM_ID = new PublicKey(
   "SNPmGgnywBvvrAKMLundzG6StojyHTHDLu7T4sdhP4k"
 );
 
+export const MOCK_SOURCE_AMM_PROGRAM_ID = new PublicKey(
+  "EdwRxTupDH8i25mg54XN7FfUd5sn2PzQXhLZJB7PLmXp"
+);
+
 export const TREASURY = new PublicKey(
   "4EWqcx3aNZmMetCnxwLYwyNjan6XLGp3Ca2W316vrSjv"
 );
@@ -39,3 +43,9 @@ export const TEST_TRANSFER_HOOK_PROGRAM_ID = new PublicKey(
 );
 
 export const SPLIT_POSITION_DENOMINATOR = 1_000_000_000;
+
+export const NUM_REWARDS = 2;
+export const NUM_EXTENSION_REWARDS = 4;
+
+export const BRIBE_EPOCH_DURATION = 7 * 24 * 60 * 60;
+export const NUM_BRIBE_EPOCH_SNAPSHOTS = 12;
@@ -23,6 +23,8 @@ import {
   MetadataPointerLayout,
   unpackAccount,
   NATIVE_MINT,
+  createTransferCheckedInstruction,
+  getTransferHook,
 } from "@solana/spl-token";
 import { unpack } from "@solana/spl-token-metadata";
 import {
@@ -36,33 +38,68 @@ import {
   SystemProgram,
   SYSVAR_INSTRUCTIONS_PUBKEY,
   Transaction,
+  AccountMeta,
 } from "@solana/web3.js";
 import { BanksClient } from "solana-bankrun";
 import CpAmmIDL from "../../target/idl/cp_amm.json";
 import { CpAmm } from "../../target/types/cp_amm";
 import { getOrCreateAssociatedTokenAccount, wrapSOL } from "./token";
 import {
+  deriveAdminRegistryAddress,
+  deriveAllowlistEntryAddress,
+  deriveBribeCampaignAddress,
+  deriveBribeClaimReceiptAddress,
+  deriveBribeLedgerAddress,
+  deriveBribeVaultAddress,
   deriveClaimFeeOperatorAddress,
   deriveConfigAddress,
   deriveCustomizablePoolAddress,
+  deriveFungibleShareAddress,
+  deriveFungibleShareMintAddress,
+  deriveLockedFeeExtensionAddress,
   derivePoolAddress,
   derivePoolAuthority,
+  derivePoolPermissionAddress,
   derivePositionAddress,
+  derivePositionBribeLedgerAddress,
   derivePositionNftAccount,
+  derivePositionRewardExtensionAddress,
+  deriveProtocolFeeRoutingAddress,
+  deriveRewardExtensionAddress,
   deriveRewardVaultAddress,
+  deriveProposalAddress,
   deriveTokenBadgeAddress,
   deriveTokenVaultAddress,
 } from "./accounts";
 import { processTransactionMaybeThrow } from "./common";
-import { CP_AMM_PROGRAM_ID } from "./constants";
+import { addExtraAccountMetasForExecute } from "./transferHook/transferHookUtils";
+import {
+  CP_AMM_PROGRAM_ID,
+  NUM_EXTENSION_REWARDS,
+  NUM_REWARDS,
+  TREASURY,
+} from "./constants";
 import { assert, expect } from "chai";
 
 export type Pool = IdlAccounts<CpAmm>["pool"];
 export type Position = IdlAccounts<CpAmm>["position"];
 export type Vesting = IdlAccounts<CpAmm>["vesting"];
+export type RewardExtension = IdlAccounts<CpAmm>["rewardExtension"];
+export type PositionRewardExtension =
+  IdlAccounts<CpAmm>["positionRewardExtension"];
+export type LockedFeeExtension = IdlAccounts<CpAmm>["lockedFeeExtension"];
+export type FungibleShare = IdlAccounts<CpAmm>["fungibleShare"];
+export type ProtocolFeeRouting = IdlAccounts<CpAmm>["protocolFeeRouting"];
+export type ProtocolFeeDestinationParameters =
+  IdlTypes<CpAmm>["protocolFeeDestinationParameters"];
+export type BribeLedger = IdlAccounts<CpAmm>["bribeLedger"];
+export type PositionBribeLedger = IdlAccounts<CpAmm>["positionBribeLedger"];
+export type BribeCampaign = IdlAccounts<CpAmm>["bribeCampaign"];
 export type Config = IdlAccounts<CpAmm>["config"];
 export type LockPositionParams = IdlTypes<CpAmm>["vestingParameters"];
 export type TokenBadge = IdlAccounts<CpAmm>["tokenBadge"];
+export type AdminRegistry = IdlAccounts<CpAmm>["adminRegistry"];
+export type Proposal = IdlAccounts<CpAmm>["proposal"];
 
 export function getSecondKey(key1: PublicKey, key2: PublicKey) {
   const buf1 = key1.toBuffer();
@@ -253,6 +290,26 @@ export async function closeConfigIx(
   expect(configState).to.be.null;
 }
 
+export async function updateConfigIx(
+  banksClient: BanksClient,
+  admin: Keypair,
+  config: PublicKey,
+  params: CreateConfigParams
+) {
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateConfig(params)
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
 export type CreateTokenBadgeParams = {
   tokenMint: PublicKey;
   admin: Keypair;
@@ -312,6 +369,75 @@ export async function closeTokenBadge(
   expect(tokenBadgeAccount).to.be.null;
 }
 
+export type UpdateTokenBadgeParams = {
+  tokenMint: PublicKey;
+  admin: Keypair;
+  allowedExtensions: BN;
+  expiryTimestamp: BN;
+  transferHookProgramId: PublicKey;
+  maxTransferFeeBps: number | null;
+};
+
+export async function updateTokenBadge(
+  banksClient: BanksClient,
+  params: UpdateTokenBadgeParams
+) {
+  const {
+    tokenMint,
+    admin,
+    allowedExtensions,
+    expiryTimestamp,
+    transferHookProgramId,
+    maxTransferFeeBps,
+  } = params;
+  const program = createCpAmmProgram();
+  const tokenBadge = deriveTokenBadgeAddress(tokenMint);
+  const transaction = await program.methods
+    .updateTokenBadge({
+      allowedExtensions,
+      expiryTimestamp,
+      transferHookProgramId,
+      maxTransferFeeBps,
+    })
+    .accountsPartial({
+      tokenBadge,
+      admin: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const tokenBadgeState = await getTokenBadge(banksClient, tokenBadge);
+  expect(tokenBadgeState.expiryTimestamp.toString()).eq(
+    expiryTimestamp.toString()
+  );
+}
+
+export async function refreshPoolTokenBadge(
+  banksClient: BanksClient,
+  params: {
+    payer: Keypair;
+    pool: PublicKey;
+    tokenMint: PublicKey;
+  }
+) {
+  const { payer, pool, tokenMint } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .refreshPoolTokenBadge()
+    .accountsPartial({
+      pool,
+      tokenBadge: deriveTokenBadgeAddress(tokenMint),
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
 export type ClaimFeeOperatorParams = {
   admin: Keypair;
   operator: PublicKey;
@@ -372,6 +498,107 @@ export async function closeClaimFeeOperator(
   expect(account).to.be.null;
 }
 
+// destination token accounts of each mint are only required once protocol fee
+// routing is initialized, token a accounts are followed by token b accounts
+export async function getProtocolFeeDestinationRemainingAccounts(
+  banksClient: BanksClient,
+  payer: Keypair,
+  mints: PublicKey[],
+  tokenPrograms: PublicKey[]
+): Promise<AccountMeta[]> {
+  const account = await banksClient.getAccount(
+    deriveProtocolFeeRoutingAddress()
+  );
+  if (account === null) {
+    return [];
+  }
+  const protocolFeeRouting = await getProtocolFeeRouting(banksClient);
+  const destinations = protocolFeeRouting.destinations.slice(
+    0,
+    protocolFeeRouting.destinationCount
+  );
+
+  const accounts: AccountMeta[] = [];
+  for (const [i, mint] of mints.entries()) {
+    for (const destination of destinations) {
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        banksClient,
+        payer,
+        mint,
+        destination.owner,
+        tokenPrograms[i]
+      );
+      accounts.push({
+        isSigner: false,
+        isWritable: true,
+        pubkey: tokenAccount,
+      });
+    }
+  }
+  return accounts;
+}
+
+export type ProtocolFeeRoutingParams = {
+  admin: Keypair;
+  destinations: ProtocolFeeDestinationParameters[];
+};
+
+export async function initializeProtocolFeeRouting(
+  banksClient: BanksClient,
+  params: ProtocolFeeRoutingParams
+) {
+  const { admin, destinations } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .initializeProtocolFeeRouting(destinations)
+    .accountsPartial({
+      protocolFeeRouting: deriveProtocolFeeRoutingAddress(),
+      admin: admin.publicKey,
+      adminRegistry: deriveAdminRegistryAddress(),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function updateProtocolFeeRouting(
+  banksClient: BanksClient,
+  params: ProtocolFeeRoutingParams
+) {
+  const { admin, destinations } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateProtocolFeeRouting(destinations)
+    .accountsPartial({
+      protocolFeeRouting: deriveProtocolFeeRoutingAddress(),
+      admin: admin.publicKey,
+      adminRegistry: deriveAdminRegistryAddress(),
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function getProtocolFeeRouting(
+  banksClient: BanksClient
+): Promise<ProtocolFeeRouting> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(
+    deriveProtocolFeeRoutingAddress()
+  );
+  return program.coder.accounts.decode(
+    "protocolFeeRouting",
+    Buffer.from(account.data)
+  );
+}
+
 export type ClaimProtocolFeeParams = {
   operator: Keypair;
   pool: PublicKey;
@@ -447,11 +674,104 @@ export async function claimProtocolFee(
       tokenBMint: poolState.tokenBMint,
       tokenAAccount,
       tokenBAccount,
+      protocolFeeRouting: deriveProtocolFeeRoutingAddress(),
       claimFeeOperator,
       operator: operator.publicKey,
       tokenAProgram,
       tokenBProgram,
     })
+    .remainingAccounts([
+      ...(await getProtocolFeeDestinationRemainingAccounts(
+        banksClient,
+        operator,
+        [poolState.tokenAMint, poolState.tokenBMint],
+        [tokenAProgram, tokenBProgram]
+      )),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        poolState.tokenAMint,
+        poolState.tokenBMint,
+      ])),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(operator);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type BatchClaimProtocolFeeParams = {
+  operator: Keypair;
+  pools: PublicKey[];
+  treasury: PublicKey;
+};
+export async function batchClaimProtocolFee(
+  banksClient: BanksClient,
+  params: BatchClaimProtocolFeeParams
+) {
+  const program = createCpAmmProgram();
+  const { operator, pools, treasury } = params;
+  const poolAuthority = derivePoolAuthority();
+  const claimFeeOperator = deriveClaimFeeOperatorAddress(operator.publicKey);
+
+  const remainingAccounts: AccountMeta[] = [];
+  for (const pool of pools) {
+    const poolState = await getPool(banksClient, pool);
+    const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+      .owner;
+    const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+      .owner;
+    const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+      banksClient,
+      operator,
+      poolState.tokenAMint,
+      treasury,
+      tokenAProgram
+    );
+    const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+      banksClient,
+      operator,
+      poolState.tokenBMint,
+      treasury,
+      tokenBProgram
+    );
+
+    // pool accounts are followed by destination token accounts if protocol fee
+    // is routed, then transfer hook accounts of token a and token b
+    remainingAccounts.push(
+      ...[
+        { pubkey: pool, isWritable: true },
+        { pubkey: poolState.tokenAVault, isWritable: true },
+        { pubkey: poolState.tokenAMint, isWritable: false },
+        { pubkey: tokenAProgram, isWritable: false },
+        { pubkey: poolState.tokenBVault, isWritable: true },
+        { pubkey: poolState.tokenBMint, isWritable: false },
+        { pubkey: tokenBProgram, isWritable: false },
+        { pubkey: tokenAAccount, isWritable: true },
+        { pubkey: tokenBAccount, isWritable: true },
+      ].map((account) => ({ ...account, isSigner: false })),
+      ...(await getProtocolFeeDestinationRemainingAccounts(
+        banksClient,
+        operator,
+        [poolState.tokenAMint, poolState.tokenBMint],
+        [tokenAProgram, tokenBProgram]
+      )),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        poolState.tokenAMint,
+        poolState.tokenBMint,
+      ]))
+    );
+  }
+
+  const transaction = await program.methods
+    .batchClaimProtocolFee()
+    .accountsPartial({
+      poolAuthority,
+      protocolFeeRouting: deriveProtocolFeeRoutingAddress(),
+      claimFeeOperator,
+      operator: operator.publicKey,
+    })
+    .remainingAccounts(remainingAccounts)
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
@@ -527,6 +847,35 @@ export type InitializePoolParams = {
   activationPoint: BN | null;
 };
 
+// token badges are required for mints with transfer hook, followed by
+// transfer hook accounts
+export async function getInitializePoolRemainingAccounts(
+  banksClient: BanksClient,
+  tokenAMint: PublicKey,
+  tokenBMint: PublicKey
+) {
+  const transferHookAccounts = await getTransferHookRemainingAccounts(
+    banksClient,
+    [tokenAMint, tokenBMint]
+  );
+  if (transferHookAccounts.length == 0) {
+    return [];
+  }
+  return [
+    {
+      isSigner: false,
+      isWritable: false,
+      pubkey: deriveTokenBadgeAddress(tokenAMint),
+    },
+    {
+      isSigner: false,
+      isWritable: false,
+      pubkey: deriveTokenBadgeAddress(tokenBMint),
+    },
+    ...transferHookAccounts,
+  ];
+}
+
 export async function initializePool(
   banksClient: BanksClient,
   params: InitializePoolParams
@@ -595,6 +944,13 @@ export async function initializePool(
       tokenBProgram,
       systemProgram: SystemProgram.programId,
     })
+    .remainingAccounts(
+      await getInitializePoolRemainingAccounts(
+        banksClient,
+        tokenAMint,
+        tokenBMint
+      )
+    )
     .transaction();
   // requires more compute budget than usual
   transaction.add(
@@ -725,6 +1081,13 @@ export async function initializePoolWithCustomizeConfig(
       tokenBProgram,
       token2022Program: TOKEN_2022_PROGRAM_ID,
     })
+    .remainingAccounts(
+      await getInitializePoolRemainingAccounts(
+        banksClient,
+        tokenAMint,
+        tokenBMint
+      )
+    )
     .transaction();
   // requires more compute budget than usual
   transaction.add(
@@ -779,609 +1142,2700 @@ export async function setPoolStatus(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type PoolFeesParams = {
-  baseFee: BaseFee;
-  padding: number[];
-  dynamicFee: DynamicFee | null;
+export enum PoolAction {
+  Swap,
+  AddLiquidity,
+  CreatePosition,
+  LockPosition,
+  SplitPosition,
+  ClaimReward,
+}
+
+export function getPoolActionMask(actions: PoolAction[]): number {
+  return actions.reduce((mask, action) => mask | (1 << action), 0);
+}
+
+export type SetPoolDisabledActionsParams = {
+  admin: Keypair;
+  pool: PublicKey;
+  disabledActions: number;
+  reason: string;
 };
 
-export type InitializeCustomizablePoolParams = {
-  payer: Keypair;
-  creator: PublicKey;
-  tokenAMint: PublicKey;
-  tokenBMint: PublicKey;
-  poolFees: PoolFeesParams;
+export async function setPoolDisabledActions(
+  banksClient: BanksClient,
+  params: SetPoolDisabledActionsParams
+) {
+  const { admin, pool, disabledActions, reason } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .setPoolDisabledActions(disabledActions, reason)
+    .accountsPartial({
+      pool,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.disabledActions).eq(disabledActions);
+}
+
+export type UpdatePriceRangeParams = {
+  admin: Keypair;
+  pool: PublicKey;
   sqrtMinPrice: BN;
   sqrtMaxPrice: BN;
-  hasAlphaVault: boolean;
-  liquidity: BN;
-  sqrtPrice: BN;
-  activationType: number;
-  collectFeeMode: number;
-  activationPoint: BN | null;
 };
 
-export async function initializeCustomizablePool(
+export async function updatePriceRange(
   banksClient: BanksClient,
-  params: InitializeCustomizablePoolParams
-): Promise<{ pool: PublicKey; position: PublicKey }> {
-  const {
-    tokenAMint,
-    tokenBMint,
-    payer,
-    creator,
-    poolFees,
-    hasAlphaVault,
-    liquidity,
-    sqrtMaxPrice,
-    sqrtMinPrice,
-    sqrtPrice,
-    collectFeeMode,
-    activationPoint,
-    activationType,
-  } = params;
+  params: UpdatePriceRangeParams
+) {
+  const { admin, pool, sqrtMinPrice, sqrtMaxPrice } = params;
   const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updatePriceRange(sqrtMinPrice, sqrtMaxPrice)
+    .accountsPartial({
+      pool,
+      admin: admin.publicKey,
+    })
+    .transaction();
 
-  const poolAuthority = derivePoolAuthority();
-  const pool = deriveCustomizablePoolAddress(tokenAMint, tokenBMint);
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
 
-  const positionNftKP = Keypair.generate();
-  const position = derivePositionAddress(positionNftKP.publicKey);
-  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+  await processTransactionMaybeThrow(banksClient, transaction);
 
-  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
-  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.sqrtMinPrice.toString()).eq(sqrtMinPrice.toString());
+  expect(poolState.sqrtMaxPrice.toString()).eq(sqrtMaxPrice.toString());
+}
 
-  const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
-  const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
+export const PERMISSIONED_POOL_TYPE = 2;
 
-  const payerTokenA = getAssociatedTokenAddressSync(
-    tokenAMint,
-    payer.publicKey,
-    true,
-    tokenAProgram
-  );
-  const payerTokenB = await getOrCreateAssociatedTokenAccount(
-    banksClient,
-    payer,
-    tokenBMint,
-    payer.publicKey,
-    tokenBProgram
-  );
+// allowlist entry is only required by permissioned pool
+export function getAllowlistRemainingAccounts(
+  poolState: Pool,
+  pool: PublicKey,
+  wallet: PublicKey
+): AccountMeta[] {
+  if (poolState.poolType != PERMISSIONED_POOL_TYPE) {
+    return [];
+  }
+  return [
+    {
+      isSigner: false,
+      isWritable: false,
+      pubkey: deriveAllowlistEntryAddress(pool, wallet),
+    },
+  ];
+}
 
-  if (tokenBMint.equals(NATIVE_MINT)) {
-    await wrapSOL(banksClient, payer, new BN(LAMPORTS_PER_SOL));
+// locked fee extension is only required by pool routing a fee share to locked liquidity
+export function getLockedFeeExtensionRemainingAccounts(
+  poolState: Pool,
+  pool: PublicKey
+): AccountMeta[] {
+  if (poolState.lockedFeeExtensionFlag != 1) {
+    return [];
   }
+  return [
+    {
+      isSigner: false,
+      isWritable: true,
+      pubkey: deriveLockedFeeExtensionAddress(pool),
+    },
+  ];
+}
 
-  const transaction = await program.methods
-    .initializeCustomizablePool({
-      poolFees,
-      sqrtMinPrice,
-      sqrtMaxPrice,
-      hasAlphaVault,
-      liquidity,
-      sqrtPrice,
-      activationType,
-      collectFeeMode,
-      activationPoint,
-    })
-    .accountsPartial({
-      creator,
-      positionNftAccount,
-      positionNftMint: positionNftKP.publicKey,
-      payer: payer.publicKey,
-      poolAuthority,
-      pool,
-      position,
-      tokenAMint,
-      tokenBMint,
-      tokenAVault,
-      tokenBVault,
-      payerTokenA,
-      payerTokenB,
-      token2022Program: TOKEN_2022_PROGRAM_ID,
-      tokenAProgram,
-      tokenBProgram,
-      systemProgram: SystemProgram.programId,
-    })
-    .transaction();
-  // requires more compute budget than usual
-  transaction.add(
-    ComputeBudgetProgram.setComputeUnitLimit({
-      units: 350_000,
-    })
-  );
-  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, positionNftKP);
-
-  await processTransactionMaybeThrow(banksClient, transaction);
-
-  // validate pool data
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.tokenAMint.toString()).eq(tokenAMint.toString());
-  expect(poolState.tokenBMint.toString()).eq(tokenBMint.toString());
-  expect(poolState.tokenAVault.toString()).eq(tokenAVault.toString());
-  expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
-  expect(poolState.liquidity.toString()).eq(liquidity.toString());
-  expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
-
-  expect(poolState.rewardInfos[0].initialized).eq(0);
-  expect(poolState.rewardInfos[1].initialized).eq(0);
-
-  return { pool, position: position };
+export function getWritablePoolRemainingAccounts(
+  poolState: Pool,
+  pool: PublicKey
+): AccountMeta[] {
+  if (poolState.vestingLiquidityFlag != 1) {
+    return [];
+  }
+  return [
+    {
+      isSigner: false,
+      isWritable: true,
+      pubkey: pool,
+    },
+  ];
 }
 
-export type InitializeRewardParams = {
+export type InitializePoolPermissionParams = {
   payer: Keypair;
-  index: number;
-  rewardDuration: BN;
+  creator: Keypair;
   pool: PublicKey;
-  rewardMint: PublicKey;
+  manager: PublicKey;
 };
 
-export async function initializeReward(
+export async function initializePoolPermission(
   banksClient: BanksClient,
-  params: InitializeRewardParams
-): Promise<void> {
-  const { index, rewardDuration, pool, rewardMint, payer } = params;
+  params: InitializePoolPermissionParams
+) {
+  const { payer, creator, pool, manager } = params;
   const program = createCpAmmProgram();
-
-  const poolAuthority = derivePoolAuthority();
-  const rewardVault = deriveRewardVaultAddress(pool, index);
-
-  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
-
   const transaction = await program.methods
-    .initializeReward(index, rewardDuration, payer.publicKey)
+    .initializePoolPermission()
     .accountsPartial({
       pool,
-      poolAuthority,
-      rewardVault,
-      rewardMint,
+      poolPermission: derivePoolPermissionAddress(pool),
+      creator: creator.publicKey,
+      manager,
       payer: payer.publicKey,
-      signer: payer.publicKey,
-      tokenProgram,
       systemProgram: SystemProgram.programId,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer);
+  transaction.sign(payer, creator);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  // validate reward data
   const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].initialized).eq(1);
-  expect(poolState.rewardInfos[index].vault.toString()).eq(
-    rewardVault.toString()
-  );
-  expect(poolState.rewardInfos[index].mint.toString()).eq(
-    rewardMint.toString()
-  );
+  expect(poolState.poolType).eq(PERMISSIONED_POOL_TYPE);
 }
 
-export type UpdateRewardDurationParams = {
-  index: number;
-  admin: Keypair;
+export type AllowlistEntryParams = {
+  manager: Keypair;
   pool: PublicKey;
-  newDuration: BN;
+  wallet: PublicKey;
 };
 
-export async function updateRewardDuration(
+export async function addAllowlistEntry(
   banksClient: BanksClient,
-  params: UpdateRewardDurationParams
-): Promise<void> {
-  const { pool, admin, index, newDuration } = params;
+  params: AllowlistEntryParams
+) {
+  const { manager, pool, wallet } = params;
   const program = createCpAmmProgram();
   const transaction = await program.methods
-    .updateRewardDuration(index, newDuration)
+    .addAllowlistEntry()
     .accountsPartial({
       pool,
-      signer: admin.publicKey,
+      poolPermission: derivePoolPermissionAddress(pool),
+      allowlistEntry: deriveAllowlistEntryAddress(pool, wallet),
+      wallet,
+      manager: manager.publicKey,
+      payer: manager.publicKey,
+      systemProgram: SystemProgram.programId,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(admin);
+  transaction.sign(manager);
 
   await processTransactionMaybeThrow(banksClient, transaction);
-
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].rewardDuration.toNumber()).eq(
-    newDuration.toNumber()
-  );
 }
 
-export type UpdateRewardFunderParams = {
-  index: number;
-  admin: Keypair;
-  pool: PublicKey;
-  newFunder: PublicKey;
-};
-
-export async function updateRewardFunder(
+export async function removeAllowlistEntry(
   banksClient: BanksClient,
-  params: UpdateRewardFunderParams
-): Promise<void> {
-  const { pool, admin, index, newFunder } = params;
+  params: AllowlistEntryParams
+) {
+  const { manager, pool, wallet } = params;
   const program = createCpAmmProgram();
+  const allowlistEntry = deriveAllowlistEntryAddress(pool, wallet);
   const transaction = await program.methods
-    .updateRewardFunder(index, newFunder)
+    .removeAllowlistEntry()
     .accountsPartial({
       pool,
-      signer: admin.publicKey,
+      poolPermission: derivePoolPermissionAddress(pool),
+      allowlistEntry,
+      manager: manager.publicKey,
+      rentReceiver: manager.publicKey,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(admin);
+  transaction.sign(manager);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const poolState = await getPool(banksClient, pool);
-  expect(poolState.rewardInfos[index].funder.toString()).eq(
-    newFunder.toString()
-  );
+  const allowlistEntryAccount = await banksClient.getAccount(allowlistEntry);
+  expect(allowlistEntryAccount).to.be.null;
 }
 
-export type FundRewardParams = {
-  funder: Keypair;
-  index: number;
+export type TransferPoolManagerParams = {
+  manager: Keypair;
   pool: PublicKey;
-  carryForward: boolean;
-  amount: BN;
+  newManager: PublicKey;
 };
 
-export async function fundReward(
+export async function transferPoolManager(
   banksClient: BanksClient,
-  params: FundRewardParams
-): Promise<void> {
-  const { index, carryForward, pool, funder, amount } = params;
+  params: TransferPoolManagerParams
+) {
+  const { manager, pool, newManager } = params;
   const program = createCpAmmProgram();
-
-  const poolState = await getPool(banksClient, pool);
-  const rewardVault = poolState.rewardInfos[index].vault;
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
-  const funderTokenAccount = getAssociatedTokenAddressSync(
-    poolState.rewardInfos[index].mint,
-    funder.publicKey,
-    true,
-    tokenProgram
-  );
-
-  const rewardVaultPreBalance = Number(
-    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
-      .amount
-  );
-  const funderPreBalance = Number(
-    AccountLayout.decode(
-      (await banksClient.getAccount(funderTokenAccount)).data
-    ).amount
-  );
-
   const transaction = await program.methods
-    .fundReward(index, amount, carryForward)
+    .transferPoolManager()
     .accountsPartial({
       pool,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      funderTokenAccount,
-      funder: funder.publicKey,
-      tokenProgram,
+      poolPermission: derivePoolPermissionAddress(pool),
+      manager: manager.publicKey,
+      newManager,
     })
     .transaction();
+
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(funder);
+  transaction.sign(manager);
 
   await processTransactionMaybeThrow(banksClient, transaction);
-
-  const rewardVaultPostBalance = Number(
-    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
-      .amount
-  );
-  const funderPostBalance = Number(
-    AccountLayout.decode(
-      (await banksClient.getAccount(funderTokenAccount)).data
-    ).amount
-  );
-
-  // expect(funderPreBalance - funderPostBalance).eq(amount.toNumber());
-
-  // expect(rewardVaultPostBalance - rewardVaultPreBalance).eq(amount.toNumber());
 }
 
-export type ClaimRewardParams = {
-  index: number;
-  user: Keypair;
-  position: PublicKey;
-  pool: PublicKey;
-  skipReward: number;
-};
+export enum AdminRole {
+  ConfigManager,
+  PoolStatusOperator,
+  TokenBadgeManager,
+  RewardManager,
+  FeeOperatorManager,
+  ProposalCanceller,
+  ProtocolFeeRoutingManager,
+}
 
-export async function claimReward(
+export async function initializeAdminRegistry(
   banksClient: BanksClient,
-  params: ClaimRewardParams
-): Promise<void> {
-  const { index, pool, user, position, skipReward } = params;
+  params: {
+    admin: Keypair;
+    superAdmin: PublicKey;
+  }
+) {
+  const { admin, superAdmin } = params;
   const program = createCpAmmProgram();
-
-  const poolState = await getPool(banksClient, pool);
-  const positionState = await getPosition(banksClient, position);
-  const poolAuthority = derivePoolAuthority();
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
-
-  // TODO should use token flag in pool state to get token program ID
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
-
-  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
-    banksClient,
-    user,
-    poolState.rewardInfos[index].mint,
-    user.publicKey,
-    tokenProgram
-  );
-
+  const adminRegistry = deriveAdminRegistryAddress();
   const transaction = await program.methods
-    .claimReward(index, skipReward)
+    .initializeAdminRegistry()
     .accountsPartial({
-      pool,
-      positionNftAccount,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      poolAuthority,
-      position,
-      userTokenAccount,
-      owner: user.publicKey,
-      tokenProgram,
+      adminRegistry,
+      superAdmin,
+      admin: admin.publicKey,
+      systemProgram: SystemProgram.programId,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(user);
+  transaction.sign(admin);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+
+  const adminRegistryState = await getAdminRegistry(banksClient);
+  expect(adminRegistryState.superAdmin.toString()).eq(superAdmin.toString());
 }
 
-export type WithdrawIneligibleRewardParams = {
-  index: number;
-  funder: Keypair;
-  pool: PublicKey;
+export type UpdateAdminRoleParams = {
+  superAdmin: Keypair;
+  admin: PublicKey;
+  role: AdminRole;
 };
 
-export async function withdrawIneligibleReward(
+export async function addAdminRole(
   banksClient: BanksClient,
-  params: WithdrawIneligibleRewardParams
-): Promise<void> {
-  const { index, pool, funder } = params;
+  params: UpdateAdminRoleParams
+) {
+  const { superAdmin, admin, role } = params;
   const program = createCpAmmProgram();
-
-  const poolState = await getPool(banksClient, pool);
-  const poolAuthority = derivePoolAuthority();
-  const tokenProgram = (
-    await banksClient.getAccount(poolState.rewardInfos[index].mint)
-  ).owner;
-  const funderTokenAccount = getAssociatedTokenAddressSync(
-    poolState.rewardInfos[index].mint,
-    funder.publicKey,
-    true,
-    tokenProgram
-  );
-
   const transaction = await program.methods
-    .withdrawIneligibleReward(index)
+    .addAdminRole(role)
     .accountsPartial({
-      pool,
-      rewardVault: poolState.rewardInfos[index].vault,
-      rewardMint: poolState.rewardInfos[index].mint,
-      poolAuthority,
-      funderTokenAccount,
-      funder: funder.publicKey,
-      tokenProgram,
+      adminRegistry: deriveAdminRegistryAddress(),
+      admin,
+      superAdmin: superAdmin.publicKey,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(funder);
+  transaction.sign(superAdmin);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function refreshVestings(
+export async function removeAdminRole(
   banksClient: BanksClient,
-  position: PublicKey,
-  pool: PublicKey,
-  owner: PublicKey,
-  payer: Keypair,
-  vestings: PublicKey[]
+  params: UpdateAdminRoleParams
 ) {
+  const { superAdmin, admin, role } = params;
   const program = createCpAmmProgram();
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
   const transaction = await program.methods
-    .refreshVesting()
+    .removeAdminRole(role)
     .accountsPartial({
-      position,
-      positionNftAccount,
-      pool,
-      owner,
+      adminRegistry: deriveAdminRegistryAddress(),
+      admin,
+      superAdmin: superAdmin.publicKey,
     })
-    .remainingAccounts(
-      vestings.map((pubkey) => {
-        return {
-          isSigner: false,
-          isWritable: true,
-          pubkey,
-        };
-      })
-    )
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer);
+  transaction.sign(superAdmin);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function permanentLockPosition(
+export async function transferSuperAdmin(
   banksClient: BanksClient,
-  position: PublicKey,
-  owner: Keypair,
-  payer: Keypair
+  params: {
+    superAdmin: Keypair;
+    newSuperAdmin: PublicKey;
+  }
 ) {
+  const { superAdmin, newSuperAdmin } = params;
   const program = createCpAmmProgram();
-
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
-
   const transaction = await program.methods
-    .permanentLockPosition(positionState.unlockedLiquidity)
+    .transferSuperAdmin()
     .accountsPartial({
-      position,
-      positionNftAccount,
-      pool: positionState.pool,
-      owner: owner.publicKey,
+      adminRegistry: deriveAdminRegistryAddress(),
+      newSuperAdmin,
+      superAdmin: superAdmin.publicKey,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, owner);
+  transaction.sign(superAdmin);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export async function lockPosition(
+export async function updateTimelock(
   banksClient: BanksClient,
-  position: PublicKey,
-  owner: Keypair,
-  payer: Keypair,
-  params: LockPositionParams
+  params: {
+    superAdmin: Keypair;
+    activationType: number;
+    delay: BN;
+  }
 ) {
+  const { superAdmin, activationType, delay } = params;
   const program = createCpAmmProgram();
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const transaction = await program.methods
+    .updateTimelock(activationType, delay)
+    .accountsPartial({
+      adminRegistry: deriveAdminRegistryAddress(),
+      superAdmin: superAdmin.publicKey,
+    })
+    .transaction();
 
-  const vestingKP = Keypair.generate();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(superAdmin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export enum ProposalAction {
+  SetPoolStatus,
+  CloseConfig,
+  UpdatePriceRange,
+  SetPoolDisabledActions,
+  UpdateConfig,
+}
+
+export function encodePriceRangeActionData(
+  sqrtMinPrice: BN,
+  sqrtMaxPrice: BN
+): Buffer {
+  return Buffer.concat([
+    sqrtMinPrice.toArrayLike(Buffer, "le", 16),
+    sqrtMaxPrice.toArrayLike(Buffer, "le", 16),
+  ]);
+}
+
+export function encodeDisabledActionsActionData(
+  disabledActions: number,
+  reason: string
+): Buffer {
+  const reasonBuffer = Buffer.from(reason);
+  const reasonLength = Buffer.alloc(4);
+  reasonLength.writeUInt32LE(reasonBuffer.length);
+  return Buffer.concat([
+    Buffer.from([disabledActions]),
+    reasonLength,
+    reasonBuffer,
+  ]);
+}
 
+export function encodeConfigActionData(params: CreateConfigParams): Buffer {
+  const program = createCpAmmProgram();
+  return program.coder.types.encode("staticConfigParameters", params);
+}
+
+export type CreateProposalParams = {
+  proposer: Keypair;
+  target: PublicKey;
+  action: ProposalAction;
+  actionData: Buffer;
+};
+
+export async function createProposal(
+  banksClient: BanksClient,
+  params: CreateProposalParams
+): Promise<PublicKey> {
+  const { proposer, target, action, actionData } = params;
+  const program = createCpAmmProgram();
+  const proposal = deriveProposalAddress(target, action);
   const transaction = await program.methods
-    .lockPosition(params)
+    .createProposal({
+      action,
+      actionData,
+    })
     .accountsPartial({
-      position,
-      positionNftAccount,
-      vesting: vestingKP.publicKey,
-      owner: owner.publicKey,
-      pool: positionState.pool,
-      program: CP_AMM_PROGRAM_ID,
+      proposal,
+      target,
+      adminRegistry: deriveAdminRegistryAddress(),
+      proposer: proposer.publicKey,
       systemProgram: SystemProgram.programId,
-      payer: payer.publicKey,
     })
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, owner, vestingKP);
+  transaction.sign(proposer);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  return vestingKP.publicKey;
+  return proposal;
 }
 
-export async function createPosition(
+export async function executeProposal(
   banksClient: BanksClient,
-  payer: Keypair,
-  owner: PublicKey,
-  pool: PublicKey
-): Promise<PublicKey> {
+  params: {
+    payer: Keypair;
+    proposal: PublicKey;
+  }
+) {
+  const { payer, proposal } = params;
+  const program = createCpAmmProgram();
+  const proposalState = await getProposal(banksClient, proposal);
+  const isConfigAction =
+    proposalState.action == ProposalAction.CloseConfig ||
+    proposalState.action == ProposalAction.UpdateConfig;
+  const transaction = await program.methods
+    .executeProposal()
+    .accountsPartial({
+      proposal,
+      proposer: proposalState.proposer,
+      pool: isConfigAction ? null : proposalState.target,
+      config: isConfigAction ? proposalState.target : null,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function cancelProposal(
+  banksClient: BanksClient,
+  params: {
+    canceller: Keypair;
+    proposal: PublicKey;
+  }
+) {
+  const { canceller, proposal } = params;
+  const program = createCpAmmProgram();
+  const proposalState = await getProposal(banksClient, proposal);
+  const transaction = await program.methods
+    .cancelProposal()
+    .accountsPartial({
+      proposal,
+      proposer: proposalState.proposer,
+      canceller: canceller.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(canceller);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type PoolFeesParams = {
+  baseFee: BaseFee;
+  padding: number[];
+  dynamicFee: DynamicFee | null;
+};
+
+export type InitializeCustomizablePoolParams = {
+  payer: Keypair;
+  creator: PublicKey;
+  tokenAMint: PublicKey;
+  tokenBMint: PublicKey;
+  poolFees: PoolFeesParams;
+  sqrtMinPrice: BN;
+  sqrtMaxPrice: BN;
+  hasAlphaVault: boolean;
+  liquidity: BN;
+  sqrtPrice: BN;
+  activationType: number;
+  collectFeeMode: number;
+  activationPoint: BN | null;
+};
+
+export async function initializeCustomizablePool(
+  banksClient: BanksClient,
+  params: InitializeCustomizablePoolParams
+): Promise<{ pool: PublicKey; position: PublicKey }> {
+  const {
+    tokenAMint,
+    tokenBMint,
+    payer,
+    creator,
+    poolFees,
+    hasAlphaVault,
+    liquidity,
+    sqrtMaxPrice,
+    sqrtMinPrice,
+    sqrtPrice,
+    collectFeeMode,
+    activationPoint,
+    activationType,
+  } = params;
   const program = createCpAmmProgram();
 
+  const poolAuthority = derivePoolAuthority();
+  const pool = deriveCustomizablePoolAddress(tokenAMint, tokenBMint);
+
   const positionNftKP = Keypair.generate();
   const position = derivePositionAddress(positionNftKP.publicKey);
-  const poolAuthority = derivePoolAuthority();
   const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
 
+  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
+  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+
+  const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
+  const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
+
+  const payerTokenA = getAssociatedTokenAddressSync(
+    tokenAMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const payerTokenB = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    payer,
+    tokenBMint,
+    payer.publicKey,
+    tokenBProgram
+  );
+
+  if (tokenBMint.equals(NATIVE_MINT)) {
+    await wrapSOL(banksClient, payer, new BN(LAMPORTS_PER_SOL));
+  }
+
   const transaction = await program.methods
-    .createPosition()
+    .initializeCustomizablePool({
+      poolFees,
+      sqrtMinPrice,
+      sqrtMaxPrice,
+      hasAlphaVault,
+      liquidity,
+      sqrtPrice,
+      activationType,
+      collectFeeMode,
+      activationPoint,
+    })
     .accountsPartial({
-      owner,
-      positionNftMint: positionNftKP.publicKey,
-      poolAuthority,
+      creator,
       positionNftAccount,
+      positionNftMint: positionNftKP.publicKey,
       payer: payer.publicKey,
+      poolAuthority,
       pool,
       position,
-      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      tokenAMint,
+      tokenBMint,
+      tokenAVault,
+      tokenBVault,
+      payerTokenA,
+      payerTokenB,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      tokenAProgram,
+      tokenBProgram,
       systemProgram: SystemProgram.programId,
     })
+    .remainingAccounts(
+      await getInitializePoolRemainingAccounts(
+        banksClient,
+        tokenAMint,
+        tokenBMint
+      )
+    )
     .transaction();
-
+  // requires more compute budget than usual
+  transaction.add(
+    ComputeBudgetProgram.setComputeUnitLimit({
+      units: 350_000,
+    })
+  );
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
   transaction.sign(payer, positionNftKP);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
-  const positionState = await getPosition(banksClient, position);
+  // validate pool data
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.tokenAMint.toString()).eq(tokenAMint.toString());
+  expect(poolState.tokenBMint.toString()).eq(tokenBMint.toString());
+  expect(poolState.tokenAVault.toString()).eq(tokenAVault.toString());
+  expect(poolState.tokenBVault.toString()).eq(tokenBVault.toString());
+  expect(poolState.liquidity.toString()).eq(liquidity.toString());
+  expect(poolState.sqrtPrice.toString()).eq(sqrtPrice.toString());
+
+  expect(poolState.rewardInfos[0].initialized).eq(0);
+  expect(poolState.rewardInfos[1].initialized).eq(0);
+
+  return { pool, position: position };
+}
+
+export type InitializeSingleSidedPoolParams = {
+  payer: Keypair;
+  creator: PublicKey;
+  tokenAMint: PublicKey;
+  tokenBMint: PublicKey;
+  poolFees: PoolFeesParams;
+  sqrtMinPrice: BN;
+  sqrtMaxPrice: BN;
+  hasAlphaVault: boolean;
+  tokenAAmount: BN;
+  activationType: number;
+  collectFeeMode: number;
+  activationPoint: BN | null;
+};
+
+export async function initializeSingleSidedPool(
+  banksClient: BanksClient,
+  params: InitializeSingleSidedPoolParams
+): Promise<{ pool: PublicKey; position: PublicKey }> {
+  const {
+    tokenAMint,
+    tokenBMint,
+    payer,
+    creator,
+    poolFees,
+    hasAlphaVault,
+    tokenAAmount,
+    sqrtMaxPrice,
+    sqrtMinPrice,
+    collectFeeMode,
+    activationPoint,
+    activationType,
+  } = params;
+  const program = createCpAmmProgram();
+
+  const poolAuthority = derivePoolAuthority();
+  const pool = deriveCustomizablePoolAddress(tokenAMint, tokenBMint);
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+
+  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
+  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+
+  const tokenAVault = deriveTokenVaultAddress(tokenAMint, pool);
+  const tokenBVault = deriveTokenVaultAddress(tokenBMint, pool);
+
+  const payerTokenA = getAssociatedTokenAddressSync(
+    tokenAMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const payerTokenB = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    payer,
+    tokenBMint,
+    payer.publicKey,
+    tokenBProgram
+  );
+
+  const transaction = await program.methods
+    .initializeSingleSidedPool({
+      poolFees,
+      sqrtMinPrice,
+      sqrtMaxPrice,
+      hasAlphaVault,
+      tokenAAmount,
+      activationType,
+      collectFeeMode,
+      activationPoint,
+    })
+    .accountsPartial({
+      creator,
+      positionNftAccount,
+      positionNftMint: positionNftKP.publicKey,
+      payer: payer.publicKey,
+      poolAuthority,
+      pool,
+      position,
+      tokenAMint,
+      tokenBMint,
+      tokenAVault,
+      tokenBVault,
+      payerTokenA,
+      payerTokenB,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      tokenAProgram,
+      tokenBProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(
+      await getInitializePoolRemainingAccounts(
+        banksClient,
+        tokenAMint,
+        tokenBMint
+      )
+    )
+    .transaction();
+  // requires more compute budget than usual
+  transaction.add(
+    ComputeBudgetProgram.setComputeUnitLimit({
+      units: 350_000,
+    })
+  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // price starts at the lower bound
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.sqrtPrice.toString()).eq(sqrtMinPrice.toString());
+  expect(poolState.sqrtMinPrice.toString()).eq(sqrtMinPrice.toString());
+  expect(poolState.sqrtMaxPrice.toString()).eq(sqrtMaxPrice.toString());
+
+  return { pool, position };
+}
+
+export type InitializeRewardParams = {
+  payer: Keypair;
+  index: number;
+  rewardDuration: BN;
+  pool: PublicKey;
+  rewardMint: PublicKey;
+};
+
+export async function initializeReward(
+  banksClient: BanksClient,
+  params: InitializeRewardParams
+): Promise<void> {
+  const { index, rewardDuration, pool, rewardMint, payer } = params;
+  const program = createCpAmmProgram();
+
+  const poolAuthority = derivePoolAuthority();
+  const rewardVault = deriveRewardVaultAddress(pool, index);
+
+  const tokenProgram = (await banksClient.getAccount(rewardMint)).owner;
+
+  const transaction = await program.methods
+    .initializeReward(index, rewardDuration, payer.publicKey)
+    .accountsPartial({
+      pool,
+      poolAuthority,
+      rewardVault,
+      rewardMint,
+      payer: payer.publicKey,
+      signer: payer.publicKey,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(getRewardExtensionAccountsForIndex(pool, index))
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // validate reward data
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  expect(rewardInfo.initialized).eq(1);
+  expect(rewardInfo.vault.toString()).eq(rewardVault.toString());
+  expect(rewardInfo.mint.toString()).eq(rewardMint.toString());
+}
+
+// reward extension is required in remaining accounts for extension reward index
+function getRewardExtensionAccountsForIndex(
+  pool: PublicKey,
+  index: number,
+  position?: PublicKey
+) {
+  if (index < NUM_REWARDS) {
+    return [];
+  }
+  const accounts = [
+    {
+      isSigner: false,
+      isWritable: true,
+      pubkey: deriveRewardExtensionAddress(pool),
+    },
+  ];
+  if (position) {
+    accounts.push({
+      isSigner: false,
+      isWritable: true,
+      pubkey: derivePositionRewardExtensionAddress(position),
+    });
+  }
+  return accounts;
+}
+
+// reward extensions are required in remaining accounts whenever liquidity
+// changes if pool has extension reward
+export async function getRewardExtensionRemainingAccounts(
+  banksClient: BanksClient,
+  pool: PublicKey,
+  positions: PublicKey[]
+) {
+  const poolState = await getPool(banksClient, pool);
+  if (poolState.rewardExtensionFlag == 0) {
+    return [];
+  }
+  const accounts = [
+    {
+      isSigner: false,
+      isWritable: true,
+      pubkey: deriveRewardExtensionAddress(pool),
+    },
+  ];
+  for (const position of positions) {
+    const positionState = await getPosition(banksClient, position);
+    if (positionState.rewardExtensionFlag == 1) {
+      accounts.push({
+        isSigner: false,
+        isWritable: true,
+        pubkey: derivePositionRewardExtensionAddress(position),
+      });
+    }
+  }
+  return accounts;
+}
+
+// bribe ledgers are required in remaining accounts whenever liquidity changes
+// if a position has bribe ledger
+export async function getBribeLedgerRemainingAccounts(
+  banksClient: BanksClient,
+  pool: PublicKey,
+  positions: PublicKey[]
+) {
+  const accounts = [];
+  for (const position of positions) {
+    const positionState = await getPosition(banksClient, position);
+    if (positionState.bribeLedgerFlag == 1) {
+      accounts.push({
+        isSigner: false,
+        isWritable: true,
+        pubkey: derivePositionBribeLedgerAddress(position),
+      });
+    }
+  }
+  if (accounts.length == 0) {
+    return [];
+  }
+  return [
+    {
+      isSigner: false,
+      isWritable: true,
+      pubkey: deriveBribeLedgerAddress(pool),
+    },
+    ...accounts,
+  ];
+}
+
+// reward extensions are followed by bribe ledgers
+export async function getLiquidityRemainingAccounts(
+  banksClient: BanksClient,
+  pool: PublicKey,
+  positions: PublicKey[]
+) {
+  return [
+    ...(await getRewardExtensionRemainingAccounts(
+      banksClient,
+      pool,
+      positions
+    )),
+    ...(await getBribeLedgerRemainingAccounts(banksClient, pool, positions)),
+  ];
+}
+
+// transfer hook accounts of each mint are ordered as transfer hook program,
+// extra account meta list, then the extra accounts
+export async function getTransferHookRemainingAccounts(
+  banksClient: BanksClient,
+  mints: PublicKey[]
+) {
+  const accounts: AccountMeta[] = [];
+  for (const mint of mints) {
+    const mintAccount = await banksClient.getAccount(mint);
+    if (!mintAccount.owner.equals(TOKEN_2022_PROGRAM_ID)) {
+      continue;
+    }
+    const mintInfo = unpackMint(
+      mint,
+      { ...mintAccount, data: Buffer.from(mintAccount.data) },
+      TOKEN_2022_PROGRAM_ID
+    );
+    const transferHook = getTransferHook(mintInfo);
+    if (!transferHook || transferHook.programId.equals(PublicKey.default)) {
+      continue;
+    }
+
+    const instruction = createTransferCheckedInstruction(
+      PublicKey.default,
+      mint,
+      PublicKey.default,
+      PublicKey.default,
+      0,
+      mintInfo.decimals,
+      [],
+      TOKEN_2022_PROGRAM_ID
+    );
+    await addExtraAccountMetasForExecute(
+      banksClient,
+      instruction,
+      transferHook.programId,
+      PublicKey.default,
+      mint,
+      PublicKey.default,
+      PublicKey.default,
+      0
+    );
+    // resolved keys are appended as extra accounts, program, extra account meta list
+    const resolvedAccounts = instruction.keys.slice(4);
+    if (resolvedAccounts.length == 0) {
+      accounts.push({
+        isSigner: false,
+        isWritable: false,
+        pubkey: transferHook.programId,
+      });
+    } else {
+      accounts.push(
+        ...resolvedAccounts.slice(-2),
+        ...resolvedAccounts.slice(0, -2)
+      );
+    }
+  }
+  return accounts;
+}
+
+export async function getRewardInfo(
+  banksClient: BanksClient,
+  pool: PublicKey,
+  index: number
+) {
+  if (index < NUM_REWARDS) {
+    const poolState = await getPool(banksClient, pool);
+    return poolState.rewardInfos[index];
+  }
+  const rewardExtensionState = await getRewardExtension(
+    banksClient,
+    deriveRewardExtensionAddress(pool)
+  );
+  return rewardExtensionState.rewardInfos[index - NUM_REWARDS];
+}
+
+export async function initializeRewardExtension(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const rewardExtension = deriveRewardExtensionAddress(pool);
+
+  const transaction = await program.methods
+    .initializeRewardExtension()
+    .accountsPartial({
+      pool,
+      rewardExtension,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return rewardExtension;
+}
+
+export async function createPositionRewardExtension(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  position: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const positionRewardExtension =
+    derivePositionRewardExtensionAddress(position);
+
+  const transaction = await program.methods
+    .createPositionRewardExtension()
+    .accountsPartial({
+      pool,
+      rewardExtension: deriveRewardExtensionAddress(pool),
+      position,
+      positionRewardExtension,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const positionState = await getPosition(banksClient, position);
+  expect(positionState.rewardExtensionFlag).eq(1);
+
+  return positionRewardExtension;
+}
+
+export type InitializeLockedFeeExtensionParams = {
+  payer: Keypair;
+  creator: Keypair;
+  pool: PublicKey;
+  lockedFeeShareBps: number;
+};
+
+export async function initializeLockedFeeExtension(
+  banksClient: BanksClient,
+  params: InitializeLockedFeeExtensionParams
+): Promise<PublicKey> {
+  const { payer, creator, pool, lockedFeeShareBps } = params;
+  const program = createCpAmmProgram();
+  const lockedFeeExtension = deriveLockedFeeExtensionAddress(pool);
+
+  const transaction = await program.methods
+    .initializeLockedFeeExtension(lockedFeeShareBps)
+    .accountsPartial({
+      pool,
+      lockedFeeExtension,
+      creator: creator.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, creator);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  expect(poolState.lockedFeeExtensionFlag).eq(1);
+
+  return lockedFeeExtension;
+}
+
+export async function getLockedFeeExtension(
+  banksClient: BanksClient,
+  lockedFeeExtension: PublicKey
+): Promise<LockedFeeExtension> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(lockedFeeExtension);
+  return program.coder.accounts.decode(
+    "lockedFeeExtension",
+    Buffer.from(account.data)
+  );
+}
+
+export type InitializeFungibleShareParams = {
+  payer: Keypair;
+  creator: Keypair;
+  pool: PublicKey;
+  autoCompound: boolean;
+};
+
+export async function initializeFungibleShare(
+  banksClient: BanksClient,
+  params: InitializeFungibleShareParams
+): Promise<PublicKey> {
+  const { payer, creator, pool, autoCompound } = params;
+  const program = createCpAmmProgram();
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+  const fungibleShare = deriveFungibleShareAddress(pool);
+  const shareMint = deriveFungibleShareMintAddress(pool);
+
+  const transaction = await program.methods
+    .initializeFungibleShare(autoCompound)
+    .accountsPartial({
+      pool,
+      fungibleShare,
+      shareMint,
+      positionNftMint: positionNftKP.publicKey,
+      positionNftAccount,
+      position,
+      poolAuthority: derivePoolAuthority(),
+      creator: creator.publicKey,
+      payer: payer.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      shareTokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, creator, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const fungibleShareState = await getFungibleShare(banksClient, fungibleShare);
+  expect(fungibleShareState.position.toString()).eq(position.toString());
+  expect(fungibleShareState.shareMint.toString()).eq(shareMint.toString());
+
+  return fungibleShare;
+}
+
+export async function getFungibleShare(
+  banksClient: BanksClient,
+  fungibleShare: PublicKey
+): Promise<FungibleShare> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(fungibleShare);
+  return program.coder.accounts.decode(
+    "fungibleShare",
+    Buffer.from(account.data)
+  );
+}
+
+export async function initializeBribeLedger(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const bribeLedger = deriveBribeLedgerAddress(pool);
+
+  const transaction = await program.methods
+    .initializeBribeLedger()
+    .accountsPartial({
+      pool,
+      bribeLedger,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return bribeLedger;
+}
+
+export async function createPositionBribeLedger(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey,
+  position: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+  const positionBribeLedger = derivePositionBribeLedgerAddress(position);
+
+  const transaction = await program.methods
+    .createPositionBribeLedger()
+    .accountsPartial({
+      pool,
+      bribeLedger: deriveBribeLedgerAddress(pool),
+      position,
+      positionBribeLedger,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const positionState = await getPosition(banksClient, position);
+  expect(positionState.bribeLedgerFlag).eq(1);
+
+  return positionBribeLedger;
+}
+
+export async function closePositionBribeLedger(
+  banksClient: BanksClient,
+  owner: Keypair,
+  pool: PublicKey,
+  position: PublicKey
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+
+  const transaction = await program.methods
+    .closePositionBribeLedger()
+    .accountsPartial({
+      pool,
+      bribeLedger: deriveBribeLedgerAddress(pool),
+      position,
+      positionBribeLedger: derivePositionBribeLedgerAddress(position),
+      positionNftAccount: derivePositionNftAccount(positionState.nftMint),
+      owner: owner.publicKey,
+      rentReceiver: owner.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type InitializeBribeCampaignParams = {
+  funder: Keypair;
+  pool: PublicKey;
+  mint: PublicKey;
+  epoch: BN;
+  amount: BN;
+};
+
+export async function initializeBribeCampaign(
+  banksClient: BanksClient,
+  params: InitializeBribeCampaignParams
+): Promise<PublicKey> {
+  const { funder, pool, mint, epoch, amount } = params;
+  const program = createCpAmmProgram();
+
+  const bribeCampaign = deriveBribeCampaignAddress(
+    pool,
+    funder.publicKey,
+    mint,
+    epoch
+  );
+  const tokenProgram = (await banksClient.getAccount(mint)).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .initializeBribeCampaign(epoch, amount)
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      bribeLedger: deriveBribeLedgerAddress(pool),
+      bribeCampaign,
+      bribeVault: deriveBribeVaultAddress(bribeCampaign),
+      mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return bribeCampaign;
+}
+
+export async function claimBribe(
+  banksClient: BanksClient,
+  owner: Keypair,
+  bribeCampaign: PublicKey,
+  position: PublicKey
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const bribeCampaignState = await getBribeCampaign(banksClient, bribeCampaign);
+  const positionState = await getPosition(banksClient, position);
+  const tokenProgram = (await banksClient.getAccount(bribeCampaignState.mint))
+    .owner;
+
+  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
+    bribeCampaignState.mint,
+    owner.publicKey,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .claimBribe()
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool: bribeCampaignState.pool,
+      bribeLedger: deriveBribeLedgerAddress(bribeCampaignState.pool),
+      bribeCampaign,
+      position,
+      positionBribeLedger: derivePositionBribeLedgerAddress(position),
+      bribeClaimReceipt: deriveBribeClaimReceiptAddress(
+        bribeCampaign,
+        position
+      ),
+      bribeVault: bribeCampaignState.vault,
+      mint: bribeCampaignState.mint,
+      userTokenAccount,
+      positionNftAccount: derivePositionNftAccount(positionState.nftMint),
+      owner: owner.publicKey,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function withdrawIneligibleBribe(
+  banksClient: BanksClient,
+  funder: Keypair,
+  bribeCampaign: PublicKey
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const bribeCampaignState = await getBribeCampaign(banksClient, bribeCampaign);
+  const tokenProgram = (await banksClient.getAccount(bribeCampaignState.mint))
+    .owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    bribeCampaignState.mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .withdrawIneligibleBribe()
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      pool: bribeCampaignState.pool,
+      bribeLedger: deriveBribeLedgerAddress(bribeCampaignState.pool),
+      bribeCampaign,
+      bribeVault: bribeCampaignState.vault,
+      mint: bribeCampaignState.mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function closeBribeCampaign(
+  banksClient: BanksClient,
+  funder: Keypair,
+  bribeCampaign: PublicKey
+): Promise<void> {
+  const program = createCpAmmProgram();
+  const bribeCampaignState = await getBribeCampaign(banksClient, bribeCampaign);
+  const tokenProgram = (await banksClient.getAccount(bribeCampaignState.mint))
+    .owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    bribeCampaignState.mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .closeBribeCampaign()
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      bribeCampaign,
+      bribeLedger: deriveBribeLedgerAddress(bribeCampaignState.pool),
+      bribeVault: bribeCampaignState.vault,
+      mint: bribeCampaignState.mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type UpdateRewardDurationParams = {
+  index: number;
+  admin: Keypair;
+  pool: PublicKey;
+  newDuration: BN;
+};
+
+export async function updateRewardDuration(
+  banksClient: BanksClient,
+  params: UpdateRewardDurationParams
+): Promise<void> {
+  const { pool, admin, index, newDuration } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardDuration(index, newDuration)
+    .accountsPartial({
+      pool,
+      signer: admin.publicKey,
+    })
+    .remainingAccounts(getRewardExtensionAccountsForIndex(pool, index))
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  expect(rewardInfo.rewardDuration.toNumber()).eq(newDuration.toNumber());
+}
+
+export type UpdateRewardBoostParams = {
+  index: number;
+  admin: Keypair;
+  pool: PublicKey;
+  permanentLockBoostBps: number;
+  vestingLockBoostBps: number;
+};
+
+export async function updateRewardBoost(
+  banksClient: BanksClient,
+  params: UpdateRewardBoostParams
+): Promise<void> {
+  const { pool, admin, index, permanentLockBoostBps, vestingLockBoostBps } =
+    params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardBoost(index, permanentLockBoostBps, vestingLockBoostBps)
+    .accountsPartial({
+      pool,
+      signer: admin.publicKey,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolState = await getPool(banksClient, pool);
+  const rewardInfo = poolState.rewardInfos[index];
+  expect(rewardInfo.permanentLockBoostBps).eq(permanentLockBoostBps);
+  expect(rewardInfo.vestingLockBoostBps).eq(vestingLockBoostBps);
+}
+
+export type UpdateRewardFunderParams = {
+  index: number;
+  admin: Keypair;
+  pool: PublicKey;
+  newFunder: PublicKey;
+};
+
+export async function updateRewardFunder(
+  banksClient: BanksClient,
+  params: UpdateRewardFunderParams
+): Promise<void> {
+  const { pool, admin, index, newFunder } = params;
+  const program = createCpAmmProgram();
+  const transaction = await program.methods
+    .updateRewardFunder(index, newFunder)
+    .accountsPartial({
+      pool,
+      signer: admin.publicKey,
+    })
+    .remainingAccounts(getRewardExtensionAccountsForIndex(pool, index))
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  expect(rewardInfo.funder.toString()).eq(newFunder.toString());
+}
+
+export type FundRewardParams = {
+  funder: Keypair;
+  index: number;
+  pool: PublicKey;
+  carryForward: boolean;
+  amount: BN;
+};
+
+export async function fundReward(
+  banksClient: BanksClient,
+  params: FundRewardParams
+): Promise<void> {
+  const { index, carryForward, pool, funder, amount } = params;
+  const program = createCpAmmProgram();
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  const rewardVault = rewardInfo.vault;
+  const tokenProgram = (await banksClient.getAccount(rewardInfo.mint)).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    rewardInfo.mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const rewardVaultPreBalance = Number(
+    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
+      .amount
+  );
+  const funderPreBalance = Number(
+    AccountLayout.decode(
+      (await banksClient.getAccount(funderTokenAccount)).data
+    ).amount
+  );
+
+  const transaction = await program.methods
+    .fundReward(index, amount, carryForward)
+    .accountsPartial({
+      pool,
+      rewardVault: rewardInfo.vault,
+      rewardMint: rewardInfo.mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .remainingAccounts([
+      ...getRewardExtensionAccountsForIndex(pool, index),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        rewardInfo.mint,
+      ])),
+    ])
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const rewardVaultPostBalance = Number(
+    AccountLayout.decode((await banksClient.getAccount(rewardVault)).data)
+      .amount
+  );
+  const funderPostBalance = Number(
+    AccountLayout.decode(
+      (await banksClient.getAccount(funderTokenAccount)).data
+    ).amount
+  );
+
+  // expect(funderPreBalance - funderPostBalance).eq(amount.toNumber());
+
+  // expect(rewardVaultPostBalance - rewardVaultPreBalance).eq(amount.toNumber());
+}
+
+export type FundReward2Params = FundRewardParams & {
+  rewardDurationStart: BN;
+  rewardDurationEnd: BN;
+};
+
+export async function fundReward2(
+  banksClient: BanksClient,
+  params: FundReward2Params
+): Promise<void> {
+  const {
+    index,
+    carryForward,
+    pool,
+    funder,
+    amount,
+    rewardDurationStart,
+    rewardDurationEnd,
+  } = params;
+  const program = createCpAmmProgram();
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  const tokenProgram = (await banksClient.getAccount(rewardInfo.mint)).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    rewardInfo.mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .fundReward2(index, {
+      amount,
+      carryForward,
+      rewardDurationStart,
+      rewardDurationEnd,
+    })
+    .accountsPartial({
+      pool,
+      rewardVault: rewardInfo.vault,
+      rewardMint: rewardInfo.mint,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .remainingAccounts([
+      ...getRewardExtensionAccountsForIndex(pool, index),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        rewardInfo.mint,
+      ])),
+    ])
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type ClaimRewardParams = {
+  index: number;
+  user: Keypair;
+  position: PublicKey;
+  pool: PublicKey;
+  skipReward: number;
+};
+
+export async function claimReward(
+  banksClient: BanksClient,
+  params: ClaimRewardParams
+): Promise<void> {
+  const { index, pool, user, position, skipReward } = params;
+  const program = createCpAmmProgram();
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  const positionState = await getPosition(banksClient, position);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  // TODO should use token flag in pool state to get token program ID
+  const tokenProgram = (await banksClient.getAccount(rewardInfo.mint)).owner;
+
+  const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    user,
+    rewardInfo.mint,
+    user.publicKey,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .claimReward(index, skipReward)
+    .accountsPartial({
+      pool,
+      positionNftAccount,
+      rewardVault: rewardInfo.vault,
+      rewardMint: rewardInfo.mint,
+      poolAuthority,
+      position,
+      userTokenAccount,
+      owner: user.publicKey,
+      tokenProgram,
+    })
+    .remainingAccounts([
+      ...getRewardExtensionAccountsForIndex(pool, index, position),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        rewardInfo.mint,
+      ])),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(user);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type WithdrawIneligibleRewardParams = {
+  index: number;
+  funder: Keypair;
+  pool: PublicKey;
+};
+
+export async function withdrawIneligibleReward(
+  banksClient: BanksClient,
+  params: WithdrawIneligibleRewardParams
+): Promise<void> {
+  const { index, pool, funder } = params;
+  const program = createCpAmmProgram();
+
+  const rewardInfo = await getRewardInfo(banksClient, pool, index);
+  const poolAuthority = derivePoolAuthority();
+  const tokenProgram = (await banksClient.getAccount(rewardInfo.mint)).owner;
+  const funderTokenAccount = getAssociatedTokenAddressSync(
+    rewardInfo.mint,
+    funder.publicKey,
+    true,
+    tokenProgram
+  );
+
+  const transaction = await program.methods
+    .withdrawIneligibleReward(index)
+    .accountsPartial({
+      pool,
+      rewardVault: rewardInfo.vault,
+      rewardMint: rewardInfo.mint,
+      poolAuthority,
+      funderTokenAccount,
+      funder: funder.publicKey,
+      tokenProgram,
+    })
+    .remainingAccounts([
+      ...getRewardExtensionAccountsForIndex(pool, index),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        rewardInfo.mint,
+      ])),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(funder);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function refreshVestings(
+  banksClient: BanksClient,
+  position: PublicKey,
+  pool: PublicKey,
+  owner: PublicKey,
+  payer: Keypair,
+  vestings: PublicKey[]
+) {
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const poolState = await getPool(banksClient, pool);
+  const transaction = await program.methods
+    .refreshVesting()
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      pool,
+      owner,
+    })
+    .remainingAccounts([
+      ...getWritablePoolRemainingAccounts(poolState, pool),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+      ...vestings.map((pubkey) => {
+        return {
+          isSigner: false,
+          isWritable: true,
+          pubkey,
+        };
+      }),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function permanentLockPosition(
+  banksClient: BanksClient,
+  position: PublicKey,
+  owner: Keypair,
+  payer: Keypair
+) {
+  const program = createCpAmmProgram();
+
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const poolState = await getPool(banksClient, positionState.pool);
+
+  const transaction = await program.methods
+    .permanentLockPosition(positionState.unlockedLiquidity)
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      pool: positionState.pool,
+      owner: owner.publicKey,
+    })
+    .remainingAccounts(
+      getLockedFeeExtensionRemainingAccounts(poolState, positionState.pool)
+    )
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function lockPosition(
+  banksClient: BanksClient,
+  position: PublicKey,
+  owner: Keypair,
+  payer: Keypair,
+  params: LockPositionParams
+) {
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const poolState = await getPool(banksClient, positionState.pool);
+
+  const vestingKP = Keypair.generate();
+
+  const transaction = await program.methods
+    .lockPosition(params)
+    .accountsPartial({
+      position,
+      positionNftAccount,
+      vesting: vestingKP.publicKey,
+      owner: owner.publicKey,
+      pool: positionState.pool,
+      program: CP_AMM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      payer: payer.publicKey,
+    })
+    .remainingAccounts([
+      ...getWritablePoolRemainingAccounts(poolState, positionState.pool),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, positionState.pool),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, owner, vestingKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return vestingKP.publicKey;
+}
+
+export async function createPosition(
+  banksClient: BanksClient,
+  payer: Keypair,
+  owner: PublicKey,
+  pool: PublicKey
+): Promise<PublicKey> {
+  const program = createCpAmmProgram();
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+
+  const transaction = await program.methods
+    .createPosition()
+    .accountsPartial({
+      owner,
+      positionNftMint: positionNftKP.publicKey,
+      poolAuthority,
+      positionNftAccount,
+      payer: payer.publicKey,
+      pool,
+      position,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const positionState = await getPosition(banksClient, position);
+
+  expect(positionState.nftMint.toString()).eq(
+    positionNftKP.publicKey.toString()
+  );
+
+  const positionNftData = AccountLayout.decode(
+    (await banksClient.getAccount(positionNftAccount)).data
+  );
+
+  // validate metadata
+  const tlvData = (
+    await banksClient.getAccount(positionState.nftMint)
+  ).data.slice(ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE);
+  const metadata = await getPositionNftMetadata(
+    banksClient,
+    positionState.nftMint
+  );
+  expect(metadata.name).eq("Meteora Position NFT");
+  expect(metadata.symbol).eq("MPN");
+  expect(metadata.uri).contains(positionState.nftMint.toString());
+  expect(getMetadataField(metadata.additionalMetadata, "pool")).eq(
+    pool.toString()
+  );
+
+  // validate metadata pointer
+  const metadataAddress = MetadataPointerLayout.decode(
+    getExtensionData(ExtensionType.MetadataPointer, Buffer.from(tlvData))
+  ).metadataAddress;
+  expect(metadataAddress.toString()).eq(positionState.nftMint.toString());
+
+  // validate owner
+  expect(positionNftData.owner.toString()).eq(owner.toString());
+  expect(Number(positionNftData.amount)).eq(1);
+  expect(positionNftData.mint.toString()).eq(
+    positionNftKP.publicKey.toString()
+  );
+
+  return position;
+}
+
+export async function getPositionNftMetadata(
+  banksClient: BanksClient,
+  positionNftMint: PublicKey
+) {
+  const tlvData = (await banksClient.getAccount(positionNftMint)).data.slice(
+    ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE
+  );
+  return unpack(
+    getExtensionData(ExtensionType.TokenMetadata, Buffer.from(tlvData))
+  );
+}
+
+export function getMetadataField(
+  additionalMetadata: (readonly [string, string])[],
+  key: string
+): string | undefined {
+  return additionalMetadata.find(([k]) => k == key)?.[1];
+}
+
+export type RefreshPositionMetadataParams = {
+  payer: Keypair;
+  position: PublicKey;
+  vestings: PublicKey[];
+};
+
+export async function refreshPositionMetadata(
+  banksClient: BanksClient,
+  params: RefreshPositionMetadataParams
+) {
+  const { payer, position, vestings } = params;
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+
+  const transaction = await program.methods
+    .refreshPositionMetadata()
+    .accountsPartial({
+      pool: positionState.pool,
+      position,
+      positionNftMint: positionState.nftMint,
+      poolAuthority: derivePoolAuthority(),
+      payer: payer.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(
+      vestings.map((pubkey) => ({
+        isSigner: false,
+        isWritable: true,
+        pubkey,
+      }))
+    )
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type AddLiquidityParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  liquidityDelta: BN;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+  maxTransferFeeBps?: number;
+};
+
+export async function addLiquidity(
+  banksClient: BanksClient,
+  params: AddLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    liquidityDelta,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    maxTransferFeeBps,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const accounts = {
+    pool,
+    position,
+    positionNftAccount,
+    owner: owner.publicKey,
+    tokenAAccount,
+    tokenBAccount,
+    tokenAVault,
+    tokenBVault,
+    tokenAProgram,
+    tokenBProgram,
+    tokenAMint,
+    tokenBMint,
+  };
+  const remainingAccounts = [
+    ...getAllowlistRemainingAccounts(poolState, pool, owner.publicKey),
+    ...(await getLiquidityRemainingAccounts(banksClient, pool, [position])),
+    ...(await getTransferHookRemainingAccounts(banksClient, [
+      tokenAMint,
+      tokenBMint,
+    ])),
+  ];
+
+  // transfer fee bound is only supported by add_liquidity2
+  const transaction =
+    maxTransferFeeBps === undefined
+      ? await program.methods
+          .addLiquidity({
+            liquidityDelta,
+            tokenAAmountThreshold,
+            tokenBAmountThreshold,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction()
+      : await program.methods
+          .addLiquidity2({
+            liquidityDelta,
+            tokenAAmountThreshold,
+            tokenBAmountThreshold,
+            maxTransferFeeBps,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type RemoveLiquidityParams = AddLiquidityParams;
+
+export async function removeLiquidity(
+  banksClient: BanksClient,
+  params: RemoveLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    liquidityDelta,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    maxTransferFeeBps,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const accounts = {
+    poolAuthority,
+    pool,
+    position,
+    positionNftAccount,
+    owner: owner.publicKey,
+    tokenAAccount,
+    tokenBAccount,
+    tokenAVault,
+    tokenBVault,
+    tokenAProgram,
+    tokenBProgram,
+    tokenAMint,
+    tokenBMint,
+  };
+  const remainingAccounts = [
+    ...(await getLiquidityRemainingAccounts(banksClient, pool, [position])),
+    ...(await getTransferHookRemainingAccounts(banksClient, [
+      tokenAMint,
+      tokenBMint,
+    ])),
+  ];
+
+  // transfer fee bound is only supported by remove_liquidity2
+  const transaction =
+    maxTransferFeeBps === undefined
+      ? await program.methods
+          .removeLiquidity({
+            liquidityDelta,
+            tokenAAmountThreshold,
+            tokenBAmountThreshold,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction()
+      : await program.methods
+          .removeLiquidity2({
+            liquidityDelta,
+            tokenAAmountThreshold,
+            tokenBAmountThreshold,
+            maxTransferFeeBps,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type RemoveAllLiquidityParams = {
+  owner: Keypair;
+  pool: PublicKey;
+  position: PublicKey;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+};
+
+export async function removeAllLiquidity(
+  banksClient: BanksClient,
+  params: RemoveAllLiquidityParams
+) {
+  const {
+    owner,
+    pool,
+    position,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
+    true,
+    tokenAProgram
+  );
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .removeAllLiquidity(tokenAAmountThreshold, tokenBAmountThreshold)
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      position,
+      positionNftAccount,
+      owner: owner.publicKey,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function closePosition(
+  banksClient: BanksClient,
+  params: {
+    owner: Keypair;
+    pool: PublicKey;
+    position: PublicKey;
+  }
+) {
+  const { owner, pool, position } = params;
+  const program = createCpAmmProgram();
+  const positionState = await getPosition(banksClient, position);
+  const poolAuthority = derivePoolAuthority();
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const transaction = await program.methods
+    .closePosition()
+    .accountsPartial({
+      positionNftMint: positionState.nftMint,
+      positionNftAccount,
+      pool,
+      position,
+      poolAuthority,
+      rentReceiver: owner.publicKey,
+      owner: owner.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type ClosePoolParams = {
+  payer: Keypair;
+  pool: PublicKey;
+};
+
+export async function closePool(
+  banksClient: BanksClient,
+  params: ClosePoolParams
+) {
+  const { payer, pool } = params;
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const poolAuthority = derivePoolAuthority();
+
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+
+  const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    payer,
+    poolState.tokenAMint,
+    TREASURY,
+    tokenAProgram
+  );
+  const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    payer,
+    poolState.tokenBMint,
+    TREASURY,
+    tokenBProgram
+  );
+
+  // pool extensions go first, followed by reward vaults and transfer hook accounts
+  const poolExtensions: PublicKey[] = [];
+  if (poolState.rewardExtensionFlag == 1) {
+    poolExtensions.push(deriveRewardExtensionAddress(pool));
+  }
+  if (poolState.lockedFeeExtensionFlag == 1) {
+    poolExtensions.push(deriveLockedFeeExtensionAddress(pool));
+  }
+  if (poolState.bribeLedgerFlag == 1) {
+    poolExtensions.push(deriveBribeLedgerAddress(pool));
+  }
+  if (poolState.poolType == PERMISSIONED_POOL_TYPE) {
+    poolExtensions.push(derivePoolPermissionAddress(pool));
+  }
+  const remainingAccounts: AccountMeta[] = poolExtensions.map((pubkey) => ({
+    isSigner: false,
+    isWritable: true,
+    pubkey,
+  }));
+  // each reward vault is followed by its token program
+  const rewardInfos = [...poolState.rewardInfos];
+  if (poolState.rewardExtensionFlag == 1) {
+    const rewardExtensionState = await getRewardExtension(
+      banksClient,
+      deriveRewardExtensionAddress(pool)
+    );
+    rewardInfos.push(...rewardExtensionState.rewardInfos);
+  }
+  const rewardVaults = rewardInfos
+    .filter((rewardInfo) => rewardInfo.initialized == 1)
+    .map((rewardInfo) => rewardInfo.vault);
+  for (const rewardInfo of rewardInfos) {
+    if (rewardInfo.initialized != 1) {
+      continue;
+    }
+    remainingAccounts.push(
+      {
+        isSigner: false,
+        isWritable: true,
+        pubkey: rewardInfo.vault,
+      },
+      {
+        isSigner: false,
+        isWritable: false,
+        pubkey: (await banksClient.getAccount(rewardInfo.mint)).owner,
+      }
+    );
+  }
+  remainingAccounts.push(
+    ...(await getTransferHookRemainingAccounts(banksClient, [
+      poolState.tokenAMint,
+      poolState.tokenBMint,
+    ]))
+  );
+
+  const transaction = await program.methods
+    .closePool()
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      creator: poolState.creator,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAProgram,
+      tokenBProgram,
+    })
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const poolAccount = await banksClient.getAccount(pool);
+  expect(poolAccount).to.be.null;
+  const tokenAVaultAccount = await banksClient.getAccount(
+    poolState.tokenAVault
+  );
+  expect(tokenAVaultAccount).to.be.null;
+  const tokenBVaultAccount = await banksClient.getAccount(
+    poolState.tokenBVault
+  );
+  expect(tokenBVaultAccount).to.be.null;
+  for (const poolExtension of poolExtensions) {
+    expect(await banksClient.getAccount(poolExtension)).to.be.null;
+  }
+  for (const rewardVault of rewardVaults) {
+    expect(await banksClient.getAccount(rewardVault)).to.be.null;
+  }
+}
+
+export type SwapParams = {
+  payer: Keypair;
+  pool: PublicKey;
+  inputTokenMint: PublicKey;
+  outputTokenMint: PublicKey;
+  amountIn: BN;
+  minimumAmountOut: BN;
+  referralTokenAccount: PublicKey | null;
+};
+
+export async function swapInstruction(
+  banksClient: BanksClient,
+  params: SwapParams
+): Promise<Transaction> {
+  const {
+    payer,
+    pool,
+    inputTokenMint,
+    outputTokenMint,
+    amountIn,
+    minimumAmountOut,
+    referralTokenAccount,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+  const inputTokenAccount = getAssociatedTokenAddressSync(
+    inputTokenMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const outputTokenAccount = getAssociatedTokenAddressSync(
+    outputTokenMint,
+    payer.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const transaction = await program.methods
+    .swap({
+      amountIn,
+      minimumAmountOut,
+    })
+    .accountsPartial({
+      poolAuthority,
+      pool,
+      payer: payer.publicKey,
+      inputTokenAccount,
+      outputTokenAccount,
+      tokenAVault,
+      tokenBVault,
+      tokenAProgram,
+      tokenBProgram,
+      tokenAMint,
+      tokenBMint,
+      referralTokenAccount,
+    })
+    .remainingAccounts(
+      // TODO should check condition to add this in remaining accounts
+      [
+        {
+          isSigner: false,
+          isWritable: false,
+          pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+        },
+        ...getAllowlistRemainingAccounts(poolState, pool, payer.publicKey),
+        ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+        ...(await getTransferHookRemainingAccounts(banksClient, [
+          tokenAMint,
+          tokenBMint,
+        ])),
+      ]
+    )
+    .transaction();
+
+  return transaction;
+}
+
+export enum SwapMode {
+  ExactIn,
+  PartialFillIn,
+  ExactOut,
+}
+
+export type Swap2Params = {
+  payer: Keypair;
+  pool: PublicKey;
+  inputTokenMint: PublicKey;
+  outputTokenMint: PublicKey;
+  amount0: BN;
+  amount1: BN;
+  swapMode: SwapMode;
+  referralTokenAccount: PublicKey | null;
+  maxTransferFeeBps?: number;
+};
+
+export async function swap2Instruction(
+  banksClient: BanksClient,
+  params: Swap2Params
+) {
+  const {
+    payer,
+    pool,
+    inputTokenMint,
+    outputTokenMint,
+    amount0,
+    amount1,
+    swapMode,
+    referralTokenAccount,
+    maxTransferFeeBps,
+  } = params;
+
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+
+  const poolAuthority = derivePoolAuthority();
+  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
+    .owner;
+
+  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
+    .owner;
+  const inputTokenAccount = getAssociatedTokenAddressSync(
+    inputTokenMint,
+    payer.publicKey,
+    true,
+    tokenAProgram
+  );
+  const outputTokenAccount = getAssociatedTokenAddressSync(
+    outputTokenMint,
+    payer.publicKey,
+    true,
+    tokenBProgram
+  );
+  const tokenAVault = poolState.tokenAVault;
+  const tokenBVault = poolState.tokenBVault;
+  const tokenAMint = poolState.tokenAMint;
+  const tokenBMint = poolState.tokenBMint;
+
+  const accounts = {
+    poolAuthority,
+    pool,
+    payer: payer.publicKey,
+    inputTokenAccount,
+    outputTokenAccount,
+    tokenAVault,
+    tokenBVault,
+    tokenAProgram,
+    tokenBProgram,
+    tokenAMint,
+    tokenBMint,
+    referralTokenAccount,
+  };
+  // TODO should check condition to add this in remaining accounts
+  const remainingAccounts = [
+    {
+      isSigner: false,
+      isWritable: false,
+      pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+    },
+    ...getAllowlistRemainingAccounts(poolState, pool, payer.publicKey),
+    ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+    ...(await getTransferHookRemainingAccounts(banksClient, [
+      tokenAMint,
+      tokenBMint,
+    ])),
+  ];
+
+  // transfer fee bound is only supported by swap3
+  const transaction =
+    maxTransferFeeBps === undefined
+      ? await program.methods
+          .swap2({
+            amount0,
+            amount1,
+            swapMode,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction()
+      : await program.methods
+          .swap3({
+            amount0,
+            amount1,
+            swapMode,
+            maxTransferFeeBps,
+          })
+          .accountsPartial(accounts)
+          .remainingAccounts(remainingAccounts)
+          .transaction();
+
+  return transaction;
+}
 
-  expect(positionState.nftMint.toString()).eq(
-    positionNftKP.publicKey.toString()
-  );
+export async function swap2ExactIn(
+  banksClient: BanksClient,
+  params: Omit<Swap2Params, "swapMode">
+) {
+  const swapIx = await swap2Instruction(banksClient, {
+    ...params,
+    swapMode: SwapMode.ExactIn,
+  });
+  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  swapIx.sign(params.payer);
+  await processTransactionMaybeThrow(banksClient, swapIx);
+}
 
-  const positionNftData = AccountLayout.decode(
-    (await banksClient.getAccount(positionNftAccount)).data
-  );
+export async function swap2ExactOut(
+  banksClient: BanksClient,
+  params: Omit<Swap2Params, "swapMode">
+) {
+  const swapIx = await swap2Instruction(banksClient, {
+    ...params,
+    swapMode: SwapMode.ExactOut,
+  });
+  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  swapIx.sign(params.payer);
+  await processTransactionMaybeThrow(banksClient, swapIx);
+}
 
-  // validate metadata
-  const tlvData = (
-    await banksClient.getAccount(positionState.nftMint)
-  ).data.slice(ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE);
-  const metadata = unpack(
-    getExtensionData(ExtensionType.TokenMetadata, Buffer.from(tlvData))
-  );
-  expect(metadata.name).eq("Meteora Position NFT");
-  expect(metadata.symbol).eq("MPN");
+export async function swap2PartialFillIn(
+  banksClient: BanksClient,
+  params: Omit<Swap2Params, "swapMode">
+) {
+  const swapIx = await swap2Instruction(banksClient, {
+    ...params,
+    swapMode: SwapMode.PartialFillIn,
+  });
+  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  swapIx.sign(params.payer);
+  await processTransactionMaybeThrow(banksClient, swapIx);
+}
 
-  // validate metadata pointer
-  const metadataAddress = MetadataPointerLayout.decode(
-    getExtensionData(ExtensionType.MetadataPointer, Buffer.from(tlvData))
-  ).metadataAddress;
-  expect(metadataAddress.toString()).eq(positionState.nftMint.toString());
+export async function swapExactIn(
+  banksClient: BanksClient,
+  params: SwapParams
+) {
+  const transaction = await swapInstruction(banksClient, params);
 
-  // validate owner
-  expect(positionNftData.owner.toString()).eq(owner.toString());
-  expect(Number(positionNftData.amount)).eq(1);
-  expect(positionNftData.mint.toString()).eq(
-    positionNftKP.publicKey.toString()
-  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(params.payer);
 
-  return position;
+  await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type AddLiquidityParams = {
+export type ClaimPositionFeeParams = {
   owner: Keypair;
   pool: PublicKey;
   position: PublicKey;
-  liquidityDelta: BN;
-  tokenAAmountThreshold: BN;
-  tokenBAmountThreshold: BN;
 };
 
-export async function addLiquidity(
+export async function claimPositionFee(
   banksClient: BanksClient,
-  params: AddLiquidityParams
+  params: ClaimPositionFeeParams
 ) {
-  const {
-    owner,
-    pool,
-    position,
-    liquidityDelta,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
+  const { owner, pool, position } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
   const positionState = await getPosition(banksClient, position);
   const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
 
+  const poolAuthority = derivePoolAuthority();
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
@@ -1405,16 +3859,13 @@ export async function addLiquidity(
   const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .addLiquidity({
-      liquidityDelta,
-      tokenAAmountThreshold,
-      tokenBAmountThreshold,
-    })
+    .claimPositionFee()
     .accountsPartial({
+      poolAuthority,
+      owner: owner.publicKey,
       pool,
       position,
       positionNftAccount,
-      owner: owner.publicKey,
       tokenAAccount,
       tokenBAccount,
       tokenAVault,
@@ -1424,6 +3875,13 @@ export async function addLiquidity(
       tokenAMint,
       tokenBMint,
     })
+    .remainingAccounts([
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        tokenAMint,
+        tokenBMint,
+      ])),
+    ])
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
@@ -1432,20 +3890,13 @@ export async function addLiquidity(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type RemoveLiquidityParams = AddLiquidityParams;
+export type ClaimPendingWithdrawalParams = ClaimPositionFeeParams;
 
-export async function removeLiquidity(
+export async function claimPendingWithdrawal(
   banksClient: BanksClient,
-  params: RemoveLiquidityParams
+  params: ClaimPendingWithdrawalParams
 ) {
-  const {
-    owner,
-    pool,
-    position,
-    liquidityDelta,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
+  const { owner, pool, position } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
@@ -1476,17 +3927,13 @@ export async function removeLiquidity(
   const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .removeLiquidity({
-      liquidityDelta,
-      tokenAAmountThreshold,
-      tokenBAmountThreshold,
-    })
+    .claimPendingWithdrawal()
     .accountsPartial({
       poolAuthority,
+      owner: owner.publicKey,
       pool,
       position,
       positionNftAccount,
-      owner: owner.publicKey,
       tokenAAccount,
       tokenBAccount,
       tokenAVault,
@@ -1496,6 +3943,12 @@ export async function removeLiquidity(
       tokenAMint,
       tokenBMint,
     })
+    .remainingAccounts(
+      await getTransferHookRemainingAccounts(banksClient, [
+        tokenAMint,
+        tokenBMint,
+      ])
+    )
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
@@ -1504,30 +3957,32 @@ export async function removeLiquidity(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type RemoveAllLiquidityParams = {
+export type TransferPositionParams = {
   owner: Keypair;
+  newOwner: PublicKey;
   pool: PublicKey;
   position: PublicKey;
-  tokenAAmountThreshold: BN;
-  tokenBAmountThreshold: BN;
 };
 
-export async function removeAllLiquidity(
+// pending fees and rewards are paid to the current owner before the position
+// nft is moved to the associated token account of the new owner
+export async function transferPosition(
   banksClient: BanksClient,
-  params: RemoveAllLiquidityParams
-) {
-  const {
-    owner,
-    pool,
-    position,
-    tokenAAmountThreshold,
-    tokenBAmountThreshold,
-  } = params;
+  params: TransferPositionParams
+): Promise<PublicKey> {
+  const { owner, newOwner, pool, position } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
   const positionState = await getPosition(banksClient, position);
   const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const newPositionNftAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
+    positionState.nftMint,
+    newOwner,
+    TOKEN_2022_PROGRAM_ID
+  );
 
   const poolAuthority = derivePoolAuthority();
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
@@ -1535,332 +3990,297 @@ export async function removeAllLiquidity(
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
 
-  const tokenAAccount = getAssociatedTokenAddressSync(
+  const tokenAAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
     poolState.tokenAMint,
     owner.publicKey,
-    true,
     tokenAProgram
   );
-  const tokenBAccount = getAssociatedTokenAddressSync(
+  const tokenBAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
     poolState.tokenBMint,
     owner.publicKey,
-    true,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
   const tokenAMint = poolState.tokenAMint;
   const tokenBMint = poolState.tokenBMint;
 
+  const remainingAccounts = getLockedFeeExtensionRemainingAccounts(
+    poolState,
+    pool
+  );
+  if (positionState.rewardExtensionFlag == 1) {
+    remainingAccounts.push(
+      {
+        isSigner: false,
+        isWritable: true,
+        pubkey: deriveRewardExtensionAddress(pool),
+      },
+      {
+        isSigner: false,
+        isWritable: true,
+        pubkey: derivePositionRewardExtensionAddress(position),
+      }
+    );
+  }
+  remainingAccounts.push(
+    ...(await getTransferHookRemainingAccounts(banksClient, [
+      tokenAMint,
+      tokenBMint,
+    ]))
+  );
+
+  const rewardCount =
+    positionState.rewardExtensionFlag == 1
+      ? NUM_REWARDS + NUM_EXTENSION_REWARDS
+      : NUM_REWARDS;
+  for (let index = 0; index < rewardCount; index++) {
+    const rewardInfo = await getRewardInfo(banksClient, pool, index);
+    if (rewardInfo.initialized == 0) {
+      continue;
+    }
+    const rewardTokenProgram = (await banksClient.getAccount(rewardInfo.mint))
+      .owner;
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      banksClient,
+      owner,
+      rewardInfo.mint,
+      owner.publicKey,
+      rewardTokenProgram
+    );
+    remainingAccounts.push(
+      { isSigner: false, isWritable: true, pubkey: rewardInfo.vault },
+      { isSigner: false, isWritable: false, pubkey: rewardInfo.mint },
+      { isSigner: false, isWritable: true, pubkey: userTokenAccount },
+      { isSigner: false, isWritable: false, pubkey: rewardTokenProgram },
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        rewardInfo.mint,
+      ]))
+    );
+  }
+
   const transaction = await program.methods
-    .removeAllLiquidity(tokenAAmountThreshold, tokenBAmountThreshold)
+    .transferPosition()
     .accountsPartial({
       poolAuthority,
       pool,
       position,
+      positionNftMint: positionState.nftMint,
       positionNftAccount,
-      owner: owner.publicKey,
+      newPositionNftAccount,
+      newOwner,
       tokenAAccount,
       tokenBAccount,
-      tokenAVault,
-      tokenBVault,
-      tokenAProgram,
-      tokenBProgram,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
       tokenAMint,
       tokenBMint,
-    })
-    .transaction();
-
-  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(owner);
-
-  await processTransactionMaybeThrow(banksClient, transaction);
-}
-
-export async function closePosition(
-  banksClient: BanksClient,
-  params: {
-    owner: Keypair;
-    pool: PublicKey;
-    position: PublicKey;
-  }
-) {
-  const { owner, pool, position } = params;
-  const program = createCpAmmProgram();
-  const positionState = await getPosition(banksClient, position);
-  const poolAuthority = derivePoolAuthority();
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
-
-  const transaction = await program.methods
-    .closePosition()
-    .accountsPartial({
-      positionNftMint: positionState.nftMint,
-      positionNftAccount,
-      pool,
-      position,
-      poolAuthority,
-      rentReceiver: owner.publicKey,
       owner: owner.publicKey,
+      tokenAProgram,
+      tokenBProgram,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
     })
+    .remainingAccounts(remainingAccounts)
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
   transaction.sign(owner);
 
   await processTransactionMaybeThrow(banksClient, transaction);
+
+  return newPositionNftAccount;
 }
 
-export type SwapParams = {
-  payer: Keypair;
+export type CompoundPositionFeeParams = {
+  owner: Keypair;
   pool: PublicKey;
-  inputTokenMint: PublicKey;
-  outputTokenMint: PublicKey;
-  amountIn: BN;
-  minimumAmountOut: BN;
-  referralTokenAccount: PublicKey | null;
+  position: PublicKey;
+  minimumLiquidityDelta: BN;
+  swapExcess: boolean;
+  sqrtPriceLimit: BN;
 };
 
-export async function swapInstruction(
+export async function compoundPositionFee(
   banksClient: BanksClient,
-  params: SwapParams
-): Promise<Transaction> {
+  params: CompoundPositionFeeParams
+) {
   const {
-    payer,
+    owner,
     pool,
-    inputTokenMint,
-    outputTokenMint,
-    amountIn,
-    minimumAmountOut,
-    referralTokenAccount,
+    position,
+    minimumLiquidityDelta,
+    swapExcess,
+    sqrtPriceLimit,
   } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
-
-  const poolAuthority = derivePoolAuthority();
-  const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
-    .owner;
-
-  const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
-    .owner;
-  const inputTokenAccount = getAssociatedTokenAddressSync(
-    inputTokenMint,
-    payer.publicKey,
-    true,
-    tokenAProgram
-  );
-  const outputTokenAccount = getAssociatedTokenAddressSync(
-    outputTokenMint,
-    payer.publicKey,
-    true,
-    tokenBProgram
-  );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
-
-  const transaction = await program.methods
-    .swap({
-      amountIn,
-      minimumAmountOut,
+  const positionState = await getPosition(banksClient, position);
+  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+
+  const transaction = await program.methods
+    .compoundPositionFee({
+      minimumLiquidityDelta,
+      swapExcess,
+      sqrtPriceLimit,
     })
     .accountsPartial({
-      poolAuthority,
+      owner: owner.publicKey,
       pool,
-      payer: payer.publicKey,
-      inputTokenAccount,
-      outputTokenAccount,
-      tokenAVault,
-      tokenBVault,
-      tokenAProgram,
-      tokenBProgram,
-      tokenAMint,
-      tokenBMint,
-      referralTokenAccount,
+      position,
+      positionNftAccount,
     })
-    .remainingAccounts(
-      // TODO should check condition to add this in remaining accounts
-      [
-        {
-          isSigner: false,
-          isWritable: false,
-          pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
-        },
-      ]
-    )
+    .remainingAccounts([
+      ...getAllowlistRemainingAccounts(poolState, pool, owner.publicKey),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+    ])
     .transaction();
 
-  return transaction;
-}
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
 
-export enum SwapMode {
-  ExactIn,
-  PartialFillIn,
-  ExactOut,
+  await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type Swap2Params = {
-  payer: Keypair;
+export type DepositFungibleShareParams = {
+  owner: Keypair;
   pool: PublicKey;
-  inputTokenMint: PublicKey;
-  outputTokenMint: PublicKey;
-  amount0: BN;
-  amount1: BN;
-  swapMode: SwapMode;
-  referralTokenAccount: PublicKey | null;
+  liquidityDelta: BN;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+  minimumShareAmount: BN;
+  maxTransferFeeBps?: number;
 };
 
-export async function swap2Instruction(
+export async function depositFungibleShare(
   banksClient: BanksClient,
-  params: Swap2Params
+  params: DepositFungibleShareParams
 ) {
   const {
-    payer,
+    owner,
     pool,
-    inputTokenMint,
-    outputTokenMint,
-    amount0,
-    amount1,
-    swapMode,
-    referralTokenAccount,
+    liquidityDelta,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    minimumShareAmount,
+    maxTransferFeeBps,
   } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
+  const fungibleShare = deriveFungibleShareAddress(pool);
+  const fungibleShareState = await getFungibleShare(banksClient, fungibleShare);
 
-  const poolAuthority = derivePoolAuthority();
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
-
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
-  const inputTokenAccount = getAssociatedTokenAddressSync(
-    inputTokenMint,
-    payer.publicKey,
+
+  const shareTokenAccount = await getOrCreateAssociatedTokenAccount(
+    banksClient,
+    owner,
+    fungibleShareState.shareMint,
+    owner.publicKey,
+    TOKEN_PROGRAM_ID
+  );
+  const tokenAAccount = getAssociatedTokenAddressSync(
+    poolState.tokenAMint,
+    owner.publicKey,
     true,
     tokenAProgram
   );
-  const outputTokenAccount = getAssociatedTokenAddressSync(
-    outputTokenMint,
-    payer.publicKey,
+  const tokenBAccount = getAssociatedTokenAddressSync(
+    poolState.tokenBMint,
+    owner.publicKey,
     true,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .swap2({
-      amount0,
-      amount1,
-      swapMode,
+    .depositFungibleShare({
+      liquidityDelta,
+      tokenAAmountThreshold,
+      tokenBAmountThreshold,
+      minimumShareAmount,
+      maxTransferFeeBps: maxTransferFeeBps ?? null,
     })
     .accountsPartial({
-      poolAuthority,
+      poolAuthority: derivePoolAuthority(),
       pool,
-      payer: payer.publicKey,
-      inputTokenAccount,
-      outputTokenAccount,
-      tokenAVault,
-      tokenBVault,
+      fungibleShare,
+      position: fungibleShareState.position,
+      shareMint: fungibleShareState.shareMint,
+      shareTokenAccount,
+      tokenAAccount,
+      tokenBAccount,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      owner: owner.publicKey,
       tokenAProgram,
       tokenBProgram,
-      tokenAMint,
-      tokenBMint,
-      referralTokenAccount,
+      shareTokenProgram: TOKEN_PROGRAM_ID,
     })
-    .remainingAccounts(
-      // TODO should check condition to add this in remaining accounts
-      [
-        {
-          isSigner: false,
-          isWritable: false,
-          pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
-        },
-      ]
-    )
+    .remainingAccounts([
+      ...getAllowlistRemainingAccounts(poolState, pool, owner.publicKey),
+      ...(await getLiquidityRemainingAccounts(banksClient, pool, [
+        fungibleShareState.position,
+      ])),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        poolState.tokenAMint,
+        poolState.tokenBMint,
+      ])),
+    ])
     .transaction();
 
-  return transaction;
-}
-
-export async function swap2ExactIn(
-  banksClient: BanksClient,
-  params: Omit<Swap2Params, "swapMode">
-) {
-  const swapIx = await swap2Instruction(banksClient, {
-    ...params,
-    swapMode: SwapMode.ExactIn,
-  });
-  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  swapIx.sign(params.payer);
-  await processTransactionMaybeThrow(banksClient, swapIx);
-}
-
-export async function swap2ExactOut(
-  banksClient: BanksClient,
-  params: Omit<Swap2Params, "swapMode">
-) {
-  const swapIx = await swap2Instruction(banksClient, {
-    ...params,
-    swapMode: SwapMode.ExactOut,
-  });
-  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  swapIx.sign(params.payer);
-  await processTransactionMaybeThrow(banksClient, swapIx);
-}
-
-export async function swap2PartialFillIn(
-  banksClient: BanksClient,
-  params: Omit<Swap2Params, "swapMode">
-) {
-  const swapIx = await swap2Instruction(banksClient, {
-    ...params,
-    swapMode: SwapMode.PartialFillIn,
-  });
-  swapIx.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  swapIx.sign(params.payer);
-  await processTransactionMaybeThrow(banksClient, swapIx);
-}
-
-export async function swapExactIn(
-  banksClient: BanksClient,
-  params: SwapParams
-) {
-  const transaction = await swapInstruction(banksClient, params);
-
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(params.payer);
+  transaction.sign(owner);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
-export type ClaimPositionFeeParams = {
+export type WithdrawFungibleShareParams = {
   owner: Keypair;
   pool: PublicKey;
-  position: PublicKey;
+  shareAmount: BN;
+  tokenAAmountThreshold: BN;
+  tokenBAmountThreshold: BN;
+  maxTransferFeeBps?: number;
 };
 
-export async function claimPositionFee(
+export async function withdrawFungibleShare(
   banksClient: BanksClient,
-  params: ClaimPositionFeeParams
+  params: WithdrawFungibleShareParams
 ) {
-  const { owner, pool, position } = params;
+  const {
+    owner,
+    pool,
+    shareAmount,
+    tokenAAmountThreshold,
+    tokenBAmountThreshold,
+    maxTransferFeeBps,
+  } = params;
 
   const program = createCpAmmProgram();
   const poolState = await getPool(banksClient, pool);
-  const positionState = await getPosition(banksClient, position);
-  const positionNftAccount = derivePositionNftAccount(positionState.nftMint);
+  const fungibleShare = deriveFungibleShareAddress(pool);
+  const fungibleShareState = await getFungibleShare(banksClient, fungibleShare);
 
-  const poolAuthority = derivePoolAuthority();
   const tokenAProgram = (await banksClient.getAccount(poolState.tokenAMint))
     .owner;
   const tokenBProgram = (await banksClient.getAccount(poolState.tokenBMint))
     .owner;
 
+  const shareTokenAccount = getAssociatedTokenAddressSync(
+    fungibleShareState.shareMint,
+    owner.publicKey,
+    true,
+    TOKEN_PROGRAM_ID
+  );
   const tokenAAccount = getAssociatedTokenAddressSync(
     poolState.tokenAMint,
     owner.publicKey,
@@ -1873,28 +4293,42 @@ export async function claimPositionFee(
     true,
     tokenBProgram
   );
-  const tokenAVault = poolState.tokenAVault;
-  const tokenBVault = poolState.tokenBVault;
-  const tokenAMint = poolState.tokenAMint;
-  const tokenBMint = poolState.tokenBMint;
 
   const transaction = await program.methods
-    .claimPositionFee()
+    .withdrawFungibleShare({
+      shareAmount,
+      tokenAAmountThreshold,
+      tokenBAmountThreshold,
+      maxTransferFeeBps: maxTransferFeeBps ?? null,
+    })
     .accountsPartial({
-      poolAuthority,
-      owner: owner.publicKey,
+      poolAuthority: derivePoolAuthority(),
       pool,
-      position,
-      positionNftAccount,
+      fungibleShare,
+      position: fungibleShareState.position,
+      shareMint: fungibleShareState.shareMint,
+      shareTokenAccount,
       tokenAAccount,
       tokenBAccount,
-      tokenAVault,
-      tokenBVault,
+      tokenAVault: poolState.tokenAVault,
+      tokenBVault: poolState.tokenBVault,
+      tokenAMint: poolState.tokenAMint,
+      tokenBMint: poolState.tokenBMint,
+      owner: owner.publicKey,
       tokenAProgram,
       tokenBProgram,
-      tokenAMint,
-      tokenBMint,
+      shareTokenProgram: TOKEN_PROGRAM_ID,
     })
+    .remainingAccounts([
+      ...(await getLiquidityRemainingAccounts(banksClient, pool, [
+        fungibleShareState.position,
+      ])),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+      ...(await getTransferHookRemainingAccounts(banksClient, [
+        poolState.tokenAMint,
+        poolState.tokenBMint,
+      ])),
+    ])
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
@@ -1903,6 +4337,38 @@ export async function claimPositionFee(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
+export async function compoundFungibleShare(
+  banksClient: BanksClient,
+  payer: Keypair,
+  pool: PublicKey
+) {
+  const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
+  const fungibleShare = deriveFungibleShareAddress(pool);
+  const fungibleShareState = await getFungibleShare(banksClient, fungibleShare);
+
+  const transaction = await program.methods
+    .compoundFungibleShare()
+    .accountsPartial({
+      pool,
+      fungibleShare,
+      position: fungibleShareState.position,
+      shareMint: fungibleShareState.shareMint,
+    })
+    .remainingAccounts([
+      ...(await getLiquidityRemainingAccounts(banksClient, pool, [
+        fungibleShareState.position,
+      ])),
+      ...getLockedFeeExtensionRemainingAccounts(poolState, pool),
+    ])
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
 export type SplitPositionParams = {
   firstPositionOwner: Keypair;
   secondPositionOwner: Keypair;
@@ -1938,6 +4404,7 @@ export async function splitPosition(
     reward1Percentage,
   } = params;
   const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
   const transaction = await program.methods
     .splitPosition({
       permanentLockedLiquidityPercentage,
@@ -1957,6 +4424,7 @@ export async function splitPosition(
       firstOwner: firstPositionOwner.publicKey,
       secondOwner: secondPositionOwner.publicKey,
     })
+    .remainingAccounts(getLockedFeeExtensionRemainingAccounts(poolState, pool))
     .transaction();
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
   transaction.sign(firstPositionOwner, secondPositionOwner);
@@ -1989,6 +4457,7 @@ export async function splitPosition2(
     numerator
   } = params;
   const program = createCpAmmProgram();
+  const poolState = await getPool(banksClient, pool);
   const transaction = await program.methods
     .splitPosition2(numerator)
     .accountsPartial({
@@ -2000,6 +4469,7 @@ export async function splitPosition2(
       firstOwner: firstPositionOwner.publicKey,
       secondOwner: secondPositionOwner.publicKey,
     })
+    .remainingAccounts(getLockedFeeExtensionRemainingAccounts(poolState, pool))
     .transaction();
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
   transaction.sign(firstPositionOwner, secondPositionOwner);
@@ -2016,6 +4486,66 @@ export async function getPool(
   return program.coder.accounts.decode("pool", Buffer.from(account.data));
 }
 
+export async function getRewardExtension(
+  banksClient: BanksClient,
+  rewardExtension: PublicKey
+): Promise<RewardExtension> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(rewardExtension);
+  return program.coder.accounts.decode(
+    "rewardExtension",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getPositionRewardExtension(
+  banksClient: BanksClient,
+  positionRewardExtension: PublicKey
+): Promise<PositionRewardExtension> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(positionRewardExtension);
+  return program.coder.accounts.decode(
+    "positionRewardExtension",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getBribeLedger(
+  banksClient: BanksClient,
+  bribeLedger: PublicKey
+): Promise<BribeLedger> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(bribeLedger);
+  return program.coder.accounts.decode(
+    "bribeLedger",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getPositionBribeLedger(
+  banksClient: BanksClient,
+  positionBribeLedger: PublicKey
+): Promise<PositionBribeLedger> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(positionBribeLedger);
+  return program.coder.accounts.decode(
+    "positionBribeLedger",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getBribeCampaign(
+  banksClient: BanksClient,
+  bribeCampaign: PublicKey
+): Promise<BribeCampaign> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(bribeCampaign);
+  return program.coder.accounts.decode(
+    "bribeCampaign",
+    Buffer.from(account.data)
+  );
+}
+
 export async function getPosition(
   banksClient: BanksClient,
   position: PublicKey
@@ -2057,6 +4587,26 @@ export function getCpAmmProgramErrorCodeHexString(errorMessage: String) {
   return "0x" + error.code.toString(16);
 }
 
+export async function getAdminRegistry(
+  banksClient: BanksClient
+): Promise<AdminRegistry> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(deriveAdminRegistryAddress());
+  return program.coder.accounts.decode(
+    "adminRegistry",
+    Buffer.from(account.data)
+  );
+}
+
+export async function getProposal(
+  banksClient: BanksClient,
+  proposal: PublicKey
+): Promise<Proposal> {
+  const program = createCpAmmProgram();
+  const account = await banksClient.getAccount(proposal);
+  return program.coder.accounts.decode("proposal", Buffer.from(account.data));
+}
+
 export async function getTokenBadge(
   banksClient: BanksClient,
   tokenBadge: PublicKey
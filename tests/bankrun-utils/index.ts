@@ -3,3 +3,4 @@ export * from "./common";
 export * from "./cpAmm";
 export * from "./token";
 export * from "./constants";
+export * from "./mockSourceAmm";
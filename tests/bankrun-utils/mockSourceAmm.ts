@@ -0,0 +1,179 @@
+import { AnchorProvider, BN, Program, Wallet } from "@coral-xyz/anchor";
+import {
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  clusterApiUrl,
+  ComputeBudgetProgram,
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { BanksClient } from "solana-bankrun";
+import MockSourceAmmIDL from "../../target/idl/mock_source_amm.json";
+import { MockSourceAmm } from "../../target/types/mock_source_amm";
+import {
+  derivePoolAddress,
+  derivePoolAuthority,
+  derivePositionAddress,
+  derivePositionNftAccount,
+  deriveTokenVaultAddress,
+} from "./accounts";
+import { processTransactionMaybeThrow } from "./common";
+import { CP_AMM_PROGRAM_ID, MOCK_SOURCE_AMM_PROGRAM_ID } from "./constants";
+import {
+  getInitializePoolRemainingAccounts,
+  PoolFeesParams,
+} from "./cpAmm";
+
+export function createMockSourceAmmProgram() {
+  const wallet = new Wallet(Keypair.generate());
+  const provider = new AnchorProvider(
+    new Connection(clusterApiUrl("devnet")),
+    wallet,
+    {}
+  );
+  const program = new Program<MockSourceAmm>(
+    MockSourceAmmIDL as MockSourceAmm,
+    provider
+  );
+  return program;
+}
+
+export function deriveMigratorAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("migrator")],
+    MOCK_SOURCE_AMM_PROGRAM_ID
+  )[0];
+}
+
+export type MigratePoolParams = {
+  payer: Keypair;
+  creator: PublicKey;
+  owner: PublicKey;
+  config: PublicKey;
+  tokenAMint: PublicKey;
+  tokenBMint: PublicKey;
+  poolFees: PoolFeesParams;
+  sqrtMinPrice: BN;
+  sqrtMaxPrice: BN;
+  hasAlphaVault: boolean;
+  liquidity: BN;
+  sqrtPrice: BN;
+  activationType: number;
+  collectFeeMode: number;
+  activationPoint: BN | null;
+  permanentLockLiquidityBps: number;
+};
+
+export async function migratePool(
+  banksClient: BanksClient,
+  params: MigratePoolParams
+): Promise<{ pool: PublicKey; position: PublicKey; positionNft: PublicKey }> {
+  const {
+    payer,
+    creator,
+    owner,
+    config,
+    tokenAMint,
+    tokenBMint,
+    poolFees,
+    sqrtMinPrice,
+    sqrtMaxPrice,
+    hasAlphaVault,
+    liquidity,
+    sqrtPrice,
+    activationType,
+    collectFeeMode,
+    activationPoint,
+    permanentLockLiquidityBps,
+  } = params;
+  const program = createMockSourceAmmProgram();
+
+  const migrator = deriveMigratorAddress();
+  const pool = derivePoolAddress(config, tokenAMint, tokenBMint);
+
+  const positionNftKP = Keypair.generate();
+  const position = derivePositionAddress(positionNftKP.publicKey);
+  const positionNftAccount = derivePositionNftAccount(positionNftKP.publicKey);
+
+  const tokenAProgram = (await banksClient.getAccount(tokenAMint)).owner;
+  const tokenBProgram = (await banksClient.getAccount(tokenBMint)).owner;
+
+  const eventAuthority = PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    CP_AMM_PROGRAM_ID
+  )[0];
+
+  const transaction = await program.methods
+    .migrate({
+      poolParameters: {
+        poolFees,
+        sqrtMinPrice,
+        sqrtMaxPrice,
+        hasAlphaVault,
+        liquidity,
+        sqrtPrice,
+        activationType,
+        collectFeeMode,
+        activationPoint,
+      },
+      permanentLockLiquidityBps,
+    })
+    .accountsPartial({
+      migrator,
+      creator,
+      owner,
+      positionNftMint: positionNftKP.publicKey,
+      positionNftAccount,
+      payer: payer.publicKey,
+      config,
+      poolAuthority: derivePoolAuthority(),
+      pool,
+      position,
+      tokenAMint,
+      tokenBMint,
+      tokenAVault: deriveTokenVaultAddress(tokenAMint, pool),
+      tokenBVault: deriveTokenVaultAddress(tokenBMint, pool),
+      migratorTokenA: getAssociatedTokenAddressSync(
+        tokenAMint,
+        migrator,
+        true,
+        tokenAProgram
+      ),
+      migratorTokenB: getAssociatedTokenAddressSync(
+        tokenBMint,
+        migrator,
+        true,
+        tokenBProgram
+      ),
+      tokenAProgram,
+      tokenBProgram,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      eventAuthority,
+      cpAmmProgram: CP_AMM_PROGRAM_ID,
+    })
+    .remainingAccounts(
+      await getInitializePoolRemainingAccounts(
+        banksClient,
+        tokenAMint,
+        tokenBMint
+      )
+    )
+    .transaction();
+  // requires more compute budget than usual
+  transaction.add(
+    ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    })
+  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, positionNftKP);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return { pool, position, positionNft: positionNftKP.publicKey };
+}
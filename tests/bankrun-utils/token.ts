@@ -2,6 +2,7 @@ import {
   AccountLayout,
   createAssociatedTokenAccountInstruction,
   createFreezeAccountInstruction,
+  createThawAccountInstruction,
   createInitializeMint2Instruction,
   createInitializeMintInstruction,
   createMintToInstruction,
@@ -114,6 +115,29 @@ export async function freezeTokenAccount(
   await banksClient.processTransaction(transaction);
 }
 
+export async function thawTokenAccount(
+  banksClient: BanksClient,
+  freezeAuthority: Keypair,
+  tokenMint: PublicKey,
+  tokenAccount: PublicKey,
+  tokenProgram = TOKEN_PROGRAM_ID
+) {
+  const thawInstruction = createThawAccountInstruction(
+    tokenAccount,
+    tokenMint,
+    freezeAuthority.publicKey,
+    [],
+    tokenProgram
+  );
+  let transaction = new Transaction();
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.add(thawInstruction);
+  transaction.sign(freezeAuthority);
+
+  await banksClient.processTransaction(transaction);
+}
+
 export async function wrapSOL(
   banksClient: BanksClient,
   payer: Keypair,
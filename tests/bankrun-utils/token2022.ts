@@ -8,6 +8,7 @@ import {
   createMintToInstruction,
   createInitializePermanentDelegateInstruction,
   createInitializeTransferHookInstruction,
+  createInitializeInterestBearingMintInstruction,
   createUpdateTransferHookInstruction,
   createSetAuthorityInstruction,
   AuthorityType,
@@ -69,6 +70,22 @@ export function createTransferFeeExtensionWithInstruction(
   };
 }
 
+export function createInterestBearingExtensionWithInstruction(
+  mint: PublicKey,
+  rateAuthority: PublicKey,
+  rate: number
+): ExtensionWithInstruction {
+  return {
+    extension: ExtensionType.InterestBearingConfig,
+    instruction: createInitializeInterestBearingMintInstruction(
+      mint,
+      rateAuthority,
+      rate,
+      TOKEN_2022_PROGRAM_ID
+    ),
+  };
+}
+
 export function createTransferHookExtensionWithInstruction(
   mint: PublicKey,
   authority: PublicKey
@@ -0,0 +1,230 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { describe } from "mocha";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { expect } from "chai";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  BRIBE_EPOCH_DURATION,
+  claimBribe,
+  closeBribeCampaign,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createPositionBribeLedger,
+  createToken,
+  getBribeCampaign,
+  initializeBribeCampaign,
+  initializeBribeLedger,
+  initializePool,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  removeLiquidity,
+  withdrawIneligibleBribe,
+  convertToByteArray,
+  expectThrowsAsync,
+  getCpAmmProgramErrorCodeHexString,
+} from "./bankrun-utils";
+import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+
+describe("Bribe", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let admin: Keypair;
+  let config: PublicKey;
+  let funder: Keypair;
+  let user: Keypair;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let bribeMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+
+  async function warpToTimestamp(timestamp: number) {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(timestamp)
+      )
+    );
+  }
+
+  async function getCurrentBribeEpoch() {
+    const clock = await context.banksClient.getClock();
+    return Math.floor(Number(clock.unixTimestamp) / BRIBE_EPOCH_DURATION);
+  }
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    funder = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    bribeMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      bribeMint,
+      context.payer,
+      funder.publicKey
+    );
+
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+  });
+
+  it("Full flow for bribe campaign", async () => {
+    const { pool } = await initializePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE),
+      activationPoint: null,
+    });
+
+    const position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    await initializeBribeLedger(context.banksClient, funder, pool);
+    await createPositionBribeLedger(context.banksClient, user, pool, position);
+
+    // liquidity changes require bribe ledgers in remaining accounts
+    const addLiquidityParams: AddLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    };
+    await addLiquidity(context.banksClient, addLiquidityParams);
+
+    // bribe for the current epoch is rejected because ledger started in it
+    const currentEpoch = await getCurrentBribeEpoch();
+    const errorCode = getCpAmmProgramErrorCodeHexString("InvalidBribeEpoch");
+    await expectThrowsAsync(async () => {
+      await initializeBribeCampaign(context.banksClient, {
+        funder,
+        pool,
+        mint: bribeMint,
+        epoch: new BN(currentEpoch),
+        amount: new BN(1_000_000),
+      });
+    }, errorCode);
+
+    const epoch = new BN(currentEpoch + 1);
+    const amount = new BN(1_000_000);
+    const bribeCampaign = await initializeBribeCampaign(context.banksClient, {
+      funder,
+      pool,
+      mint: bribeMint,
+      epoch,
+      amount,
+    });
+
+    // can't claim before the epoch ends
+    await warpToTimestamp((currentEpoch + 1) * BRIBE_EPOCH_DURATION + 3600);
+    await expectThrowsAsync(async () => {
+      await claimBribe(context.banksClient, user, bribeCampaign, position);
+    }, getCpAmmProgramErrorCodeHexString("BribeEpochNotEnded"));
+
+    await removeLiquidity(context.banksClient, {
+      ...addLiquidityParams,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+
+    await warpToTimestamp((currentEpoch + 2) * BRIBE_EPOCH_DURATION);
+    await claimBribe(context.banksClient, user, bribeCampaign, position);
+    await withdrawIneligibleBribe(context.banksClient, funder, bribeCampaign);
+
+    // position owns the whole epoch except the time after liquidity removal
+    const bribeCampaignState = await getBribeCampaign(
+      context.banksClient,
+      bribeCampaign
+    );
+    expect(bribeCampaignState.claimedAmount.toNumber()).gt(0);
+    expect(bribeCampaignState.ineligibleAmount.toNumber()).gt(0);
+    expect(
+      bribeCampaignState.claimedAmount
+        .add(bribeCampaignState.ineligibleAmount)
+        .lte(amount)
+    ).eq(true);
+
+    // campaign can only be closed once epoch snapshot is evicted
+    await expectThrowsAsync(async () => {
+      await closeBribeCampaign(context.banksClient, funder, bribeCampaign);
+    }, getCpAmmProgramErrorCodeHexString("BribeCampaignNotExpired"));
+  });
+});
@@ -1,3 +1,4 @@
+import { expect } from "chai";
 import { ProgramTestContext } from "solana-bankrun";
 import {
   convertToByteArray,
@@ -26,9 +27,15 @@ import {
   closeClaimFeeOperator,
   mintSplTokenTo,
   createToken,
+  batchClaimProtocolFee,
+  getPool,
+  getTokenAccount,
 } from "./bankrun-utils";
 import BN from "bn.js";
-import { ExtensionType } from "@solana/spl-token";
+import {
+  ExtensionType,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import {
   createToken2022,
   createTransferFeeExtensionWithInstruction,
@@ -207,6 +214,98 @@ describe("Claim fee", () => {
         rentReceiver: operator.publicKey,
       });
     });
+
+    it("Batch claim protocol fee across pools", async () => {
+      const secondConfig = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(randomID()),
+        {
+          poolFees: {
+            baseFee: {
+              cliffFeeNumerator: new BN(2_500_000),
+              firstFactor: 0,
+              secondFactor: convertToByteArray(new BN(0)),
+              thirdFactor: new BN(0),
+              baseFeeMode: 0,
+            },
+            padding: [],
+            dynamicFee: null,
+          },
+          sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+          sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+          vaultConfigKey: PublicKey.default,
+          poolCreatorAuthority: partner.publicKey,
+          activationType: 0,
+          collectFeeMode: 0,
+        }
+      );
+      const { pool: poolWithoutFee } = await initializePool(
+        context.banksClient,
+        {
+          payer: partner,
+          creator: partner.publicKey,
+          config: secondConfig,
+          tokenAMint: inputTokenMint,
+          tokenBMint: outputTokenMint,
+          liquidity,
+          sqrtPrice,
+          activationPoint: null,
+        }
+      );
+
+      await addLiquidity(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: MIN_SQRT_PRICE,
+        tokenAAmountThreshold: new BN(2_000_000_000),
+        tokenBAmountThreshold: new BN(2_000_000_000),
+      });
+      await swapExactIn(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+
+      const poolState = await getPool(context.banksClient, pool);
+      const protocolFee = poolState.protocolAFee.add(poolState.protocolBFee);
+      expect(protocolFee.gtn(0)).to.be.true;
+
+      // pool without protocol fee is skipped
+      await batchClaimProtocolFee(context.banksClient, {
+        operator,
+        pools: [poolWithoutFee, pool],
+        treasury: TREASURY,
+      });
+
+      const treasuryTokenAAccount = getAssociatedTokenAddressSync(
+        inputTokenMint,
+        TREASURY,
+        true
+      );
+      const treasuryTokenBAccount = getAssociatedTokenAddressSync(
+        outputTokenMint,
+        TREASURY,
+        true
+      );
+      const treasuryAmountA = (
+        await getTokenAccount(context.banksClient, treasuryTokenAAccount)
+      ).amount;
+      const treasuryAmountB = (
+        await getTokenAccount(context.banksClient, treasuryTokenBAccount)
+      ).amount;
+      expect(treasuryAmountA.toString()).eq(poolState.protocolAFee.toString());
+      expect(treasuryAmountB.toString()).eq(poolState.protocolBFee.toString());
+
+      const poolStateAfter = await getPool(context.banksClient, pool);
+      expect(poolStateAfter.protocolAFee.toNumber()).eq(0);
+      expect(poolStateAfter.protocolBFee.toNumber()).eq(0);
+    });
   });
 
   describe("Token 2022", () => {
@@ -0,0 +1,245 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addAllowlistEntry,
+  claimProtocolFee,
+  closePool,
+  closePosition,
+  createClaimFeeOperator,
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  getTokenAccount,
+  initializeBribeLedger,
+  initializeCustomizablePool,
+  initializeLockedFeeExtension,
+  initializePoolPermission,
+  initializeReward,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  removeAllLiquidity,
+  removeAllowlistEntry,
+  swapExactIn,
+  TREASURY,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Close pool", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let operator: Keypair;
+  let creator: Keypair;
+  let user: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let activationSlot: bigint;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    operator = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    // pool can only become permissioned before activation
+    activationSlot = (await context.banksClient.getClock()).slot + BigInt(10);
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: new BN(activationSlot.toString()),
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+    pool = result.pool;
+    position = result.position;
+
+    await createClaimFeeOperator(context.banksClient, {
+      admin,
+      operator: operator.publicKey,
+    });
+  });
+
+  it("Close drained pool and sweep dust to treasury", async () => {
+    context.warpToSlot(activationSlot);
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    // position is still open
+    await expectThrowsAsync(async () => {
+      await closePool(context.banksClient, { payer: user, pool });
+    }, getCpAmmProgramErrorCodeHexString("PoolIsNotEmpty"));
+
+    await removeAllLiquidity(context.banksClient, {
+      owner: creator,
+      pool,
+      position,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+    await closePosition(context.banksClient, {
+      owner: creator,
+      pool,
+      position,
+    });
+
+    // protocol fee is not claimed
+    await expectThrowsAsync(async () => {
+      await closePool(context.banksClient, { payer: user, pool });
+    }, getCpAmmProgramErrorCodeHexString("PoolIsNotEmpty"));
+
+    await claimProtocolFee(context.banksClient, {
+      operator,
+      pool,
+      treasury: TREASURY,
+    });
+    const treasuryTokenA = getAssociatedTokenAddressSync(
+      tokenAMint,
+      TREASURY,
+      true
+    );
+    const treasuryAmountBefore = new BN(
+      (
+        await getTokenAccount(context.banksClient, treasuryTokenA)
+      ).amount.toString()
+    );
+
+    const creatorLamportsBefore = (
+      await context.banksClient.getAccount(creator.publicKey)
+    ).lamports;
+
+    // permissionless
+    await closePool(context.banksClient, { payer: user, pool });
+
+    const creatorLamportsAfter = (
+      await context.banksClient.getAccount(creator.publicKey)
+    ).lamports;
+    expect(creatorLamportsAfter > creatorLamportsBefore).to.be.true;
+
+    const treasuryAmountAfter = new BN(
+      (
+        await getTokenAccount(context.banksClient, treasuryTokenA)
+      ).amount.toString()
+    );
+    expect(treasuryAmountAfter.gte(treasuryAmountBefore)).to.be.true;
+  });
+
+  it("Close pool extensions along with the pool", async () => {
+    await initializeReward(context.banksClient, {
+      index: 0,
+      payer: creator,
+      rewardDuration: new BN(24 * 60 * 60),
+      pool,
+      rewardMint: tokenAMint,
+    });
+    await initializeLockedFeeExtension(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      lockedFeeShareBps: 2_000,
+    });
+    await initializeBribeLedger(context.banksClient, creator, pool);
+    await initializePoolPermission(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      manager: creator.publicKey,
+    });
+    await addAllowlistEntry(context.banksClient, {
+      manager: creator,
+      pool,
+      wallet: user.publicKey,
+    });
+    context.warpToSlot(activationSlot);
+
+    await removeAllLiquidity(context.banksClient, {
+      owner: creator,
+      pool,
+      position,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+    await closePosition(context.banksClient, {
+      owner: creator,
+      pool,
+      position,
+    });
+
+    // allowlist entry is still open
+    await expectThrowsAsync(async () => {
+      await closePool(context.banksClient, { payer: user, pool });
+    }, getCpAmmProgramErrorCodeHexString("PoolIsNotEmpty"));
+
+    await removeAllowlistEntry(context.banksClient, {
+      manager: creator,
+      pool,
+      wallet: user.publicKey,
+    });
+
+    // reward vault, locked fee extension, bribe ledger and pool permission are closed too
+    await closePool(context.banksClient, { payer: user, pool });
+  });
+});
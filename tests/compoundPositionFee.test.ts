@@ -0,0 +1,263 @@
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  compoundPositionFee,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  initializePool,
+  InitializePoolParams,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  swapExactIn,
+  SwapParams,
+  createToken,
+  mintSplTokenTo,
+  getPool,
+  getPosition,
+  getCpAmmProgramErrorCodeHexString,
+} from "./bankrun-utils";
+import BN from "bn.js";
+import { expect } from "chai";
+
+describe("Compound position fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let config: PublicKey;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      user.publicKey
+    );
+
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      user.publicKey
+    );
+
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      creator.publicKey
+    );
+
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      creator.publicKey
+    );
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+
+    const initPoolParams: InitializePoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    };
+
+    const result = await initializePool(context.banksClient, initPoolParams);
+    pool = result.pool;
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+  });
+
+  async function accrueFee() {
+    const addLiquidityParams: AddLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    };
+    await addLiquidity(context.banksClient, addLiquidityParams);
+
+    const swapParams: SwapParams = {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(100_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    };
+    await swapExactIn(context.banksClient, swapParams);
+
+    await swapExactIn(context.banksClient, {
+      ...swapParams,
+      inputTokenMint: tokenBMint,
+      outputTokenMint: tokenAMint,
+    });
+  }
+
+  it("User compound position fee", async () => {
+    await accrueFee();
+    const positionBefore = await getPosition(context.banksClient, position);
+
+    await compoundPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      minimumLiquidityDelta: new BN(1),
+      swapExcess: false,
+      sqrtPriceLimit: new BN(0),
+    });
+
+    const positionAfter = await getPosition(context.banksClient, position);
+    expect(
+      positionAfter.unlockedLiquidity.gt(positionBefore.unlockedLiquidity)
+    ).to.be.true;
+  });
+
+  // price limit on the side the excess fee is swapped to
+  async function getLoosePriceLimit(): Promise<BN> {
+    const { sqrtPrice, sqrtMinPrice, sqrtMaxPrice } = await getPool(
+      context.banksClient,
+      pool
+    );
+    const { feeAPending, feeBPending } = await getPosition(
+      context.banksClient,
+      position
+    );
+    // liquidity from token a is lower than liquidity from token b, so token b is swapped
+    const isExcessB = feeAPending
+      .mul(sqrtPrice)
+      .mul(sqrtMaxPrice)
+      .mul(sqrtPrice.sub(sqrtMinPrice))
+      .lt(feeBPending.shln(128).mul(sqrtMaxPrice.sub(sqrtPrice)));
+    return isExcessB ? MAX_SQRT_PRICE : MIN_SQRT_PRICE;
+  }
+
+  it("User compound position fee with swapping excess fee", async () => {
+    await accrueFee();
+    const positionBefore = await getPosition(context.banksClient, position);
+
+    await compoundPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      minimumLiquidityDelta: new BN(1),
+      swapExcess: true,
+      sqrtPriceLimit: await getLoosePriceLimit(),
+    });
+
+    const positionAfter = await getPosition(context.banksClient, position);
+    expect(
+      positionAfter.unlockedLiquidity.gt(positionBefore.unlockedLiquidity)
+    ).to.be.true;
+  });
+
+  it("Compound position fee fails when swap crosses price limit", async () => {
+    await accrueFee();
+    const { sqrtPrice } = await getPool(context.banksClient, pool);
+
+    // swapping the excess in any direction moves the price past the current price
+    const errorCode = getCpAmmProgramErrorCodeHexString("ExceededSlippage");
+    await expectThrowsAsync(async () => {
+      await compoundPositionFee(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        minimumLiquidityDelta: new BN(1),
+        swapExcess: true,
+        sqrtPriceLimit: sqrtPrice,
+      });
+    }, errorCode);
+  });
+
+  it("Compound position fee fails when liquidity is below minimum", async () => {
+    await accrueFee();
+
+    const errorCode = getCpAmmProgramErrorCodeHexString("ExceededSlippage");
+    await expectThrowsAsync(async () => {
+      await compoundPositionFee(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+        minimumLiquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        swapExcess: false,
+        sqrtPriceLimit: new BN(0),
+      });
+    }, errorCode);
+  });
+});
@@ -0,0 +1,247 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addLiquidity,
+  claimPendingWithdrawal,
+  claimPositionFee,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createToken,
+  freezeTokenAccount,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  getPosition,
+  getTokenAccount,
+  initializePool,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  removeLiquidity,
+  swapExactIn,
+  thawTokenAccount,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Frozen pool vault", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+  const liquidity = new BN(1_000_000_000).shln(64);
+
+  async function getUserBalances() {
+    const tokenAAccount = await getTokenAccount(
+      context.banksClient,
+      getAssociatedTokenAddressSync(tokenAMint, user.publicKey)
+    );
+    const tokenBAccount = await getTokenAccount(
+      context.banksClient,
+      getAssociatedTokenAddressSync(tokenBMint, user.publicKey)
+    );
+    return [tokenAAccount.amount, tokenBAccount.amount];
+  }
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    // creator is freeze authority of token a
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey,
+      creator.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+
+    const result = await initializePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      // price 1
+      sqrtPrice: new BN(1).shln(64),
+      activationPoint: null,
+    });
+    pool = result.pool;
+
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+
+    // accrue fee in both tokens
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenBMint,
+      outputTokenMint: tokenAMint,
+      amountIn: new BN(10_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+  });
+
+  it("Frozen token is kept pending and claimed once unfrozen", async () => {
+    const poolState = await getPool(context.banksClient, pool);
+    await freezeTokenAccount(
+      context.banksClient,
+      creator,
+      tokenAMint,
+      poolState.tokenAVault
+    );
+
+    // claim fee of token b only
+    let [balanceABefore, balanceBBefore] = await getUserBalances();
+    await claimPositionFee(context.banksClient, { owner: user, pool, position });
+    let [balanceAAfter, balanceBAfter] = await getUserBalances();
+    expect(balanceAAfter).eq(balanceABefore);
+    expect(balanceBAfter > balanceBBefore).to.be.true;
+
+    let positionState = await getPosition(context.banksClient, position);
+    expect(positionState.feeAPending.gtn(0)).to.be.true;
+    expect(positionState.feeBPending.toNumber()).eq(0);
+
+    // remove liquidity, token a is recorded as pending withdrawal
+    [balanceABefore, balanceBBefore] = await getUserBalances();
+    await removeLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+    [balanceAAfter, balanceBAfter] = await getUserBalances();
+    expect(balanceAAfter).eq(balanceABefore);
+    expect(balanceBAfter > balanceBBefore).to.be.true;
+
+    positionState = await getPosition(context.banksClient, position);
+    const pendingWithdrawalA = positionState.pendingWithdrawalA;
+    expect(pendingWithdrawalA.gtn(0)).to.be.true;
+    expect(positionState.pendingWithdrawalB.toNumber()).eq(0);
+
+    // nothing to claim while token a is frozen
+    const errorCode = getCpAmmProgramErrorCodeHexString("AmountIsZero");
+    await expectThrowsAsync(async () => {
+      await claimPendingWithdrawal(context.banksClient, {
+        owner: user,
+        pool,
+        position,
+      });
+    }, errorCode);
+
+    await thawTokenAccount(
+      context.banksClient,
+      creator,
+      tokenAMint,
+      poolState.tokenAVault
+    );
+
+    [balanceABefore] = await getUserBalances();
+    await claimPendingWithdrawal(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+    });
+    [balanceAAfter] = await getUserBalances();
+    expect(balanceAAfter - balanceABefore).eq(
+      BigInt(pendingWithdrawalA.toString())
+    );
+
+    await claimPositionFee(context.banksClient, { owner: user, pool, position });
+    positionState = await getPosition(context.banksClient, position);
+    expect(positionState.pendingWithdrawalA.toNumber()).eq(0);
+    expect(positionState.feeAPending.toNumber()).eq(0);
+  });
+});
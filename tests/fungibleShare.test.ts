@@ -0,0 +1,472 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  compoundFungibleShare,
+  createPositionBribeLedger,
+  createToken,
+  depositFungibleShare,
+  deriveBribeLedgerAddress,
+  deriveFungibleShareAddress,
+  deriveFungibleShareMintAddress,
+  getBribeLedger,
+  getCpAmmProgramErrorCodeHexString,
+  getFungibleShare,
+  getMint,
+  getPosition,
+  getPositionBribeLedger,
+  getTokenAccount,
+  initializeBribeLedger,
+  initializeCustomizablePool,
+  initializeFungibleShare,
+  initializeReward,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  swapExactIn,
+  U64_MAX,
+  withdrawFungibleShare,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Fungible share", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let alice: Keypair;
+  let bob: Keypair;
+  let pool: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    alice = await generateKpAndFund(context.banksClient, context.payer);
+    bob = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, alice, bob]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+    pool = result.pool;
+  });
+
+  it("Only pool creator can initialize fungible share", async () => {
+    // has_one constraint
+    await expectThrowsAsync(async () => {
+      await initializeFungibleShare(context.banksClient, {
+        payer: alice,
+        creator: alice,
+        pool,
+        autoCompound: true,
+      });
+    }, "0x7d1");
+
+    const fungibleShare = await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: true,
+    });
+    const fungibleShareState = await getFungibleShare(
+      context.banksClient,
+      fungibleShare
+    );
+    expect(fungibleShareState.autoCompound).eq(1);
+    expect(fungibleShareState.shareMint.toString()).eq(
+      deriveFungibleShareMintAddress(pool).toString()
+    );
+  });
+
+  it("Shares are minted and burnt proportional to shared liquidity", async () => {
+    await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: false,
+    });
+    const fungibleShareState = await getFungibleShare(
+      context.banksClient,
+      deriveFungibleShareAddress(pool)
+    );
+    const { shareMint, position } = fungibleShareState;
+
+    const liquidityDelta = new BN(1_000_000_000).shln(64);
+    for (const owner of [alice, bob]) {
+      await depositFungibleShare(context.banksClient, {
+        owner,
+        pool,
+        liquidityDelta,
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+        minimumShareAmount: new BN(0),
+      });
+    }
+
+    const aliceShareAccount = getAssociatedTokenAddressSync(
+      shareMint,
+      alice.publicKey
+    );
+    const bobShareAccount = getAssociatedTokenAddressSync(
+      shareMint,
+      bob.publicKey
+    );
+    const aliceShares = (
+      await getTokenAccount(context.banksClient, aliceShareAccount)
+    ).amount;
+    const bobShares = (
+      await getTokenAccount(context.banksClient, bobShareAccount)
+    ).amount;
+    expect(aliceShares.toString()).eq("1000000000");
+    expect(bobShares.toString()).eq(aliceShares.toString());
+
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.unlockedLiquidity.toString()).eq(
+      liquidityDelta.muln(2).toString()
+    );
+
+    // slippage on minimum shares
+    await expectThrowsAsync(async () => {
+      await depositFungibleShare(context.banksClient, {
+        owner: alice,
+        pool,
+        liquidityDelta,
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+        minimumShareAmount: new BN(1_000_000_001),
+      });
+    }, getCpAmmProgramErrorCodeHexString("ExceededSlippage"));
+
+    await withdrawFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      shareAmount: new BN(aliceShares.toString()),
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+
+    expect(
+      (await getTokenAccount(context.banksClient, aliceShareAccount)).amount
+    ).eq(BigInt(0));
+    expect(
+      (await getMint(context.banksClient, shareMint)).supply.toString()
+    ).eq(bobShares.toString());
+    const positionStateAfter = await getPosition(
+      context.banksClient,
+      position
+    );
+    expect(positionStateAfter.unlockedLiquidity.toString()).eq(
+      liquidityDelta.toString()
+    );
+  });
+
+  it("Compounded fee increases liquidity backing each share", async () => {
+    await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: true,
+    });
+    const { position } = await getFungibleShare(
+      context.banksClient,
+      deriveFungibleShareAddress(pool)
+    );
+
+    await depositFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      minimumShareAmount: new BN(0),
+    });
+
+    for (const [inputTokenMint, outputTokenMint] of [
+      [tokenAMint, tokenBMint],
+      [tokenBMint, tokenAMint],
+    ]) {
+      await swapExactIn(context.banksClient, {
+        payer: creator,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+    }
+
+    const liquidityBefore = (await getPosition(context.banksClient, position))
+      .unlockedLiquidity;
+    await compoundFungibleShare(context.banksClient, creator, pool);
+    const liquidityAfter = (await getPosition(context.banksClient, position))
+      .unlockedLiquidity;
+    expect(liquidityAfter.gt(liquidityBefore)).to.be.true;
+
+    // nothing left to compound
+    await expectThrowsAsync(async () => {
+      await compoundFungibleShare(context.banksClient, creator, pool);
+    }, getCpAmmProgramErrorCodeHexString("AmountIsZero"));
+
+    // later depositor gets fewer shares for the same liquidity
+    await depositFungibleShare(context.banksClient, {
+      owner: bob,
+      pool,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      minimumShareAmount: new BN(0),
+    });
+    const bobShares = (
+      await getTokenAccount(
+        context.banksClient,
+        getAssociatedTokenAddressSync(
+          deriveFungibleShareMintAddress(pool),
+          bob.publicKey
+        )
+      )
+    ).amount;
+    expect(bobShares < BigInt(1_000_000_000)).to.be.true;
+
+    // last holder withdraws all liquidity left in the shared position
+    for (const owner of [alice, bob]) {
+      const shares = (
+        await getTokenAccount(
+          context.banksClient,
+          getAssociatedTokenAddressSync(
+            deriveFungibleShareMintAddress(pool),
+            owner.publicKey
+          )
+        )
+      ).amount;
+      await withdrawFungibleShare(context.banksClient, {
+        owner,
+        pool,
+        shareAmount: new BN(shares.toString()),
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+    }
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.unlockedLiquidity.isZero()).to.be.true;
+  });
+
+  it("Rewards can't be initialized in fungible share mode", async () => {
+    await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: true,
+    });
+
+    await expectThrowsAsync(async () => {
+      await initializeReward(context.banksClient, {
+        index: 0,
+        payer: creator,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint: tokenAMint,
+      });
+    }, getCpAmmProgramErrorCodeHexString("FungibleShareRewardNotSupported"));
+  });
+
+  it("Bribe ledger of shared position follows shared liquidity", async () => {
+    await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: false,
+    });
+    const { position } = await getFungibleShare(
+      context.banksClient,
+      deriveFungibleShareAddress(pool)
+    );
+    await initializeBribeLedger(context.banksClient, creator, pool);
+    const positionBribeLedger = await createPositionBribeLedger(
+      context.banksClient,
+      alice,
+      pool,
+      position
+    );
+
+    const liquidityDelta = new BN(1_000_000_000).shln(64);
+    await depositFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      liquidityDelta,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      minimumShareAmount: new BN(0),
+    });
+
+    let positionBribeLedgerState = await getPositionBribeLedger(
+      context.banksClient,
+      positionBribeLedger
+    );
+    expect(positionBribeLedgerState.liquidity.toString()).eq(
+      liquidityDelta.toString()
+    );
+
+    await withdrawFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      shareAmount: new BN(500_000_000),
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+
+    const positionState = await getPosition(context.banksClient, position);
+    positionBribeLedgerState = await getPositionBribeLedger(
+      context.banksClient,
+      positionBribeLedger
+    );
+    expect(positionBribeLedgerState.liquidity.toString()).eq(
+      positionState.unlockedLiquidity.toString()
+    );
+    const bribeLedgerState = await getBribeLedger(
+      context.banksClient,
+      deriveBribeLedgerAddress(pool)
+    );
+    expect(bribeLedgerState.enrolledLiquidity.toString()).eq(
+      positionState.unlockedLiquidity.toString()
+    );
+  });
+
+  it("Pending fee is settled pro rata to shares", async () => {
+    await initializeFungibleShare(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      autoCompound: false,
+    });
+    const { position } = await getFungibleShare(
+      context.banksClient,
+      deriveFungibleShareAddress(pool)
+    );
+
+    const liquidityDelta = new BN(1_000_000_000).shln(64);
+    await depositFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      liquidityDelta,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      minimumShareAmount: new BN(0),
+    });
+
+    for (const [inputTokenMint, outputTokenMint] of [
+      [tokenAMint, tokenBMint],
+      [tokenBMint, tokenAMint],
+    ]) {
+      await swapExactIn(context.banksClient, {
+        payer: creator,
+        pool,
+        inputTokenMint,
+        outputTokenMint,
+        amountIn: new BN(1_000_000_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+    }
+
+    // bob buys into the fee earned before depositing, getting the same shares as alice
+    await depositFungibleShare(context.banksClient, {
+      owner: bob,
+      pool,
+      liquidityDelta,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      minimumShareAmount: new BN(0),
+    });
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.feeAPending.gtn(0)).to.be.true;
+    expect(positionState.feeBPending.gtn(0)).to.be.true;
+    expect(positionState.feeAPending.modn(2)).eq(0);
+    expect(positionState.feeBPending.modn(2)).eq(0);
+
+    // alice receives half of the pending fee when withdrawing all shares
+    const aliceShares = (
+      await getTokenAccount(
+        context.banksClient,
+        getAssociatedTokenAddressSync(
+          deriveFungibleShareMintAddress(pool),
+          alice.publicKey
+        )
+      )
+    ).amount;
+    await withdrawFungibleShare(context.banksClient, {
+      owner: alice,
+      pool,
+      shareAmount: new BN(aliceShares.toString()),
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+    const positionStateAfter = await getPosition(
+      context.banksClient,
+      position
+    );
+    expect(positionStateAfter.feeAPending.toString()).eq(
+      positionState.feeAPending.divn(2).toString()
+    );
+    expect(positionStateAfter.feeBPending.toString()).eq(
+      positionState.feeBPending.divn(2).toString()
+    );
+  });
+});
@@ -0,0 +1,174 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  getPool,
+  initializePool,
+  InitializePoolParams,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  swapExactIn,
+  createToken,
+  mintSplTokenTo,
+} from "./bankrun-utils";
+import BN from "bn.js";
+import {
+  getInterestBearingMintConfigState,
+  unpackMint,
+} from "@solana/spl-token";
+import {
+  createInterestBearingExtensionWithInstruction,
+  createToken2022,
+  mintToToken2022,
+} from "./bankrun-utils/token2022";
+
+describe("Interest bearing token", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let config: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+  // 5% per year
+  const rate = 500;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    const tokenAMintKeypair = Keypair.generate();
+    tokenAMint = tokenAMintKeypair.publicKey;
+    await createToken2022(
+      context.banksClient,
+      context.payer,
+      [
+        createInterestBearingExtensionWithInstruction(
+          tokenAMint,
+          context.payer.publicKey,
+          rate
+        ),
+      ],
+      tokenAMintKeypair
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintToToken2022(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+  });
+
+  it("Initialize pool and swap without token badge", async () => {
+    const mintAccount = await context.banksClient.getAccount(tokenAMint);
+    const mintInfo = unpackMint(
+      tokenAMint,
+      { ...mintAccount, data: Buffer.from(mintAccount.data) },
+      mintAccount.owner
+    );
+    expect(getInterestBearingMintConfigState(mintInfo).currentRate).eq(rate);
+
+    const initPoolParams: InitializePoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    };
+    const { pool } = await initializePool(context.banksClient, initPoolParams);
+
+    const position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    const addLiquidityParams: AddLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    };
+    await addLiquidity(context.banksClient, addLiquidityParams);
+
+    const poolStateBefore = await getPool(context.banksClient, pool);
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    const poolStateAfter = await getPool(context.banksClient, pool);
+
+    // pool always works with raw amounts, interest only affects UI amounts
+    expect(poolStateAfter.sqrtPrice.lt(poolStateBefore.sqrtPrice)).to.be.true;
+  });
+});
@@ -217,6 +217,10 @@ describe("Lock position", () => {
         const positionState = await getPosition(context.banksClient, position);
         expect(positionState.vestedLiquidity.eq(liquidityToLock)).to.be.true;
 
+        // pool is passed writable in remaining accounts to track vesting locked liquidity
+        const poolState = await getPool(context.banksClient, pool);
+        expect(poolState.vestingLockedLiquidity.eq(liquidityToLock)).to.be.true;
+
         const vestingState = await getVesting(context.banksClient, vesting);
         expect(!vestingState.cliffPoint.isZero()).to.be.true;
         expect(vestingState.cliffUnlockLiquidity.eq(cliffUnlockLiquidity)).to.be
@@ -337,6 +341,9 @@ describe("Lock position", () => {
         const positionState = await getPosition(context.banksClient, position);
         expect(positionState.vestedLiquidity.isZero()).to.be.true;
         expect(positionState.unlockedLiquidity.eq(liquidityDelta)).to.be.true;
+
+        const poolState = await getPool(context.banksClient, pool);
+        expect(poolState.vestingLockedLiquidity.isZero()).to.be.true;
       });
 
       it("Permanent lock position", async () => {
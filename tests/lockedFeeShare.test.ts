@@ -0,0 +1,206 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addLiquidity,
+  claimPositionFee,
+  createPosition,
+  createToken,
+  deriveLockedFeeExtensionAddress,
+  getCpAmmProgramErrorCodeHexString,
+  getLockedFeeExtension,
+  getTokenAccount,
+  initializeCustomizablePool,
+  initializeLockedFeeExtension,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  permanentLockPosition,
+  swapExactIn,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Locked fee share", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let locker: Keypair;
+  let lp: Keypair;
+  let pool: PublicKey;
+  let lockedPosition: PublicKey;
+  let unlockedPosition: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    locker = await generateKpAndFund(context.banksClient, context.payer);
+    lp = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, locker, lp]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 1,
+    });
+    pool = result.pool;
+
+    lockedPosition = await createPosition(
+      context.banksClient,
+      locker,
+      locker.publicKey,
+      pool
+    );
+    unlockedPosition = await createPosition(
+      context.banksClient,
+      lp,
+      lp.publicKey,
+      pool
+    );
+    for (const [owner, position] of [
+      [locker, lockedPosition],
+      [lp, unlockedPosition],
+    ] as [Keypair, PublicKey][]) {
+      await addLiquidity(context.banksClient, {
+        owner,
+        pool,
+        position,
+        liquidityDelta: new BN(1_000_000_000).shln(64),
+        tokenAAmountThreshold: U64_MAX,
+        tokenBAmountThreshold: U64_MAX,
+      });
+    }
+  });
+
+  it("Only pool creator can initialize locked fee extension with valid share", async () => {
+    await expectThrowsAsync(async () => {
+      await initializeLockedFeeExtension(context.banksClient, {
+        payer: creator,
+        creator,
+        pool,
+        lockedFeeShareBps: 10_001,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidLockedFeeShare"));
+
+    // has_one constraint
+    await expectThrowsAsync(async () => {
+      await initializeLockedFeeExtension(context.banksClient, {
+        payer: lp,
+        creator: lp,
+        pool,
+        lockedFeeShareBps: 2_000,
+      });
+    }, "0x7d1");
+  });
+
+  it("Locked liquidity earns the fee share on top of its share as liquidity", async () => {
+    await initializeLockedFeeExtension(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      lockedFeeShareBps: 2_000,
+    });
+    await permanentLockPosition(
+      context.banksClient,
+      lockedPosition,
+      locker,
+      locker
+    );
+
+    await swapExactIn(context.banksClient, {
+      payer: creator,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(1_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    const lockedFeeExtensionState = await getLockedFeeExtension(
+      context.banksClient,
+      deriveLockedFeeExtensionAddress(pool)
+    );
+    expect(lockedFeeExtensionState.totalLockedBFee.toNumber()).gt(0);
+
+    const claimFee = async (owner: Keypair, position: PublicKey) => {
+      const tokenBAccount = getAssociatedTokenAddressSync(
+        tokenBMint,
+        owner.publicKey
+      );
+      const before = (await getTokenAccount(context.banksClient, tokenBAccount))
+        .amount;
+      await claimPositionFee(context.banksClient, { owner, pool, position });
+      const after = (await getTokenAccount(context.banksClient, tokenBAccount))
+        .amount;
+      return after - before;
+    };
+
+    const lockedFee = await claimFee(locker, lockedPosition);
+    const unlockedFee = await claimFee(lp, unlockedPosition);
+
+    expect(lockedFee > unlockedFee).to.be.true;
+    // locked position takes the whole locked fee share, up to rounding
+    const lockedFeeShare = BigInt(
+      lockedFeeExtensionState.totalLockedBFee.toString()
+    );
+    expect(lockedFee - unlockedFee <= lockedFeeShare).to.be.true;
+    expect(lockedFee - unlockedFee >= lockedFeeShare - BigInt(2)).to.be.true;
+  });
+});
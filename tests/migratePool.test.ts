@@ -0,0 +1,162 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createDynamicConfigIx,
+  createToken,
+  deriveMigratorAddress,
+  derivePositionNftAccount,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  getPosition,
+  getTokenAccount,
+  MAX_SQRT_PRICE,
+  migratePool,
+  MigratePoolParams,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Migrate pool through CPI", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let creator: Keypair;
+  let owner: Keypair;
+  let params: MigratePoolParams;
+  const liquidity = MIN_LP_AMOUNT.muln(1_000);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    owner = await generateKpAndFund(context.banksClient, context.payer);
+
+    const tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    const tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    // tokens of the source pool are held by the migrator PDA
+    const migrator = deriveMigratorAddress();
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      migrator
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      migrator
+    );
+
+    const config = await createDynamicConfigIx(
+      context.banksClient,
+      admin,
+      new BN(Math.floor(Math.random() * 1000)),
+      {
+        poolCreatorAuthority: migrator,
+      }
+    );
+
+    params = {
+      payer: creator,
+      creator: creator.publicKey,
+      owner: owner.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+      permanentLockLiquidityBps: 5_000,
+    };
+  });
+
+  it("Migrate liquidity with half of it permanently locked", async () => {
+    const { pool, position, positionNft } = await migratePool(
+      context.banksClient,
+      params
+    );
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.liquidity.toString()).eq(liquidity.toString());
+    expect(poolState.creator.toString()).eq(creator.publicKey.toString());
+    expect(poolState.permanentLockLiquidity.toString()).eq(
+      liquidity.divn(2).toString()
+    );
+
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.pool.toString()).eq(pool.toString());
+    expect(positionState.permanentLockedLiquidity.toString()).eq(
+      liquidity.divn(2).toString()
+    );
+    expect(positionState.unlockedLiquidity.toString()).eq(
+      liquidity.sub(liquidity.divn(2)).toString()
+    );
+
+    // position nft is minted to the owner, not to the migrator
+    const positionNftAccount = await getTokenAccount(
+      context.banksClient,
+      derivePositionNftAccount(positionNft)
+    );
+    expect(positionNftAccount.owner.toString()).eq(owner.publicKey.toString());
+    expect(positionNftAccount.amount.toString()).eq("1");
+  });
+
+  it("Migrate liquidity without lock", async () => {
+    const { position } = await migratePool(context.banksClient, {
+      ...params,
+      permanentLockLiquidityBps: 0,
+    });
+
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.permanentLockedLiquidity.toString()).eq("0");
+    expect(positionState.unlockedLiquidity.toString()).eq(
+      liquidity.toString()
+    );
+  });
+
+  it("Lock percentage above 100% is rejected", async () => {
+    await expectThrowsAsync(async () => {
+      await migratePool(context.banksClient, {
+        ...params,
+        permanentLockLiquidityBps: 10_001,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidParameters"));
+  });
+});
@@ -0,0 +1,249 @@
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addAllowlistEntry,
+  addLiquidity,
+  createPosition,
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  initializeCustomizablePool,
+  initializePoolPermission,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  removeAllowlistEntry,
+  removeLiquidity,
+  swapExactIn,
+  transferPoolManager,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Permissioned pool", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let manager: Keypair;
+  let user: Keypair;
+  let pool: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    manager = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    // pool can only become permissioned before activation
+    const activationSlot =
+      (await context.banksClient.getClock()).slot + BigInt(10);
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: new BN(activationSlot.toString()),
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+    pool = result.pool;
+
+    await initializePoolPermission(context.banksClient, {
+      payer: creator,
+      creator,
+      pool,
+      manager: manager.publicKey,
+    });
+    context.warpToSlot(activationSlot);
+  });
+
+  it("Only allowlisted wallet can swap and add liquidity", async () => {
+    const position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    const liquidityDelta = new BN(1_000_000_000).shln(64);
+    const addLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    };
+    const swapParams = {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    };
+
+    // allowlist entry doesn't exist
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "InvalidAllowlistEntry"
+    );
+    await expectThrowsAsync(async () => {
+      await addLiquidity(context.banksClient, addLiquidityParams);
+    }, errorCode);
+    await expectThrowsAsync(async () => {
+      await swapExactIn(context.banksClient, swapParams);
+    }, errorCode);
+
+    await addAllowlistEntry(context.banksClient, {
+      manager,
+      pool,
+      wallet: user.publicKey,
+    });
+    await addLiquidity(context.banksClient, addLiquidityParams);
+    await swapExactIn(context.banksClient, swapParams);
+
+    // removed wallet can still withdraw
+    await removeAllowlistEntry(context.banksClient, {
+      manager,
+      pool,
+      wallet: user.publicKey,
+    });
+    await expectThrowsAsync(async () => {
+      await swapExactIn(context.banksClient, swapParams);
+    }, errorCode);
+    await removeLiquidity(context.banksClient, {
+      ...addLiquidityParams,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+  });
+
+  it("Only manager can update allowlist", async () => {
+    const errorCode = getCpAmmProgramErrorCodeHexString("InvalidPoolManager");
+    await expectThrowsAsync(async () => {
+      await addAllowlistEntry(context.banksClient, {
+        manager: user,
+        pool,
+        wallet: user.publicKey,
+      });
+    }, errorCode);
+
+    await transferPoolManager(context.banksClient, {
+      manager,
+      pool,
+      newManager: user.publicKey,
+    });
+    await addAllowlistEntry(context.banksClient, {
+      manager: user,
+      pool,
+      wallet: user.publicKey,
+    });
+    await expectThrowsAsync(async () => {
+      await removeAllowlistEntry(context.banksClient, {
+        manager,
+        pool,
+        wallet: user.publicKey,
+      });
+    }, errorCode);
+  });
+
+  it("Activated pool can't become permissioned", async () => {
+    const tokenCMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenCMint,
+      context.payer,
+      creator.publicKey
+    );
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint: tokenCMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+
+    await expectThrowsAsync(async () => {
+      await initializePoolPermission(context.banksClient, {
+        payer: creator,
+        creator,
+        pool: result.pool,
+        manager: manager.publicKey,
+      });
+    }, getCpAmmProgramErrorCodeHexString("PoolAlreadyActivated"));
+  });
+});
@@ -0,0 +1,206 @@
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  getPoolActionMask,
+  initializePool,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  PoolAction,
+  removeLiquidity,
+  setPoolDisabledActions,
+  swapExactIn,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Pool disabled actions", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [admin, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+    const result = await initializePool(context.banksClient, {
+      payer: admin,
+      creator: admin.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(1).shln(64),
+      activationPoint: null,
+    });
+    pool = result.pool;
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+  });
+
+  it("Halt swap while liquidity can still be added and removed", async () => {
+    await setPoolDisabledActions(context.banksClient, {
+      admin,
+      pool,
+      disabledActions: getPoolActionMask([PoolAction.Swap]),
+      reason: "oracle incident",
+    });
+
+    const errorCode = getCpAmmProgramErrorCodeHexString("PoolDisabled");
+    await expectThrowsAsync(async () => {
+      await swapExactIn(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(10_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+    }, errorCode);
+
+    const liquidityDelta = new BN(1_000_000_000).shln(64);
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+    await removeLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+
+    // resume swap
+    await setPoolDisabledActions(context.banksClient, {
+      admin,
+      pool,
+      disabledActions: 0,
+      reason: "",
+    });
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+  });
+
+  it("Freeze new positions only", async () => {
+    await setPoolDisabledActions(context.banksClient, {
+      admin,
+      pool,
+      disabledActions: getPoolActionMask([PoolAction.CreatePosition]),
+      reason: "migration",
+    });
+
+    await expectThrowsAsync(async () => {
+      await createPosition(context.banksClient, user, user.publicKey, pool);
+    }, getCpAmmProgramErrorCodeHexString("PoolDisabled"));
+
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+  });
+
+  it("Unknown action flag is rejected", async () => {
+    await expectThrowsAsync(async () => {
+      await setPoolDisabledActions(context.banksClient, {
+        admin,
+        pool,
+        disabledActions: 1 << 6,
+        reason: "",
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidParameters"));
+  });
+});
@@ -0,0 +1,200 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  createPosition,
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  getMetadataField,
+  getPosition,
+  getPositionNftMetadata,
+  getVesting,
+  initializeCustomizablePool,
+  lockPosition,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  permanentLockPosition,
+  refreshPositionMetadata,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Position NFT metadata", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let user: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const liquidity = new BN(1_000_000_000).shln(64);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+    pool = result.pool;
+
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+  });
+
+  it("Pool pair and fee tier are set on creation", async () => {
+    const positionState = await getPosition(context.banksClient, position);
+    const metadata = await getPositionNftMetadata(
+      context.banksClient,
+      positionState.nftMint
+    );
+    const fields = metadata.additionalMetadata;
+    expect(getMetadataField(fields, "pool")).eq(pool.toString());
+    expect(getMetadataField(fields, "token_a_mint")).eq(tokenAMint.toString());
+    expect(getMetadataField(fields, "token_b_mint")).eq(tokenBMint.toString());
+    expect(getMetadataField(fields, "fee_bps")).eq("25");
+    // liquidity is only rendered on refresh
+    expect(getMetadataField(fields, "liquidity")).to.be.undefined;
+  });
+
+  it("Refresh liquidity and lock status", async () => {
+    const vesting = await lockPosition(
+      context.banksClient,
+      position,
+      user,
+      user,
+      {
+        cliffPoint: null,
+        periodFrequency: new BN(1),
+        cliffUnlockLiquidity: liquidity.divn(4),
+        liquidityPerPeriod: liquidity.divn(4),
+        numberOfPeriod: 1,
+      }
+    );
+
+    // vesting must be passed
+    await expectThrowsAsync(async () => {
+      await refreshPositionMetadata(context.banksClient, {
+        payer: creator,
+        position,
+        vestings: [],
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidVestingAccount"));
+
+    // permissionless
+    await refreshPositionMetadata(context.banksClient, {
+      payer: creator,
+      position,
+      vestings: [vesting],
+    });
+
+    const positionState = await getPosition(context.banksClient, position);
+    const vestingState = await getVesting(context.banksClient, vesting);
+    let metadata = await getPositionNftMetadata(
+      context.banksClient,
+      positionState.nftMint
+    );
+    expect(getMetadataField(metadata.additionalMetadata, "liquidity")).eq(
+      liquidity.toString()
+    );
+    expect(
+      getMetadataField(metadata.additionalMetadata, "vested_liquidity")
+    ).eq(liquidity.divn(2).toString());
+    expect(
+      getMetadataField(metadata.additionalMetadata, "vesting_end_point")
+    ).eq(vestingState.cliffPoint.addn(1).toString());
+
+    await permanentLockPosition(context.banksClient, position, user, user);
+    await refreshPositionMetadata(context.banksClient, {
+      payer: creator,
+      position,
+      vestings: [vesting],
+    });
+
+    metadata = await getPositionNftMetadata(
+      context.banksClient,
+      positionState.nftMint
+    );
+    expect(
+      getMetadataField(metadata.additionalMetadata, "unlocked_liquidity")
+    ).eq("0");
+    expect(
+      getMetadataField(
+        metadata.additionalMetadata,
+        "permanent_locked_liquidity"
+      )
+    ).eq(liquidity.divn(2).toString());
+  });
+});
@@ -0,0 +1,265 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addAdminRole,
+  addLiquidity,
+  AdminRole,
+  claimProtocolFee,
+  createClaimFeeOperator,
+  createConfigIx,
+  createPosition,
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  getProtocolFeeRouting,
+  getTokenAccount,
+  initializeAdminRegistry,
+  initializePool,
+  initializeProtocolFeeRouting,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  swapExactIn,
+  TREASURY,
+  updateProtocolFeeRouting,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Protocol fee routing", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let operator: Keypair;
+  let pool: PublicKey;
+  let inputTokenMint: PublicKey;
+  let outputTokenMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    operator = await generateKpAndFund(context.banksClient, context.payer);
+
+    inputTokenMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    outputTokenMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const mint of [inputTokenMint, outputTokenMint]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        mint,
+        context.payer,
+        user.publicKey
+      );
+    }
+
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(randomID()),
+      {
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            firstFactor: 0,
+            secondFactor: convertToByteArray(new BN(0)),
+            thirdFactor: new BN(0),
+            baseFeeMode: 0,
+          },
+          padding: [],
+          dynamicFee: null,
+        },
+        sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+        sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+        vaultConfigKey: PublicKey.default,
+        poolCreatorAuthority: PublicKey.default,
+        activationType: 0,
+        collectFeeMode: 0,
+      }
+    );
+
+    const result = await initializePool(context.banksClient, {
+      payer: user,
+      creator: user.publicKey,
+      config,
+      tokenAMint: inputTokenMint,
+      tokenBMint: outputTokenMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    });
+    pool = result.pool;
+
+    const position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: MIN_SQRT_PRICE,
+      tokenAAmountThreshold: new BN(2_000_000_000),
+      tokenBAmountThreshold: new BN(2_000_000_000),
+    });
+
+    await createClaimFeeOperator(context.banksClient, {
+      admin,
+      operator: operator.publicKey,
+    });
+  });
+
+  it("Destination shares must sum to 100%", async () => {
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "InvalidProtocolFeeRouting"
+    );
+    await expectThrowsAsync(async () => {
+      await initializeProtocolFeeRouting(context.banksClient, {
+        admin,
+        destinations: [
+          { owner: Keypair.generate().publicKey, bps: 6_000 },
+          { owner: Keypair.generate().publicKey, bps: 3_000 },
+        ],
+      });
+    }, errorCode);
+
+    const owner = Keypair.generate().publicKey;
+    await expectThrowsAsync(async () => {
+      await initializeProtocolFeeRouting(context.banksClient, {
+        admin,
+        destinations: [
+          { owner, bps: 5_000 },
+          { owner, bps: 5_000 },
+        ],
+      });
+    }, errorCode);
+
+    await initializeProtocolFeeRouting(context.banksClient, {
+      admin,
+      destinations: [{ owner, bps: 10_000 }],
+    });
+    const protocolFeeRouting = await getProtocolFeeRouting(
+      context.banksClient
+    );
+    expect(protocolFeeRouting.destinationCount).eq(1);
+    expect(protocolFeeRouting.destinations[0].owner.toString()).eq(
+      owner.toString()
+    );
+  });
+
+  it("Only protocol fee routing manager can update routing", async () => {
+    const superAdmin = await generateKpAndFund(
+      context.banksClient,
+      context.payer
+    );
+    await initializeProtocolFeeRouting(context.banksClient, {
+      admin,
+      destinations: [{ owner: TREASURY, bps: 10_000 }],
+    });
+    await initializeAdminRegistry(context.banksClient, {
+      admin,
+      superAdmin: superAdmin.publicKey,
+    });
+
+    const destinations = [
+      { owner: TREASURY, bps: 5_000 },
+      { owner: Keypair.generate().publicKey, bps: 5_000 },
+    ];
+    await expectThrowsAsync(async () => {
+      await updateProtocolFeeRouting(context.banksClient, {
+        admin: operator,
+        destinations,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidAdmin"));
+
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ProtocolFeeRoutingManager,
+    });
+    await updateProtocolFeeRouting(context.banksClient, {
+      admin: operator,
+      destinations,
+    });
+    const protocolFeeRouting = await getProtocolFeeRouting(
+      context.banksClient
+    );
+    expect(protocolFeeRouting.destinationCount).eq(2);
+  });
+
+  it("Claimed protocol fee is split to destinations", async () => {
+    const buyback = Keypair.generate().publicKey;
+    const staking = Keypair.generate().publicKey;
+    await initializeProtocolFeeRouting(context.banksClient, {
+      admin,
+      destinations: [
+        { owner: buyback, bps: 6_000 },
+        { owner: staking, bps: 3_000 },
+        { owner: TREASURY, bps: 1_000 },
+      ],
+    });
+
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint,
+      outputTokenMint,
+      amountIn: new BN(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    const protocolFee = (await getPool(context.banksClient, pool))
+      .protocolAFee;
+    expect(protocolFee.gtn(0)).to.be.true;
+
+    await claimProtocolFee(context.banksClient, {
+      operator,
+      pool,
+      treasury: TREASURY,
+    });
+
+    const getAmount = async (owner: PublicKey) =>
+      new BN(
+        (
+          await getTokenAccount(
+            context.banksClient,
+            getAssociatedTokenAddressSync(inputTokenMint, owner, true)
+          )
+        ).amount.toString()
+      );
+    const buybackAmount = await getAmount(buyback);
+    const stakingAmount = await getAmount(staking);
+    const treasuryAmount = await getAmount(TREASURY);
+
+    expect(buybackAmount.toString()).eq(
+      protocolFee.muln(6_000).divn(10_000).toString()
+    );
+    expect(stakingAmount.toString()).eq(
+      protocolFee.muln(3_000).divn(10_000).toString()
+    );
+    // treasury takes the rounding remainder as the last destination
+    expect(
+      buybackAmount.add(stakingAmount).add(treasuryAmount).toString()
+    ).eq(protocolFee.toString());
+  });
+});
@@ -0,0 +1,273 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { describe } from "mocha";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { expect } from "chai";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  claimReward,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createToken,
+  fundReward,
+  getPosition,
+  permanentLockPosition,
+  initializePool,
+  InitializePoolParams,
+  initializeReward,
+  InitializeRewardParams,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  updateRewardBoost,
+  convertToByteArray,
+  expectThrowsAsync,
+  getCpAmmProgramErrorCodeHexString,
+} from "./bankrun-utils";
+import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+
+describe("Reward boost", () => {
+  describe("Reward boost with SPL-Token", () => {
+    let context: ProgramTestContext;
+    let creator: Keypair;
+    let admin: Keypair;
+    let config: PublicKey;
+    let funder: Keypair;
+    let user: Keypair;
+    let tokenAMint: PublicKey;
+    let tokenBMint: PublicKey;
+    let rewardMint: PublicKey;
+    let liquidity: BN;
+    let sqrtPrice: BN;
+    const configId = Math.floor(Math.random() * 1000);
+
+    beforeEach(async () => {
+      const root = Keypair.generate();
+      context = await startTest(root);
+
+      user = await generateKpAndFund(context.banksClient, context.payer);
+      funder = await generateKpAndFund(context.banksClient, context.payer);
+      creator = await generateKpAndFund(context.banksClient, context.payer);
+      admin = await generateKpAndFund(context.banksClient, context.payer);
+
+      tokenAMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+      tokenBMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      rewardMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        funder.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        admin.publicKey
+      );
+      // create config
+      const createConfigParams: CreateConfigParams = {
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            firstFactor: 0,
+            secondFactor: convertToByteArray(new BN(0)),
+            thirdFactor: new BN(0),
+            baseFeeMode: 0,
+          },
+          padding: [],
+          dynamicFee: null,
+        },
+        sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+        sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+        vaultConfigKey: PublicKey.default,
+        poolCreatorAuthority: PublicKey.default,
+        activationType: 0,
+        collectFeeMode: 0,
+      };
+
+      config = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(configId),
+        createConfigParams
+      );
+    });
+
+    it("Permanent locked position earns boosted reward", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      };
+
+      const { pool } = await initializePool(
+        context.banksClient,
+        initPoolParams
+      );
+
+      // 2 positions with same liquidity, one of them is permanent locked
+      const unlockedPosition = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const lockedPosition = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      for (const position of [unlockedPosition, lockedPosition]) {
+        const addLiquidityParams: AddLiquidityParams = {
+          owner: user,
+          pool,
+          position,
+          liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+          tokenAAmountThreshold: new BN(200),
+          tokenBAmountThreshold: new BN(200),
+        };
+        await addLiquidity(context.banksClient, addLiquidityParams);
+      }
+      await permanentLockPosition(
+        context.banksClient,
+        lockedPosition,
+        user,
+        user
+      );
+
+      const initRewardParams: InitializeRewardParams = {
+        index: 0,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      };
+      await initializeReward(context.banksClient, initRewardParams);
+
+      // permanent locked liquidity counts 2x
+      await updateRewardBoost(context.banksClient, {
+        index: 0,
+        admin,
+        pool,
+        permanentLockBoostBps: 10_000,
+        vestingLockBoostBps: 0,
+      });
+
+      await fundReward(context.banksClient, {
+        index: 0,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      const clock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          clock.slot,
+          clock.epochStartTimestamp,
+          clock.epoch,
+          clock.leaderScheduleEpoch,
+          clock.unixTimestamp + BigInt(3600)
+        )
+      );
+
+      for (const position of [unlockedPosition, lockedPosition]) {
+        await claimReward(context.banksClient, {
+          index: 0,
+          user,
+          pool,
+          position,
+          skipReward: 0,
+        });
+      }
+
+      const unlockedPositionState = await getPosition(
+        context.banksClient,
+        unlockedPosition
+      );
+      const lockedPositionState = await getPosition(
+        context.banksClient,
+        lockedPosition
+      );
+      expect(
+        lockedPositionState.rewardInfos[0].totalClaimedRewards.toNumber()
+      ).gt(unlockedPositionState.rewardInfos[0].totalClaimedRewards.toNumber());
+
+      // boost can't be updated once reward is distributed
+      const errorCode = getCpAmmProgramErrorCodeHexString(
+        "RewardAlreadyDistributed"
+      );
+      await expectThrowsAsync(async () => {
+        await updateRewardBoost(context.banksClient, {
+          index: 0,
+          admin,
+          pool,
+          permanentLockBoostBps: 0,
+          vestingLockBoostBps: 0,
+        });
+      }, errorCode);
+    });
+  });
+});
@@ -0,0 +1,343 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { describe } from "mocha";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { expect } from "chai";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  claimReward,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createPositionRewardExtension,
+  createToken,
+  fundReward,
+  getPool,
+  getPositionRewardExtension,
+  getRewardExtension,
+  getRewardInfo,
+  deriveRewardExtensionAddress,
+  initializePool,
+  InitializePoolParams,
+  initializeReward,
+  initializeRewardExtension,
+  InitializeRewardParams,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  NUM_EXTENSION_REWARDS,
+  NUM_REWARDS,
+  removeLiquidity,
+  updateRewardDuration,
+  updateRewardFunder,
+  withdrawIneligibleReward,
+  convertToByteArray,
+  expectThrowsAsync,
+  getCpAmmProgramErrorCodeHexString,
+} from "./bankrun-utils";
+import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+
+describe("Reward extension", () => {
+  describe("Reward extension with SPL-Token", () => {
+    let context: ProgramTestContext;
+    let creator: Keypair;
+    let admin: Keypair;
+    let config: PublicKey;
+    let funder: Keypair;
+    let user: Keypair;
+    let tokenAMint: PublicKey;
+    let tokenBMint: PublicKey;
+    let rewardMint: PublicKey;
+    let liquidity: BN;
+    let sqrtPrice: BN;
+    const configId = Math.floor(Math.random() * 1000);
+
+    beforeEach(async () => {
+      const root = Keypair.generate();
+      context = await startTest(root);
+
+      user = await generateKpAndFund(context.banksClient, context.payer);
+      funder = await generateKpAndFund(context.banksClient, context.payer);
+      creator = await generateKpAndFund(context.banksClient, context.payer);
+      admin = await generateKpAndFund(context.banksClient, context.payer);
+
+      tokenAMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+      tokenBMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      rewardMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        funder.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        admin.publicKey
+      );
+      // create config
+      const createConfigParams: CreateConfigParams = {
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            firstFactor: 0,
+            secondFactor: convertToByteArray(new BN(0)),
+            thirdFactor: new BN(0),
+            baseFeeMode: 0,
+          },
+          padding: [],
+          dynamicFee: null,
+        },
+        sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+        sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+        vaultConfigKey: PublicKey.default,
+        poolCreatorAuthority: PublicKey.default,
+        activationType: 0,
+        collectFeeMode: 0,
+      };
+
+      config = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(configId),
+        createConfigParams
+      );
+    });
+
+    it("Full flow for extension reward", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      };
+
+      const { pool } = await initializePool(
+        context.banksClient,
+        initPoolParams
+      );
+
+      // user create postion and reward extensions
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      await initializeRewardExtension(context.banksClient, user, pool);
+      const positionRewardExtension = await createPositionRewardExtension(
+        context.banksClient,
+        user,
+        pool,
+        position
+      );
+
+      // init reward at all extension indexes
+      for (let i = 0; i < NUM_EXTENSION_REWARDS; i++) {
+        const initRewardParams: InitializeRewardParams = {
+          index: NUM_REWARDS + i,
+          payer: admin,
+          rewardDuration: new BN(24 * 60 * 60),
+          pool,
+          rewardMint,
+        };
+        await initializeReward(context.banksClient, initRewardParams);
+      }
+      const poolState = await getPool(context.banksClient, pool);
+      expect(poolState.rewardExtensionFlag).eq(1);
+
+      const index = NUM_REWARDS + 1;
+
+      // update duration
+      await updateRewardDuration(context.banksClient, {
+        index,
+        admin: admin,
+        pool,
+        newDuration: new BN(2 * 24 * 60 * 60),
+      });
+
+      // update new funder
+      await updateRewardFunder(context.banksClient, {
+        index,
+        admin: admin,
+        pool,
+        newFunder: funder.publicKey,
+      });
+
+      // fund reward
+      await fundReward(context.banksClient, {
+        index,
+        funder: funder,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+      });
+
+      // liquidity changes require reward extensions in remaining accounts
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      // only liquidity of positions with reward extension earns extension rewards
+      const rewardExtension = deriveRewardExtensionAddress(pool);
+      expect(
+        (
+          await getRewardExtension(context.banksClient, rewardExtension)
+        ).enrolledLiquidity.toString()
+      ).eq(addLiquidityParams.liquidityDelta.toString());
+
+      // pass some time
+      const currentClock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          currentClock.slot,
+          currentClock.epochStartTimestamp,
+          currentClock.epoch,
+          currentClock.leaderScheduleEpoch,
+          currentClock.unixTimestamp + BigInt(3600)
+        )
+      );
+
+      await removeLiquidity(context.banksClient, {
+        ...addLiquidityParams,
+        tokenAAmountThreshold: new BN(0),
+        tokenBAmountThreshold: new BN(0),
+      });
+      expect(
+        (
+          await getRewardExtension(context.banksClient, rewardExtension)
+        ).enrolledLiquidity.toNumber()
+      ).eq(0);
+
+      // claim reward
+      await claimReward(context.banksClient, {
+        index,
+        user,
+        pool,
+        position,
+        skipReward: 0,
+      });
+      const positionRewardExtensionState = await getPositionRewardExtension(
+        context.banksClient,
+        positionRewardExtension
+      );
+      expect(
+        positionRewardExtensionState.rewardInfos[
+          index - NUM_REWARDS
+        ].totalClaimedRewards.toNumber()
+      ).gt(0);
+
+      // claim ineligible reward
+      const rewardInfo = await getRewardInfo(context.banksClient, pool, index);
+      // set new timestamp to pass reward duration end
+      const timestamp = rewardInfo.rewardDurationEnd.addn(5000);
+      const clock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          clock.slot,
+          clock.epochStartTimestamp,
+          clock.epoch,
+          clock.leaderScheduleEpoch,
+          BigInt(timestamp.toString())
+        )
+      );
+      await withdrawIneligibleReward(context.banksClient, {
+        index,
+        funder,
+        pool,
+      });
+    });
+
+    it("Extension reward index is out of range", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+      await initializeRewardExtension(context.banksClient, user, pool);
+
+      const errorCode = getCpAmmProgramErrorCodeHexString("InvalidRewardIndex");
+      await expectThrowsAsync(async () => {
+        await initializeReward(context.banksClient, {
+          index: NUM_REWARDS + NUM_EXTENSION_REWARDS,
+          payer: admin,
+          rewardDuration: new BN(24 * 60 * 60),
+          pool,
+          rewardMint,
+        });
+      }, errorCode);
+    });
+  });
+});
@@ -0,0 +1,297 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { describe } from "mocha";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { expect } from "chai";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  claimReward,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createToken,
+  fundReward2,
+  getPool,
+  getPosition,
+  initializePool,
+  InitializePoolParams,
+  initializeReward,
+  InitializeRewardParams,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  convertToByteArray,
+  expectThrowsAsync,
+  getCpAmmProgramErrorCodeHexString,
+} from "./bankrun-utils";
+import { generateKpAndFund, startTest } from "./bankrun-utils/common";
+
+describe("Scheduled reward", () => {
+  describe("Scheduled reward with SPL-Token", () => {
+    let context: ProgramTestContext;
+    let creator: Keypair;
+    let admin: Keypair;
+    let config: PublicKey;
+    let funder: Keypair;
+    let user: Keypair;
+    let tokenAMint: PublicKey;
+    let tokenBMint: PublicKey;
+    let rewardMint: PublicKey;
+    let liquidity: BN;
+    let sqrtPrice: BN;
+    const configId = Math.floor(Math.random() * 1000);
+
+    beforeEach(async () => {
+      const root = Keypair.generate();
+      context = await startTest(root);
+
+      user = await generateKpAndFund(context.banksClient, context.payer);
+      funder = await generateKpAndFund(context.banksClient, context.payer);
+      creator = await generateKpAndFund(context.banksClient, context.payer);
+      admin = await generateKpAndFund(context.banksClient, context.payer);
+
+      tokenAMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+      tokenBMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      rewardMint = await createToken(
+        context.banksClient,
+        context.payer,
+        context.payer.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        user.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        creator.publicKey
+      );
+
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        funder.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        rewardMint,
+        context.payer,
+        admin.publicKey
+      );
+      // create config
+      const createConfigParams: CreateConfigParams = {
+        poolFees: {
+          baseFee: {
+            cliffFeeNumerator: new BN(2_500_000),
+            firstFactor: 0,
+            secondFactor: convertToByteArray(new BN(0)),
+            thirdFactor: new BN(0),
+            baseFeeMode: 0,
+          },
+          padding: [],
+          dynamicFee: null,
+        },
+        sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+        sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+        vaultConfigKey: PublicKey.default,
+        poolCreatorAuthority: PublicKey.default,
+        activationType: 0,
+        collectFeeMode: 0,
+      };
+
+      config = await createConfigIx(
+        context.banksClient,
+        admin,
+        new BN(configId),
+        createConfigParams
+      );
+    });
+
+    async function warpTo(timestamp: BN) {
+      const clock = await context.banksClient.getClock();
+      context.setClock(
+        new Clock(
+          clock.slot,
+          clock.epochStartTimestamp,
+          clock.epoch,
+          clock.leaderScheduleEpoch,
+          BigInt(timestamp.toString())
+        )
+      );
+    }
+
+    it("Reward is only distributed inside the scheduled window", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const initPoolParams: InitializePoolParams = {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      };
+
+      const { pool } = await initializePool(
+        context.banksClient,
+        initPoolParams
+      );
+
+      const position = await createPosition(
+        context.banksClient,
+        user,
+        user.publicKey,
+        pool
+      );
+      const addLiquidityParams: AddLiquidityParams = {
+        owner: user,
+        pool,
+        position,
+        liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+        tokenAAmountThreshold: new BN(200),
+        tokenBAmountThreshold: new BN(200),
+      };
+      await addLiquidity(context.banksClient, addLiquidityParams);
+
+      const initRewardParams: InitializeRewardParams = {
+        index: 0,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      };
+      await initializeReward(context.banksClient, initRewardParams);
+
+      const clock = await context.banksClient.getClock();
+      const currentTimestamp = new BN(clock.unixTimestamp.toString());
+      const rewardDurationStart = currentTimestamp.addn(24 * 60 * 60);
+      const rewardDurationEnd = rewardDurationStart.addn(2 * 24 * 60 * 60);
+
+      await fundReward2(context.banksClient, {
+        index: 0,
+        funder: admin,
+        pool,
+        carryForward: true,
+        amount: new BN("1000000"),
+        rewardDurationStart,
+        rewardDurationEnd,
+      });
+
+      let poolState = await getPool(context.banksClient, pool);
+      expect(poolState.rewardInfos[0].rewardDurationStart.toString()).eq(
+        rewardDurationStart.toString()
+      );
+      expect(poolState.rewardInfos[0].rewardDurationEnd.toString()).eq(
+        rewardDurationEnd.toString()
+      );
+
+      // nothing is distributed before start
+      await warpTo(rewardDurationStart);
+      await claimReward(context.banksClient, {
+        index: 0,
+        user,
+        pool,
+        position,
+        skipReward: 0,
+      });
+      let positionState = await getPosition(context.banksClient, position);
+      expect(positionState.rewardInfos[0].totalClaimedRewards.toNumber()).eq(0);
+
+      // distributed inside the window
+      await warpTo(rewardDurationStart.addn(3600));
+      await claimReward(context.banksClient, {
+        index: 0,
+        user,
+        pool,
+        position,
+        skipReward: 0,
+      });
+      positionState = await getPosition(context.banksClient, position);
+      expect(positionState.rewardInfos[0].totalClaimedRewards.toNumber()).gt(
+        0
+      );
+    });
+
+    it("Cannot schedule reward in the past", async () => {
+      liquidity = new BN(MIN_LP_AMOUNT);
+      sqrtPrice = new BN(MIN_SQRT_PRICE);
+
+      const { pool } = await initializePool(context.banksClient, {
+        payer: creator,
+        creator: creator.publicKey,
+        config,
+        tokenAMint,
+        tokenBMint,
+        liquidity,
+        sqrtPrice,
+        activationPoint: null,
+      });
+
+      await initializeReward(context.banksClient, {
+        index: 0,
+        payer: admin,
+        rewardDuration: new BN(24 * 60 * 60),
+        pool,
+        rewardMint,
+      });
+
+      const clock = await context.banksClient.getClock();
+      const currentTimestamp = new BN(clock.unixTimestamp.toString());
+
+      const errorCode = getCpAmmProgramErrorCodeHexString(
+        "InvalidRewardSchedule"
+      );
+      await expectThrowsAsync(async () => {
+        await fundReward2(context.banksClient, {
+          index: 0,
+          funder: admin,
+          pool,
+          carryForward: true,
+          amount: new BN("1000000"),
+          rewardDurationStart: currentTimestamp.subn(1),
+          rewardDurationEnd: currentTimestamp.addn(24 * 60 * 60),
+        });
+      }, errorCode);
+    });
+  });
+});
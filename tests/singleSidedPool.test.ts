@@ -0,0 +1,153 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createToken,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  getTokenAccount,
+  initializeSingleSidedPool,
+  InitializeSingleSidedPoolParams,
+  MAX_SQRT_PRICE,
+  mintSplTokenTo,
+  swapExactIn,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Initialize single-sided pool", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let user: Keypair;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let params: InitializeSingleSidedPoolParams;
+  const tokenAAmount = new BN(1_000_000_000_000);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    params = {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      tokenAAmount,
+      sqrtMinPrice: new BN(1).shln(64),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+  });
+
+  it("Pool starts at the lower bound with only token a", async () => {
+    const { pool } = await initializeSingleSidedPool(
+      context.banksClient,
+      params
+    );
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.liquidity.gt(new BN(0))).to.be.true;
+
+    const tokenAVault = await getTokenAccount(
+      context.banksClient,
+      poolState.tokenAVault
+    );
+    const tokenBVault = await getTokenAccount(
+      context.banksClient,
+      poolState.tokenBVault
+    );
+    expect(new BN(tokenAVault.amount.toString()).lte(tokenAAmount)).to.be.true;
+    expect(
+      new BN(tokenAVault.amount.toString()).gte(tokenAAmount.subn(1))
+    ).to.be.true;
+    // only the lamport proving ownership of token b
+    expect(tokenBVault.amount.toString()).eq("1");
+
+    // nothing to sell token a for
+    await expectThrowsAsync(async () => {
+      await swapExactIn(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenAMint,
+        outputTokenMint: tokenBMint,
+        amountIn: new BN(1_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+    }, getCpAmmProgramErrorCodeHexString("PriceRangeViolation"));
+
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenBMint,
+      outputTokenMint: tokenAMint,
+      amountIn: new BN(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+  });
+
+  it("Invalid token a amount or price range is rejected", async () => {
+    await expectThrowsAsync(async () => {
+      await initializeSingleSidedPool(context.banksClient, {
+        ...params,
+        tokenAAmount: new BN(0),
+      });
+    }, getCpAmmProgramErrorCodeHexString("AmountIsZero"));
+
+    await expectThrowsAsync(async () => {
+      await initializeSingleSidedPool(context.banksClient, {
+        ...params,
+        sqrtMaxPrice: params.sqrtMinPrice,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidPriceRange"));
+  });
+});
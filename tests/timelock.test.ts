@@ -0,0 +1,310 @@
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addAdminRole,
+  AdminRole,
+  cancelProposal,
+  closeConfigIx,
+  createConfigIx,
+  CreateConfigParams,
+  createProposal,
+  createToken,
+  encodeConfigActionData,
+  encodeDisabledActionsActionData,
+  executeProposal,
+  getConfig,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  initializeAdminRegistry,
+  initializePool,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  ProposalAction,
+  setPoolDisabledActions,
+  setPoolStatus,
+  updateConfigIx,
+  updateTimelock,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Timelocked admin actions", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let superAdmin: Keypair;
+  let operator: Keypair;
+  let canceller: Keypair;
+  let config: PublicKey;
+  let createConfigParams: CreateConfigParams;
+  let pool: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+  // seconds
+  const delay = 20;
+
+  async function warpTimestamp(seconds: number) {
+    const currentClock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        currentClock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    superAdmin = await generateKpAndFund(context.banksClient, context.payer);
+    operator = await generateKpAndFund(context.banksClient, context.payer);
+    canceller = await generateKpAndFund(context.banksClient, context.payer);
+
+    const tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    const tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      admin.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      admin.publicKey
+    );
+
+    createConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+    const result = await initializePool(context.banksClient, {
+      payer: admin,
+      creator: admin.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    });
+    pool = result.pool;
+
+    await initializeAdminRegistry(context.banksClient, {
+      admin,
+      superAdmin: superAdmin.publicKey,
+    });
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.PoolStatusOperator,
+    });
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: canceller.publicKey,
+      role: AdminRole.ProposalCanceller,
+    });
+    // timestamp based delay
+    await updateTimelock(context.banksClient, {
+      superAdmin,
+      activationType: 1,
+      delay: new BN(delay),
+    });
+  });
+
+  it("Pool is disabled immediately and enabled after the delay", async () => {
+    // halting is not delayed
+    await setPoolStatus(context.banksClient, {
+      admin: operator,
+      pool,
+      status: 1,
+    });
+    let poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolStatus).eq(1);
+
+    await expectThrowsAsync(async () => {
+      await setPoolStatus(context.banksClient, {
+        admin: operator,
+        pool,
+        status: 0,
+      });
+    }, getCpAmmProgramErrorCodeHexString("TimelockRequired"));
+
+    const proposal = await createProposal(context.banksClient, {
+      proposer: operator,
+      target: pool,
+      action: ProposalAction.SetPoolStatus,
+      actionData: Buffer.from([0]),
+    });
+
+    await expectThrowsAsync(async () => {
+      await executeProposal(context.banksClient, {
+        payer: admin,
+        proposal,
+      });
+    }, getCpAmmProgramErrorCodeHexString("ProposalNotExecutable"));
+
+    await warpTimestamp(delay);
+
+    // anyone can execute
+    await executeProposal(context.banksClient, {
+      payer: admin,
+      proposal,
+    });
+
+    poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolStatus).eq(0);
+
+    const proposalAccount = await context.banksClient.getAccount(proposal);
+    expect(proposalAccount).to.be.null;
+  });
+
+  it("Pool actions are disabled immediately and re-enabled after the delay", async () => {
+    // halting is not delayed
+    await setPoolDisabledActions(context.banksClient, {
+      admin: operator,
+      pool,
+      disabledActions: 1,
+      reason: "incident",
+    });
+    let poolState = await getPool(context.banksClient, pool);
+    expect(poolState.disabledActions).eq(1);
+
+    await expectThrowsAsync(async () => {
+      await setPoolDisabledActions(context.banksClient, {
+        admin: operator,
+        pool,
+        disabledActions: 0,
+        reason: "resolved",
+      });
+    }, getCpAmmProgramErrorCodeHexString("TimelockRequired"));
+
+    const proposal = await createProposal(context.banksClient, {
+      proposer: operator,
+      target: pool,
+      action: ProposalAction.SetPoolDisabledActions,
+      actionData: encodeDisabledActionsActionData(0, "resolved"),
+    });
+
+    await warpTimestamp(delay);
+
+    await executeProposal(context.banksClient, {
+      payer: admin,
+      proposal,
+    });
+
+    poolState = await getPool(context.banksClient, pool);
+    expect(poolState.disabledActions).eq(0);
+  });
+
+  it("Config is updated after the delay", async () => {
+    const newConfigParams: CreateConfigParams = {
+      ...createConfigParams,
+      collectFeeMode: 1,
+    };
+    await expectThrowsAsync(async () => {
+      await updateConfigIx(
+        context.banksClient,
+        operator,
+        config,
+        newConfigParams
+      );
+    }, getCpAmmProgramErrorCodeHexString("TimelockRequired"));
+
+    const proposal = await createProposal(context.banksClient, {
+      proposer: operator,
+      target: config,
+      action: ProposalAction.UpdateConfig,
+      actionData: encodeConfigActionData(newConfigParams),
+    });
+
+    await warpTimestamp(delay);
+
+    await executeProposal(context.banksClient, {
+      payer: admin,
+      proposal,
+    });
+
+    const configState = await getConfig(context.banksClient, config);
+    expect(configState.collectFeeMode).eq(1);
+    expect(configState.version.toNumber()).eq(1);
+  });
+
+  it("Canceller vetoes a proposal", async () => {
+    await expectThrowsAsync(async () => {
+      await closeConfigIx(context.banksClient, operator, config);
+    }, getCpAmmProgramErrorCodeHexString("TimelockRequired"));
+
+    const proposal = await createProposal(context.banksClient, {
+      proposer: operator,
+      target: config,
+      action: ProposalAction.CloseConfig,
+      actionData: Buffer.from([]),
+    });
+
+    // proposer doesn't hold canceller role
+    await expectThrowsAsync(async () => {
+      await cancelProposal(context.banksClient, {
+        canceller: operator,
+        proposal,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidAdmin"));
+
+    await cancelProposal(context.banksClient, {
+      canceller,
+      proposal,
+    });
+
+    const proposalAccount = await context.banksClient.getAccount(proposal);
+    expect(proposalAccount).to.be.null;
+    const configAccount = await context.banksClient.getAccount(config);
+    expect(configAccount).to.be.not.null;
+  });
+});
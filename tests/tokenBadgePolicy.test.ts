@@ -0,0 +1,235 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createTokenBadge,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  initializePool,
+  InitializePoolParams,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  refreshPoolTokenBadge,
+  removeLiquidity,
+  swapExactIn,
+  createToken,
+  mintSplTokenTo,
+  updateTokenBadge,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+import {
+  createPermenantDelegateExtensionWithInstruction,
+  createToken2022,
+  createTransferFeeExtensionWithInstruction,
+  mintToToken2022,
+} from "./bankrun-utils/token2022";
+
+describe("Token badge policy", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let config: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let initPoolParams: InitializePoolParams;
+  const configId = Math.floor(Math.random() * 1000);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    // permanent delegate requires token badge, transfer fee is 100 bps
+    const tokenAMintKeypair = Keypair.generate();
+    tokenAMint = tokenAMintKeypair.publicKey;
+    await createToken2022(
+      context.banksClient,
+      context.payer,
+      [
+        createPermenantDelegateExtensionWithInstruction(
+          tokenAMint,
+          admin.publicKey
+        ),
+        createTransferFeeExtensionWithInstruction(tokenAMint),
+      ],
+      tokenAMintKeypair
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintToToken2022(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    await createTokenBadge(context.banksClient, {
+      tokenMint: tokenAMint,
+      admin,
+    });
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+
+    initPoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(1).shln(64),
+      activationPoint: null,
+    };
+  });
+
+  it("Initialize pool fails when mint violates token badge policy", async () => {
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "TokenBadgePolicyViolation"
+    );
+    // zero limit requires the mint to have no transfer fee
+    for (const maxTransferFeeBps of [0, 50]) {
+      await updateTokenBadge(context.banksClient, {
+        tokenMint: tokenAMint,
+        admin,
+        allowedExtensions: new BN(0),
+        expiryTimestamp: new BN(0),
+        transferHookProgramId: PublicKey.default,
+        maxTransferFeeBps,
+      });
+
+      await expectThrowsAsync(async () => {
+        await initializePool(context.banksClient, initPoolParams);
+      }, errorCode);
+    }
+
+    await updateTokenBadge(context.banksClient, {
+      tokenMint: tokenAMint,
+      admin,
+      allowedExtensions: new BN(0),
+      expiryTimestamp: new BN(0),
+      transferHookProgramId: PublicKey.default,
+      maxTransferFeeBps: 100,
+    });
+    await initializePool(context.banksClient, initPoolParams);
+  });
+
+  it("Pool with expired token badge can only be withdrawn", async () => {
+    const { pool } = await initializePool(context.banksClient, initPoolParams);
+    const position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    const liquidity = new BN(1_000_000_000).shln(64);
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+
+    // revoke token badge
+    const clock = await context.banksClient.getClock();
+    const expiryTimestamp = new BN(clock.unixTimestamp.toString());
+    await updateTokenBadge(context.banksClient, {
+      tokenMint: tokenAMint,
+      admin,
+      allowedExtensions: new BN(0),
+      expiryTimestamp,
+      transferHookProgramId: PublicKey.default,
+      maxTransferFeeBps: null,
+    });
+    await refreshPoolTokenBadge(context.banksClient, {
+      payer: user,
+      pool,
+      tokenMint: tokenAMint,
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.tokenABadgeExpiryTimestamp.toString()).eq(
+      expiryTimestamp.toString()
+    );
+    expect(poolState.tokenBBadgeExpiryTimestamp.toNumber()).eq(0);
+
+    const errorCode = getCpAmmProgramErrorCodeHexString("PoolDisabled");
+    await expectThrowsAsync(async () => {
+      await swapExactIn(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenBMint,
+        outputTokenMint: tokenAMint,
+        amountIn: new BN(10_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+    }, errorCode);
+
+    await removeLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+  });
+});
@@ -0,0 +1,216 @@
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  getCpAmmProgramErrorCodeHexString,
+  initializePool,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  removeLiquidity,
+  swap2ExactIn,
+  createToken,
+  mintSplTokenTo,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+import {
+  createToken2022,
+  createTransferFeeExtensionWithInstruction,
+  mintToToken2022,
+} from "./bankrun-utils/token2022";
+
+describe("Transfer fee bound", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+  // transfer fee of token a
+  const transferFeeBps = 100;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    const tokenAMintKeypair = Keypair.generate();
+    tokenAMint = tokenAMintKeypair.publicKey;
+    await createToken2022(
+      context.banksClient,
+      context.payer,
+      [
+        createTransferFeeExtensionWithInstruction(
+          tokenAMint,
+          undefined,
+          transferFeeBps
+        ),
+      ],
+      tokenAMintKeypair
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintToToken2022(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+
+    const result = await initializePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(1).shln(64),
+      activationPoint: null,
+    });
+    pool = result.pool;
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+  });
+
+  it("Add and remove liquidity respect max transfer fee bps", async () => {
+    const addLiquidityParams: AddLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+      maxTransferFeeBps: transferFeeBps - 1,
+    };
+
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "ExceededMaxTransferFeeBps"
+    );
+    await expectThrowsAsync(async () => {
+      await addLiquidity(context.banksClient, addLiquidityParams);
+    }, errorCode);
+
+    await addLiquidity(context.banksClient, {
+      ...addLiquidityParams,
+      maxTransferFeeBps: transferFeeBps,
+    });
+
+    const removeLiquidityParams = {
+      ...addLiquidityParams,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    };
+    await expectThrowsAsync(async () => {
+      await removeLiquidity(context.banksClient, removeLiquidityParams);
+    }, errorCode);
+
+    // no bound
+    await removeLiquidity(context.banksClient, {
+      ...removeLiquidityParams,
+      maxTransferFeeBps: undefined,
+    });
+  });
+
+  it("Swap respects max transfer fee bps of input and output token", async () => {
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "ExceededMaxTransferFeeBps"
+    );
+
+    // token a is the output token
+    await expectThrowsAsync(async () => {
+      await swap2ExactIn(context.banksClient, {
+        payer: user,
+        pool,
+        inputTokenMint: tokenBMint,
+        outputTokenMint: tokenAMint,
+        amount0: new BN(10_000),
+        amount1: new BN(0),
+        referralTokenAccount: null,
+        maxTransferFeeBps: transferFeeBps - 1,
+      });
+    }, errorCode);
+
+    await swap2ExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenBMint,
+      outputTokenMint: tokenAMint,
+      amount0: new BN(10_000),
+      amount1: new BN(0),
+      referralTokenAccount: null,
+      maxTransferFeeBps: transferFeeBps,
+    });
+  });
+});
@@ -0,0 +1,254 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  processTransactionMaybeThrow,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addLiquidity,
+  AddLiquidityParams,
+  claimPositionFee,
+  createConfigIx,
+  CreateConfigParams,
+  createPosition,
+  createTokenBadge,
+  getCpAmmProgramErrorCodeHexString,
+  getPosition,
+  getTransferHookRemainingAccounts,
+  initializePool,
+  InitializePoolParams,
+  MIN_LP_AMOUNT,
+  MAX_SQRT_PRICE,
+  MIN_SQRT_PRICE,
+  removeLiquidity,
+  swapExactIn,
+  swapInstruction,
+  SwapParams,
+  createToken,
+  mintSplTokenTo,
+} from "./bankrun-utils";
+import BN from "bn.js";
+import {
+  createToken2022,
+  createTransferHookExtensionWithInstruction,
+  mintToToken2022,
+} from "./bankrun-utils/token2022";
+import {
+  createExtraAccountMetaListAndCounter,
+  deriveCounter,
+  TRANSFER_HOOK_COUNTER_PROGRAM_ID,
+} from "./bankrun-utils/transferHook";
+
+describe("Transfer hook token", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let user: Keypair;
+  let creator: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const configId = Math.floor(Math.random() * 1000);
+
+  async function getTransferHookCounter() {
+    const counterAccount = await context.banksClient.getAccount(
+      deriveCounter(tokenAMint, TRANSFER_HOOK_COUNTER_PROGRAM_ID)
+    );
+    return Buffer.from(counterAccount.data).readUInt32LE(8);
+  }
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+
+    user = await generateKpAndFund(context.banksClient, context.payer);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+
+    const tokenAMintKeypair = Keypair.generate();
+    tokenAMint = tokenAMintKeypair.publicKey;
+    await createToken2022(
+      context.banksClient,
+      context.payer,
+      [
+        createTransferHookExtensionWithInstruction(
+          tokenAMint,
+          context.payer.publicKey
+        ),
+      ],
+      tokenAMintKeypair
+    );
+    await createExtraAccountMetaListAndCounter(
+      context.banksClient,
+      admin,
+      tokenAMint
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+
+    for (const owner of [user, creator]) {
+      await mintToToken2022(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    await createTokenBadge(context.banksClient, {
+      tokenMint: tokenAMint,
+      admin,
+    });
+
+    // create config
+    const createConfigParams: CreateConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(configId),
+      createConfigParams
+    );
+
+    const initPoolParams: InitializePoolParams = {
+      payer: creator,
+      creator: creator.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    };
+
+    const result = await initializePool(context.banksClient, initPoolParams);
+    pool = result.pool;
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+  });
+
+  it("Transfer hook is invoked on every transfer of hooked token", async () => {
+    // pool initialization transfers token a once
+    expect(await getTransferHookCounter()).eq(1);
+
+    const addLiquidityParams: AddLiquidityParams = {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: new BN(MIN_SQRT_PRICE.muln(30)),
+      tokenAAmountThreshold: new BN(200),
+      tokenBAmountThreshold: new BN(200),
+    };
+    await addLiquidity(context.banksClient, addLiquidityParams);
+    expect(await getTransferHookCounter()).eq(2);
+
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    expect(await getTransferHookCounter()).eq(3);
+
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenBMint,
+      outputTokenMint: tokenAMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    expect(await getTransferHookCounter()).eq(4);
+
+    // token a fee is only transferred when it is not zero
+    const counterBeforeClaim = await getTransferHookCounter();
+    await claimPositionFee(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+    });
+    expect(await getTransferHookCounter()).gte(counterBeforeClaim);
+    const counterBeforeRemove = await getTransferHookCounter();
+
+    const positionState = await getPosition(context.banksClient, position);
+    await removeLiquidity(context.banksClient, {
+      ...addLiquidityParams,
+      liquidityDelta: positionState.unlockedLiquidity,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+    expect(await getTransferHookCounter()).eq(counterBeforeRemove + 1);
+  });
+
+  it("Swap fails without transfer hook accounts", async () => {
+    const swapParams: SwapParams = {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(10),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    };
+    const transferHookAccounts = await getTransferHookRemainingAccounts(
+      context.banksClient,
+      [tokenAMint]
+    );
+
+    const transaction = await swapInstruction(context.banksClient, swapParams);
+    const swapIx = transaction.instructions[transaction.instructions.length - 1];
+    swapIx.keys.splice(-transferHookAccounts.length);
+    transaction.recentBlockhash = (
+      await context.banksClient.getLatestBlockhash()
+    )[0];
+    transaction.sign(user);
+
+    const errorCode = getCpAmmProgramErrorCodeHexString(
+      "InvalidTransferHookAccounts"
+    );
+    await expectThrowsAsync(async () => {
+      await processTransactionMaybeThrow(context.banksClient, transaction);
+    }, errorCode);
+  });
+});
@@ -0,0 +1,221 @@
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  addLiquidity,
+  claimPositionFee,
+  createPosition,
+  createToken,
+  derivePositionNftAccount,
+  fundReward,
+  getPool,
+  getPosition,
+  getTokenAccount,
+  initializeCustomizablePool,
+  initializeReward,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  swapExactIn,
+  transferPosition,
+  U64_MAX,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Transfer position", () => {
+  let context: ProgramTestContext;
+  let creator: Keypair;
+  let seller: Keypair;
+  let buyer: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  let rewardMint: PublicKey;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    seller = await generateKpAndFund(context.banksClient, context.payer);
+    buyer = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    rewardMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, seller]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      rewardMint,
+      context.payer,
+      creator.publicKey
+    );
+
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice: new BN(1).shln(64),
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 1,
+    });
+    pool = result.pool;
+
+    position = await createPosition(
+      context.banksClient,
+      seller,
+      seller.publicKey,
+      pool
+    );
+    await addLiquidity(context.banksClient, {
+      owner: seller,
+      pool,
+      position,
+      liquidityDelta: new BN(1_000_000_000).shln(64),
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+
+    await initializeReward(context.banksClient, {
+      payer: creator,
+      index: 0,
+      rewardDuration: new BN(24 * 60 * 60),
+      pool,
+      rewardMint,
+    });
+    await fundReward(context.banksClient, {
+      funder: creator,
+      index: 0,
+      pool,
+      carryForward: true,
+      amount: new BN(1_000_000),
+    });
+  });
+
+  it("Settle fees and rewards to seller then move nft to buyer", async () => {
+    await swapExactIn(context.banksClient, {
+      payer: creator,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    const currentClock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        BigInt(poolState.rewardInfos[0].rewardDurationEnd.toString())
+      )
+    );
+
+    const sellerTokenB = getAssociatedTokenAddressSync(
+      tokenBMint,
+      seller.publicKey
+    );
+    const sellerTokenBBefore = (
+      await getTokenAccount(context.banksClient, sellerTokenB)
+    ).amount;
+
+    const buyerNftAccount = await transferPosition(context.banksClient, {
+      owner: seller,
+      newOwner: buyer.publicKey,
+      pool,
+      position,
+    });
+
+    const sellerTokenBAfter = (
+      await getTokenAccount(context.banksClient, sellerTokenB)
+    ).amount;
+    expect(sellerTokenBAfter > sellerTokenBBefore).to.be.true;
+
+    const sellerReward = await getTokenAccount(
+      context.banksClient,
+      getAssociatedTokenAddressSync(rewardMint, seller.publicKey)
+    );
+    expect(sellerReward.amount > BigInt(0)).to.be.true;
+
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.feeBPending.toNumber()).eq(0);
+    expect(positionState.rewardInfos[0].rewardPendings.toNumber()).eq(0);
+
+    const buyerNft = await getTokenAccount(
+      context.banksClient,
+      buyerNftAccount
+    );
+    expect(buyerNft.amount.toString()).eq("1");
+    const sellerNft = await getTokenAccount(
+      context.banksClient,
+      derivePositionNftAccount(positionState.nftMint)
+    );
+    expect(sellerNft.amount.toString()).eq("0");
+
+    // seller no longer holds the position nft, ConstraintRaw
+    await expectThrowsAsync(async () => {
+      await claimPositionFee(context.banksClient, {
+        owner: seller,
+        pool,
+        position,
+      });
+    }, "0x7d3");
+  });
+});
@@ -0,0 +1,165 @@
+import { expect } from "chai";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  randomID,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createConfigIx,
+  CreateConfigParams,
+  createDynamicConfigIx,
+  createToken,
+  getConfig,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  initializePool,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  updateConfigIx,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Update config", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let createConfigParams: CreateConfigParams;
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+
+    createConfigParams = {
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      vaultConfigKey: PublicKey.default,
+      poolCreatorAuthority: PublicKey.default,
+      activationType: 0,
+      collectFeeMode: 0,
+    };
+  });
+
+  it("Update static config only affects new pools", async () => {
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(randomID()),
+      createConfigParams
+    );
+
+    const tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    const tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenAMint,
+      context.payer,
+      admin.publicKey
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      context.payer,
+      tokenBMint,
+      context.payer,
+      admin.publicKey
+    );
+    const { pool } = await initializePool(context.banksClient, {
+      payer: admin,
+      creator: admin.publicKey,
+      config,
+      tokenAMint,
+      tokenBMint,
+      liquidity: new BN(MIN_LP_AMOUNT),
+      sqrtPrice: new BN(MIN_SQRT_PRICE.muln(2)),
+      activationPoint: null,
+    });
+
+    const newCliffFeeNumerator = new BN(5_000_000);
+    await updateConfigIx(context.banksClient, admin, config, {
+      ...createConfigParams,
+      poolFees: {
+        ...createConfigParams.poolFees,
+        baseFee: {
+          ...createConfigParams.poolFees.baseFee,
+          cliffFeeNumerator: newCliffFeeNumerator,
+        },
+      },
+      collectFeeMode: 1,
+    });
+
+    const configState = await getConfig(context.banksClient, config);
+    expect(configState.version.toNumber()).eq(1);
+    expect(configState.collectFeeMode).eq(1);
+    expect(configState.poolFees.baseFee.cliffFeeNumerator.toString()).eq(
+      newCliffFeeNumerator.toString()
+    );
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.poolFees.baseFee.cliffFeeNumerator.toString()).eq(
+      createConfigParams.poolFees.baseFee.cliffFeeNumerator.toString()
+    );
+    expect(poolState.collectFeeMode).eq(0);
+  });
+
+  it("Update config validates parameters", async () => {
+    const config = await createConfigIx(
+      context.banksClient,
+      admin,
+      new BN(randomID()),
+      createConfigParams
+    );
+
+    await expectThrowsAsync(async () => {
+      await updateConfigIx(context.banksClient, admin, config, {
+        ...createConfigParams,
+        sqrtMinPrice: createConfigParams.sqrtMaxPrice,
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidPriceRange"));
+
+    const configState = await getConfig(context.banksClient, config);
+    expect(configState.version.toNumber()).eq(0);
+  });
+
+  it("Dynamic config can't be updated", async () => {
+    const config = await createDynamicConfigIx(
+      context.banksClient,
+      admin,
+      new BN(randomID()),
+      { poolCreatorAuthority: admin.publicKey }
+    );
+
+    await expectThrowsAsync(async () => {
+      await updateConfigIx(
+        context.banksClient,
+        admin,
+        config,
+        createConfigParams
+      );
+    }, getCpAmmProgramErrorCodeHexString("InvalidConfigType"));
+  });
+});
@@ -0,0 +1,292 @@
+import { expect } from "chai";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import {
+  convertToByteArray,
+  expectThrowsAsync,
+  generateKpAndFund,
+  startTest,
+} from "./bankrun-utils/common";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  addAdminRole,
+  addLiquidity,
+  AdminRole,
+  createPosition,
+  createProposal,
+  createToken,
+  encodePriceRangeActionData,
+  executeProposal,
+  getCpAmmProgramErrorCodeHexString,
+  getPool,
+  getPosition,
+  initializeAdminRegistry,
+  initializeCustomizablePool,
+  MAX_SQRT_PRICE,
+  MIN_LP_AMOUNT,
+  MIN_SQRT_PRICE,
+  mintSplTokenTo,
+  ProposalAction,
+  removeLiquidity,
+  swapExactIn,
+  U64_MAX,
+  updatePriceRange,
+  updateTimelock,
+} from "./bankrun-utils";
+import BN from "bn.js";
+
+describe("Update price range", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let superAdmin: Keypair;
+  let operator: Keypair;
+  let creator: Keypair;
+  let user: Keypair;
+  let pool: PublicKey;
+  let position: PublicKey;
+  let tokenAMint: PublicKey;
+  let tokenBMint: PublicKey;
+  const sqrtPrice = new BN(1).shln(64);
+  const liquidity = new BN(1_000_000_000).shln(64);
+
+  beforeEach(async () => {
+    const root = Keypair.generate();
+    context = await startTest(root);
+    admin = await generateKpAndFund(context.banksClient, context.payer);
+    superAdmin = await generateKpAndFund(context.banksClient, context.payer);
+    operator = await generateKpAndFund(context.banksClient, context.payer);
+    creator = await generateKpAndFund(context.banksClient, context.payer);
+    user = await generateKpAndFund(context.banksClient, context.payer);
+
+    tokenAMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    tokenBMint = await createToken(
+      context.banksClient,
+      context.payer,
+      context.payer.publicKey
+    );
+    for (const owner of [creator, user]) {
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenAMint,
+        context.payer,
+        owner.publicKey
+      );
+      await mintSplTokenTo(
+        context.banksClient,
+        context.payer,
+        tokenBMint,
+        context.payer,
+        owner.publicKey
+      );
+    }
+
+    const result = await initializeCustomizablePool(context.banksClient, {
+      payer: creator,
+      creator: creator.publicKey,
+      tokenAMint,
+      tokenBMint,
+      liquidity: MIN_LP_AMOUNT,
+      sqrtPrice,
+      sqrtMinPrice: MIN_SQRT_PRICE,
+      sqrtMaxPrice: MAX_SQRT_PRICE,
+      hasAlphaVault: false,
+      activationPoint: null,
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000),
+          firstFactor: 0,
+          secondFactor: convertToByteArray(new BN(0)),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      activationType: 0,
+      collectFeeMode: 0,
+    });
+    pool = result.pool;
+
+    position = await createPosition(
+      context.banksClient,
+      user,
+      user.publicKey,
+      pool
+    );
+    await addLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: U64_MAX,
+      tokenBAmountThreshold: U64_MAX,
+    });
+
+    // hard-coded admins can't act once admin registry is initialized
+    await initializeAdminRegistry(context.banksClient, {
+      admin,
+      superAdmin: superAdmin.publicKey,
+    });
+  });
+
+  it("Config manager narrows price range without touching positions", async () => {
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+
+    // pool creator can't update price range
+    await expectThrowsAsync(async () => {
+      await updatePriceRange(context.banksClient, {
+        admin: creator,
+        pool,
+        sqrtMinPrice: sqrtPrice.shrn(1),
+        sqrtMaxPrice: sqrtPrice.shln(1),
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidAdmin"));
+
+    // current price must stay in the range
+    await expectThrowsAsync(async () => {
+      await updatePriceRange(context.banksClient, {
+        admin: operator,
+        pool,
+        sqrtMinPrice: sqrtPrice.shln(1),
+        sqrtMaxPrice: sqrtPrice.shln(2),
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidPriceRange"));
+
+    // reserves must be preserved, token b doesn't fit the new range
+    await expectThrowsAsync(async () => {
+      await updatePriceRange(context.banksClient, {
+        admin: operator,
+        pool,
+        sqrtMinPrice: sqrtPrice.shrn(1),
+        sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+      });
+    }, getCpAmmProgramErrorCodeHexString("InvalidPriceRange"));
+
+    const poolStateBefore = await getPool(context.banksClient, pool);
+    await updatePriceRange(context.banksClient, {
+      admin: operator,
+      pool,
+      sqrtMinPrice: sqrtPrice.shrn(1),
+      sqrtMaxPrice: sqrtPrice.shln(1),
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.liquidity.toString()).eq(
+      poolStateBefore.liquidity.toString()
+    );
+    expect(poolState.sqrtPrice.toString()).eq(
+      poolStateBefore.sqrtPrice.toString()
+    );
+    // curve liquidity is doubled
+    expect(poolState.liquidityScaleFactor.gt(new BN(0))).to.be.true;
+    expect(poolState.liquidityScaleFactor.lte(new BN(2).shln(32))).to.be.true;
+
+    const positionState = await getPosition(context.banksClient, position);
+    expect(positionState.unlockedLiquidity.toString()).eq(
+      liquidity.toString()
+    );
+
+    await swapExactIn(context.banksClient, {
+      payer: user,
+      pool,
+      inputTokenMint: tokenAMint,
+      outputTokenMint: tokenBMint,
+      amountIn: new BN(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    await removeLiquidity(context.banksClient, {
+      owner: user,
+      pool,
+      position,
+      liquidityDelta: liquidity,
+      tokenAAmountThreshold: new BN(0),
+      tokenBAmountThreshold: new BN(0),
+    });
+  });
+
+  it("Price range update is timelocked", async () => {
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+    // timestamp based delay
+    const delay = 20;
+    await updateTimelock(context.banksClient, {
+      superAdmin,
+      activationType: 1,
+      delay: new BN(delay),
+    });
+
+    const sqrtMinPrice = sqrtPrice.shrn(1);
+    const sqrtMaxPrice = sqrtPrice.shln(1);
+    await expectThrowsAsync(async () => {
+      await updatePriceRange(context.banksClient, {
+        admin: operator,
+        pool,
+        sqrtMinPrice,
+        sqrtMaxPrice,
+      });
+    }, getCpAmmProgramErrorCodeHexString("TimelockRequired"));
+
+    const proposal = await createProposal(context.banksClient, {
+      proposer: operator,
+      target: pool,
+      action: ProposalAction.UpdatePriceRange,
+      actionData: encodePriceRangeActionData(sqrtMinPrice, sqrtMaxPrice),
+    });
+
+    const currentClock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        currentClock.unixTimestamp + BigInt(delay)
+      )
+    );
+
+    await executeProposal(context.banksClient, {
+      payer: user,
+      proposal,
+    });
+
+    const poolState = await getPool(context.banksClient, pool);
+    expect(poolState.sqrtMinPrice.toString()).eq(sqrtMinPrice.toString());
+    expect(poolState.sqrtMaxPrice.toString()).eq(sqrtMaxPrice.toString());
+  });
+
+  it("Config manager updates price range", async () => {
+    await addAdminRole(context.banksClient, {
+      superAdmin,
+      admin: operator.publicKey,
+      role: AdminRole.ConfigManager,
+    });
+
+    await updatePriceRange(context.banksClient, {
+      admin: operator,
+      pool,
+      sqrtMinPrice: sqrtPrice.shrn(1),
+      sqrtMaxPrice: sqrtPrice.shln(1),
+    });
+
+    // widen back to the full range
+    await updatePriceRange(context.banksClient, {
+      admin: operator,
+      pool,
+      sqrtMinPrice: new BN(MIN_SQRT_PRICE),
+      sqrtMaxPrice: new BN(MAX_SQRT_PRICE),
+    });
+  });
+});